@@ -0,0 +1,15 @@
+#![no_main]
+
+use std::io::Cursor;
+
+use io_unity::serialized_file::SerializedFile;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(serialized_file) = SerializedFile::read(Box::new(Cursor::new(data.to_vec())), 0, None)
+    {
+        for path_id in serialized_file.get_object_map().keys() {
+            let _ = serialized_file.get_tt_object_by_path_id(*path_id);
+        }
+    }
+});
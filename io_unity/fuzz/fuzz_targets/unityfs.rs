@@ -0,0 +1,14 @@
+#![no_main]
+
+use std::io::Cursor;
+
+use io_unity::unityfs::UnityFS;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(unity_fs) = UnityFS::read(Box::new(Cursor::new(data.to_vec())), None) {
+        for path in unity_fs.get_file_paths() {
+            let _ = unity_fs.get_file_data_by_path(&path);
+        }
+    }
+});
@@ -0,0 +1,53 @@
+pub mod type_tree;
+
+use crate::{def_unity_class, error::ReadResult, type_tree::TypeTreeObjectRef};
+
+def_unity_class!(AnimatorController);
+
+/// A layer and the states of the state machine it plays.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimatorLayer {
+    pub name: String,
+    pub state_machine_index: u32,
+    pub states: Vec<AnimatorState>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimatorState {
+    /// Name from the controller's string table, or the hash when absent.
+    pub name: String,
+    pub name_hash: u32,
+    /// Indices into [`AnimatorControllerObject::get_animation_clips`] of the
+    /// clips the state's motion or blend tree plays.
+    pub clips: Vec<usize>,
+    pub transitions: Vec<AnimatorTransition>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimatorTransition {
+    /// Index of the destination in the layer's states. Values past the end
+    /// are exits or transitions to another state machine.
+    pub destination_state: u32,
+    pub conditions: Vec<AnimatorCondition>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimatorCondition {
+    /// Unity's `AnimatorConditionMode`: 1 If, 2 IfNot, 3 Greater, 4 Less,
+    /// 5 ExitTime, 6 Equals and 7 NotEqual.
+    pub mode: u32,
+    /// Parameter name, or its hash when absent from the string table.
+    pub parameter: String,
+    pub threshold: f32,
+    pub exit_time: f32,
+}
+
+pub trait AnimatorControllerObject {
+    fn get_name(&self) -> ReadResult<String>;
+    /// PPtrs to every AnimationClip the controller uses, which states refer
+    /// to by index.
+    fn get_animation_clips(&self) -> ReadResult<Vec<TypeTreeObjectRef>>;
+    /// PPtrs to the StateMachineBehaviour MonoBehaviours of the controller.
+    fn get_state_machine_behaviours(&self) -> ReadResult<Vec<TypeTreeObjectRef>>;
+    fn get_layers(&self) -> ReadResult<Vec<AnimatorLayer>>;
+}
@@ -0,0 +1,133 @@
+use super::{
+    AnimatorCondition, AnimatorController, AnimatorControllerObject, AnimatorLayer, AnimatorState,
+    AnimatorTransition,
+};
+use crate::error::ReadResult;
+use crate::type_tree::convert::TryCastFrom;
+use crate::type_tree::TypeTreeObjectRef;
+use std::collections::HashMap;
+
+impl AnimatorControllerObject for AnimatorController<'_> {
+    fn get_name(&self) -> ReadResult<String> {
+        String::try_cast_from(self.inner, "/Base/m_Name")
+    }
+
+    fn get_animation_clips(&self) -> ReadResult<Vec<TypeTreeObjectRef>> {
+        <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/m_AnimationClips/Array")
+    }
+
+    fn get_state_machine_behaviours(&self) -> ReadResult<Vec<TypeTreeObjectRef>> {
+        // Added in 5.0.
+        Ok(<Vec<TypeTreeObjectRef>>::try_cast_from(
+            self.inner,
+            "/Base/m_StateMachineBehaviours/Array",
+        )
+        .unwrap_or_default())
+    }
+
+    fn get_layers(&self) -> ReadResult<Vec<AnimatorLayer>> {
+        let names = self.get_tos()?;
+        let name_of = |hash: u32| names.get(&hash).cloned().unwrap_or(hash.to_string());
+        let state_machines = <Vec<TypeTreeObjectRef>>::try_cast_from(
+            self.inner,
+            "/Base/m_Controller/m_StateMachineArray/Array",
+        )?;
+
+        <Vec<TypeTreeObjectRef>>::try_cast_from(
+            self.inner,
+            "/Base/m_Controller/m_LayerArray/Array",
+        )?
+        .iter()
+        .map(|layer| {
+            let state_machine_index = u32::try_cast_from(layer, "/Base/data/m_StateMachineIndex")?;
+            let states = match state_machines.get(state_machine_index as usize) {
+                Some(state_machine) => <Vec<TypeTreeObjectRef>>::try_cast_from(
+                    state_machine,
+                    "/Base/data/m_StateConstantArray/Array",
+                )?
+                .iter()
+                .map(|state| read_state(state, &name_of))
+                .collect::<ReadResult<_>>()?,
+                None => Vec::new(),
+            };
+            Ok(AnimatorLayer {
+                name: name_of(u32::try_cast_from(layer, "/Base/data/m_Binding")?),
+                state_machine_index,
+                states,
+            })
+        })
+        .collect()
+    }
+}
+
+impl AnimatorController<'_> {
+    /// `m_TOS`, the names of layers, states and parameters by hash.
+    fn get_tos(&self) -> ReadResult<HashMap<u32, String>> {
+        <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/m_TOS/Array")?
+            .iter()
+            .map(|pair| {
+                Ok((
+                    u32::try_cast_from(pair, "/Base/first")?,
+                    String::try_cast_from(pair, "/Base/second")?,
+                ))
+            })
+            .collect()
+    }
+}
+
+fn read_state(
+    state: &TypeTreeObjectRef,
+    name_of: &impl Fn(u32) -> String,
+) -> ReadResult<AnimatorState> {
+    let name_hash = u32::try_cast_from(state, "/Base/data/m_NameID")?;
+
+    let mut clips = Vec::new();
+    let blend_trees = <Vec<TypeTreeObjectRef>>::try_cast_from(
+        state,
+        "/Base/data/m_BlendTreeConstantArray/Array",
+    )?;
+    for blend_tree in &blend_trees {
+        for node in
+            <Vec<TypeTreeObjectRef>>::try_cast_from(blend_tree, "/Base/data/m_NodeArray/Array")?
+        {
+            let clip_id = u32::try_cast_from(&node, "/Base/data/m_ClipID")?;
+            if clip_id != u32::MAX && !clips.contains(&(clip_id as usize)) {
+                clips.push(clip_id as usize);
+            }
+        }
+    }
+
+    let transitions = <Vec<TypeTreeObjectRef>>::try_cast_from(
+        state,
+        "/Base/data/m_TransitionConstantArray/Array",
+    )?
+    .iter()
+    .map(|transition| {
+        let conditions = <Vec<TypeTreeObjectRef>>::try_cast_from(
+            transition,
+            "/Base/data/m_ConditionConstantArray/Array",
+        )?
+        .iter()
+        .map(|condition| {
+            Ok(AnimatorCondition {
+                mode: u32::try_cast_from(condition, "/Base/data/m_ConditionMode")?,
+                parameter: name_of(u32::try_cast_from(condition, "/Base/data/m_EventID")?),
+                threshold: f32::try_cast_from(condition, "/Base/data/m_EventThreshold")?,
+                exit_time: f32::try_cast_from(condition, "/Base/data/m_ExitTime")?,
+            })
+        })
+        .collect::<ReadResult<_>>()?;
+        Ok(AnimatorTransition {
+            destination_state: u32::try_cast_from(transition, "/Base/data/m_DestinationState")?,
+            conditions,
+        })
+    })
+    .collect::<ReadResult<_>>()?;
+
+    Ok(AnimatorState {
+        name: name_of(name_hash),
+        name_hash,
+        clips,
+        transitions,
+    })
+}
@@ -0,0 +1,21 @@
+pub mod type_tree;
+
+use crate::{def_unity_class, error::ReadResult, type_tree::Hash128};
+
+def_unity_class!(AssetBundleManifest);
+
+/// The manifest of a whole bundle build, held by the bundle named after the
+/// build output folder, see
+/// [`crate::unity_asset_view::UnityAssetViewer::asset_bundle_manifest`].
+pub trait AssetBundleManifestObject {
+    /// Names of every bundle of the build, in index order.
+    fn all_asset_bundles(&self) -> ReadResult<Vec<String>>;
+    /// Names of the bundles built with a variant.
+    fn asset_bundles_with_variant(&self) -> ReadResult<Vec<String>>;
+    /// Names of the bundles `bundle` depends on, which must be loaded
+    /// before it. Fails for a bundle the manifest does not list.
+    fn dependencies_for(&self, bundle: &str) -> ReadResult<Vec<String>>;
+    /// The content hash of `bundle`, `None` for a bundle the manifest does
+    /// not list.
+    fn asset_bundle_hash(&self, bundle: &str) -> ReadResult<Option<Hash128>>;
+}
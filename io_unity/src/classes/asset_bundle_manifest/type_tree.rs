@@ -0,0 +1,78 @@
+use super::{AssetBundleManifest, AssetBundleManifestObject};
+use crate::{
+    error::{Error, ReadResult},
+    type_tree::{convert::TryCastFrom, Hash128, TypeTreeObjectRef},
+};
+
+impl AssetBundleManifest<'_> {
+    /// `AssetBundleNames` as stored, by bundle index.
+    fn names(&self) -> ReadResult<Vec<(i32, String)>> {
+        let mut names =
+            <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/AssetBundleNames/Array")?
+                .iter()
+                .map(|pair| {
+                    Ok((
+                        i32::try_cast_from(pair, "/Base/first")?,
+                        String::try_cast_from(pair, "/Base/second")?,
+                    ))
+                })
+                .collect::<ReadResult<Vec<_>>>()?;
+        names.sort_by_key(|(index, _)| *index);
+        Ok(names)
+    }
+
+    fn name_of(names: &[(i32, String)], index: i32) -> ReadResult<String> {
+        names
+            .iter()
+            .find(|(name_index, _)| *name_index == index)
+            .map(|(_, name)| name.clone())
+            .ok_or_else(|| Error::Other(format!("no asset bundle with index {index}")))
+    }
+
+    /// The `AssetBundleInfos` entry of `bundle`.
+    fn info(&self, names: &[(i32, String)], bundle: &str) -> ReadResult<Option<TypeTreeObjectRef>> {
+        let Some((index, _)) = names.iter().find(|(_, name)| name == bundle) else {
+            return Ok(None);
+        };
+        for pair in
+            <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/AssetBundleInfos/Array")?
+        {
+            if i32::try_cast_from(&pair, "/Base/first")? == *index {
+                return TypeTreeObjectRef::try_cast_from(&pair, "/Base/second").map(Some);
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl AssetBundleManifestObject for AssetBundleManifest<'_> {
+    fn all_asset_bundles(&self) -> ReadResult<Vec<String>> {
+        Ok(self.names()?.into_iter().map(|(_, name)| name).collect())
+    }
+
+    fn asset_bundles_with_variant(&self) -> ReadResult<Vec<String>> {
+        let names = self.names()?;
+        <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/AssetBundlesWithVariant/Array")?
+            .iter()
+            .map(|index| Self::name_of(&names, i32::try_cast_from(index, "/Base")?))
+            .collect()
+    }
+
+    fn dependencies_for(&self, bundle: &str) -> ReadResult<Vec<String>> {
+        let names = self.names()?;
+        let info = self
+            .info(&names, bundle)?
+            .ok_or_else(|| Error::Other(format!("no asset bundle named {bundle}")))?;
+        <Vec<TypeTreeObjectRef>>::try_cast_from(&info, "/Base/AssetBundleDependencies/Array")?
+            .iter()
+            .map(|index| Self::name_of(&names, i32::try_cast_from(index, "/Base")?))
+            .collect()
+    }
+
+    fn asset_bundle_hash(&self, bundle: &str) -> ReadResult<Option<Hash128>> {
+        let names = self.names()?;
+        self.info(&names, bundle)?
+            .map(|info| Hash128::try_cast_from(&info, "/Base/AssetBundleHash"))
+            .transpose()
+    }
+}
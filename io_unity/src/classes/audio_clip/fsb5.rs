@@ -0,0 +1,433 @@
+//! Decoding of the FMOD FSB5 sound banks Unity stores AudioClip data in.
+
+use crate::error::{Error, ReadResult};
+
+const FREQUENCIES: [u32; 11] = [
+    4000, 8000, 11000, 11025, 16000, 22050, 24000, 32000, 44100, 48000, 96000,
+];
+
+/// Channel counts by the 2 bit channel field of a sample header.
+const CHANNELS: [u16; 4] = [1, 2, 6, 8];
+
+const CHUNK_CHANNELS: u32 = 1;
+const CHUNK_FREQUENCY: u32 = 2;
+
+/// Codec of the samples in an FSB5 bank, from the `mode` header field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fsb5Codec {
+    Pcm8,
+    Pcm16,
+    Pcm24,
+    Pcm32,
+    PcmFloat,
+    GcAdpcm,
+    ImaAdpcm,
+    Vag,
+    HeVag,
+    Xma,
+    Mpeg,
+    Celt,
+    Atrac9,
+    Xwma,
+    Vorbis,
+    FAdpcm,
+    Opus,
+    Unknown(u32),
+}
+
+impl From<u32> for Fsb5Codec {
+    fn from(mode: u32) -> Self {
+        match mode {
+            1 => Fsb5Codec::Pcm8,
+            2 => Fsb5Codec::Pcm16,
+            3 => Fsb5Codec::Pcm24,
+            4 => Fsb5Codec::Pcm32,
+            5 => Fsb5Codec::PcmFloat,
+            6 => Fsb5Codec::GcAdpcm,
+            7 => Fsb5Codec::ImaAdpcm,
+            8 => Fsb5Codec::Vag,
+            9 => Fsb5Codec::HeVag,
+            10 => Fsb5Codec::Xma,
+            11 => Fsb5Codec::Mpeg,
+            12 => Fsb5Codec::Celt,
+            13 => Fsb5Codec::Atrac9,
+            14 => Fsb5Codec::Xwma,
+            15 => Fsb5Codec::Vorbis,
+            16 => Fsb5Codec::FAdpcm,
+            17 => Fsb5Codec::Opus,
+            mode => Fsb5Codec::Unknown(mode),
+        }
+    }
+}
+
+/// The first subsound of an FSB5 bank, which is the only one Unity writes.
+#[derive(Debug)]
+pub struct Fsb5Sample<'a> {
+    pub codec: Fsb5Codec,
+    pub frequency: u32,
+    pub channels: u16,
+    pub sample_count: u32,
+    pub data: &'a [u8],
+}
+
+fn read_u32(data: &[u8], offset: usize) -> ReadResult<u32> {
+    data.get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or(Error::Other("fsb5 data too short".to_owned()))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> ReadResult<u64> {
+    data.get(offset..offset + 8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or(Error::Other("fsb5 data too short".to_owned()))
+}
+
+/// Offset of a sample's data within the bank's sample data, from bits 7 to
+/// 33 of its header, counted in units of 32 bytes.
+fn sample_data_offset(sample_header: u64) -> usize {
+    (((sample_header >> 7) & 0x07FF_FFFF) << 5) as usize
+}
+
+pub fn read_first_sample(data: &[u8]) -> ReadResult<Fsb5Sample<'_>> {
+    if data.get(0..4) != Some(b"FSB5") {
+        return Err(Error::Other("not a fsb5 sound bank".to_owned()));
+    }
+    let version = read_u32(data, 0x04)?;
+    let sample_count = read_u32(data, 0x08)?;
+    let sample_headers_size = read_u32(data, 0x0C)? as usize;
+    let name_table_size = read_u32(data, 0x10)? as usize;
+    let data_size = read_u32(data, 0x14)? as usize;
+    let codec = Fsb5Codec::from(read_u32(data, 0x18)?);
+    if sample_count == 0 {
+        return Err(Error::Other("fsb5 sound bank has no samples".to_owned()));
+    }
+
+    let header_size = if version == 0 { 0x40 } else { 0x3C };
+    let mut offset = header_size;
+    let sample_header = read_u64(data, offset)?;
+    offset += 8;
+    let mut frequency = FREQUENCIES
+        .get(((sample_header >> 1) & 0x0F) as usize)
+        .copied()
+        .unwrap_or(44100);
+    let mut channels = CHANNELS[((sample_header >> 5) & 0x03) as usize];
+    let data_offset = sample_data_offset(sample_header);
+    let samples = (sample_header >> 34) as u32;
+
+    let mut has_chunk = sample_header & 0x01 == 1;
+    while has_chunk {
+        let chunk = read_u32(data, offset)?;
+        offset += 4;
+        has_chunk = chunk & 0x01 == 1;
+        let chunk_size = ((chunk >> 1) & 0x00FF_FFFF) as usize;
+        match chunk >> 25 {
+            CHUNK_CHANNELS => {
+                channels = *data
+                    .get(offset)
+                    .ok_or(Error::Other("fsb5 data too short".to_owned()))?
+                    as u16
+            }
+            CHUNK_FREQUENCY => frequency = read_u32(data, offset)?,
+            _ => (),
+        }
+        offset += chunk_size;
+    }
+
+    let data_end = if sample_count > 1 {
+        sample_data_offset(read_u64(data, offset)?)
+    } else {
+        data_size
+    };
+    let data_start = header_size + sample_headers_size + name_table_size;
+    let sample_data = data
+        .get(data_start + data_offset..data_start + data_end)
+        .ok_or(Error::Other("fsb5 sample data out of range".to_owned()))?;
+
+    Ok(Fsb5Sample {
+        codec,
+        frequency,
+        channels,
+        sample_count: samples,
+        data: sample_data,
+    })
+}
+
+/// Decode the first subsound of an FSB5 bank to interleaved PCM16, returning
+/// the samples, sample rate and channel count.
+pub fn decode_pcm(data: &[u8]) -> ReadResult<(Vec<i16>, u32, u16)> {
+    let sample = read_first_sample(data)?;
+    let channels = sample.channels.max(1);
+    let samples = match sample.codec {
+        Fsb5Codec::Pcm8 => sample
+            .data
+            .iter()
+            .map(|s| ((*s as i16) - 128) << 8)
+            .collect(),
+        Fsb5Codec::Pcm16 => sample
+            .data
+            .chunks_exact(2)
+            .map(|s| i16::from_le_bytes([s[0], s[1]]))
+            .collect(),
+        Fsb5Codec::PcmFloat => sample
+            .data
+            .chunks_exact(4)
+            .map(|s| {
+                let s = f32::from_le_bytes([s[0], s[1], s[2], s[3]]);
+                (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+            })
+            .collect(),
+        Fsb5Codec::FAdpcm => decode_fadpcm(sample.data, channels as usize),
+        Fsb5Codec::ImaAdpcm => decode_xbox_ima(sample.data, channels as usize),
+        #[cfg(feature = "external-class-handle-vorbis")]
+        Fsb5Codec::Vorbis => return decode_vorbis(data),
+        codec => return Err(Error::UnsupportedAudioCodec(format!("{:?}", codec))),
+    };
+
+    let frames = (sample.sample_count as usize).min(samples.len() / channels as usize);
+    let mut samples = samples;
+    samples.truncate(frames * channels as usize);
+    Ok((samples, sample.frequency, channels))
+}
+
+#[cfg(feature = "external-class-handle-vorbis")]
+fn decode_vorbis(data: &[u8]) -> ReadResult<(Vec<i16>, u32, u16)> {
+    // FMOD strips the Vorbis headers, so the stream is rebuilt as Ogg first.
+    let bank = fsbex::Bank::new(data).map_err(|err| Error::Other(err.to_string()))?;
+    let stream = bank
+        .into_iter()
+        .next()
+        .ok_or(Error::Other("fsb5 sound bank has no samples".to_owned()))?;
+    let ogg = stream
+        .write(Vec::new())
+        .map_err(|err| Error::Other(err.to_string()))?;
+
+    let mut reader = lewton::inside_ogg::OggStreamReader::new(std::io::Cursor::new(ogg))
+        .map_err(|err| Error::Other(err.to_string()))?;
+    let mut samples = Vec::new();
+    while let Some(packet) = reader
+        .read_dec_packet_itl()
+        .map_err(|err| Error::Other(err.to_string()))?
+    {
+        samples.extend(packet);
+    }
+    Ok((
+        samples,
+        reader.ident_hdr.audio_sample_rate,
+        reader.ident_hdr.audio_channels as u16,
+    ))
+}
+
+const FADPCM_COEFS: [[i32; 2]; 8] = [
+    [0, 0],
+    [60, 0],
+    [122, 60],
+    [115, 52],
+    [98, 55],
+    [0, 0],
+    [0, 0],
+    [0, 0],
+];
+
+/// FMOD's own ADPCM, frames of 0x8C bytes holding 256 samples, interleaved
+/// by frame between channels.
+fn decode_fadpcm(data: &[u8], channels: usize) -> Vec<i16> {
+    const FRAME_SIZE: usize = 0x8C;
+    const FRAME_SAMPLES: usize = 256;
+
+    let frame_count = data.len() / (FRAME_SIZE * channels);
+    let mut output = vec![0i16; frame_count * FRAME_SAMPLES * channels];
+    for (index, frame) in data.chunks_exact(FRAME_SIZE).enumerate() {
+        let (frame_index, channel) = (index / channels, index % channels);
+        if frame_index >= frame_count {
+            break;
+        }
+        let coefs = u32::from_le_bytes(frame[0..4].try_into().unwrap());
+        let shifts = u32::from_le_bytes(frame[4..8].try_into().unwrap());
+        let mut hist1 = i16::from_le_bytes([frame[8], frame[9]]) as i32;
+        let mut hist2 = i16::from_le_bytes([frame[10], frame[11]]) as i32;
+
+        let mut sample_index = 0;
+        for group in 0..8 {
+            let [coef1, coef2] = FADPCM_COEFS[(((coefs >> (group * 4)) & 0x0F) % 0x07) as usize];
+            let shift = 22 - ((shifts >> (group * 4)) & 0x0F) as i32;
+            for word in 0..4 {
+                let offset = 0x0C + 0x10 * group + 0x04 * word;
+                let nibbles = u32::from_le_bytes(frame[offset..offset + 4].try_into().unwrap());
+                for nibble in 0..8 {
+                    let sample = ((((nibbles >> (nibble * 4)) & 0x0F) << 28) as i32) >> shift;
+                    let sample = ((sample - hist2 * coef2 + hist1 * coef1) >> 6)
+                        .clamp(i16::MIN as i32, i16::MAX as i32);
+                    output[(frame_index * FRAME_SAMPLES + sample_index) * channels + channel] =
+                        sample as i16;
+                    sample_index += 1;
+                    hist2 = hist1;
+                    hist1 = sample;
+                }
+            }
+        }
+    }
+    output
+}
+
+const IMA_INDEX_TABLE: [i32; 16] = [-1, -1, -1, -1, 2, 4, 6, 8, -1, -1, -1, -1, 2, 4, 6, 8];
+
+const IMA_STEP_TABLE: [i32; 89] = [
+    7, 8, 9, 10, 11, 12, 13, 14, 16, 17, 19, 21, 23, 25, 28, 31, 34, 37, 41, 45, 50, 55, 60, 66,
+    73, 80, 88, 97, 107, 118, 130, 143, 157, 173, 190, 209, 230, 253, 279, 307, 337, 371, 408, 449,
+    494, 544, 598, 658, 724, 796, 876, 963, 1060, 1166, 1282, 1411, 1552, 1707, 1878, 2066, 2272,
+    2499, 2749, 3024, 3327, 3660, 4026, 4428, 4871, 5358, 5894, 6484, 7132, 7845, 8630, 9493,
+    10442, 11487, 12635, 13899, 15289, 16818, 18500, 20350, 22385, 24623, 27086, 29794, 32767,
+];
+
+/// Xbox style IMA ADPCM, blocks of 0x24 bytes per channel holding 64
+/// samples, with channel data interleaved every 4 bytes.
+fn decode_xbox_ima(data: &[u8], channels: usize) -> Vec<i16> {
+    const BLOCK_SAMPLES: usize = 64;
+
+    let block_size = 0x24 * channels;
+    let mut output = Vec::with_capacity(data.len() / block_size * BLOCK_SAMPLES * channels);
+    for block in data.chunks_exact(block_size) {
+        let start = output.len();
+        output.resize(start + BLOCK_SAMPLES * channels, 0);
+        for channel in 0..channels {
+            let header = &block[channel * 4..channel * 4 + 4];
+            let mut predictor = i16::from_le_bytes([header[0], header[1]]) as i32;
+            let mut step_index = (header[2] as i32).clamp(0, 88);
+            for sample_index in 0..BLOCK_SAMPLES {
+                let byte_offset = 4 * channels
+                    + (sample_index / 8) * 4 * channels
+                    + channel * 4
+                    + (sample_index % 8) / 2;
+                let nibble = (block[byte_offset] >> ((sample_index % 2) * 4)) & 0x0F;
+
+                let step = IMA_STEP_TABLE[step_index as usize];
+                let mut diff = step >> 3;
+                if nibble & 1 != 0 {
+                    diff += step >> 2;
+                }
+                if nibble & 2 != 0 {
+                    diff += step >> 1;
+                }
+                if nibble & 4 != 0 {
+                    diff += step;
+                }
+                if nibble & 8 != 0 {
+                    predictor -= diff;
+                } else {
+                    predictor += diff;
+                }
+                predictor = predictor.clamp(i16::MIN as i32, i16::MAX as i32);
+                step_index = (step_index + IMA_INDEX_TABLE[nibble as usize]).clamp(0, 88);
+                output[start + sample_index * channels + channel] = predictor as i16;
+            }
+        }
+    }
+    output
+}
+
+/// Wrap interleaved PCM16 samples in a RIFF WAVE file.
+pub fn pcm_to_wav(samples: &[i16], sample_rate: u32, channels: u16) -> Vec<u8> {
+    let data_size = (samples.len() * 2) as u32;
+    let block_align = channels * 2;
+    let mut wav = Vec::with_capacity(44 + data_size as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_size).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&channels.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_size.to_le_bytes());
+    for sample in samples {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode_pcm16_bank() {
+        let samples: Vec<i16> = vec![1, -1, 2, -2, 3, -3];
+        let data: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+
+        // frequency index 8 (44100), stereo, data offset 0, 3 frames
+        let sample_header: u64 = (8 << 1) | (1 << 5) | (3 << 34);
+        let mut bank = Vec::new();
+        bank.extend_from_slice(b"FSB5");
+        for field in [1u32, 1, 8, 0, data.len() as u32, 2] {
+            bank.extend_from_slice(&field.to_le_bytes());
+        }
+        bank.extend_from_slice(&[0; 0x3C - 0x1C]);
+        bank.extend_from_slice(&sample_header.to_le_bytes());
+        bank.extend_from_slice(&data);
+
+        let (decoded, sample_rate, channels) = decode_pcm(&bank).unwrap();
+        assert_eq!(
+            (decoded.as_slice(), sample_rate, channels),
+            (samples.as_slice(), 44100, 2)
+        );
+        assert_eq!(
+            pcm_to_wav(&decoded, sample_rate, channels).len(),
+            44 + data.len()
+        );
+    }
+
+    /// A one sample PCM16 bank with `sample_header` and `chunks` after it.
+    fn pcm16_bank(sample_header: u64, chunks: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut bank = Vec::new();
+        bank.extend_from_slice(b"FSB5");
+        let headers_size = 8 + chunks.len() as u32;
+        for field in [1u32, 1, headers_size, 0, data.len() as u32, 2] {
+            bank.extend_from_slice(&field.to_le_bytes());
+        }
+        bank.extend_from_slice(&[0; 0x3C - 0x1C]);
+        bank.extend_from_slice(&sample_header.to_le_bytes());
+        bank.extend_from_slice(chunks);
+        bank.extend_from_slice(data);
+        bank
+    }
+
+    #[test]
+    fn six_channel_bank_with_its_data_offset() {
+        // 6 channels, data 32 bytes into the sample data, 2 frames.
+        let mut data = vec![0xAAu8; 32];
+        let samples: Vec<i16> = (1..=12).collect();
+        data.extend(samples.iter().flat_map(|s| s.to_le_bytes()));
+        let sample_header: u64 = (8 << 1) | (2 << 5) | (1 << 7) | (2 << 34);
+
+        let bank = pcm16_bank(sample_header, &[], &data);
+        let sample = read_first_sample(&bank).unwrap();
+        assert_eq!(sample.channels, 6);
+        assert_eq!(sample.data, &data[32..]);
+        let (decoded, _, channels) = decode_pcm(&bank).unwrap();
+        assert_eq!((decoded, channels), (samples, 6));
+
+        let eight_channels: u64 = (8 << 1) | (3 << 5);
+        let bank = pcm16_bank(eight_channels, &[], &[0; 16]);
+        assert_eq!(read_first_sample(&bank).unwrap().channels, 8);
+    }
+
+    #[test]
+    fn zero_channel_chunk_decodes_as_mono() {
+        // A channels chunk of one byte set to 0, the last chunk.
+        let chunk = ((CHUNK_CHANNELS << 25) | (1 << 1)).to_le_bytes();
+        let mut chunks = chunk.to_vec();
+        chunks.push(0);
+        let data: Vec<u8> = [5i16, 6].iter().flat_map(|s| s.to_le_bytes()).collect();
+        let sample_header: u64 = 1 | (8 << 1) | (2 << 34);
+
+        let (decoded, sample_rate, channels) =
+            decode_pcm(&pcm16_bank(sample_header, &chunks, &data)).unwrap();
+        assert_eq!((decoded, channels), (vec![5, 6], 1));
+        let wav = pcm_to_wav(&[5, 6], sample_rate, channels);
+        // block_align
+        assert_eq!(u16::from_le_bytes([wav[32], wav[33]]), 2);
+    }
+}
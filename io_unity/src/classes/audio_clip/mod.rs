@@ -1,3 +1,4 @@
+pub mod fsb5;
 pub mod type_tree;
 
 use crate::{def_unity_class, error::ReadResult, unity_asset_view::UnityAssetViewer};
@@ -6,4 +7,18 @@ def_unity_class!(AudioClip);
 
 pub trait AudioClipObject {
     fn get_audio_data(&self, viewer: &UnityAssetViewer) -> ReadResult<Vec<u8>>;
+
+    /// Decode the clip to interleaved PCM16, returning the samples, sample
+    /// rate and channel count. PCM, FADPCM and IMA ADPCM banks are decoded
+    /// natively and Vorbis with the `external-class-handle-vorbis` feature;
+    /// other codecs fail with [`crate::error::Error::UnsupportedAudioCodec`]
+    /// and are only available through [`Self::get_audio_data`].
+    fn decode_pcm(&self, viewer: &UnityAssetViewer) -> ReadResult<(Vec<i16>, u32, u16)> {
+        fsb5::decode_pcm(&self.get_audio_data(viewer)?)
+    }
+
+    fn get_wav(&self, viewer: &UnityAssetViewer) -> ReadResult<Vec<u8>> {
+        let (samples, sample_rate, channels) = self.decode_pcm(viewer)?;
+        Ok(fsb5::pcm_to_wav(&samples, sample_rate, channels))
+    }
 }
@@ -5,7 +5,6 @@ use crate::unity_asset_view::UnityAssetViewer;
 use crate::{classes::SerializedFileRef, error::ReadResult};
 use binrw::binrw;
 use num_enum::TryFromPrimitive;
-use std::io::{prelude::*, SeekFrom};
 
 impl AudioClipObject for AudioClip<'_> {
     fn get_audio_data(&self, viewer: &UnityAssetViewer) -> ReadResult<Vec<u8>> {
@@ -13,16 +12,14 @@ impl AudioClipObject for AudioClip<'_> {
         let resource_offset = self.get_resource_offset()?;
         let resource_size = self.get_resource_size()?;
 
-        if let Some(mut file) = viewer.get_resource_file_by_serialized_file_id_and_path(
-            self.get_serialized_file_id(),
-            &resource_source,
-        ) {
-            file.seek(SeekFrom::Start(resource_offset))?;
-            let mut data = vec![0u8; resource_size as usize];
-            file.read_exact(&mut data)?;
-            return Ok(data);
-        }
-        Err(Error::Other("Get audio data fail".to_owned()))
+        viewer
+            .read_stream_data(
+                self.get_serialized_file_id(),
+                &resource_source,
+                resource_offset,
+                resource_size as usize,
+            )?
+            .ok_or_else(|| Error::Other("Get audio data fail".to_owned()))
     }
 }
 
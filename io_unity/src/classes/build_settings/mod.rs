@@ -0,0 +1,14 @@
+pub mod type_tree;
+
+use crate::{def_unity_class, error::ReadResult};
+
+def_unity_class!(BuildSettings);
+
+/// The BuildSettings of `globalgamemanagers`, see
+/// [`crate::unity_asset_view::UnityAssetViewer::build_settings`].
+pub trait BuildSettingsObject {
+    /// Paths of the scenes in the build, in build index order.
+    fn get_scenes(&self) -> ReadResult<Vec<String>>;
+    /// The Unity version the game was built with.
+    fn get_version(&self) -> ReadResult<String>;
+}
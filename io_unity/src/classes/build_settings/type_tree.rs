@@ -0,0 +1,20 @@
+use super::{BuildSettings, BuildSettingsObject};
+use crate::{
+    error::ReadResult,
+    type_tree::{convert::TryCastFrom, TypeTreeObjectRef},
+};
+
+impl BuildSettingsObject for BuildSettings<'_> {
+    fn get_scenes(&self) -> ReadResult<Vec<String>> {
+        // Before 5.3 the scene list is named `levels`.
+        <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/m_Scenes/Array")
+            .or_else(|_| <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/levels/Array"))?
+            .iter()
+            .map(|scene| String::try_cast_from(scene, "/Base"))
+            .collect()
+    }
+
+    fn get_version(&self) -> ReadResult<String> {
+        String::try_cast_from(self.inner, "/Base/m_Version")
+    }
+}
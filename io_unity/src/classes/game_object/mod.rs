@@ -0,0 +1,16 @@
+pub mod type_tree;
+
+use crate::{def_unity_class, error::ReadResult, type_tree::TypeTreeObjectRef};
+
+def_unity_class!(GameObject);
+
+pub trait GameObjectObject {
+    fn get_name(&self) -> ReadResult<String>;
+    /// Component PPtrs, the Transform being the first.
+    fn get_components(&self) -> ReadResult<Vec<TypeTreeObjectRef>>;
+    /// PPtr to the object of the source prefab this one is an instance of,
+    /// null when it isn't part of a prefab instance.
+    fn get_corresponding_source_object(&self) -> ReadResult<TypeTreeObjectRef>;
+    /// PPtr to the PrefabInstance holding the overrides of this object.
+    fn get_prefab_instance(&self) -> ReadResult<TypeTreeObjectRef>;
+}
@@ -0,0 +1,39 @@
+use super::{GameObject, GameObjectObject};
+use crate::error::ReadResult;
+use crate::type_tree::convert::TryCastFrom;
+use crate::type_tree::TypeTreeObjectRef;
+
+impl GameObjectObject for GameObject<'_> {
+    fn get_name(&self) -> ReadResult<String> {
+        String::try_cast_from(self.inner, "/Base/m_Name")
+    }
+
+    fn get_components(&self) -> ReadResult<Vec<TypeTreeObjectRef>> {
+        <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/m_Component/Array")?
+            .iter()
+            .map(|pair| TypeTreeObjectRef::try_cast_from(pair, "/Base/component"))
+            .collect()
+    }
+
+    fn get_corresponding_source_object(&self) -> ReadResult<TypeTreeObjectRef> {
+        get_corresponding_source_object(self.inner)
+    }
+
+    fn get_prefab_instance(&self) -> ReadResult<TypeTreeObjectRef> {
+        get_prefab_instance(self.inner)
+    }
+}
+
+/// `m_CorrespondingSourceObject`, named `m_PrefabParentObject` before 2018.3.
+pub(crate) fn get_corresponding_source_object(
+    inner: &TypeTreeObjectRef,
+) -> ReadResult<TypeTreeObjectRef> {
+    TypeTreeObjectRef::try_cast_from(inner, "/Base/m_CorrespondingSourceObject")
+        .or_else(|_| TypeTreeObjectRef::try_cast_from(inner, "/Base/m_PrefabParentObject"))
+}
+
+/// `m_PrefabInstance`, named `m_PrefabInternal` before 2018.3.
+pub(crate) fn get_prefab_instance(inner: &TypeTreeObjectRef) -> ReadResult<TypeTreeObjectRef> {
+    TypeTreeObjectRef::try_cast_from(inner, "/Base/m_PrefabInstance")
+        .or_else(|_| TypeTreeObjectRef::try_cast_from(inner, "/Base/m_PrefabInternal"))
+}
@@ -0,0 +1,20 @@
+pub mod type_tree;
+
+use crate::{def_unity_class, error::ReadResult};
+
+def_unity_class!(LineRenderer);
+
+/// The line a LineRenderer draws. Its material and other renderer fields
+/// are read with [`crate::classes::renderer::RendererObject`].
+pub trait LineRendererObject {
+    /// The points of the line, in world space when
+    /// [`Self::get_use_world_space`] is set and local space otherwise.
+    fn get_positions(&self) -> ReadResult<Vec<glam::Vec3>>;
+    fn get_use_world_space(&self) -> ReadResult<bool>;
+    /// Whether the last point joins back to the first. Added in 2017.3,
+    /// `false` before.
+    fn get_loop(&self) -> ReadResult<bool>;
+    /// The width the width curve is scaled by. Versions before 5.5 store
+    /// a start and end width instead and give the start width.
+    fn get_width_multiplier(&self) -> ReadResult<f32>;
+}
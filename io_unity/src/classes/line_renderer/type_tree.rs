@@ -0,0 +1,27 @@
+use super::{LineRenderer, LineRendererObject};
+use crate::{
+    error::ReadResult,
+    type_tree::{convert::TryCastFrom, TypeTreeObjectRef},
+};
+
+impl LineRendererObject for LineRenderer<'_> {
+    fn get_positions(&self) -> ReadResult<Vec<glam::Vec3>> {
+        <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/m_Positions/Array")?
+            .iter()
+            .map(|position| glam::Vec3::try_cast_from(position, "/Base"))
+            .collect()
+    }
+
+    fn get_use_world_space(&self) -> ReadResult<bool> {
+        bool::try_cast_from(self.inner, "/Base/m_UseWorldSpace")
+    }
+
+    fn get_loop(&self) -> ReadResult<bool> {
+        Ok(bool::try_cast_from(self.inner, "/Base/m_Loop").unwrap_or(false))
+    }
+
+    fn get_width_multiplier(&self) -> ReadResult<f32> {
+        f32::try_cast_from(self.inner, "/Base/m_Parameters/widthMultiplier")
+            .or_else(|_| f32::try_cast_from(self.inner, "/Base/m_Parameters/startWidth"))
+    }
+}
@@ -0,0 +1,24 @@
+pub mod type_tree;
+
+use crate::{
+    def_unity_class, error::ReadResult, type_tree::TypeTreeObjectRef,
+    unity_asset_view::UnityAssetViewer,
+};
+
+def_unity_class!(Material);
+
+pub trait MaterialObject {
+    fn get_name(&self) -> ReadResult<String>;
+    fn get_shader(&self) -> ReadResult<TypeTreeObjectRef>;
+    /// Texture properties of `m_SavedProperties` by name, each a
+    /// `UnityTexEnv` holding the texture PPtr, scale and offset.
+    fn get_tex_envs(&self) -> ReadResult<Vec<(String, TypeTreeObjectRef)>>;
+    /// The Texture2D assigned to each texture property, resolving PPtrs
+    /// across the files of `viewer`. Unassigned slots and textures of other
+    /// classes, such as Cubemap or RenderTexture, are left out. A texture
+    /// that cannot be found is an error.
+    fn get_referenced_textures(
+        &self,
+        viewer: &UnityAssetViewer,
+    ) -> ReadResult<Vec<(String, TypeTreeObjectRef)>>;
+}
@@ -0,0 +1,56 @@
+use super::{Material, MaterialObject};
+
+use crate::classes::p_ptr::{PPtr, PPtrObject};
+use crate::classes::ClassIDType;
+use crate::error::{Error, ReadResult};
+use crate::type_tree::convert::TryCastFrom;
+use crate::type_tree::TypeTreeObjectRef;
+use crate::unity_asset_view::UnityAssetViewer;
+
+impl MaterialObject for Material<'_> {
+    fn get_name(&self) -> ReadResult<String> {
+        String::try_cast_from(self.inner, "/Base/m_Name")
+    }
+
+    fn get_shader(&self) -> ReadResult<TypeTreeObjectRef> {
+        TypeTreeObjectRef::try_cast_from(self.inner, "/Base/m_Shader")
+    }
+
+    fn get_tex_envs(&self) -> ReadResult<Vec<(String, TypeTreeObjectRef)>> {
+        let mut tex_envs = Vec::new();
+        for pair in <Vec<TypeTreeObjectRef>>::try_cast_from(
+            self.inner,
+            "/Base/m_SavedProperties/m_TexEnvs/Array",
+        )? {
+            // Before 2017.3 the name is a FastPropertyName struct.
+            let name = String::try_cast_from(&pair, "/Base/first")
+                .or_else(|_| String::try_cast_from(&pair, "/Base/first/name"))?;
+            tex_envs.push((
+                name,
+                TypeTreeObjectRef::try_cast_from(&pair, "/Base/second")?,
+            ));
+        }
+        Ok(tex_envs)
+    }
+
+    fn get_referenced_textures(
+        &self,
+        viewer: &UnityAssetViewer,
+    ) -> ReadResult<Vec<(String, TypeTreeObjectRef)>> {
+        let mut textures = Vec::new();
+        for (name, tex_env) in self.get_tex_envs()? {
+            let texture = TypeTreeObjectRef::try_cast_from(&tex_env, "/Base/m_Texture")?;
+            let texture = PPtr::new(&texture);
+            if texture.is_null() {
+                continue;
+            }
+            let texture = texture
+                .get_type_tree_object_in_view(viewer)?
+                .ok_or_else(|| Error::Other(format!("texture of {name} not found")))?;
+            if texture.class_id == ClassIDType::Texture2D as i32 {
+                textures.push((name, texture.into()));
+            }
+        }
+        Ok(textures)
+    }
+}
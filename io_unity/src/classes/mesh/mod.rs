@@ -1,7 +1,11 @@
 pub mod type_tree;
 
-use crate::{def_unity_class, error::ReadResult};
+use crate::{
+    def_unity_class,
+    error::{Error, ReadResult},
+};
 use binrw::binrw;
+use glam::{Mat4, Vec3};
 use num_enum::TryFromPrimitive;
 
 def_unity_class!(Mesh);
@@ -11,8 +15,236 @@ pub trait MeshObject {
     fn get_vertex_buff(&self, sub_mesh_id: usize) -> ReadResult<Vec<f32>>;
     fn get_normal_buff(&self, sub_mesh_id: usize) -> ReadResult<Vec<f32>>;
     fn get_uv0_buff(&self, sub_mesh_id: usize) -> ReadResult<Vec<f32>>;
+    /// rgba per vertex of the sub mesh, empty when the mesh has no colors.
+    fn get_color_buff(&self, sub_mesh_id: usize) -> ReadResult<Vec<f32>>;
     fn get_sub_mesh_count(&self) -> ReadResult<usize>;
     fn get_bone_weights_buff(&self, sub_mesh_id: usize) -> ReadResult<Vec<BoneWeights>>;
+    /// Inverse bind matrices, one per bone.
+    fn get_bind_pose(&self) -> ReadResult<Vec<Mat4>>;
+    /// CRC32 of each bone's transform path, in bone order.
+    fn get_bone_name_hashes(&self) -> ReadResult<Vec<u32>>;
+    /// Bone weights of every vertex decoded from `m_CompressedMesh`,
+    /// for meshes that were imported with mesh compression.
+    fn get_compressed_bone_weights(&self) -> ReadResult<Vec<BoneWeights>>;
+    /// Bounds stored in `m_LocalAABB`, read without decoding any vertex.
+    fn get_local_aabb(&self) -> ReadResult<AABB>;
+    /// Every vertex attribute of the whole mesh, decoded to floats and
+    /// laid out the way glTF and most exporters expect.
+    fn attributes(&self) -> ReadResult<MeshAttributes>;
+    /// The vertex colors of the whole mesh, 8 bit colors scaled to 0..1.
+    /// Empty when the mesh has no color channel.
+    fn colors(&self) -> ReadResult<Vec<[f32; 4]>>;
+    /// Index range and topology of every sub mesh, in material slot order.
+    fn sub_meshes(&self) -> ReadResult<Vec<SubMeshInfo>>;
+    /// Blend shape channels decoded from `m_Shapes`, in channel order. Their
+    /// deltas are never part of `m_CompressedMesh`, so compressed meshes
+    /// have them too.
+    fn get_blend_shapes(&self) -> ReadResult<Vec<BlendShapeChannel>>;
+
+    /// Bounds of the decoded vertex positions of every sub mesh,
+    /// for when the stored bounds are zeroed or suspect.
+    fn get_computed_aabb(&self) -> ReadResult<AABB> {
+        let mut min = Vec3::splat(f32::INFINITY);
+        let mut max = Vec3::splat(f32::NEG_INFINITY);
+        for sub_mesh_id in 0..self.get_sub_mesh_count()? {
+            for position in self.get_vertex_buff(sub_mesh_id)?.chunks_exact(3) {
+                let position = Vec3::from_slice(position);
+                min = min.min(position);
+                max = max.max(position);
+            }
+        }
+        if min.x > max.x {
+            return Ok(AABB::default());
+        }
+        Ok(AABB {
+            center: (min + max) / 2.0,
+            extent: (max - min) / 2.0,
+        })
+    }
+
+    /// Wavefront OBJ text with one group per sub mesh. Normals and UVs are
+    /// written when the sub mesh has one per vertex, and vertex colors as
+    /// the rgb after each position, the common extension to the format.
+    /// Triangle strips are written as triangles, quads as four sided faces,
+    /// and lines and points as OBJ line and point elements.
+    fn to_obj(&self) -> ReadResult<String> {
+        let mut obj = String::new();
+        let mut vertex_base = 1;
+        for (sub_mesh_id, sub_mesh) in self.sub_meshes()?.into_iter().enumerate() {
+            let vertices = self.get_vertex_buff(sub_mesh_id)?;
+            let vertex_count = vertices.len() / 3;
+            let normals = self
+                .get_normal_buff(sub_mesh_id)
+                .ok()
+                .filter(|normals| normals.len() == vertex_count * 3);
+            let uvs = self
+                .get_uv0_buff(sub_mesh_id)
+                .ok()
+                .filter(|uvs| uvs.len() == vertex_count * 2);
+            let colors = self
+                .get_color_buff(sub_mesh_id)
+                .ok()
+                .filter(|colors| colors.len() == vertex_count * 4);
+
+            obj.push_str(&format!("g sub_mesh_{sub_mesh_id}\n"));
+            for (index, v) in vertices.chunks_exact(3).enumerate() {
+                match &colors {
+                    Some(colors) => {
+                        let c = &colors[index * 4..index * 4 + 3];
+                        obj.push_str(&format!(
+                            "v {} {} {} {} {} {}\n",
+                            v[0], v[1], v[2], c[0], c[1], c[2]
+                        ))
+                    }
+                    None => obj.push_str(&format!("v {} {} {}\n", v[0], v[1], v[2])),
+                }
+            }
+            for vn in normals.iter().flat_map(|normals| normals.chunks_exact(3)) {
+                obj.push_str(&format!("vn {} {} {}\n", vn[0], vn[1], vn[2]));
+            }
+            for vt in uvs.iter().flat_map(|uvs| uvs.chunks_exact(2)) {
+                obj.push_str(&format!("vt {} {}\n", vt[0], vt[1]));
+            }
+            let vertex_ref = |index: u32| {
+                let index = index as usize + vertex_base;
+                match (normals.is_some(), uvs.is_some()) {
+                    (true, true) => format!("{index}/{index}/{index}"),
+                    (true, false) => format!("{index}//{index}"),
+                    (false, true) => format!("{index}/{index}"),
+                    (false, false) => index.to_string(),
+                }
+            };
+            let position_ref = |index: u32| (index as usize + vertex_base).to_string();
+            let indices = self.get_index_buff(sub_mesh_id)?;
+            match sub_mesh.topology {
+                GfxPrimitiveType::Triangles => {
+                    for face in indices.chunks_exact(3) {
+                        obj.push_str(&obj_element("f", face, vertex_ref));
+                    }
+                }
+                GfxPrimitiveType::TriangleStrip => {
+                    // Every other triangle of a strip winds the other way.
+                    // Strips are joined by repeating indices, which leaves
+                    // degenerate triangles to skip.
+                    for (index, face) in indices.windows(3).enumerate() {
+                        if face[0] == face[1] || face[1] == face[2] || face[0] == face[2] {
+                            continue;
+                        }
+                        let face = if index % 2 == 0 {
+                            [face[0], face[1], face[2]]
+                        } else {
+                            [face[1], face[0], face[2]]
+                        };
+                        obj.push_str(&obj_element("f", &face, vertex_ref));
+                    }
+                }
+                GfxPrimitiveType::Quads => {
+                    for face in indices.chunks_exact(4) {
+                        obj.push_str(&obj_element("f", face, vertex_ref));
+                    }
+                }
+                GfxPrimitiveType::Lines => {
+                    for line in indices.chunks_exact(2) {
+                        obj.push_str(&obj_element("l", line, position_ref));
+                    }
+                }
+                GfxPrimitiveType::LineStrip => {
+                    if indices.len() >= 2 {
+                        obj.push_str(&obj_element("l", &indices, position_ref));
+                    }
+                }
+                GfxPrimitiveType::Points => {
+                    if !indices.is_empty() {
+                        obj.push_str(&obj_element("p", &indices, position_ref));
+                    }
+                }
+            }
+            vertex_base += vertex_count;
+        }
+        Ok(obj)
+    }
+}
+
+/// One OBJ element line, such as `f 1 2 3`, over the given indices.
+fn obj_element(kind: &str, indices: &[u32], vertex_ref: impl Fn(u32) -> String) -> String {
+    let refs: Vec<_> = indices.iter().map(|index| vertex_ref(*index)).collect();
+    format!("{kind} {}\n", refs.join(" "))
+}
+
+/// Vertex attributes of a mesh with a fixed number of components per
+/// vertex, whatever format they were stored in. A channel the mesh does
+/// not have is an empty vector, as are all of them for meshes only stored
+/// in `m_CompressedMesh`.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct MeshAttributes {
+    /// xyz per vertex.
+    pub positions: Vec<f32>,
+    /// xyz per vertex.
+    pub normals: Vec<f32>,
+    /// xyzw per vertex, w being the handedness of the bitangent.
+    pub tangents: Vec<f32>,
+    /// uv per vertex for each of Unity's eight texture coordinate channels.
+    pub uvs: [Vec<f32>; 8],
+    /// rgba per vertex, in 0..1 for normalized formats.
+    pub colors: Vec<f32>,
+    /// Triangle list of every sub mesh in order, indexing the whole vertex
+    /// buffer rather than each sub mesh.
+    pub indices: Vec<u32>,
+}
+
+/// A part of a mesh drawn with one material, that of the renderer's
+/// material slot `material_index`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SubMeshInfo {
+    pub topology: GfxPrimitiveType,
+    /// First index of the sub mesh in the index buffer, counted in indices
+    /// rather than bytes whatever `m_IndexFormat` is.
+    pub first_index: u64,
+    pub index_count: u64,
+    /// Added to every index of the sub mesh to get the vertex it refers to.
+    pub base_vertex: u64,
+    /// The range of vertices the indices refer to.
+    pub first_vertex: u64,
+    pub vertex_count: u64,
+    pub material_index: usize,
+}
+
+/// A named blend shape, whose frames are blended by the renderer's weight
+/// for the channel.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BlendShapeChannel {
+    pub name: String,
+    /// CRC32 of the name, which animation curves refer to the channel by.
+    pub name_hash: u32,
+    pub frames: Vec<BlendShapeFrame>,
+}
+
+/// The deltas applied to the mesh at full `weight`, between 0 and 100.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BlendShapeFrame {
+    pub weight: f32,
+    pub has_normals: bool,
+    pub has_tangents: bool,
+    /// Only the vertices the frame moves.
+    pub vertices: Vec<BlendShapeVertex>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BlendShapeVertex {
+    /// The vertex of the mesh the deltas apply to.
+    pub index: u32,
+    pub position: Vec3,
+    /// Zero unless the frame `has_normals`.
+    pub normal: Vec3,
+    /// Zero unless the frame `has_tangents`.
+    pub tangent: Vec3,
+}
+
+/// Axis aligned bounding box as Unity stores it, a center and half size.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct AABB {
+    pub center: Vec3,
+    pub extent: Vec3,
 }
 
 pub fn get_format_size(format: VertexFormat) -> u8 {
@@ -29,6 +261,95 @@ pub fn get_format_size(format: VertexFormat) -> u8 {
     }
 }
 
+/// Unpack the integers of a Unity `PackedBitVector`, `bit_size` bits each.
+/// Fails when `bit_size` is not 1 to 32, or when `data` is too short to
+/// hold `num_items` items.
+pub fn unpack_ints(data: &[u8], num_items: usize, bit_size: u8) -> ReadResult<Vec<u32>> {
+    if num_items == 0 {
+        return Ok(Vec::new());
+    }
+    if !(1..=32).contains(&bit_size) {
+        return Err(Error::Other(format!(
+            "packed bit vector of {bit_size} bit items"
+        )));
+    }
+    let bit_size = bit_size as usize;
+    let fits = num_items
+        .checked_mul(bit_size)
+        .is_some_and(|bits| bits.div_ceil(8) <= data.len());
+    if !fits {
+        return Err(Error::Other(format!(
+            "{num_items} items of {bit_size} bits do not fit in {} bytes",
+            data.len()
+        )));
+    }
+    let mut items = Vec::with_capacity(num_items);
+    let mut index_pos = 0;
+    let mut bit_pos = 0;
+    for _ in 0..num_items {
+        let mut item = 0u32;
+        let mut bits = 0;
+        while bits < bit_size {
+            let Some(byte) = data.get(index_pos) else {
+                break;
+            };
+            item |= ((*byte as u32) >> bit_pos) << bits;
+            let num = (bit_size - bits).min(8 - bit_pos);
+            bit_pos += num;
+            bits += num;
+            if bit_pos == 8 {
+                index_pos += 1;
+                bit_pos = 0;
+            }
+        }
+        if bit_size < 32 {
+            item &= (1 << bit_size) - 1;
+        }
+        items.push(item);
+    }
+    Ok(items)
+}
+
+/// Rebuild per vertex bone weights from the compressed mesh streams.
+/// Weights are stored in 1/31 steps and a vertex ends once they sum to 31,
+/// or after three weights, the fourth then taking the remainder.
+pub fn decode_compressed_bone_weights(weights: &[u32], bone_indices: &[u32]) -> Vec<BoneWeights> {
+    let mut result = Vec::new();
+    let mut bone_indices = bone_indices.iter();
+    let mut current = BoneWeights {
+        weight: Vec::with_capacity(4),
+        bone_index: Vec::with_capacity(4),
+    };
+    let mut sum = 0;
+    for weight in weights {
+        current.weight.push(*weight as f32 / 31.0);
+        current
+            .bone_index
+            .push(bone_indices.next().copied().unwrap_or_default() as i64);
+        sum += weight;
+
+        if sum >= 31 || current.weight.len() == 3 {
+            if sum < 31 {
+                current.weight.push((31 - sum) as f32 / 31.0);
+                current
+                    .bone_index
+                    .push(bone_indices.next().copied().unwrap_or_default() as i64);
+            }
+            current.weight.resize(4, 0.0);
+            current.bone_index.resize(4, 0);
+            result.push(std::mem::replace(
+                &mut current,
+                BoneWeights {
+                    weight: Vec::with_capacity(4),
+                    bone_index: Vec::with_capacity(4),
+                },
+            ));
+            sum = 0;
+        }
+    }
+    result
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum StreamBuff {
     Float(Vec<Vec<f32>>),
@@ -133,3 +454,24 @@ pub enum ChannelType {
     kShaderChannelBlendWeight,
     kShaderChannelBlendIndices,
 }
+
+#[cfg(test)]
+mod test {
+    use super::unpack_ints;
+
+    #[test]
+    fn unpack_ints_reads_items_across_bytes() {
+        // 3, 5 and 1 as 4 bit items, then 0x1ff as a 9 bit one.
+        assert_eq!(unpack_ints(&[0x53, 0x01], 3, 4).unwrap(), [3, 5, 1]);
+        assert_eq!(unpack_ints(&[0xff, 0x01], 1, 9).unwrap(), [0x1ff]);
+        assert!(unpack_ints(&[], 0, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn unpack_ints_rejects_bad_sizes() {
+        assert!(unpack_ints(&[0; 8], 1, 0).is_err());
+        assert!(unpack_ints(&[0; 8], 1, 33).is_err());
+        assert!(unpack_ints(&[0; 2], 3, 8).is_err());
+        assert!(unpack_ints(&[0; 2], usize::MAX, 32).is_err());
+    }
+}
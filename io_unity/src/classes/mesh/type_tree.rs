@@ -1,7 +1,10 @@
 use std::io::{Cursor, Seek, SeekFrom};
+use std::ops::Range;
 
 use super::{
-    get_format_size, BoneWeights, ChannelType, Mesh, MeshObject, StreamBuff, VertexFormat,
+    decode_compressed_bone_weights, get_format_size, unpack_ints, BlendShapeChannel,
+    BlendShapeFrame, BlendShapeVertex, BoneWeights, ChannelType, GfxPrimitiveType, Mesh,
+    MeshAttributes, MeshObject, StreamBuff, SubMeshInfo, VertexFormat, AABB,
 };
 
 use crate::def_unity_class;
@@ -11,6 +14,7 @@ use crate::type_tree::TypeTreeObjectRef;
 use crate::{classes::CastRef, error::ReadResult};
 
 use binrw::{BinRead, VecArgs};
+use glam::Mat4;
 
 impl MeshObject for Mesh<'_> {
     fn get_index_buff(&self, sub_mesh_id: usize) -> ReadResult<Vec<u32>> {
@@ -22,36 +26,44 @@ impl MeshObject for Mesh<'_> {
             )))?
             .cast_as();
 
+        // Sub meshes index into one buffer shared by the whole mesh. Each
+        // stored index is relative to the sub mesh's base vertex, and the
+        // result relative to its first vertex.
         let buff = self.get_index_buffer()?;
         let mut reader = Cursor::new(buff);
         reader.seek(SeekFrom::Start(sub_mesh.get_first_byte()?))?;
+        let count = sub_mesh.get_index_count()? as usize;
 
-        let first_vertex = sub_mesh.get_first_vertex()?;
-
-        if self.get_index_format()? == 0 {
-            let buff = <Vec<u16>>::read_options(
+        // Meshes before 2017.3 have no m_IndexFormat and 16 bit indices.
+        let indices = if self.get_index_format().unwrap_or(0) == 0 {
+            <Vec<u16>>::read_options(
                 &mut reader,
                 self.inner.get_endian(),
-                VecArgs {
-                    count: sub_mesh.get_index_count()? as usize,
-                    inner: (),
-                },
-            )?;
-            Ok(buff
-                .into_iter()
-                .map(|i| i as u32 - first_vertex as u32)
-                .collect())
+                VecArgs { count, inner: () },
+            )?
+            .into_iter()
+            .map(u32::from)
+            .collect()
         } else {
-            let buff = <Vec<u32>>::read_options(
+            <Vec<u32>>::read_options(
                 &mut reader,
                 self.inner.get_endian(),
-                VecArgs {
-                    count: sub_mesh.get_index_count()? as usize,
-                    inner: (),
-                },
-            )?;
-            Ok(buff.into_iter().map(|i| i - first_vertex as u32).collect())
-        }
+                VecArgs { count, inner: () },
+            )?
+        };
+
+        let offset =
+            sub_mesh.get_base_vertex().unwrap_or(0) as i64 - sub_mesh.get_first_vertex()? as i64;
+        indices
+            .into_iter()
+            .map(|index| {
+                u32::try_from(index as i64 + offset).map_err(|_| {
+                    Error::Other(format!(
+                        "index {index} of sub mesh {sub_mesh_id} is before its first vertex"
+                    ))
+                })
+            })
+            .collect()
     }
 
     fn get_vertex_buff(&self, sub_mesh_id: usize) -> ReadResult<Vec<f32>> {
@@ -132,6 +144,17 @@ impl MeshObject for Mesh<'_> {
         let vertex_data_obj = self.get_vertex_data()?;
         let vertex_data: VertexData = (&vertex_data_obj).cast_as();
 
+        if vertex_data.get_vertex_count()? == 0 {
+            let first_vertex = sub_mesh.get_first_vertex()? as usize;
+            let vertex_count = sub_mesh.get_vertex_count()? as usize;
+            return Ok(self
+                .get_compressed_bone_weights()?
+                .into_iter()
+                .skip(first_vertex)
+                .take(vertex_count)
+                .collect());
+        }
+
         let weight_buff = match vertex_data.get_channel_stream_buff(
             &ChannelType::kShaderChannelBlendWeight,
             &sub_mesh,
@@ -159,9 +182,226 @@ impl MeshObject for Mesh<'_> {
     fn get_sub_mesh_count(&self) -> ReadResult<usize> {
         Ok(self.get_sub_meshes()?.len())
     }
+
+    fn get_bind_pose(&self) -> ReadResult<Vec<Mat4>> {
+        let mut bind_pose = Vec::new();
+        for matrix in <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/m_BindPose/Array")?
+        {
+            let mut cols = [0f32; 16];
+            for (i, value) in cols.iter_mut().enumerate() {
+                let (col, row) = (i / 4, i % 4);
+                *value = f32::try_cast_from(&matrix, format!("/Base/e{row}{col}").as_str())?;
+            }
+            bind_pose.push(Mat4::from_cols_array(&cols));
+        }
+        Ok(bind_pose)
+    }
+
+    fn get_bone_name_hashes(&self) -> ReadResult<Vec<u32>> {
+        <Vec<u32>>::try_cast_from(self.inner, "/Base/m_BoneNameHashes/Array")
+    }
+
+    fn get_compressed_bone_weights(&self) -> ReadResult<Vec<BoneWeights>> {
+        let weights = self.get_compressed_ints("/Base/m_CompressedMesh/m_Weights")?;
+        let bone_indices = self.get_compressed_ints("/Base/m_CompressedMesh/m_BoneIndices")?;
+        Ok(decode_compressed_bone_weights(&weights, &bone_indices))
+    }
+
+    fn get_local_aabb(&self) -> ReadResult<AABB> {
+        Ok(AABB {
+            center: glam::Vec3::try_cast_from(self.inner, "/Base/m_LocalAABB/m_Center")?,
+            extent: glam::Vec3::try_cast_from(self.inner, "/Base/m_LocalAABB/m_Extent")?,
+        })
+    }
+
+    fn get_color_buff(&self, sub_mesh_id: usize) -> ReadResult<Vec<f32>> {
+        let binding = self.get_sub_meshes()?;
+        let sub_mesh: SubMesh = binding
+            .get(sub_mesh_id)
+            .ok_or(Error::Other(format!(
+                "cannot get sub mesh at {sub_mesh_id}"
+            )))?
+            .cast_as();
+        let first_vertex = sub_mesh.get_first_vertex()?;
+        self.read_attribute(
+            ChannelType::kShaderChannelColor,
+            first_vertex..first_vertex + sub_mesh.get_vertex_count()?,
+            4,
+            1.0,
+        )
+    }
+
+    fn colors(&self) -> ReadResult<Vec<[f32; 4]>> {
+        let vertex_data_obj = self.get_vertex_data()?;
+        let vertex_data: VertexData = (&vertex_data_obj).cast_as();
+        Ok(self
+            .read_attribute(
+                ChannelType::kShaderChannelColor,
+                0..vertex_data.get_vertex_count()?,
+                4,
+                1.0,
+            )?
+            .chunks_exact(4)
+            .map(|color| [color[0], color[1], color[2], color[3]])
+            .collect())
+    }
+
+    fn attributes(&self) -> ReadResult<MeshAttributes> {
+        let vertex_data_obj = self.get_vertex_data()?;
+        let vertex_data: VertexData = (&vertex_data_obj).cast_as();
+        let vertices = 0..vertex_data.get_vertex_count()?;
+        let read = |channel: ChannelType, components: usize, fill: f32| {
+            self.read_attribute(channel, vertices.clone(), components, fill)
+        };
+
+        let mut indices = Vec::new();
+        for (sub_mesh_id, sub_mesh) in self.get_sub_meshes()?.iter().enumerate() {
+            let first_vertex = SubMesh::new(sub_mesh).get_first_vertex()? as u32;
+            indices.extend(
+                self.get_index_buff(sub_mesh_id)?
+                    .into_iter()
+                    .map(|i| i + first_vertex),
+            );
+        }
+
+        Ok(MeshAttributes {
+            positions: read(ChannelType::kShaderChannelVertex, 3, 0.0)?,
+            normals: read(ChannelType::kShaderChannelNormal, 3, 0.0)?,
+            tangents: read(ChannelType::kShaderChannelTangent, 4, 1.0)?,
+            uvs: [
+                read(ChannelType::kShaderChannelTexCoord0, 2, 0.0)?,
+                read(ChannelType::kShaderChannelTexCoord1, 2, 0.0)?,
+                read(ChannelType::kShaderChannelTexCoord2, 2, 0.0)?,
+                read(ChannelType::kShaderChannelTexCoord3, 2, 0.0)?,
+                read(ChannelType::kShaderChannelTexCoord4, 2, 0.0)?,
+                read(ChannelType::kShaderChannelTexCoord5, 2, 0.0)?,
+                read(ChannelType::kShaderChannelTexCoord6, 2, 0.0)?,
+                read(ChannelType::kShaderChannelTexCoord7, 2, 0.0)?,
+            ],
+            colors: read(ChannelType::kShaderChannelColor, 4, 1.0)?,
+            indices,
+        })
+    }
+
+    fn sub_meshes(&self) -> ReadResult<Vec<SubMeshInfo>> {
+        // Meshes before 2017.3 have no m_IndexFormat and 16 bit indices.
+        let index_size = if self.get_index_format().unwrap_or(0) == 0 {
+            2
+        } else {
+            4
+        };
+        self.get_sub_meshes()?
+            .iter()
+            .enumerate()
+            .map(|(material_index, sub_mesh)| {
+                let sub_mesh: SubMesh = sub_mesh.cast_as();
+                Ok(SubMeshInfo {
+                    topology: sub_mesh.get_topology()?,
+                    first_index: sub_mesh.get_first_byte()? / index_size,
+                    index_count: sub_mesh.get_index_count()?,
+                    base_vertex: sub_mesh.get_base_vertex().unwrap_or(0),
+                    first_vertex: sub_mesh.get_first_vertex()?,
+                    vertex_count: sub_mesh.get_vertex_count()?,
+                    material_index,
+                })
+            })
+            .collect()
+    }
+
+    fn get_blend_shapes(&self) -> ReadResult<Vec<BlendShapeChannel>> {
+        let vertices =
+            <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/m_Shapes/vertices/Array")?;
+        let shapes =
+            <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/m_Shapes/shapes/Array")?;
+        let full_weights =
+            <Vec<f32>>::try_cast_from(self.inner, "/Base/m_Shapes/fullWeights/Array")?;
+        let mut channels = Vec::new();
+        for channel in
+            <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/m_Shapes/channels/Array")?
+        {
+            let frame_index = i64::try_cast_from(&channel, "/Base/frameIndex")?.max(0) as usize;
+            let frame_count = i64::try_cast_from(&channel, "/Base/frameCount")?.max(0) as usize;
+            let mut frames = Vec::new();
+            for frame in frame_index..frame_index + frame_count {
+                let shape = shapes.get(frame).ok_or(Error::Other(format!(
+                    "blend shape frame {frame} outside the {} shapes",
+                    shapes.len()
+                )))?;
+                let first_vertex = u64::try_cast_from(shape, "/Base/firstVertex")? as usize;
+                let vertex_count = u64::try_cast_from(shape, "/Base/vertexCount")? as usize;
+                let frame_vertices = vertices
+                    .get(first_vertex..first_vertex + vertex_count)
+                    .ok_or(Error::Other(format!(
+                        "blend shape vertices {}..{} outside the {} vertices",
+                        first_vertex,
+                        first_vertex + vertex_count,
+                        vertices.len()
+                    )))?;
+                frames.push(BlendShapeFrame {
+                    weight: full_weights.get(frame).copied().unwrap_or(100.0),
+                    has_normals: bool::try_cast_from(shape, "/Base/hasNormals")?,
+                    has_tangents: bool::try_cast_from(shape, "/Base/hasTangents")?,
+                    vertices: frame_vertices
+                        .iter()
+                        .map(|vertex| {
+                            Ok(BlendShapeVertex {
+                                index: u32::try_cast_from(vertex, "/Base/index")?,
+                                position: glam::Vec3::try_cast_from(vertex, "/Base/vertex")?,
+                                normal: glam::Vec3::try_cast_from(vertex, "/Base/normal")?,
+                                tangent: glam::Vec3::try_cast_from(vertex, "/Base/tangent")?,
+                            })
+                        })
+                        .collect::<ReadResult<_>>()?,
+                });
+            }
+            channels.push(BlendShapeChannel {
+                name: String::try_cast_from(&channel, "/Base/name")?,
+                name_hash: u32::try_cast_from(&channel, "/Base/nameHash")?,
+                frames,
+            });
+        }
+        Ok(channels)
+    }
 }
 
 impl Mesh<'_> {
+    /// `components` floats per vertex of `vertices` from `channel`, padded
+    /// with `fill`. Empty when the mesh has no such channel.
+    fn read_attribute(
+        &self,
+        channel: ChannelType,
+        vertices: Range<u64>,
+        components: usize,
+        fill: f32,
+    ) -> ReadResult<Vec<f32>> {
+        let vertex_data_obj = self.get_vertex_data()?;
+        let vertex_data: VertexData = (&vertex_data_obj).cast_as();
+        if channel_index(&channel, vertex_data.get_channels()?.len()).is_none() {
+            return Ok(Vec::new());
+        }
+        let buff = match vertex_data.get_channel_range_buff(
+            &channel,
+            vertices,
+            self.inner.get_endian(),
+        )? {
+            StreamBuff::Float(buff) => buff,
+            StreamBuff::I64(buff) => buff
+                .into_iter()
+                .map(|v| v.into_iter().map(|v| v as f32).collect())
+                .collect(),
+        };
+        if buff.first().is_none_or(Vec::is_empty) {
+            return Ok(Vec::new());
+        }
+        Ok(buff
+            .into_iter()
+            .flat_map(|mut v| {
+                v.resize(components, fill);
+                v
+            })
+            .collect())
+    }
+
     pub fn get_sub_meshes(&self) -> ReadResult<Vec<TypeTreeObjectRef>> {
         <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/m_SubMeshes/Array")
     }
@@ -177,6 +417,13 @@ impl Mesh<'_> {
     pub fn get_vertex_data(&self) -> ReadResult<TypeTreeObjectRef> {
         TypeTreeObjectRef::try_cast_from(self.inner, "/Base/m_VertexData")
     }
+
+    fn get_compressed_ints(&self, path: &str) -> ReadResult<Vec<u32>> {
+        let num_items = u64::try_cast_from(self.inner, format!("{path}/m_NumItems").as_str())?;
+        let data = <Vec<u8>>::try_cast_from(self.inner, format!("{path}/m_Data/Array").as_str())?;
+        let bit_size = u8::try_cast_from(self.inner, format!("{path}/m_BitSize").as_str())?;
+        unpack_ints(&data, num_items as usize, bit_size)
+    }
 }
 
 def_unity_class!(SubMesh);
@@ -194,6 +441,54 @@ impl SubMesh<'_> {
     pub fn get_vertex_count(&self) -> ReadResult<u64> {
         u64::try_cast_from(self.inner, "/Base/vertexCount")
     }
+    /// Added in 2017.3.
+    pub fn get_base_vertex(&self) -> ReadResult<u64> {
+        u64::try_cast_from(self.inner, "/Base/baseVertex")
+    }
+    pub fn get_topology(&self) -> ReadResult<GfxPrimitiveType> {
+        // Before 4.0 only triangle lists and strips are told apart.
+        let topology = match i64::try_cast_from(self.inner, "/Base/topology") {
+            Ok(topology) => topology,
+            Err(_) => i64::try_cast_from(self.inner, "/Base/isTriStrip")?.min(1),
+        };
+        u32::try_from(topology)
+            .ok()
+            .and_then(|topology| GfxPrimitiveType::try_from(topology).ok())
+            .ok_or(Error::Other(format!(
+                "unknown sub mesh topology {topology}"
+            )))
+    }
+}
+
+/// Index of a channel in `m_Channels`, whose order changed as Unity added
+/// texture coordinates, told apart by the number of channels.
+fn channel_index(channel: &ChannelType, channel_count: usize) -> Option<usize> {
+    use ChannelType::*;
+    match channel_count {
+        // Before 5.0.
+        6 => match channel {
+            kShaderChannelVertex => Some(0),
+            kShaderChannelNormal => Some(1),
+            kShaderChannelColor => Some(2),
+            kShaderChannelTexCoord0 => Some(3),
+            kShaderChannelTexCoord1 => Some(4),
+            kShaderChannelTangent => Some(5),
+            _ => None,
+        },
+        // 5.0 to 2017.4.
+        8 => match channel {
+            kShaderChannelVertex => Some(0),
+            kShaderChannelNormal => Some(1),
+            kShaderChannelColor => Some(2),
+            kShaderChannelTexCoord0 => Some(3),
+            kShaderChannelTexCoord1 => Some(4),
+            kShaderChannelTexCoord2 => Some(5),
+            kShaderChannelTexCoord3 => Some(6),
+            kShaderChannelTangent => Some(7),
+            _ => None,
+        },
+        _ => Some(channel.clone() as usize).filter(|index| *index < channel_count),
+    }
 }
 
 def_unity_class!(VertexData);
@@ -212,7 +507,13 @@ impl Channel<'_> {
             .map_err(|e| Error::Other(e.to_string()))
     }
     pub fn get_dimension(&self) -> ReadResult<u64> {
-        u64::try_cast_from(self.inner, "/Base/dimension")
+        let dimension = u64::try_cast_from(self.inner, "/Base/dimension")?;
+        // Before 2018 a color is one component of the 4 byte Color format,
+        // read here as 4 UNorm8 components.
+        if dimension == 1 && u8::try_cast_from(self.inner, "/Base/format")? == 2 {
+            return Ok(4);
+        }
+        Ok(dimension)
     }
 }
 
@@ -231,7 +532,30 @@ impl VertexData<'_> {
 }
 
 impl VertexData<'_> {
+    /// `offset` and `stride` of each entry of `m_Streams`, which meshes
+    /// before 5.0 store rather than leaving them to be derived from the
+    /// channels. `None` for later meshes.
+    fn get_streams(&self) -> Option<Vec<(u64, u64)>> {
+        let streams =
+            <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/m_Streams/Array").ok()?;
+        streams
+            .iter()
+            .map(|stream| {
+                Some((
+                    u64::try_cast_from(stream, "/Base/offset").ok()?,
+                    u64::try_cast_from(stream, "/Base/stride").ok()?,
+                ))
+            })
+            .collect()
+    }
+
     fn get_stream_offset(&self, stream: u8) -> ReadResult<usize> {
+        if let Some(streams) = self.get_streams() {
+            return streams
+                .get(stream as usize)
+                .map(|(offset, _)| *offset as usize)
+                .ok_or(Error::Other(format!("vertex stream {stream} not found")));
+        }
         let mut offset = 0;
         for s in 0..stream {
             offset += self.get_stream_stride(s)? * (self.get_vertex_count()? as usize);
@@ -243,6 +567,12 @@ impl VertexData<'_> {
     }
 
     fn get_stream_stride(&self, stream: u8) -> ReadResult<usize> {
+        if let Some(streams) = self.get_streams() {
+            return streams
+                .get(stream as usize)
+                .map(|(_, stride)| *stride as usize)
+                .ok_or(Error::Other(format!("vertex stream {stream} not found")));
+        }
         let mut stride = 0u64;
         for channel in &self.get_channels()? {
             let channel: Channel = channel.cast_as();
@@ -259,17 +589,31 @@ impl VertexData<'_> {
         sub_mesh: &SubMesh,
         endian: binrw::Endian,
     ) -> ReadResult<StreamBuff> {
-        let channel = &self.get_channels()?[channel.clone() as u8 as usize];
+        let first_vertex = sub_mesh.get_first_vertex()?;
+        let vertices = first_vertex..first_vertex + sub_mesh.get_vertex_count()?;
+        self.get_channel_range_buff(channel, vertices, endian)
+    }
+
+    fn get_channel_range_buff(
+        &self,
+        channel: &ChannelType,
+        vertices: Range<u64>,
+        endian: binrw::Endian,
+    ) -> ReadResult<StreamBuff> {
+        let channels = self.get_channels()?;
+        let channel = channel_index(channel, channels.len())
+            .and_then(|index| channels.get(index))
+            .ok_or(Error::Other(format!("mesh has no {channel:?} channel")))?;
         let channel: Channel = channel.cast_as();
 
         match &channel.get_format()? {
             VertexFormat::Float => {
-                let buff = self.get_channel(&channel, sub_mesh, endian)?;
+                let buff = self.get_channel(&channel, vertices, endian)?;
                 Ok(StreamBuff::Float(buff))
             }
             VertexFormat::Float16 => {
                 let buff = self
-                    .get_channel(&channel, sub_mesh, endian)?
+                    .get_channel(&channel, vertices, endian)?
                     .into_iter()
                     .map(|f| {
                         f.into_iter()
@@ -281,7 +625,7 @@ impl VertexData<'_> {
             }
             VertexFormat::UNorm8 => {
                 let buff = self
-                    .get_channel::<u8>(&channel, sub_mesh, endian)?
+                    .get_channel::<u8>(&channel, vertices, endian)?
                     .into_iter()
                     .map(|f| f.into_iter().map(|f| f as f32 / 255.0).collect())
                     .collect();
@@ -289,7 +633,7 @@ impl VertexData<'_> {
             }
             VertexFormat::SNorm8 => {
                 let buff = self
-                    .get_channel::<i8>(&channel, sub_mesh, endian)?
+                    .get_channel::<i8>(&channel, vertices, endian)?
                     .into_iter()
                     .map(|f| f.into_iter().map(|f| (f as f32 / 127.0).max(1.0)).collect())
                     .collect();
@@ -297,7 +641,7 @@ impl VertexData<'_> {
             }
             VertexFormat::UNorm16 => {
                 let buff = self
-                    .get_channel::<u16>(&channel, sub_mesh, endian)?
+                    .get_channel::<u16>(&channel, vertices, endian)?
                     .into_iter()
                     .map(|f| f.into_iter().map(|f| f as f32 / 65535.0).collect())
                     .collect();
@@ -305,7 +649,7 @@ impl VertexData<'_> {
             }
             VertexFormat::SNorm16 => {
                 let buff = self
-                    .get_channel::<i16>(&channel, sub_mesh, endian)?
+                    .get_channel::<i16>(&channel, vertices, endian)?
                     .into_iter()
                     .map(|f| {
                         f.into_iter()
@@ -317,7 +661,7 @@ impl VertexData<'_> {
             }
             VertexFormat::UInt8 => {
                 let buff = self
-                    .get_channel::<u8>(&channel, sub_mesh, endian)?
+                    .get_channel::<u8>(&channel, vertices, endian)?
                     .into_iter()
                     .map(|f| f.into_iter().map(|f| f as i64).collect())
                     .collect();
@@ -325,7 +669,7 @@ impl VertexData<'_> {
             }
             VertexFormat::SInt8 => {
                 let buff = self
-                    .get_channel::<i8>(&channel, sub_mesh, endian)?
+                    .get_channel::<i8>(&channel, vertices, endian)?
                     .into_iter()
                     .map(|f| f.into_iter().map(|f| f as i64).collect())
                     .collect();
@@ -333,7 +677,7 @@ impl VertexData<'_> {
             }
             VertexFormat::UInt16 => {
                 let buff = self
-                    .get_channel::<u16>(&channel, sub_mesh, endian)?
+                    .get_channel::<u16>(&channel, vertices, endian)?
                     .into_iter()
                     .map(|f| f.into_iter().map(|f| f as i64).collect())
                     .collect();
@@ -341,7 +685,7 @@ impl VertexData<'_> {
             }
             VertexFormat::SInt16 => {
                 let buff = self
-                    .get_channel::<i16>(&channel, sub_mesh, endian)?
+                    .get_channel::<i16>(&channel, vertices, endian)?
                     .into_iter()
                     .map(|f| f.into_iter().map(|f| f as i64).collect())
                     .collect();
@@ -349,7 +693,7 @@ impl VertexData<'_> {
             }
             VertexFormat::UInt32 => {
                 let buff = self
-                    .get_channel::<u32>(&channel, sub_mesh, endian)?
+                    .get_channel::<u32>(&channel, vertices, endian)?
                     .into_iter()
                     .map(|f| f.into_iter().map(|f| f as i64).collect())
                     .collect();
@@ -357,7 +701,7 @@ impl VertexData<'_> {
             }
             VertexFormat::SInt32 => {
                 let buff = self
-                    .get_channel::<i32>(&channel, sub_mesh, endian)?
+                    .get_channel::<i32>(&channel, vertices, endian)?
                     .into_iter()
                     .map(|f| f.into_iter().map(|f| f as i64).collect())
                     .collect();
@@ -369,7 +713,7 @@ impl VertexData<'_> {
     fn get_channel<T: for<'a> BinRead<Args<'a> = ()> + 'static>(
         &self,
         channel: &Channel,
-        sub_mesh: &SubMesh,
+        vertices: Range<u64>,
         endian: binrw::Endian,
     ) -> ReadResult<Vec<Vec<T>>> {
         let offset = self.get_stream_offset(channel.get_stream()? as u8)?;
@@ -378,9 +722,7 @@ impl VertexData<'_> {
         let mut reader = Cursor::new(buff);
 
         let mut buff = vec![];
-        for i in sub_mesh.get_first_vertex()?
-            ..sub_mesh.get_vertex_count()? + sub_mesh.get_first_vertex()?
-        {
+        for i in vertices {
             reader.seek(SeekFrom::Start(
                 offset as u64 + i * stride as u64 + channel.get_offset()?,
             ))?;
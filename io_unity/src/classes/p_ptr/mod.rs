@@ -4,18 +4,112 @@ use crate::def_unity_class;
 use crate::error::{Error, ReadResult};
 use crate::serialized_file::SerializedFile;
 use crate::type_tree::TypeTreeObject;
-use crate::unity_asset_view::UnityAssetViewer;
-
-use std::path::PathBuf;
+use crate::unity_asset_view::{normalize_external_name, UnityAssetViewer};
 
 use super::SerializedFileRef;
 
 def_unity_class!(PPtr);
 
+/// The ids of a PPtr, owned and free of the TypeTree it was read from. It
+/// resolves like any other [`PPtrObject`].
+///
+/// With the `serde` feature it is stored as
+/// `{"serialized_file_id": .., "file_id": .., "path_id": ..}`, the shape
+/// [`PPtr`] serializes to as well. `file_id` and `path_id` are those of the
+/// file, while `serialized_file_id` is the id the viewer gave the file
+/// holding the PPtr: it only resolves against a viewer that loaded the same
+/// files in the same order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PPtrIds {
+    pub serialized_file_id: i64,
+    pub file_id: i64,
+    pub path_id: i64,
+}
+
+impl SerializedFileRef for PPtrIds {
+    fn get_serialized_file_id(&self) -> i64 {
+        self.serialized_file_id
+    }
+}
+
+impl PPtrObject for PPtrIds {
+    fn get_path_id(&self) -> ReadResult<i64> {
+        Ok(self.path_id)
+    }
+
+    fn get_file_id(&self) -> ReadResult<i64> {
+        Ok(self.file_id)
+    }
+}
+
+impl PPtr<'_> {
+    pub fn ids(&self) -> ReadResult<PPtrIds> {
+        Ok(PPtrIds {
+            serialized_file_id: self.get_serialized_file_id(),
+            file_id: self.get_file_id()?,
+            path_id: self.get_path_id()?,
+        })
+    }
+
+    /// Rebuild a PPtr from its ids, such as ones read back from
+    /// [`PPtrIds`]. A `PPtr` borrows the TypeTree it was read from, so this
+    /// gives the owned [`PPtrIds`].
+    pub fn from_ids(serialized_file_id: i64, file_id: i64, path_id: i64) -> PPtrIds {
+        PPtrIds {
+            serialized_file_id,
+            file_id,
+            path_id,
+        }
+    }
+
+    /// Whether both PPtrs refer to the same object. Each file id is resolved
+    /// through the externals of the file holding its PPtr, so PPtrs from
+    /// different files match even when their `m_FileID`s differ. Null PPtrs
+    /// and ones into files the viewer has not loaded match nothing.
+    pub fn points_to_same(&self, other: &PPtr, viewer: &UnityAssetViewer) -> bool {
+        match (canonical_ids(self, viewer), canonical_ids(other, viewer)) {
+            (Some(ids), Some(other_ids)) => ids == other_ids,
+            _ => false,
+        }
+    }
+}
+
+/// The `(serialized_file_id, path_id)` of the object a PPtr refers to.
+fn canonical_ids(pptr: &PPtr, viewer: &UnityAssetViewer) -> Option<(i64, i64)> {
+    if pptr.is_null() {
+        return None;
+    }
+    let serialized_file = viewer
+        .serialized_file_map
+        .get(&pptr.get_serialized_file_id())?;
+    let file_id = viewer.resolve_file_id(serialized_file, pptr.get_file_id().ok()?)?;
+    Some((file_id, pptr.get_path_id().ok()?))
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PPtr<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.ids()
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
 pub trait PPtrObject: SerializedFileRef {
     fn get_path_id(&self) -> ReadResult<i64>;
     fn get_file_id(&self) -> ReadResult<i64>;
 
+    /// Whether the PPtr refers to nothing, as unassigned references do.
+    /// Resolving one gives `Ok(None)` whatever its file id.
+    fn is_null(&self) -> bool {
+        self.get_path_id().is_ok_and(|path_id| path_id == 0)
+    }
+
+    /// The file the PPtr points into. A file id of 0 is `self_serialized_file`
+    /// itself, with or without a viewer, while external file ids are looked
+    /// up in `viewer` and fail with [`Error::ExternalSerializedFileNotFound`]
+    /// without one.
     fn get_serialized_file<'a>(
         &self,
         self_serialized_file: &'a SerializedFile,
@@ -32,15 +126,9 @@ pub trait PPtrObject: SerializedFileRef {
 
             if file_id > 0 {
                 if let Some(external) = externals.get(file_id as usize - 1) {
-                    if let Some(file_name) = PathBuf::from(&external.path.to_string())
-                        .file_name()
-                        .map(|f| f.to_string_lossy().into_owned())
-                    {
-                        if let Some(serialized_file) =
-                            viewer.get_serialized_file_by_path(&file_name)
-                        {
-                            return Ok(serialized_file);
-                        }
+                    let file_name = normalize_external_name(&external.path.to_string());
+                    if let Some(serialized_file) = viewer.get_serialized_file_by_path(&file_name) {
+                        return Ok(serialized_file);
                     }
                 }
             }
@@ -48,12 +136,19 @@ pub trait PPtrObject: SerializedFileRef {
         Err(Error::ExternalSerializedFileNotFound)
     }
 
+    /// Read the object the PPtr points to. Same-file PPtrs resolve against
+    /// `self_serialized_file` alone, so a single file can be walked without
+    /// building a [`UnityAssetViewer`]; external ones need the viewer, see
+    /// [`PPtrObject::get_serialized_file`].
     fn get_type_tree_object(
         &self,
         self_serialized_file: &SerializedFile,
         viewer: Option<&UnityAssetViewer>,
     ) -> Result<Option<TypeTreeObject>, Error> {
         let path_id = self.get_path_id()?;
+        if path_id == 0 {
+            return Ok(None);
+        }
         let serialized_file = self.get_serialized_file(self_serialized_file, viewer)?;
         serialized_file.get_tt_object_by_path_id(path_id)
     }
@@ -62,6 +157,9 @@ pub trait PPtrObject: SerializedFileRef {
         &self,
         viewer: &UnityAssetViewer,
     ) -> Result<Option<TypeTreeObject>, Error> {
+        if self.is_null() {
+            return Ok(None);
+        }
         let self_serialized_file = viewer
             .serialized_file_map
             .get(&self.get_serialized_file_id())
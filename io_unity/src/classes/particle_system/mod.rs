@@ -0,0 +1,46 @@
+pub mod type_tree;
+
+use crate::{def_unity_class, error::ReadResult, type_tree::ColorRGBA};
+
+def_unity_class!(ParticleSystem);
+
+/// A `MinMaxCurve` as far as its constants go. The curves of the curve
+/// modes are not decoded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinMaxCurve {
+    /// Unity's `ParticleSystemCurveMode`: constant, curve, two curves or
+    /// two constants.
+    pub mode: i16,
+    /// The constant, or the upper of two constants.
+    pub scalar: f32,
+    /// The lower of two constants. Added in 5.6, `0.0` before.
+    pub min_scalar: f32,
+}
+
+/// A `MinMaxGradient` as far as its colors go. The gradients of the
+/// gradient modes are not decoded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinMaxGradient {
+    /// Unity's `ParticleSystemGradientMode`: color, gradient, two colors,
+    /// two gradients or random color.
+    pub mode: i16,
+    /// The color, or the upper of two colors.
+    pub max_color: ColorRGBA,
+    pub min_color: ColorRGBA,
+}
+
+/// The main module and emission rate of a ParticleSystem, the fields read
+/// most often. The other modules can be read from the TypeTree directly.
+pub trait ParticleSystemObject {
+    /// `lengthInSec`, the duration of one cycle.
+    fn get_duration(&self) -> ReadResult<f32>;
+    fn get_looping(&self) -> ReadResult<bool>;
+    fn get_max_particles(&self) -> ReadResult<i32>;
+    fn get_start_lifetime(&self) -> ReadResult<MinMaxCurve>;
+    fn get_start_speed(&self) -> ReadResult<MinMaxCurve>;
+    fn get_start_size(&self) -> ReadResult<MinMaxCurve>;
+    fn get_start_color(&self) -> ReadResult<MinMaxGradient>;
+    fn get_emission_enabled(&self) -> ReadResult<bool>;
+    /// Particles emitted per second. Versions before 5.5 name it `rate`.
+    fn get_rate_over_time(&self) -> ReadResult<MinMaxCurve>;
+}
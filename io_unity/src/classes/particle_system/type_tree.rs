@@ -0,0 +1,60 @@
+use super::{MinMaxCurve, MinMaxGradient, ParticleSystem, ParticleSystemObject};
+use crate::{
+    error::ReadResult,
+    type_tree::{convert::TryCastFrom, ColorRGBA},
+};
+
+impl ParticleSystem<'_> {
+    fn get_min_max_curve(&self, path: &str) -> ReadResult<MinMaxCurve> {
+        Ok(MinMaxCurve {
+            mode: i16::try_cast_from(self.inner, format!("{path}/minMaxState").as_str())?,
+            scalar: f32::try_cast_from(self.inner, format!("{path}/scalar").as_str())?,
+            min_scalar: f32::try_cast_from(self.inner, format!("{path}/minScalar").as_str())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+impl ParticleSystemObject for ParticleSystem<'_> {
+    fn get_duration(&self) -> ReadResult<f32> {
+        f32::try_cast_from(self.inner, "/Base/lengthInSec")
+    }
+
+    fn get_looping(&self) -> ReadResult<bool> {
+        bool::try_cast_from(self.inner, "/Base/looping")
+    }
+
+    fn get_max_particles(&self) -> ReadResult<i32> {
+        i32::try_cast_from(self.inner, "/Base/InitialModule/maxNumParticles")
+    }
+
+    fn get_start_lifetime(&self) -> ReadResult<MinMaxCurve> {
+        self.get_min_max_curve("/Base/InitialModule/startLifetime")
+    }
+
+    fn get_start_speed(&self) -> ReadResult<MinMaxCurve> {
+        self.get_min_max_curve("/Base/InitialModule/startSpeed")
+    }
+
+    fn get_start_size(&self) -> ReadResult<MinMaxCurve> {
+        self.get_min_max_curve("/Base/InitialModule/startSize")
+    }
+
+    fn get_start_color(&self) -> ReadResult<MinMaxGradient> {
+        let path = "/Base/InitialModule/startColor";
+        Ok(MinMaxGradient {
+            mode: i16::try_cast_from(self.inner, format!("{path}/minMaxState").as_str())?,
+            max_color: ColorRGBA::try_cast_from(self.inner, format!("{path}/maxColor").as_str())?,
+            min_color: ColorRGBA::try_cast_from(self.inner, format!("{path}/minColor").as_str())?,
+        })
+    }
+
+    fn get_emission_enabled(&self) -> ReadResult<bool> {
+        bool::try_cast_from(self.inner, "/Base/EmissionModule/enabled")
+    }
+
+    fn get_rate_over_time(&self) -> ReadResult<MinMaxCurve> {
+        self.get_min_max_curve("/Base/EmissionModule/rateOverTime")
+            .or_else(|_| self.get_min_max_curve("/Base/EmissionModule/rate"))
+    }
+}
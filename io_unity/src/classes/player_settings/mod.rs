@@ -0,0 +1,14 @@
+pub mod type_tree;
+
+use crate::{def_unity_class, error::ReadResult};
+
+def_unity_class!(PlayerSettings);
+
+/// The PlayerSettings of `globalgamemanagers`, see
+/// [`crate::unity_asset_view::UnityAssetViewer::player_settings`].
+pub trait PlayerSettingsObject {
+    fn get_company_name(&self) -> ReadResult<String>;
+    fn get_product_name(&self) -> ReadResult<String>;
+    /// The version set in the player settings, such as `1.0.3`.
+    fn get_bundle_version(&self) -> ReadResult<String>;
+}
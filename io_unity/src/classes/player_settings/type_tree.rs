@@ -0,0 +1,16 @@
+use super::{PlayerSettings, PlayerSettingsObject};
+use crate::{error::ReadResult, type_tree::convert::TryCastFrom};
+
+impl PlayerSettingsObject for PlayerSettings<'_> {
+    fn get_company_name(&self) -> ReadResult<String> {
+        String::try_cast_from(self.inner, "/Base/companyName")
+    }
+
+    fn get_product_name(&self) -> ReadResult<String> {
+        String::try_cast_from(self.inner, "/Base/productName")
+    }
+
+    fn get_bundle_version(&self) -> ReadResult<String> {
+        String::try_cast_from(self.inner, "/Base/bundleVersion")
+    }
+}
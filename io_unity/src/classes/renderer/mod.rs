@@ -0,0 +1,41 @@
+pub mod type_tree;
+
+use super::mesh::AABB;
+use crate::{
+    def_unity_class, error::ReadResult, type_tree::TypeTreeObjectRef,
+    unity_asset_view::UnityAssetViewer,
+};
+use num_enum::TryFromPrimitive;
+
+def_unity_class!(Renderer);
+
+/// The fields every renderer component, such as MeshRenderer and
+/// SkinnedMeshRenderer, inherits from Unity's `Renderer`.
+pub trait RendererObject {
+    fn get_game_object(&self) -> ReadResult<TypeTreeObjectRef>;
+    fn get_enabled(&self) -> ReadResult<bool>;
+    fn get_cast_shadows(&self) -> ReadResult<ShadowCastingMode>;
+    fn get_receive_shadows(&self) -> ReadResult<bool>;
+    /// PPtrs of `m_Materials`, one per sub mesh.
+    fn get_material_pptrs(&self) -> ReadResult<Vec<TypeTreeObjectRef>>;
+    /// The Material of each sub mesh, resolving PPtrs across the files of
+    /// `viewer`. Empty slots and materials in files that are not loaded are
+    /// `None`, so indices still match the sub meshes.
+    fn get_materials(
+        &self,
+        viewer: &UnityAssetViewer,
+    ) -> ReadResult<Vec<Option<TypeTreeObjectRef>>>;
+    /// Bounds stored in `m_AABB`. Only SkinnedMeshRenderer stores them, the
+    /// bounds of a MeshRenderer being those of its MeshFilter's mesh.
+    fn get_bounds(&self) -> ReadResult<Option<AABB>>;
+}
+
+/// `m_CastShadows`. Versions before 5.0 store a bool, read as `Off` or `On`.
+#[derive(Debug, Eq, PartialEq, TryFromPrimitive, Clone, Copy)]
+#[repr(u8)]
+pub enum ShadowCastingMode {
+    Off,
+    On,
+    TwoSided,
+    ShadowsOnly,
+}
@@ -0,0 +1,61 @@
+use super::{Renderer, RendererObject, ShadowCastingMode};
+
+use crate::classes::mesh::AABB;
+use crate::classes::p_ptr::{PPtr, PPtrObject};
+use crate::error::{Error, ReadResult};
+use crate::type_tree::convert::TryCastFrom;
+use crate::type_tree::TypeTreeObjectRef;
+use crate::unity_asset_view::UnityAssetViewer;
+
+/// Flags stored as a bool in older versions and as a UInt8 since.
+fn read_flag(inner: &TypeTreeObjectRef, path: &str) -> ReadResult<u8> {
+    u8::try_cast_from(inner, path).or_else(|_| bool::try_cast_from(inner, path).map(u8::from))
+}
+
+impl RendererObject for Renderer<'_> {
+    fn get_game_object(&self) -> ReadResult<TypeTreeObjectRef> {
+        TypeTreeObjectRef::try_cast_from(self.inner, "/Base/m_GameObject")
+    }
+
+    fn get_enabled(&self) -> ReadResult<bool> {
+        Ok(read_flag(self.inner, "/Base/m_Enabled")? != 0)
+    }
+
+    fn get_cast_shadows(&self) -> ReadResult<ShadowCastingMode> {
+        let mode = read_flag(self.inner, "/Base/m_CastShadows")?;
+        ShadowCastingMode::try_from(mode)
+            .map_err(|_| Error::Other(format!("unknown shadow casting mode {mode}")))
+    }
+
+    fn get_receive_shadows(&self) -> ReadResult<bool> {
+        Ok(read_flag(self.inner, "/Base/m_ReceiveShadows")? != 0)
+    }
+
+    fn get_material_pptrs(&self) -> ReadResult<Vec<TypeTreeObjectRef>> {
+        <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/m_Materials/Array")
+    }
+
+    fn get_materials(
+        &self,
+        viewer: &UnityAssetViewer,
+    ) -> ReadResult<Vec<Option<TypeTreeObjectRef>>> {
+        self.get_material_pptrs()?
+            .iter()
+            .map(|material| {
+                Ok(PPtr::new(material)
+                    .get_type_tree_object_in_view(viewer)?
+                    .map(Into::into))
+            })
+            .collect()
+    }
+
+    fn get_bounds(&self) -> ReadResult<Option<AABB>> {
+        let Ok(center) = glam::Vec3::try_cast_from(self.inner, "/Base/m_AABB/m_Center") else {
+            return Ok(None);
+        };
+        Ok(Some(AABB {
+            center,
+            extent: glam::Vec3::try_cast_from(self.inner, "/Base/m_AABB/m_Extent")?,
+        }))
+    }
+}
@@ -0,0 +1,45 @@
+pub mod type_tree;
+
+use crate::{def_unity_class, error::ReadResult};
+
+def_unity_class!(Shader);
+
+/// The shader program stages a pass can hold subprograms for, in the order
+/// of the pass's `prog*` fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShaderStage {
+    Vertex,
+    Fragment,
+    Geometry,
+    Hull,
+    Domain,
+    /// Added in 2019.3.
+    RayTracing,
+}
+
+/// One compiled variant of a pass stage and the keywords that select it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShaderSubProgram {
+    pub sub_shader: usize,
+    pub pass: usize,
+    pub stage: ShaderStage,
+    /// Index of the variant's bytecode in the decompressed shader blob.
+    pub blob_index: u32,
+    /// Unity's `ShaderGpuProgramType`, the graphics API the bytecode is for.
+    pub gpu_program_type: i8,
+    /// Names of the global and local keywords enabled for the variant. An
+    /// index missing from the keyword tables is kept as its number.
+    pub keywords: Vec<String>,
+}
+
+pub trait ShaderObject {
+    fn get_name(&self) -> ReadResult<String>;
+    /// The keyword table `m_KeywordNames` of the parsed form, which
+    /// subprograms index into since 2021.2. Empty for earlier versions,
+    /// whose keywords are listed per pass in `m_NameIndices`.
+    fn get_keyword_names(&self) -> ReadResult<Vec<String>>;
+    /// Every subprogram of every pass of the parsed form, with its keywords
+    /// resolved to names. Shaders built before 5.5 have no parsed form and
+    /// give none.
+    fn get_sub_programs(&self) -> ReadResult<Vec<ShaderSubProgram>>;
+}
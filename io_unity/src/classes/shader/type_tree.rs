@@ -0,0 +1,118 @@
+use super::{Shader, ShaderObject, ShaderStage, ShaderSubProgram};
+use crate::error::ReadResult;
+use crate::type_tree::convert::TryCastFrom;
+use crate::type_tree::TypeTreeObjectRef;
+use std::collections::HashMap;
+
+const STAGES: [(&str, ShaderStage); 6] = [
+    ("progVertex", ShaderStage::Vertex),
+    ("progFragment", ShaderStage::Fragment),
+    ("progGeometry", ShaderStage::Geometry),
+    ("progHull", ShaderStage::Hull),
+    ("progDomain", ShaderStage::Domain),
+    ("progRayTracing", ShaderStage::RayTracing),
+];
+
+impl ShaderObject for Shader<'_> {
+    fn get_name(&self) -> ReadResult<String> {
+        String::try_cast_from(self.inner, "/Base/m_ParsedForm/m_Name")
+            .or_else(|_| String::try_cast_from(self.inner, "/Base/m_Name"))
+    }
+
+    fn get_keyword_names(&self) -> ReadResult<Vec<String>> {
+        let Ok(names) = <Vec<TypeTreeObjectRef>>::try_cast_from(
+            self.inner,
+            "/Base/m_ParsedForm/m_KeywordNames/Array",
+        ) else {
+            return Ok(Vec::new());
+        };
+        names
+            .iter()
+            .map(|name| String::try_cast_from(name, "/Base"))
+            .collect()
+    }
+
+    fn get_sub_programs(&self) -> ReadResult<Vec<ShaderSubProgram>> {
+        let Ok(sub_shaders) = <Vec<TypeTreeObjectRef>>::try_cast_from(
+            self.inner,
+            "/Base/m_ParsedForm/m_SubShaders/Array",
+        ) else {
+            return Ok(Vec::new());
+        };
+        let shader_keywords: HashMap<u16, String> = self
+            .get_keyword_names()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, name)| (index as u16, name))
+            .collect();
+
+        let mut sub_programs = Vec::new();
+        for (sub_shader_index, sub_shader) in sub_shaders.iter().enumerate() {
+            let passes =
+                <Vec<TypeTreeObjectRef>>::try_cast_from(sub_shader, "/Base/m_Passes/Array")?;
+            for (pass_index, pass) in passes.iter().enumerate() {
+                let keywords = if shader_keywords.is_empty() {
+                    pass_keywords(pass)
+                } else {
+                    shader_keywords.clone()
+                };
+                for (field, stage) in STAGES {
+                    let Ok(programs) = <Vec<TypeTreeObjectRef>>::try_cast_from(
+                        pass,
+                        format!("/Base/{field}/m_SubPrograms/Array").as_str(),
+                    ) else {
+                        continue;
+                    };
+                    for program in programs {
+                        sub_programs.push(ShaderSubProgram {
+                            sub_shader: sub_shader_index,
+                            pass: pass_index,
+                            stage,
+                            blob_index: u32::try_cast_from(&program, "/Base/m_BlobIndex")?,
+                            gpu_program_type: i8::try_cast_from(&program, "/Base/m_GpuProgramType")
+                                .unwrap_or_default(),
+                            keywords: keyword_indices(&program)
+                                .into_iter()
+                                .map(|index| {
+                                    keywords
+                                        .get(&index)
+                                        .cloned()
+                                        .unwrap_or_else(|| index.to_string())
+                                })
+                                .collect(),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(sub_programs)
+    }
+}
+
+/// Keyword names by index from a pass's `m_NameIndices`, which also lists
+/// property names, for versions without a shader-wide keyword table.
+fn pass_keywords(pass: &TypeTreeObjectRef) -> HashMap<u16, String> {
+    <HashMap<String, TypeTreeObjectRef>>::try_cast_from(pass, "/Base/m_NameIndices/Array")
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(name, index)| {
+            let index = i32::try_cast_from(&index, "/Base").ok()?;
+            Some((u16::try_from(index).ok()?, name))
+        })
+        .collect()
+}
+
+/// `m_KeywordIndices`, else the global then local indices it was split
+/// into from 2019.1 to 2021.1.
+fn keyword_indices(program: &TypeTreeObjectRef) -> Vec<u16> {
+    if let Ok(indices) = <Vec<u16>>::try_cast_from(program, "/Base/m_KeywordIndices/Array") {
+        return indices;
+    }
+    ["m_GlobalKeywordIndices", "m_LocalKeywordIndices"]
+        .iter()
+        .flat_map(|field| {
+            <Vec<u16>>::try_cast_from(program, format!("/Base/{field}/Array").as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
@@ -0,0 +1,398 @@
+//! ASCII FBX 7.4 export of a GameObject hierarchy with its skinned meshes.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+
+use glam::{EulerRot, Mat4, Vec3};
+
+use super::{SkinnedMeshRenderer, SkinnedMeshRendererObject};
+use crate::classes::game_object::{GameObject, GameObjectObject};
+use crate::classes::mesh::{Mesh, MeshObject};
+use crate::classes::p_ptr::{PPtr, PPtrObject};
+use crate::classes::transform::{Transform, TransformObject};
+use crate::classes::ClassIDType;
+use crate::error::{Error, ReadResult};
+use crate::type_tree::{TypeTreeObject, TypeTreeObjectRef};
+use crate::unity_asset_view::UnityAssetViewer;
+
+/// A Transform of the exported hierarchy.
+struct Node {
+    name: String,
+    parent: Option<usize>,
+    /// Local and world matrices, mirrored on x into FBX's right handed space.
+    local: Mat4,
+    global: Mat4,
+    skinned_mesh_renderer: Option<TypeTreeObjectRef>,
+}
+
+/// ASCII FBX of `game_object` and every GameObject under it, one model per
+/// Transform. SkinnedMeshRenderers are exported with their mesh, bind poses
+/// and skin weights, their bones as limb nodes. Bones outside the hierarchy
+/// are left out, as are meshes only stored in `m_CompressedMesh`.
+///
+/// Unity's left handed coordinates are mirrored on x, and one unit is a meter.
+pub fn export_fbx(viewer: &UnityAssetViewer, game_object: &GameObject) -> ReadResult<String> {
+    let root = find_component(
+        viewer,
+        game_object,
+        &[ClassIDType::Transform, ClassIDType::RectTransform],
+    )?
+    .ok_or(Error::Other("GameObject has no Transform".to_owned()))?;
+
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut node_by_transform = HashMap::new();
+    let mut pending = vec![(root, None::<usize>)];
+    while let Some((transform, parent)) = pending.pop() {
+        node_by_transform.insert(
+            (transform.serialized_file_id, transform.path_id),
+            nodes.len(),
+        );
+        let transform: TypeTreeObjectRef = transform.into();
+        let transform = Transform::new(&transform);
+        let game_object = resolve(viewer, &transform.get_game_object()?)?
+            .ok_or(Error::Other("Transform has no GameObject".to_owned()))?
+            .into();
+        let game_object = GameObject::new(&game_object);
+        let local = mirror(transform.get_local_mat()?);
+        let global = parent.map_or(local, |parent| nodes[parent].global * local);
+        let index = nodes.len();
+        nodes.push(Node {
+            name: game_object.get_name()?,
+            parent,
+            local,
+            global,
+            skinned_mesh_renderer: find_component(
+                viewer,
+                &game_object,
+                &[ClassIDType::SkinnedMeshRenderer],
+            )?
+            .map(Into::into),
+        });
+        // Reversed so children are exported in order.
+        for child in transform.get_children().unwrap_or_default().iter().rev() {
+            if let Some(child) = resolve(viewer, child)? {
+                pending.push((child, Some(index)));
+            }
+        }
+    }
+
+    let mut ids = 1_000_000i64..;
+    let model_ids: Vec<i64> = nodes.iter().map(|_| ids.next().unwrap()).collect();
+    let mut bones_of_meshes = Vec::new();
+    for node in &nodes {
+        let Some(renderer) = &node.skinned_mesh_renderer else {
+            bones_of_meshes.push(Vec::new());
+            continue;
+        };
+        let mut bones = Vec::new();
+        for bone in SkinnedMeshRenderer::new(renderer).get_bones()? {
+            let bone = resolve(viewer, &bone)?;
+            bones.push(bone.and_then(|bone| {
+                node_by_transform
+                    .get(&(bone.serialized_file_id, bone.path_id))
+                    .copied()
+            }));
+        }
+        bones_of_meshes.push(bones);
+    }
+    let is_bone: Vec<bool> = (0..nodes.len())
+        .map(|index| {
+            bones_of_meshes
+                .iter()
+                .any(|bones| bones.contains(&Some(index)))
+        })
+        .collect();
+
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut objects = String::new();
+    let mut connections = String::new();
+    let mut bind_poses = BTreeMap::new();
+
+    for (index, node) in nodes.iter().enumerate() {
+        let model_id = model_ids[index];
+        let kind = if node.skinned_mesh_renderer.is_some() {
+            "Mesh"
+        } else if is_bone[index] {
+            "LimbNode"
+        } else {
+            "Null"
+        };
+        if kind == "LimbNode" {
+            let attribute_id = ids.next().unwrap();
+            *counts.entry("NodeAttribute").or_default() += 1;
+            objects.push_str(&format!(
+                "\tNodeAttribute: {attribute_id}, \"NodeAttribute::{}\", \"LimbNode\" {{\n\t\tTypeFlags: \"Skeleton\"\n\t}}\n",
+                escape(&node.name)
+            ));
+            connections.push_str(&format!("\tC: \"OO\",{attribute_id},{model_id}\n"));
+        }
+
+        let (scale, rotation, translation) = node.local.to_scale_rotation_translation();
+        let (z, y, x) = rotation.to_euler(EulerRot::ZYX);
+        let rotation = Vec3::new(x, y, z) * (180.0 / std::f32::consts::PI);
+        *counts.entry("Model").or_default() += 1;
+        objects.push_str(&format!(
+            "\tModel: {model_id}, \"Model::{}\", \"{kind}\" {{\n\t\tVersion: 232\n\t\tProperties70:  {{\n",
+            escape(&node.name)
+        ));
+        for (name, value) in [
+            ("Lcl Translation", translation),
+            ("Lcl Rotation", rotation),
+            ("Lcl Scaling", scale),
+        ] {
+            objects.push_str(&format!(
+                "\t\t\tP: \"{name}\", \"{name}\", \"\", \"A\",{},{},{}\n",
+                value.x + 0.0,
+                value.y + 0.0,
+                value.z + 0.0
+            ));
+        }
+        objects.push_str("\t\t}\n\t\tShading: T\n\t\tCulling: \"CullingOff\"\n\t}\n");
+        let parent_id = node.parent.map_or(0, |parent| model_ids[parent]);
+        connections.push_str(&format!("\tC: \"OO\",{model_id},{parent_id}\n"));
+
+        let Some(renderer) = &node.skinned_mesh_renderer else {
+            continue;
+        };
+        let mesh = resolve(viewer, &SkinnedMeshRenderer::new(renderer).get_mesh()?)?
+            .ok_or(Error::Other(format!("{} has no mesh", node.name)))?
+            .into();
+        let mesh = Mesh::new(&mesh);
+        let attributes = mesh.attributes()?;
+        if attributes.positions.is_empty() {
+            return Err(Error::Other(format!(
+                "mesh of {} has no vertex data to export",
+                node.name
+            )));
+        }
+        let vertex_count = attributes.positions.len() / 3;
+
+        let geometry_id = ids.next().unwrap();
+        *counts.entry("Geometry").or_default() += 1;
+        objects.push_str(&format!(
+            "\tGeometry: {geometry_id}, \"Geometry::{}\", \"Mesh\" {{\n",
+            escape(&node.name)
+        ));
+        push_array(
+            &mut objects,
+            2,
+            "Vertices",
+            &mirror_vectors(&attributes.positions),
+        );
+        // Mirroring flips the winding, so the triangles are reversed. The
+        // last index of each polygon is stored as its bitwise complement.
+        let polygons: Vec<i64> = attributes
+            .indices
+            .chunks_exact(3)
+            .flat_map(|face| [face[0] as i64, face[2] as i64, !(face[1] as i64)])
+            .collect();
+        push_array(&mut objects, 2, "PolygonVertexIndex", &polygons);
+        objects.push_str("\t\tGeometryVersion: 124\n");
+        let mut layers = Vec::new();
+        if attributes.normals.len() == vertex_count * 3 {
+            objects.push_str(
+                "\t\tLayerElementNormal: 0 {\n\t\t\tVersion: 101\n\t\t\tName: \"\"\n\t\t\tMappingInformationType: \"ByVertice\"\n\t\t\tReferenceInformationType: \"Direct\"\n",
+            );
+            push_array(
+                &mut objects,
+                2,
+                "Normals",
+                &mirror_vectors(&attributes.normals),
+            );
+            objects.push_str("\t\t}\n");
+            layers.push("LayerElementNormal");
+        }
+        if attributes.uvs[0].len() == vertex_count * 2 {
+            objects.push_str(
+                "\t\tLayerElementUV: 0 {\n\t\t\tVersion: 101\n\t\t\tName: \"UVChannel_1\"\n\t\t\tMappingInformationType: \"ByVertice\"\n\t\t\tReferenceInformationType: \"Direct\"\n",
+            );
+            push_array(&mut objects, 2, "UV", &attributes.uvs[0]);
+            objects.push_str("\t\t}\n");
+            layers.push("LayerElementUV");
+        }
+        if attributes.colors.len() == vertex_count * 4 {
+            objects.push_str(
+                "\t\tLayerElementColor: 0 {\n\t\t\tVersion: 101\n\t\t\tName: \"Color\"\n\t\t\tMappingInformationType: \"ByVertice\"\n\t\t\tReferenceInformationType: \"Direct\"\n",
+            );
+            push_array(&mut objects, 2, "Colors", &attributes.colors);
+            objects.push_str("\t\t}\n");
+            layers.push("LayerElementColor");
+        }
+        objects.push_str("\t\tLayer: 0 {\n\t\t\tVersion: 100\n");
+        for layer in layers {
+            objects.push_str(&format!(
+                "\t\t\tLayerElement:  {{\n\t\t\t\tType: \"{layer}\"\n\t\t\t\tTypedIndex: 0\n\t\t\t}}\n"
+            ));
+        }
+        objects.push_str("\t\t}\n\t}\n");
+        connections.push_str(&format!("\tC: \"OO\",{geometry_id},{model_id}\n"));
+
+        let bones = &bones_of_meshes[index];
+        if bones.is_empty() {
+            continue;
+        }
+        let inverse_bind_poses = mesh.get_bind_pose()?;
+        let mut weights = vec![Vec::new(); bones.len()];
+        let mut weighted = vec![false; vertex_count];
+        for (sub_mesh_id, sub_mesh) in mesh.sub_meshes()?.iter().enumerate() {
+            let vertices = sub_mesh.first_vertex as usize..;
+            for (vertex, bone_weights) in vertices.zip(mesh.get_bone_weights_buff(sub_mesh_id)?) {
+                if vertex >= vertex_count || std::mem::replace(&mut weighted[vertex], true) {
+                    continue;
+                }
+                for (weight, bone) in bone_weights.weight.iter().zip(&bone_weights.bone_index) {
+                    if let Some(weights) = usize::try_from(*bone)
+                        .ok()
+                        .and_then(|bone| weights.get_mut(bone))
+                        .filter(|_| *weight > 0.0)
+                    {
+                        weights.push((vertex as i64, *weight));
+                    }
+                }
+            }
+        }
+
+        let skin_id = ids.next().unwrap();
+        *counts.entry("Deformer").or_default() += 1;
+        objects.push_str(&format!(
+            "\tDeformer: {skin_id}, \"Deformer::{}\", \"Skin\" {{\n\t\tVersion: 101\n\t\tLink_DeformAcuracy: 50\n\t}}\n",
+            escape(&node.name)
+        ));
+        connections.push_str(&format!("\tC: \"OO\",{skin_id},{geometry_id}\n"));
+        bind_poses.entry(index).or_insert(node.global);
+
+        for (bone_index, (bone, weights)) in bones.iter().zip(weights).enumerate() {
+            let (Some(bone), Some(inverse_bind_pose)) = (bone, inverse_bind_poses.get(bone_index))
+            else {
+                continue;
+            };
+            // The bone's world matrix when the mesh was bound, which the
+            // transform of the cluster takes back to the mesh's.
+            let transform = mirror(*inverse_bind_pose);
+            let transform_link = node.global * transform.inverse();
+            bind_poses.entry(*bone).or_insert(transform_link);
+
+            let cluster_id = ids.next().unwrap();
+            *counts.entry("Deformer").or_default() += 1;
+            objects.push_str(&format!(
+                "\tDeformer: {cluster_id}, \"SubDeformer::{}\", \"Cluster\" {{\n\t\tVersion: 100\n\t\tUserData: \"\", \"\"\n",
+                escape(&nodes[*bone].name)
+            ));
+            let (vertices, weights): (Vec<i64>, Vec<f32>) = weights.into_iter().unzip();
+            push_array(&mut objects, 2, "Indexes", &vertices);
+            push_array(&mut objects, 2, "Weights", &weights);
+            push_array(&mut objects, 2, "Transform", &transform.to_cols_array());
+            push_array(
+                &mut objects,
+                2,
+                "TransformLink",
+                &transform_link.to_cols_array(),
+            );
+            objects.push_str("\t}\n");
+            connections.push_str(&format!("\tC: \"OO\",{cluster_id},{skin_id}\n"));
+            connections.push_str(&format!("\tC: \"OO\",{},{cluster_id}\n", model_ids[*bone]));
+        }
+    }
+
+    if !bind_poses.is_empty() {
+        *counts.entry("Pose").or_default() += 1;
+        objects.push_str(&format!(
+            "\tPose: {}, \"Pose::BIND_POSES\", \"BindPose\" {{\n\t\tType: \"BindPose\"\n\t\tVersion: 100\n\t\tNbPoseNodes: {}\n",
+            ids.next().unwrap(),
+            bind_poses.len()
+        ));
+        for (node, matrix) in &bind_poses {
+            objects.push_str(&format!(
+                "\t\tPoseNode:  {{\n\t\t\tNode: {}\n",
+                model_ids[*node]
+            ));
+            push_array(&mut objects, 3, "Matrix", &matrix.to_cols_array());
+            objects.push_str("\t\t}\n");
+        }
+        objects.push_str("\t}\n");
+    }
+
+    let mut fbx = String::from(
+        "; FBX 7.4.0 project file\n\
+         FBXHeaderExtension:  {\n\tFBXHeaderVersion: 1003\n\tFBXVersion: 7400\n\tCreator: \"io_unity\"\n}\n\
+         GlobalSettings:  {\n\tVersion: 1000\n\tProperties70:  {\n\
+         \t\tP: \"UpAxis\", \"int\", \"Integer\", \"\",1\n\
+         \t\tP: \"UpAxisSign\", \"int\", \"Integer\", \"\",1\n\
+         \t\tP: \"FrontAxis\", \"int\", \"Integer\", \"\",2\n\
+         \t\tP: \"FrontAxisSign\", \"int\", \"Integer\", \"\",1\n\
+         \t\tP: \"CoordAxis\", \"int\", \"Integer\", \"\",0\n\
+         \t\tP: \"CoordAxisSign\", \"int\", \"Integer\", \"\",1\n\
+         \t\tP: \"UnitScaleFactor\", \"double\", \"Number\", \"\",100\n\
+         \t}\n}\n",
+    );
+    fbx.push_str(&format!(
+        "Definitions:  {{\n\tVersion: 100\n\tCount: {}\n",
+        counts.values().sum::<usize>()
+    ));
+    for (object_type, count) in &counts {
+        fbx.push_str(&format!(
+            "\tObjectType: \"{object_type}\" {{\n\t\tCount: {count}\n\t}}\n"
+        ));
+    }
+    fbx.push_str("}\nObjects:  {\n");
+    fbx.push_str(&objects);
+    fbx.push_str("}\nConnections:  {\n");
+    fbx.push_str(&connections);
+    fbx.push_str("}\n");
+    Ok(fbx)
+}
+
+fn resolve(
+    viewer: &UnityAssetViewer,
+    pptr: &TypeTreeObjectRef,
+) -> ReadResult<Option<TypeTreeObject>> {
+    PPtr::new(pptr).get_type_tree_object_in_view(viewer)
+}
+
+/// The first component of `game_object` of one of `classes`.
+fn find_component(
+    viewer: &UnityAssetViewer,
+    game_object: &GameObject,
+    classes: &[ClassIDType],
+) -> ReadResult<Option<TypeTreeObject>> {
+    for component in game_object.get_components()? {
+        if let Some(component) = resolve(viewer, &component)? {
+            if classes
+                .iter()
+                .any(|class| class.clone() as i32 == component.class_id)
+            {
+                return Ok(Some(component));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// A Unity matrix in FBX's space, mirrored on x. Adding zero turns the
+/// negative zeros mirroring makes into zeros, so they print as `0`.
+fn mirror(matrix: Mat4) -> Mat4 {
+    let flip = Mat4::from_scale(Vec3::new(-1.0, 1.0, 1.0));
+    Mat4::from_cols_array(&(flip * matrix * flip).to_cols_array().map(|v| v + 0.0))
+}
+
+fn mirror_vectors(values: &[f32]) -> Vec<f32> {
+    values
+        .chunks_exact(3)
+        .flat_map(|v| [0.0 - v[0], v[1], v[2]])
+        .collect()
+}
+
+/// An array property `depth` tabs in.
+fn push_array<T: Display>(fbx: &mut String, depth: usize, name: &str, values: &[T]) {
+    let indent = "\t".repeat(depth);
+    let values: Vec<String> = values.iter().map(ToString::to_string).collect();
+    fbx.push_str(&format!(
+        "{indent}{name}: *{} {{\n{indent}\ta: {}\n{indent}}}\n",
+        values.len(),
+        values.join(",")
+    ));
+}
+
+fn escape(name: &str) -> String {
+    name.replace('"', "&quot;")
+}
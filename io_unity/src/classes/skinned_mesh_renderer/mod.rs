@@ -0,0 +1,13 @@
+pub mod fbx;
+pub mod type_tree;
+
+use crate::{def_unity_class, error::ReadResult, type_tree::TypeTreeObjectRef};
+
+def_unity_class!(SkinnedMeshRenderer);
+
+pub trait SkinnedMeshRendererObject {
+    fn get_mesh(&self) -> ReadResult<TypeTreeObjectRef>;
+    /// Transform PPtrs of the bones, indexed by the mesh bone weights.
+    fn get_bones(&self) -> ReadResult<Vec<TypeTreeObjectRef>>;
+    fn get_root_bone(&self) -> ReadResult<TypeTreeObjectRef>;
+}
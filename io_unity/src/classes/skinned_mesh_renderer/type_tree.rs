@@ -0,0 +1,19 @@
+use super::{SkinnedMeshRenderer, SkinnedMeshRendererObject};
+
+use crate::error::ReadResult;
+use crate::type_tree::convert::TryCastFrom;
+use crate::type_tree::TypeTreeObjectRef;
+
+impl SkinnedMeshRendererObject for SkinnedMeshRenderer<'_> {
+    fn get_mesh(&self) -> ReadResult<TypeTreeObjectRef> {
+        TypeTreeObjectRef::try_cast_from(self.inner, "/Base/m_Mesh")
+    }
+
+    fn get_bones(&self) -> ReadResult<Vec<TypeTreeObjectRef>> {
+        <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/m_Bones/Array")
+    }
+
+    fn get_root_bone(&self) -> ReadResult<TypeTreeObjectRef> {
+        TypeTreeObjectRef::try_cast_from(self.inner, "/Base/m_RootBone")
+    }
+}
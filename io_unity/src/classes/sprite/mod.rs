@@ -0,0 +1,34 @@
+pub mod type_tree;
+
+use crate::{
+    def_unity_class,
+    error::ReadResult,
+    type_tree::{Hash128, TypeTreeObjectRef},
+};
+
+def_unity_class!(Sprite);
+
+pub trait SpriteObject {
+    fn get_name(&self) -> ReadResult<String>;
+    /// `x`, `y`, `width` and `height` of the sprite in its texture, in pixels.
+    fn get_rect(&self) -> ReadResult<glam::Vec4>;
+    fn get_pivot(&self) -> ReadResult<glam::Vec2>;
+    fn get_pixels_to_units(&self) -> ReadResult<f32>;
+    /// Nine-slice border as `left`, `bottom`, `right` and `top` in pixels.
+    fn get_border(&self) -> ReadResult<glam::Vec4>;
+    /// Custom physics shape outlines in units relative to the pivot, scale
+    /// by [`Self::get_pixels_to_units`] for pixels. Empty when none is stored.
+    fn get_physics_shape(&self) -> ReadResult<Vec<Vec<glam::Vec2>>>;
+    /// PPtr to the Texture2D, or the atlas texture when the sprite is packed.
+    fn get_texture(&self) -> ReadResult<TypeTreeObjectRef>;
+    /// PPtr to the texture holding the alpha of [`Self::get_texture`] in its
+    /// red channel, as ETC1 builds store it. `None` before Unity 5.2; the
+    /// PPtr is null when the main texture has its own alpha.
+    fn get_alpha_texture(&self) -> ReadResult<Option<TypeTreeObjectRef>>;
+    /// The key of the sprite's packing in the `m_RenderDataMap` of its
+    /// SpriteAtlas. `None` before 2017.1.
+    fn get_render_data_key(&self) -> ReadResult<Option<(Hash128, i64)>>;
+    /// PPtr to the SpriteAtlas the sprite is packed into, null when it is
+    /// not packed. `None` before 2017.1.
+    fn get_sprite_atlas(&self) -> ReadResult<Option<TypeTreeObjectRef>>;
+}
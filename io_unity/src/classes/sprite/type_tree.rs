@@ -0,0 +1,74 @@
+use super::{Sprite, SpriteObject};
+use crate::error::ReadResult;
+use crate::type_tree::convert::TryCastFrom;
+use crate::type_tree::{Hash128, TypeTreeObjectRef};
+
+impl SpriteObject for Sprite<'_> {
+    fn get_name(&self) -> ReadResult<String> {
+        String::try_cast_from(self.inner, "/Base/m_Name")
+    }
+
+    fn get_rect(&self) -> ReadResult<glam::Vec4> {
+        Ok(glam::Vec4::new(
+            f32::try_cast_from(self.inner, "/Base/m_Rect/x")?,
+            f32::try_cast_from(self.inner, "/Base/m_Rect/y")?,
+            f32::try_cast_from(self.inner, "/Base/m_Rect/width")?,
+            f32::try_cast_from(self.inner, "/Base/m_Rect/height")?,
+        ))
+    }
+
+    fn get_pivot(&self) -> ReadResult<glam::Vec2> {
+        glam::Vec2::try_cast_from(self.inner, "/Base/m_Pivot")
+    }
+
+    fn get_pixels_to_units(&self) -> ReadResult<f32> {
+        f32::try_cast_from(self.inner, "/Base/m_PixelsToUnits")
+    }
+
+    fn get_border(&self) -> ReadResult<glam::Vec4> {
+        glam::Vec4::try_cast_from(self.inner, "/Base/m_Border")
+    }
+
+    fn get_physics_shape(&self) -> ReadResult<Vec<Vec<glam::Vec2>>> {
+        // Added in 2017.1.
+        let Ok(outlines) =
+            <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/m_PhysicsShape/Array")
+        else {
+            return Ok(Vec::new());
+        };
+        outlines
+            .iter()
+            .map(|outline| {
+                <Vec<TypeTreeObjectRef>>::try_cast_from(outline, "/Base/Array")?
+                    .iter()
+                    .map(|point| glam::Vec2::try_cast_from(point, "/Base"))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn get_texture(&self) -> ReadResult<TypeTreeObjectRef> {
+        TypeTreeObjectRef::try_cast_from(self.inner, "/Base/m_RD/texture")
+    }
+
+    fn get_alpha_texture(&self) -> ReadResult<Option<TypeTreeObjectRef>> {
+        // Added in 5.2.
+        Ok(TypeTreeObjectRef::try_cast_from(self.inner, "/Base/m_RD/alphaTexture").ok())
+    }
+
+    fn get_render_data_key(&self) -> ReadResult<Option<(Hash128, i64)>> {
+        // Added in 2017.1.
+        let Ok(guid) = Hash128::try_cast_from(self.inner, "/Base/m_RenderDataKey/first") else {
+            return Ok(None);
+        };
+        Ok(Some((
+            guid,
+            i64::try_cast_from(self.inner, "/Base/m_RenderDataKey/second")?,
+        )))
+    }
+
+    fn get_sprite_atlas(&self) -> ReadResult<Option<TypeTreeObjectRef>> {
+        // Added in 2017.1.
+        Ok(TypeTreeObjectRef::try_cast_from(self.inner, "/Base/m_SpriteAtlas").ok())
+    }
+}
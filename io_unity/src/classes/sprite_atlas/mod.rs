@@ -0,0 +1,63 @@
+pub mod type_tree;
+
+use crate::{
+    def_unity_class, error::ReadResult, type_tree::Hash128, type_tree::TypeTreeObjectRef,
+    unity_asset_view::UnityAssetViewer,
+};
+#[cfg(feature = "external-class-handle-texture2d")]
+use image::RgbaImage;
+
+def_unity_class!(SpriteAtlas);
+
+/// The packing of one sprite, an entry of `m_RenderDataMap`.
+#[derive(Debug, Clone)]
+pub struct SpriteAtlasData {
+    /// The sprite's `m_RenderDataKey`.
+    pub key: (Hash128, i64),
+    /// PPtr to the page texture.
+    pub texture: TypeTreeObjectRef,
+    /// PPtr to the page's alpha texture, null when the page has its own alpha.
+    pub alpha_texture: TypeTreeObjectRef,
+    /// `x`, `y`, `width` and `height` in the page texture, in pixels.
+    pub texture_rect: glam::Vec4,
+    pub settings_raw: u32,
+}
+
+impl SpriteAtlasData {
+    /// Whether the sprite was packed rotated or flipped, from the packing
+    /// rotation in bits 2 to 5 of `settingsRaw`.
+    pub fn is_rotated(&self) -> bool {
+        (self.settings_raw >> 2) & 0xf != 0
+    }
+}
+
+/// A sprite packed into an atlas page, see [`SpriteAtlasObject::pages`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpriteRect {
+    pub name: String,
+    /// `x`, `y`, `width` and `height` in the page, in pixels, counting rows
+    /// from the bottom like the decoded page.
+    pub rect: glam::Vec4,
+    pub rotated: bool,
+}
+
+pub trait SpriteAtlasObject {
+    fn get_name(&self) -> ReadResult<String>;
+    /// PPtrs to the packed sprites.
+    fn get_packed_sprites(&self) -> ReadResult<Vec<TypeTreeObjectRef>>;
+    /// The name of each of [`Self::get_packed_sprites`], in the same order.
+    fn get_packed_sprite_names(&self) -> ReadResult<Vec<String>>;
+    fn get_render_data(&self) -> ReadResult<Vec<SpriteAtlasData>>;
+    /// The rects of [`Self::get_render_data`], named after the packed
+    /// sprite with the same render data key, resolving the sprites across
+    /// the files of `viewer`. Rects of sprites that are not loaded are left
+    /// unnamed.
+    fn get_sprite_rects(
+        &self,
+        viewer: &UnityAssetViewer,
+    ) -> ReadResult<Vec<(SpriteAtlasData, SpriteRect)>>;
+    /// Each page texture, decoded, with the sprites packed into it. Pages
+    /// come in the order the render data first uses them.
+    #[cfg(feature = "external-class-handle-texture2d")]
+    fn pages(&self, viewer: &UnityAssetViewer) -> ReadResult<Vec<(RgbaImage, Vec<SpriteRect>)>>;
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use super::{SpriteAtlas, SpriteAtlasData, SpriteAtlasObject, SpriteRect};
+use crate::classes::p_ptr::PPtr;
+use crate::classes::sprite::{Sprite, SpriteObject};
+use crate::error::ReadResult;
+use crate::type_tree::convert::TryCastFrom;
+use crate::type_tree::{Hash128, TypeTreeObjectRef};
+use crate::unity_asset_view::UnityAssetViewer;
+#[cfg(feature = "external-class-handle-texture2d")]
+use crate::{classes::p_ptr::PPtrObject, error::Error};
+#[cfg(feature = "external-class-handle-texture2d")]
+use image::RgbaImage;
+
+impl SpriteAtlasObject for SpriteAtlas<'_> {
+    fn get_name(&self) -> ReadResult<String> {
+        String::try_cast_from(self.inner, "/Base/m_Name")
+    }
+
+    fn get_packed_sprites(&self) -> ReadResult<Vec<TypeTreeObjectRef>> {
+        <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/m_PackedSprites/Array")
+    }
+
+    fn get_packed_sprite_names(&self) -> ReadResult<Vec<String>> {
+        <Vec<TypeTreeObjectRef>>::try_cast_from(
+            self.inner,
+            "/Base/m_PackedSpriteNamesToIndex/Array",
+        )?
+        .iter()
+        .map(|name| String::try_cast_from(name, "/Base"))
+        .collect()
+    }
+
+    fn get_render_data(&self) -> ReadResult<Vec<SpriteAtlasData>> {
+        <Vec<TypeTreeObjectRef>>::try_cast_from(self.inner, "/Base/m_RenderDataMap/Array")?
+            .iter()
+            .map(|pair| {
+                Ok(SpriteAtlasData {
+                    key: (
+                        Hash128::try_cast_from(pair, "/Base/first/first")?,
+                        i64::try_cast_from(pair, "/Base/first/second")?,
+                    ),
+                    texture: TypeTreeObjectRef::try_cast_from(pair, "/Base/second/texture")?,
+                    alpha_texture: TypeTreeObjectRef::try_cast_from(
+                        pair,
+                        "/Base/second/alphaTexture",
+                    )?,
+                    texture_rect: glam::Vec4::new(
+                        f32::try_cast_from(pair, "/Base/second/textureRect/x")?,
+                        f32::try_cast_from(pair, "/Base/second/textureRect/y")?,
+                        f32::try_cast_from(pair, "/Base/second/textureRect/width")?,
+                        f32::try_cast_from(pair, "/Base/second/textureRect/height")?,
+                    ),
+                    settings_raw: u32::try_cast_from(pair, "/Base/second/settingsRaw")?,
+                })
+            })
+            .collect()
+    }
+
+    fn get_sprite_rects(
+        &self,
+        viewer: &UnityAssetViewer,
+    ) -> ReadResult<Vec<(SpriteAtlasData, SpriteRect)>> {
+        let names = self.get_packed_sprite_names()?;
+        let mut names_by_key = HashMap::new();
+        for (index, sprite) in self.get_packed_sprites()?.iter().enumerate() {
+            let Some(sprite) = viewer.deref(&PPtr::new(sprite))? else {
+                continue;
+            };
+            let sprite = sprite.into();
+            let sprite = Sprite::new(&sprite);
+            if let Some(key) = sprite.get_render_data_key()? {
+                let name = match names.get(index) {
+                    Some(name) => name.clone(),
+                    None => sprite.get_name()?,
+                };
+                names_by_key.insert(key, name);
+            }
+        }
+        Ok(self
+            .get_render_data()?
+            .into_iter()
+            .map(|data| {
+                let rect = SpriteRect {
+                    name: names_by_key.get(&data.key).cloned().unwrap_or_default(),
+                    rect: data.texture_rect,
+                    rotated: data.is_rotated(),
+                };
+                (data, rect)
+            })
+            .collect())
+    }
+
+    #[cfg(feature = "external-class-handle-texture2d")]
+    fn pages(&self, viewer: &UnityAssetViewer) -> ReadResult<Vec<(RgbaImage, Vec<SpriteRect>)>> {
+        let mut pages: Vec<((i64, i64), SpriteAtlasData, Vec<SpriteRect>)> = Vec::new();
+        for (data, rect) in self.get_sprite_rects(viewer)? {
+            let texture = PPtr::new(&data.texture);
+            let key = (texture.get_file_id()?, texture.get_path_id()?);
+            match pages.iter_mut().find(|(page_key, _, _)| *page_key == key) {
+                Some((_, _, rects)) => rects.push(rect),
+                None => pages.push((key, data, vec![rect])),
+            }
+        }
+        pages
+            .into_iter()
+            .map(|(_, data, rects)| {
+                let image = viewer
+                    .texture_with_alpha(&data.texture, Some(&data.alpha_texture))?
+                    .ok_or_else(|| Error::Other("atlas page texture not found".to_owned()))?;
+                Ok((image, rects))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,15 @@
+pub mod type_tree;
+
+use crate::{def_unity_class, error::ReadResult};
+
+def_unity_class!(TextAsset);
+
+pub trait TextAssetObject {
+    /// The raw `m_Script` bytes. Not necessarily text, `.bytes` assets are
+    /// stored the same way.
+    fn get_script(&self) -> ReadResult<Vec<u8>>;
+
+    fn get_text(&self) -> ReadResult<String> {
+        Ok(String::from_utf8_lossy(&self.get_script()?).into_owned())
+    }
+}
@@ -0,0 +1,8 @@
+use super::{TextAsset, TextAssetObject};
+use crate::{error::ReadResult, type_tree::convert::TryCastFrom};
+
+impl TextAssetObject for TextAsset<'_> {
+    fn get_script(&self) -> ReadResult<Vec<u8>> {
+        <Vec<u8>>::try_cast_from(self.inner, "/Base/m_Script/Array")
+    }
+}
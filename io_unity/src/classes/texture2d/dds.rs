@@ -0,0 +1,192 @@
+use super::{max_mip_count, mip_dimensions, mip_offsets, storage_size, TextureFormat};
+use crate::error::{Error, ReadResult};
+
+const DDSD_CAPS: u32 = 0x1;
+const DDSD_HEIGHT: u32 = 0x2;
+const DDSD_WIDTH: u32 = 0x4;
+const DDSD_PITCH: u32 = 0x8;
+const DDSD_PIXELFORMAT: u32 = 0x1000;
+const DDSD_MIPMAPCOUNT: u32 = 0x20000;
+const DDSD_LINEARSIZE: u32 = 0x80000;
+
+const DDPF_ALPHAPIXELS: u32 = 0x1;
+const DDPF_ALPHA: u32 = 0x2;
+const DDPF_FOURCC: u32 = 0x4;
+const DDPF_RGB: u32 = 0x40;
+const DDPF_LUMINANCE: u32 = 0x20000;
+
+const DDSCAPS_COMPLEX: u32 = 0x8;
+const DDSCAPS_TEXTURE: u32 = 0x1000;
+const DDSCAPS_MIPMAP: u32 = 0x400000;
+
+const DDS_DIMENSION_TEXTURE2D: u32 = 3;
+
+/// How a [`TextureFormat`] is named in a DDS header.
+enum DdsPixelFormat {
+    FourCC(&'static [u8; 4]),
+    /// A `DXGI_FORMAT`, stored after a `DX10` extension header.
+    Dxgi(u32),
+    /// Uncompressed pixels described by their bit count and channel masks.
+    Masks {
+        flags: u32,
+        bit_count: u32,
+        /// Red, green, blue and alpha masks.
+        masks: [u32; 4],
+    },
+}
+
+fn dds_pixel_format(texture_format: &TextureFormat) -> Option<DdsPixelFormat> {
+    use DdsPixelFormat::*;
+
+    Some(match texture_format {
+        TextureFormat::DXT1 => FourCC(b"DXT1"),
+        TextureFormat::DXT3 => FourCC(b"DXT3"),
+        TextureFormat::DXT5 => FourCC(b"DXT5"),
+        TextureFormat::BC4 => FourCC(b"ATI1"),
+        TextureFormat::BC5 => FourCC(b"ATI2"),
+        TextureFormat::BC6H => Dxgi(95),
+        TextureFormat::BC7 => Dxgi(98),
+        TextureFormat::RHalf => Dxgi(54),
+        TextureFormat::RGHalf => Dxgi(34),
+        TextureFormat::RGBAHalf => Dxgi(10),
+        TextureFormat::RFloat => Dxgi(41),
+        TextureFormat::RGFloat => Dxgi(16),
+        TextureFormat::RGBFloat => Dxgi(6),
+        TextureFormat::RGBAFloat => Dxgi(2),
+        TextureFormat::RGB9e5Float => Dxgi(67),
+        TextureFormat::R16 => Dxgi(56),
+        TextureFormat::RG16 => Dxgi(49),
+        TextureFormat::RG32 => Dxgi(35),
+        TextureFormat::RGBA64 => Dxgi(11),
+        TextureFormat::Alpha8 => Masks {
+            flags: DDPF_ALPHA,
+            bit_count: 8,
+            masks: [0, 0, 0, 0xff],
+        },
+        TextureFormat::R8 => Masks {
+            flags: DDPF_LUMINANCE,
+            bit_count: 8,
+            masks: [0xff, 0, 0, 0],
+        },
+        TextureFormat::RGB565 => Masks {
+            flags: DDPF_RGB,
+            bit_count: 16,
+            masks: [0xf800, 0x7e0, 0x1f, 0],
+        },
+        TextureFormat::RGB24 => Masks {
+            flags: DDPF_RGB,
+            bit_count: 24,
+            masks: [0xff, 0xff00, 0xff0000, 0],
+        },
+        TextureFormat::BGR24 => Masks {
+            flags: DDPF_RGB,
+            bit_count: 24,
+            masks: [0xff0000, 0xff00, 0xff, 0],
+        },
+        TextureFormat::RGBA32 => Masks {
+            flags: DDPF_RGB | DDPF_ALPHAPIXELS,
+            bit_count: 32,
+            masks: [0xff, 0xff00, 0xff0000, 0xff000000],
+        },
+        TextureFormat::BGRA32 => Masks {
+            flags: DDPF_RGB | DDPF_ALPHAPIXELS,
+            bit_count: 32,
+            masks: [0xff0000, 0xff00, 0xff, 0xff000000],
+        },
+        TextureFormat::ARGB32 => Masks {
+            flags: DDPF_RGB | DDPF_ALPHAPIXELS,
+            bit_count: 32,
+            masks: [0xff00, 0xff0000, 0xff000000, 0xff],
+        },
+        _ => return None,
+    })
+}
+
+/// Wrap the stored pixels of a texture in a DDS file, the blocks of
+/// compressed formats kept as they are. `data` holds `mip_count` levels
+/// from the full size one down, capped at what the size allows; anything
+/// after them is left out. Formats DDS has no name for, such as ETC, ASTC
+/// and crunched textures, fail.
+pub fn encode_dds(
+    data: &[u8],
+    texture_format: &TextureFormat,
+    width: usize,
+    height: usize,
+    mip_count: usize,
+) -> ReadResult<Vec<u8>> {
+    let pixel_format = dds_pixel_format(texture_format).ok_or_else(|| {
+        Error::Other(format!("{texture_format} textures cannot be stored as DDS"))
+    })?;
+    let mip_count = mip_count.clamp(1, max_mip_count(width, height));
+    let (last_width, last_height) = mip_dimensions(width, height, mip_count - 1);
+    let size = mip_offsets(texture_format, width, height, mip_count)[mip_count - 1]
+        + storage_size(texture_format, last_width, last_height);
+    let data = data.get(..size).ok_or_else(|| {
+        Error::Other(format!(
+            "{mip_count} mip levels need {size} bytes, texture has {}",
+            data.len()
+        ))
+    })?;
+
+    let mut flags = DDSD_CAPS | DDSD_HEIGHT | DDSD_WIDTH | DDSD_PIXELFORMAT;
+    let pitch_or_linear_size = if texture_format.is_compressed() {
+        flags |= DDSD_LINEARSIZE;
+        storage_size(texture_format, width, height)
+    } else {
+        flags |= DDSD_PITCH;
+        storage_size(texture_format, width, 1)
+    };
+    let mut caps = DDSCAPS_TEXTURE;
+    if mip_count > 1 {
+        flags |= DDSD_MIPMAPCOUNT;
+        caps |= DDSCAPS_COMPLEX | DDSCAPS_MIPMAP;
+    }
+
+    let mut out = Vec::with_capacity(148 + data.len());
+    let mut put = |value: u32| out.extend_from_slice(&value.to_le_bytes());
+    put(u32::from_le_bytes(*b"DDS "));
+    put(124);
+    put(flags);
+    put(height as u32);
+    put(width as u32);
+    put(pitch_or_linear_size as u32);
+    put(0);
+    put(mip_count as u32);
+    (0..11).for_each(|_| put(0));
+
+    put(32);
+    match &pixel_format {
+        DdsPixelFormat::FourCC(four_cc) => {
+            put(DDPF_FOURCC);
+            put(u32::from_le_bytes(**four_cc));
+            (0..5).for_each(|_| put(0));
+        }
+        DdsPixelFormat::Dxgi(_) => {
+            put(DDPF_FOURCC);
+            put(u32::from_le_bytes(*b"DX10"));
+            (0..5).for_each(|_| put(0));
+        }
+        DdsPixelFormat::Masks {
+            flags,
+            bit_count,
+            masks,
+        } => {
+            put(*flags);
+            put(0);
+            put(*bit_count);
+            masks.iter().for_each(|mask| put(*mask));
+        }
+    }
+
+    put(caps);
+    (0..4).for_each(|_| put(0));
+    if let DdsPixelFormat::Dxgi(dxgi_format) = pixel_format {
+        put(dxgi_format);
+        put(DDS_DIMENSION_TEXTURE2D);
+        put(0);
+        put(1);
+        put(0);
+    }
+    out.extend_from_slice(data);
+    Ok(out)
+}
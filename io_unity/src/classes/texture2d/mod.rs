@@ -1,3 +1,4 @@
+pub mod dds;
 pub mod type_tree;
 
 use crate::{
@@ -6,7 +7,7 @@ use crate::{
     unity_asset_view::UnityAssetViewer,
 };
 use binrw::binrw;
-use image::{DynamicImage, GrayAlphaImage, RgbImage, RgbaImage};
+use image::{DynamicImage, GrayAlphaImage, Rgb32FImage, RgbImage, Rgba32FImage, RgbaImage};
 use num_enum::TryFromPrimitive;
 
 def_unity_class!(Texture2D);
@@ -16,6 +17,34 @@ pub trait Texture2DObject {
     fn get_height(&self) -> ReadResult<u64>;
     fn get_texture_format(&self) -> ReadResult<TextureFormat>;
     fn get_image_data(&self, viewer: &UnityAssetViewer) -> ReadResult<Vec<u8>>;
+    fn get_mip_count(&self) -> ReadResult<u64>;
+    /// Check, without decoding, that the pixels are present and in a
+    /// format [`decode_image`] handles. Non-readable textures built with
+    /// stripped data, and streams missing from the loaded files, fail here
+    /// rather than deep in decoding.
+    fn check_decodable(&self, viewer: &UnityAssetViewer) -> Result<(), UndecodableReason>;
+
+    fn is_decodable(&self, viewer: &UnityAssetViewer) -> bool {
+        self.check_decodable(viewer).is_ok()
+    }
+    /// How the stored color values are encoded, from `m_ColorSpace`. Color
+    /// textures are sRGB, while normal maps, masks and HDR data are linear.
+    /// Textures from versions without the field are taken to be sRGB.
+    fn get_color_space(&self) -> ReadResult<ColorSpace>;
+
+    /// Decode to linear 32 bit float RGBA, converting the color channels of
+    /// sRGB textures and leaving linear textures as they are stored.
+    fn get_linear_image(&self, viewer: &UnityAssetViewer) -> ReadResult<Rgba32FImage> {
+        let mut image = self.get_image(viewer)?.to_rgba32f();
+        if self.get_color_space()? == ColorSpace::SRGB {
+            for pixel in image.pixels_mut() {
+                for channel in &mut pixel.0[..3] {
+                    *channel = srgb_to_linear(*channel);
+                }
+            }
+        }
+        Ok(image)
+    }
 
     fn get_image(&self, viewer: &UnityAssetViewer) -> ReadResult<DynamicImage> {
         let data = self.get_image_data(viewer)?;
@@ -23,126 +52,403 @@ pub trait Texture2DObject {
         let width = self.get_width()? as usize;
         let height = self.get_height()? as usize;
 
-        match &texture_format {
-            TextureFormat::DXT1
-            | TextureFormat::DXT3
-            | TextureFormat::DXT5
-            | TextureFormat::BC4
-            | TextureFormat::BC5
-            | TextureFormat::BC6H
-            | TextureFormat::BC7
-            | TextureFormat::DXT1Crunched
-            | TextureFormat::DXT5Crunched => {
-                let size = width * height * 4;
-                let mut output = vec![0; size];
-                match &texture_format {
-                    TextureFormat::DXT1 => {
-                        texpresso::Format::Bc1.decompress(&data, width, height, &mut output)
-                    }
-                    TextureFormat::DXT3 => {
-                        texpresso::Format::Bc2.decompress(&data, width, height, &mut output)
-                    }
-                    TextureFormat::DXT5 => {
-                        texpresso::Format::Bc3.decompress(&data, width, height, &mut output)
-                    }
-
-                    TextureFormat::BC4 => {
-                        texpresso::Format::Bc4.decompress(&data, width, height, &mut output)
-                    }
-                    TextureFormat::BC5 => {
-                        texpresso::Format::Bc5.decompress(&data, width, height, &mut output)
-                    }
-                    TextureFormat::BC6H
-                    | TextureFormat::BC7
-                    | TextureFormat::DXT1Crunched
-                    | TextureFormat::DXT5Crunched => {
-                        return Err(Error::Other(format!(
-                            "unsupport {:?}",
-                            self.get_texture_format()
-                        )))
-                    }
-                    _ => unreachable!(),
+        decode_image(data, &texture_format, width, height)
+    }
+
+    /// Decode a single mip level, 0 being the full size image.
+    fn get_mip_image(&self, viewer: &UnityAssetViewer, level: usize) -> ReadResult<DynamicImage> {
+        let texture_format = self.get_texture_format()?;
+        let width = self.get_width()? as usize;
+        let height = self.get_height()? as usize;
+        let mip_count = (self.get_mip_count()? as usize).min(max_mip_count(width, height));
+        if level >= mip_count {
+            return Err(Error::Other(format!(
+                "mip level {level} out of range, texture has {mip_count}"
+            )));
+        }
+
+        let offset = mip_offsets(&texture_format, width, height, mip_count)[level];
+        let (mip_width, mip_height) = mip_dimensions(width, height, level);
+        let size = storage_size(&texture_format, mip_width, mip_height);
+        let data = self.get_image_data(viewer)?;
+        let data = data
+            .get(offset..offset + size)
+            .ok_or(Error::Other(format!("mip level {level} data out of range")))?
+            .to_vec();
+        decode_image(data, &texture_format, mip_width, mip_height)
+    }
+
+    /// The texture as a DDS file holding its stored pixels and mip levels,
+    /// without decoding them, so block compressed data survives unchanged.
+    /// See [`dds::encode_dds`].
+    fn to_dds(&self, viewer: &UnityAssetViewer) -> ReadResult<Vec<u8>> {
+        dds::encode_dds(
+            &self.get_image_data(viewer)?,
+            &self.get_texture_format()?,
+            self.get_width()? as usize,
+            self.get_height()? as usize,
+            self.get_mip_count()? as usize,
+        )
+    }
+}
+
+/// Encoding of a texture's color values, as stored in `m_ColorSpace`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[repr(i32)]
+pub enum ColorSpace {
+    Linear = 0,
+    SRGB = 1,
+}
+
+/// Convert an sRGB encoded value in `[0, 1]` to linear.
+pub fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Convert a linear value in `[0, 1]` to sRGB encoding.
+pub fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Why a texture cannot be decoded, from [`Texture2DObject::check_decodable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndecodableReason {
+    /// Neither inline image data nor a stream, as for non-readable textures
+    /// whose CPU copy was stripped.
+    NoData,
+    /// The stream named by `m_StreamData` cannot be found.
+    MissingStream,
+    /// The texture format is unknown or not supported by [`decode_image`].
+    UnsupportedFormat,
+}
+
+impl std::fmt::Display for UndecodableReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UndecodableReason::NoData => write!(f, "texture has no image data"),
+            UndecodableReason::MissingStream => write!(f, "texture stream not found"),
+            UndecodableReason::UnsupportedFormat => write!(f, "unsupported texture format"),
+        }
+    }
+}
+
+/// Decode to 32 bit float RGBA. HDR formats such as BC6H and the half and
+/// float formats, see [`TextureFormat::is_hdr`], keep their full
+/// range here, while [`decode_image`] followed by `to_rgba8` clamps them to
+/// `[0, 1]`. LDR formats are decoded as usual and converted.
+pub fn decode_rgba_f32(
+    data: Vec<u8>,
+    texture_format: &TextureFormat,
+    width: usize,
+    height: usize,
+) -> ReadResult<Rgba32FImage> {
+    Ok(decode_image(data, texture_format, width, height)?.to_rgba32f())
+}
+
+/// Rebuild the Z channel of a tangent space normal map stored as X and Y
+/// only, as BC5 normal maps are. Red and green are read as the X and Y
+/// components in `[-1, 1]` and blue is set to the matching positive Z.
+pub fn reconstruct_normal_z(image: &mut RgbaImage) {
+    for pixel in image.pixels_mut() {
+        let x = pixel[0] as f32 / 127.5 - 1.0;
+        let y = pixel[1] as f32 / 127.5 - 1.0;
+        let z = (1.0 - x * x - y * y).max(0.0).sqrt();
+        pixel[2] = ((z + 1.0) * 127.5).round() as u8;
+        pixel[3] = u8::MAX;
+    }
+}
+
+fn decode_bc6h(data: &[u8], width: usize, height: usize) -> ReadResult<Vec<f32>> {
+    decode_bc_blocks(data, width, height, 16, 3, |block, output, pitch| {
+        bcdec_rs::bc6h_float(block, output, pitch, false)
+    })
+}
+
+/// Decode 4x4 blocks of `block_size` bytes into rows of `channels` values,
+/// cropping the padding of dimensions that aren't a multiple of 4.
+fn decode_bc_blocks<T: Copy + Default>(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    block_size: usize,
+    channels: usize,
+    decode_block: impl Fn(&[u8], &mut [T], usize),
+) -> ReadResult<Vec<T>> {
+    let blocks_x = width.div_ceil(4);
+    let blocks_y = height.div_ceil(4);
+    if data.len() < blocks_x * blocks_y * block_size {
+        return Err(Error::Other("texture data too short".to_owned()));
+    }
+
+    let pitch = blocks_x * 4 * channels;
+    let mut padded = vec![T::default(); pitch * blocks_y * 4];
+    for (index, block) in data
+        .chunks_exact(block_size)
+        .take(blocks_x * blocks_y)
+        .enumerate()
+    {
+        let (block_x, block_y) = (index % blocks_x, index / blocks_x);
+        let start = block_y * 4 * pitch + block_x * 4 * channels;
+        decode_block(block, &mut padded[start..], pitch);
+    }
+
+    let mut output = Vec::with_capacity(width * height * channels);
+    for row in padded.chunks_exact(pitch).take(height) {
+        output.extend_from_slice(&row[..width * channels]);
+    }
+    Ok(output)
+}
+
+/// Decode uncompressed float pixels of `channels` values of `size` bytes
+/// each to RGBA, filling missing color channels with 0 and alpha with 1.
+fn decode_float_pixels(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    size: usize,
+    read: impl Fn(&[u8]) -> f32,
+) -> ReadResult<DynamicImage> {
+    let pixel_size = channels * size;
+    if data.len() < width * height * pixel_size {
+        return Err(Error::Other("texture data too short".to_owned()));
+    }
+    let mut output = Vec::with_capacity(width * height * 4);
+    for pixel in data.chunks_exact(pixel_size).take(width * height) {
+        let mut rgba = [0.0, 0.0, 0.0, 1.0];
+        for (value, bytes) in rgba.iter_mut().zip(pixel.chunks_exact(size)) {
+            *value = read(bytes);
+        }
+        output.extend_from_slice(&rgba);
+    }
+    let result = Rgba32FImage::from_raw(width as u32, height as u32, output)
+        .ok_or(Error::Other("from_raw".to_owned()))?;
+    Ok(DynamicImage::ImageRgba32F(result))
+}
+
+/// Bytes needed to store one image, with the dimensions rounded up to
+/// whole blocks for block compressed formats.
+pub fn storage_size(texture_format: &TextureFormat, width: usize, height: usize) -> usize {
+    let (block_width, block_height) = texture_format.block_dimensions();
+    let blocks_x = width.max(1).div_ceil(block_width);
+    let blocks_y = height.max(1).div_ceil(block_height);
+    blocks_x * blocks_y * texture_format.bytes_per_block()
+}
+
+/// Size of a mip level, halving each dimension per level down to 1.
+pub fn mip_dimensions(width: usize, height: usize, level: usize) -> (usize, usize) {
+    let halve = |size: usize| {
+        u32::try_from(level)
+            .ok()
+            .and_then(|level| size.checked_shr(level))
+            .unwrap_or(0)
+            .max(1)
+    };
+    (halve(width), halve(height))
+}
+
+/// Number of mip levels a texture of this size can have, down to and
+/// including 1x1. `m_MipCount` is read from the file and may claim more.
+pub fn max_mip_count(width: usize, height: usize) -> usize {
+    (usize::BITS - width.max(height).max(1).leading_zeros()) as usize
+}
+
+/// Byte offset of each mip level within the image data of one layer,
+/// `mip_count` capped at [`max_mip_count`].
+pub fn mip_offsets(
+    texture_format: &TextureFormat,
+    width: usize,
+    height: usize,
+    mip_count: usize,
+) -> Vec<usize> {
+    let mip_count = mip_count.min(max_mip_count(width, height));
+    let mut offsets = Vec::with_capacity(mip_count);
+    let mut offset = 0;
+    for level in 0..mip_count {
+        offsets.push(offset);
+        let (mip_width, mip_height) = mip_dimensions(width, height, level);
+        offset += storage_size(texture_format, mip_width, mip_height);
+    }
+    offsets
+}
+
+pub fn decode_image(
+    data: Vec<u8>,
+    texture_format: &TextureFormat,
+    width: usize,
+    height: usize,
+) -> ReadResult<DynamicImage> {
+    match texture_format {
+        TextureFormat::DXT1
+        | TextureFormat::DXT3
+        | TextureFormat::DXT5
+        | TextureFormat::BC4
+        | TextureFormat::BC5
+        | TextureFormat::BC6H
+        | TextureFormat::BC7
+        | TextureFormat::DXT1Crunched
+        | TextureFormat::DXT5Crunched => {
+            let size = width * height * 4;
+            let mut output = vec![0; size];
+            match texture_format {
+                TextureFormat::DXT1 => {
+                    texpresso::Format::Bc1.decompress(&data, width, height, &mut output)
                 }
-                let result = RgbaImage::from_raw(width as u32, height as u32, output)
-                    .ok_or(Error::Other("from_raw".to_owned()))?;
-                Ok(DynamicImage::ImageRgba8(result))
-            }
-            TextureFormat::ASTC_RGB_4x4
-            | TextureFormat::ASTC_RGB_5x5
-            | TextureFormat::ASTC_RGB_6x6
-            | TextureFormat::ASTC_RGB_8x8
-            | TextureFormat::ASTC_RGB_10x10
-            | TextureFormat::ASTC_RGB_12x12
-            | TextureFormat::ASTC_RGBA_4x4
-            | TextureFormat::ASTC_RGBA_5x5
-            | TextureFormat::ASTC_RGBA_6x6
-            | TextureFormat::ASTC_RGBA_8x8
-            | TextureFormat::ASTC_RGBA_10x10
-            | TextureFormat::ASTC_RGBA_12x12
-            | TextureFormat::ASTC_HDR_4x4
-            | TextureFormat::ASTC_HDR_5x5
-            | TextureFormat::ASTC_HDR_6x6
-            | TextureFormat::ASTC_HDR_8x8
-            | TextureFormat::ASTC_HDR_10x10
-            | TextureFormat::ASTC_HDR_12x12 => {
-                let size = width * height;
-                let mut output = vec![[0u8; 4]; size];
-                let footprint = match &texture_format {
-                    TextureFormat::ASTC_RGB_4x4
-                    | TextureFormat::ASTC_RGBA_4x4
-                    | TextureFormat::ASTC_HDR_4x4 => astc_decode::Footprint::new(4, 4),
-                    TextureFormat::ASTC_RGB_5x5
-                    | TextureFormat::ASTC_RGBA_5x5
-                    | TextureFormat::ASTC_HDR_5x5 => astc_decode::Footprint::new(5, 5),
-                    TextureFormat::ASTC_RGB_6x6
-                    | TextureFormat::ASTC_RGBA_6x6
-                    | TextureFormat::ASTC_HDR_6x6 => astc_decode::Footprint::new(6, 6),
-                    TextureFormat::ASTC_RGB_8x8
-                    | TextureFormat::ASTC_RGBA_8x8
-                    | TextureFormat::ASTC_HDR_8x8 => astc_decode::Footprint::new(8, 8),
-                    TextureFormat::ASTC_RGB_10x10
-                    | TextureFormat::ASTC_RGBA_10x10
-                    | TextureFormat::ASTC_HDR_10x10 => astc_decode::Footprint::new(10, 10),
-                    TextureFormat::ASTC_RGB_12x12
-                    | TextureFormat::ASTC_RGBA_12x12
-                    | TextureFormat::ASTC_HDR_12x12 => astc_decode::Footprint::new(12, 12),
-                    _ => unreachable!(),
-                };
-                astc_decode::astc_decode(
-                    &*data,
-                    width as u32,
-                    height as u32,
-                    footprint,
-                    |x, y, color| {
-                        output[x as usize + y as usize * width] = color;
-                    },
-                )?;
-
-                let result = RgbaImage::from_raw(width as u32, height as u32, output.concat())
-                    .ok_or(Error::Other("from_raw".to_owned()))?;
-                Ok(DynamicImage::ImageRgba8(result))
-            }
-            TextureFormat::Alpha8 => {
-                let buff: Vec<[u8; 2]> = data.into_iter().map(|f| [0, f]).collect();
-                let result = GrayAlphaImage::from_raw(width as u32, height as u32, buff.concat())
-                    .ok_or(Error::Other("from_raw".to_owned()))?;
-                Ok(DynamicImage::ImageLumaA8(result))
-            }
-            TextureFormat::RGB24 => {
-                let result = RgbImage::from_raw(width as u32, height as u32, data.to_vec())
-                    .ok_or(Error::Other("from_raw".to_owned()))?;
-                Ok(DynamicImage::ImageRgb8(result))
+                TextureFormat::DXT3 => {
+                    texpresso::Format::Bc2.decompress(&data, width, height, &mut output)
+                }
+                TextureFormat::DXT5 => {
+                    texpresso::Format::Bc3.decompress(&data, width, height, &mut output)
+                }
+
+                TextureFormat::BC4 => {
+                    texpresso::Format::Bc4.decompress(&data, width, height, &mut output)
+                }
+                TextureFormat::BC5 => {
+                    texpresso::Format::Bc5.decompress(&data, width, height, &mut output)
+                }
+                TextureFormat::BC7 => {
+                    output = decode_bc_blocks(&data, width, height, 16, 4, bcdec_rs::bc7)?
+                }
+                TextureFormat::BC6H => {
+                    let output = decode_bc6h(&data, width, height)?;
+                    let result = Rgb32FImage::from_raw(width as u32, height as u32, output)
+                        .ok_or(Error::Other("from_raw".to_owned()))?;
+                    return Ok(DynamicImage::ImageRgb32F(result));
+                }
+                TextureFormat::DXT1Crunched | TextureFormat::DXT5Crunched => {
+                    return Err(Error::Other(format!("unsupport {:?}", texture_format)))
+                }
+                _ => unreachable!(),
             }
-            TextureFormat::RGBA32 => {
-                let result = RgbaImage::from_raw(width as u32, height as u32, data.to_vec())
-                    .ok_or(Error::Other("from_raw".to_owned()))?;
-                Ok(DynamicImage::ImageRgba8(result))
+            let result = RgbaImage::from_raw(width as u32, height as u32, output)
+                .ok_or(Error::Other("from_raw".to_owned()))?;
+            Ok(DynamicImage::ImageRgba8(result))
+        }
+        TextureFormat::ASTC_RGB_4x4
+        | TextureFormat::ASTC_RGB_5x5
+        | TextureFormat::ASTC_RGB_6x6
+        | TextureFormat::ASTC_RGB_8x8
+        | TextureFormat::ASTC_RGB_10x10
+        | TextureFormat::ASTC_RGB_12x12
+        | TextureFormat::ASTC_RGBA_4x4
+        | TextureFormat::ASTC_RGBA_5x5
+        | TextureFormat::ASTC_RGBA_6x6
+        | TextureFormat::ASTC_RGBA_8x8
+        | TextureFormat::ASTC_RGBA_10x10
+        | TextureFormat::ASTC_RGBA_12x12
+        | TextureFormat::ASTC_HDR_4x4
+        | TextureFormat::ASTC_HDR_5x5
+        | TextureFormat::ASTC_HDR_6x6
+        | TextureFormat::ASTC_HDR_8x8
+        | TextureFormat::ASTC_HDR_10x10
+        | TextureFormat::ASTC_HDR_12x12 => {
+            let size = width * height;
+            let mut output = vec![[0u8; 4]; size];
+            let footprint = match texture_format {
+                TextureFormat::ASTC_RGB_4x4
+                | TextureFormat::ASTC_RGBA_4x4
+                | TextureFormat::ASTC_HDR_4x4 => astc_decode::Footprint::new(4, 4),
+                TextureFormat::ASTC_RGB_5x5
+                | TextureFormat::ASTC_RGBA_5x5
+                | TextureFormat::ASTC_HDR_5x5 => astc_decode::Footprint::new(5, 5),
+                TextureFormat::ASTC_RGB_6x6
+                | TextureFormat::ASTC_RGBA_6x6
+                | TextureFormat::ASTC_HDR_6x6 => astc_decode::Footprint::new(6, 6),
+                TextureFormat::ASTC_RGB_8x8
+                | TextureFormat::ASTC_RGBA_8x8
+                | TextureFormat::ASTC_HDR_8x8 => astc_decode::Footprint::new(8, 8),
+                TextureFormat::ASTC_RGB_10x10
+                | TextureFormat::ASTC_RGBA_10x10
+                | TextureFormat::ASTC_HDR_10x10 => astc_decode::Footprint::new(10, 10),
+                TextureFormat::ASTC_RGB_12x12
+                | TextureFormat::ASTC_RGBA_12x12
+                | TextureFormat::ASTC_HDR_12x12 => astc_decode::Footprint::new(12, 12),
+                _ => unreachable!(),
+            };
+            astc_decode::astc_decode(
+                &*data,
+                width as u32,
+                height as u32,
+                footprint,
+                |x, y, color| {
+                    output[x as usize + y as usize * width] = color;
+                },
+            )?;
+
+            let result = RgbaImage::from_raw(width as u32, height as u32, output.concat())
+                .ok_or(Error::Other("from_raw".to_owned()))?;
+            Ok(DynamicImage::ImageRgba8(result))
+        }
+        TextureFormat::RHalf | TextureFormat::RGHalf | TextureFormat::RGBAHalf => {
+            let channels = match texture_format {
+                TextureFormat::RHalf => 1,
+                TextureFormat::RGHalf => 2,
+                _ => 4,
+            };
+            decode_float_pixels(&data, width, height, channels, 2, |value| {
+                half::f16::from_le_bytes([value[0], value[1]]).to_f32()
+            })
+        }
+        TextureFormat::RFloat
+        | TextureFormat::RGFloat
+        | TextureFormat::RGBFloat
+        | TextureFormat::RGBAFloat => {
+            let channels = match texture_format {
+                TextureFormat::RFloat => 1,
+                TextureFormat::RGFloat => 2,
+                TextureFormat::RGBFloat => 3,
+                _ => 4,
+            };
+            decode_float_pixels(&data, width, height, channels, 4, |value| {
+                f32::from_le_bytes([value[0], value[1], value[2], value[3]])
+            })
+        }
+        TextureFormat::RGB9e5Float => {
+            let mut output = Vec::with_capacity(width * height * 4);
+            for pixel in data.chunks_exact(4).take(width * height) {
+                let packed = u32::from_le_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]);
+                // Nine bit mantissas sharing a five bit exponent biased by 15.
+                let scale = 2f32.powi((packed >> 27) as i32 - 15 - 9);
+                output.extend(
+                    [0, 9, 18]
+                        .map(|shift| ((packed >> shift) & 0x1ff) as f32 * scale)
+                        .into_iter()
+                        .chain([1.0]),
+                );
             }
-            _ => Err(Error::Other(format!(
-                "unsupport texture_format: {:?}",
-                self.get_texture_format()
-            ))),
+            let result = Rgba32FImage::from_raw(width as u32, height as u32, output)
+                .ok_or(Error::Other("from_raw".to_owned()))?;
+            Ok(DynamicImage::ImageRgba32F(result))
+        }
+        TextureFormat::Alpha8 => {
+            let buff: Vec<[u8; 2]> = data.into_iter().map(|f| [0, f]).collect();
+            let result = GrayAlphaImage::from_raw(width as u32, height as u32, buff.concat())
+                .ok_or(Error::Other("from_raw".to_owned()))?;
+            Ok(DynamicImage::ImageLumaA8(result))
         }
+        TextureFormat::RGB24 => {
+            let result = RgbImage::from_raw(width as u32, height as u32, data.to_vec())
+                .ok_or(Error::Other("from_raw".to_owned()))?;
+            Ok(DynamicImage::ImageRgb8(result))
+        }
+        TextureFormat::RGBA32 => {
+            let result = RgbaImage::from_raw(width as u32, height as u32, data.to_vec())
+                .ok_or(Error::Other("from_raw".to_owned()))?;
+            Ok(DynamicImage::ImageRgba8(result))
+        }
+        _ => Err(Error::Other(format!(
+            "unsupport texture_format: {:?}",
+            texture_format
+        ))),
     }
 }
 
@@ -223,3 +529,275 @@ pub enum TextureFormat {
     RGB48,
     RGBA64,
 }
+
+impl std::fmt::Display for TextureFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl TextureFormat {
+    /// Whether [`decode_image`] can decode the format.
+    pub fn is_decodable(&self) -> bool {
+        matches!(
+            self,
+            TextureFormat::DXT1
+                | TextureFormat::DXT3
+                | TextureFormat::DXT5
+                | TextureFormat::BC4
+                | TextureFormat::BC5
+                | TextureFormat::BC6H
+                | TextureFormat::BC7
+                | TextureFormat::ASTC_RGB_4x4
+                | TextureFormat::ASTC_RGB_5x5
+                | TextureFormat::ASTC_RGB_6x6
+                | TextureFormat::ASTC_RGB_8x8
+                | TextureFormat::ASTC_RGB_10x10
+                | TextureFormat::ASTC_RGB_12x12
+                | TextureFormat::ASTC_RGBA_4x4
+                | TextureFormat::ASTC_RGBA_5x5
+                | TextureFormat::ASTC_RGBA_6x6
+                | TextureFormat::ASTC_RGBA_8x8
+                | TextureFormat::ASTC_RGBA_10x10
+                | TextureFormat::ASTC_RGBA_12x12
+                | TextureFormat::ASTC_HDR_4x4
+                | TextureFormat::ASTC_HDR_5x5
+                | TextureFormat::ASTC_HDR_6x6
+                | TextureFormat::ASTC_HDR_8x8
+                | TextureFormat::ASTC_HDR_10x10
+                | TextureFormat::ASTC_HDR_12x12
+                | TextureFormat::Alpha8
+                | TextureFormat::RGB24
+                | TextureFormat::RGBA32
+                | TextureFormat::RHalf
+                | TextureFormat::RGHalf
+                | TextureFormat::RGBAHalf
+                | TextureFormat::RFloat
+                | TextureFormat::RGFloat
+                | TextureFormat::RGBFloat
+                | TextureFormat::RGBAFloat
+                | TextureFormat::RGB9e5Float
+        )
+    }
+
+    /// Whether the format holds values outside `[0, 1]`, which only
+    /// [`decode_rgba_f32`] keeps.
+    pub fn is_hdr(&self) -> bool {
+        matches!(
+            self,
+            TextureFormat::ARGBFloat
+                | TextureFormat::RHalf
+                | TextureFormat::RGHalf
+                | TextureFormat::RGBAHalf
+                | TextureFormat::RFloat
+                | TextureFormat::RGFloat
+                | TextureFormat::RGBFloat
+                | TextureFormat::RGBAFloat
+                | TextureFormat::RGB9e5Float
+                | TextureFormat::BC6H
+                | TextureFormat::ASTC_HDR_4x4
+                | TextureFormat::ASTC_HDR_5x5
+                | TextureFormat::ASTC_HDR_6x6
+                | TextureFormat::ASTC_HDR_8x8
+                | TextureFormat::ASTC_HDR_10x10
+                | TextureFormat::ASTC_HDR_12x12
+        )
+    }
+
+    /// Whether the format stores pixels in blocks larger than one pixel.
+    pub fn is_compressed(&self) -> bool {
+        self.block_dimensions() != (1, 1)
+    }
+
+    /// Width and height in pixels of one block, `(1, 1)` for uncompressed formats.
+    pub fn block_dimensions(&self) -> (usize, usize) {
+        match self {
+            TextureFormat::YUY2 => (2, 1),
+            TextureFormat::PVRTC_RGB2 | TextureFormat::PVRTC_RGBA2 => (8, 4),
+            TextureFormat::DXT1
+            | TextureFormat::DXT3
+            | TextureFormat::DXT5
+            | TextureFormat::BC4
+            | TextureFormat::BC5
+            | TextureFormat::BC6H
+            | TextureFormat::BC7
+            | TextureFormat::DXT1Crunched
+            | TextureFormat::DXT5Crunched
+            | TextureFormat::PVRTC_RGB4
+            | TextureFormat::PVRTC_RGBA4
+            | TextureFormat::ETC_RGB4
+            | TextureFormat::ATC_RGB4
+            | TextureFormat::ATC_RGBA8
+            | TextureFormat::EAC_R
+            | TextureFormat::EAC_R_SIGNED
+            | TextureFormat::EAC_RG
+            | TextureFormat::EAC_RG_SIGNED
+            | TextureFormat::ETC2_RGB
+            | TextureFormat::ETC2_RGBA1
+            | TextureFormat::ETC2_RGBA8
+            | TextureFormat::ETC_RGB4_3DS
+            | TextureFormat::ETC_RGBA8_3DS
+            | TextureFormat::ETC_RGB4Crunched
+            | TextureFormat::ETC2_RGBA8Crunched
+            | TextureFormat::ASTC_RGB_4x4
+            | TextureFormat::ASTC_RGBA_4x4
+            | TextureFormat::ASTC_HDR_4x4 => (4, 4),
+            TextureFormat::ASTC_RGB_5x5
+            | TextureFormat::ASTC_RGBA_5x5
+            | TextureFormat::ASTC_HDR_5x5 => (5, 5),
+            TextureFormat::ASTC_RGB_6x6
+            | TextureFormat::ASTC_RGBA_6x6
+            | TextureFormat::ASTC_HDR_6x6 => (6, 6),
+            TextureFormat::ASTC_RGB_8x8
+            | TextureFormat::ASTC_RGBA_8x8
+            | TextureFormat::ASTC_HDR_8x8 => (8, 8),
+            TextureFormat::ASTC_RGB_10x10
+            | TextureFormat::ASTC_RGBA_10x10
+            | TextureFormat::ASTC_HDR_10x10 => (10, 10),
+            TextureFormat::ASTC_RGB_12x12
+            | TextureFormat::ASTC_RGBA_12x12
+            | TextureFormat::ASTC_HDR_12x12 => (12, 12),
+            _ => (1, 1),
+        }
+    }
+
+    /// Bytes used by one block, which is the bytes per pixel for
+    /// uncompressed formats. Crunched formats report their decoded block size.
+    pub fn bytes_per_block(&self) -> usize {
+        match self {
+            TextureFormat::Alpha8 | TextureFormat::R8 => 1,
+            TextureFormat::ARGB4444
+            | TextureFormat::RGB565
+            | TextureFormat::R16
+            | TextureFormat::RGBA4444
+            | TextureFormat::RHalf
+            | TextureFormat::RG16 => 2,
+            TextureFormat::RGB24 | TextureFormat::BGR24 => 3,
+            TextureFormat::RGBA32
+            | TextureFormat::ARGB32
+            | TextureFormat::BGRA32
+            | TextureFormat::RGHalf
+            | TextureFormat::RFloat
+            | TextureFormat::RGB9e5Float
+            | TextureFormat::RG32
+            | TextureFormat::YUY2 => 4,
+            TextureFormat::RGB48 => 6,
+            TextureFormat::RGBAHalf | TextureFormat::RGFloat | TextureFormat::RGBA64 => 8,
+            TextureFormat::RGBFloat => 12,
+            TextureFormat::ARGBFloat | TextureFormat::RGBAFloat => 16,
+            TextureFormat::DXT1
+            | TextureFormat::BC4
+            | TextureFormat::DXT1Crunched
+            | TextureFormat::PVRTC_RGB2
+            | TextureFormat::PVRTC_RGBA2
+            | TextureFormat::PVRTC_RGB4
+            | TextureFormat::PVRTC_RGBA4
+            | TextureFormat::ETC_RGB4
+            | TextureFormat::ATC_RGB4
+            | TextureFormat::EAC_R
+            | TextureFormat::EAC_R_SIGNED
+            | TextureFormat::ETC2_RGB
+            | TextureFormat::ETC2_RGBA1
+            | TextureFormat::ETC_RGB4_3DS
+            | TextureFormat::ETC_RGB4Crunched => 8,
+            _ => 16,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn npot_dxt1_mip_offsets() {
+        let format = TextureFormat::DXT1;
+        assert_eq!(storage_size(&format, 130, 130), 33 * 33 * 8);
+        assert_eq!(storage_size(&format, 2, 2), 8);
+        assert_eq!(
+            mip_offsets(&format, 130, 130, 8),
+            vec![0, 8712, 11024, 11536, 11664, 11696, 11704, 11712]
+        );
+    }
+
+    #[test]
+    fn mip_counts_are_capped_by_texture_size() {
+        assert_eq!(max_mip_count(0, 0), 1);
+        assert_eq!(max_mip_count(1, 1), 1);
+        assert_eq!(max_mip_count(130, 4), 8);
+        assert_eq!(max_mip_count(256, 256), 9);
+        assert_eq!(mip_dimensions(256, 64, 100), (1, 1));
+        assert_eq!(mip_dimensions(256, 64, usize::MAX), (1, 1));
+        assert_eq!(
+            mip_offsets(&TextureFormat::Alpha8, 4, 4, u32::MAX as usize).len(),
+            3
+        );
+
+        let data = vec![0u8; 16 + 4 + 1];
+        let dds = dds::encode_dds(&data, &TextureFormat::Alpha8, 4, 4, 1000).unwrap();
+        assert_eq!(u32::from_le_bytes(dds[28..32].try_into().unwrap()), 3);
+    }
+
+    #[test]
+    fn npot_bptc_decode_is_cropped() {
+        let data = vec![0u8; storage_size(&TextureFormat::BC7, 6, 5)];
+        let image = decode_image(data.clone(), &TextureFormat::BC7, 6, 5).unwrap();
+        assert_eq!((image.width(), image.height()), (6, 5));
+
+        let image = decode_rgba_f32(data, &TextureFormat::BC6H, 6, 5).unwrap();
+        assert_eq!((image.width(), image.height()), (6, 5));
+        assert_eq!(image.get_pixel(5, 4).0, [0.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn dds_keeps_blocks_and_names_the_format() {
+        let data: Vec<u8> = (0..storage_size(&TextureFormat::DXT1, 8, 8) as u8 + 8 + 8).collect();
+        let dds = dds::encode_dds(&data, &TextureFormat::DXT1, 8, 8, 3).unwrap();
+        let word = |offset: usize| u32::from_le_bytes(dds[offset..offset + 4].try_into().unwrap());
+        assert_eq!(&dds[..4], b"DDS ");
+        assert_eq!((word(12), word(16), word(20), word(28)), (8, 8, 32, 3));
+        assert_eq!(&dds[84..88], b"DXT1");
+        assert_eq!(&dds[128..], &data[..]);
+
+        let data = vec![0u8; storage_size(&TextureFormat::BC7, 4, 4)];
+        let dds = dds::encode_dds(&data, &TextureFormat::BC7, 4, 4, 1).unwrap();
+        assert_eq!(&dds[84..88], b"DX10");
+        assert_eq!(dds[128], 98);
+        assert_eq!(dds.len(), 148 + 16);
+
+        assert!(dds::encode_dds(&data, &TextureFormat::ETC2_RGBA8, 4, 4, 1).is_err());
+        assert!(dds::encode_dds(&data[..8], &TextureFormat::BC7, 4, 4, 1).is_err());
+    }
+
+    #[test]
+    fn float_formats_keep_hdr_values() {
+        let halves: Vec<u8> = [2.5f32, -1.0]
+            .iter()
+            .flat_map(|value| half::f16::from_f32(*value).to_le_bytes())
+            .collect();
+        let image = decode_rgba_f32(halves, &TextureFormat::RGHalf, 1, 1).unwrap();
+        assert_eq!(image.get_pixel(0, 0).0, [2.5, -1.0, 0.0, 1.0]);
+
+        let floats: Vec<u8> = [16.0f32, 0.5, 0.25, 0.0, 8.0, 4.0, 2.0, 1.0]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        let image = decode_rgba_f32(floats, &TextureFormat::RGBAFloat, 2, 1).unwrap();
+        assert_eq!(image.get_pixel(0, 0).0, [16.0, 0.5, 0.25, 0.0]);
+        assert_eq!(image.get_pixel(1, 0).0, [8.0, 4.0, 2.0, 1.0]);
+
+        // Mantissas 256, 128 and 0 scaled by 2^(16 - 15 - 9).
+        let packed: u32 = 256 | (128 << 9) | (16 << 27);
+        let image = decode_rgba_f32(
+            packed.to_le_bytes().to_vec(),
+            &TextureFormat::RGB9e5Float,
+            1,
+            1,
+        )
+        .unwrap();
+        assert_eq!(image.get_pixel(0, 0).0, [1.0, 0.5, 0.0, 1.0]);
+
+        assert!(TextureFormat::RHalf.is_hdr() && !TextureFormat::RGBA32.is_hdr());
+        assert!(decode_image(vec![0; 3], &TextureFormat::RFloat, 1, 1).is_err());
+    }
+}
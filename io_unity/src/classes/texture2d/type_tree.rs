@@ -1,12 +1,10 @@
-use super::{Texture2D, Texture2DObject, TextureFormat};
+use super::{ColorSpace, Texture2D, Texture2DObject, TextureFormat, UndecodableReason};
 use crate::error::Error;
 use crate::{classes::SerializedFileRef, error::ReadResult};
 
 use crate::type_tree::convert::TryCastFrom;
 use crate::unity_asset_view::UnityAssetViewer;
 
-use std::io::{prelude::*, SeekFrom};
-
 impl Texture2DObject for Texture2D<'_> {
     fn get_width(&self) -> ReadResult<u64> {
         self.get_width().map(|i| i as u64)
@@ -22,23 +20,67 @@ impl Texture2DObject for Texture2D<'_> {
             .map_err(|e| Error::Other(e.to_string()))
     }
 
-    fn get_image_data(&self, viewer: &UnityAssetViewer) -> ReadResult<Vec<u8>> {
-        if let Ok(data) = self.get_image_data() {
-            if !data.is_empty() {
-                return Ok(data);
-            }
+    fn get_mip_count(&self) -> ReadResult<u64> {
+        if let Ok(mip_count) = i64::try_cast_from(self.inner, "/Base/m_MipCount") {
+            return Ok(mip_count.max(1) as u64);
+        }
+        Ok(1)
+    }
+
+    fn get_color_space(&self) -> ReadResult<ColorSpace> {
+        let Ok(color_space) = i64::try_cast_from(self.inner, "/Base/m_ColorSpace") else {
+            return Ok(ColorSpace::SRGB);
+        };
+        ColorSpace::try_from(color_space as i32).map_err(|e| Error::Other(e.to_string()))
+    }
+
+    fn check_decodable(&self, viewer: &UnityAssetViewer) -> Result<(), UndecodableReason> {
+        if !Texture2DObject::get_texture_format(self).is_ok_and(|format| format.is_decodable()) {
+            return Err(UndecodableReason::UnsupportedFormat);
+        }
+        let stream_data_path = self.get_stream_data_path().unwrap_or_default();
+        if !stream_data_path.is_empty() && self.get_stream_data_size().unwrap_or_default() > 0 {
+            return viewer
+                .get_resource_file_by_serialized_file_id_and_path(
+                    self.get_serialized_file_id(),
+                    &stream_data_path,
+                )
+                .map(|_| ())
+                .ok_or(UndecodableReason::MissingStream);
         }
+        if self.get_image_data().unwrap_or_default().is_empty()
+            && self.get_stream_data_size().unwrap_or_default() == 0
+        {
+            return Err(UndecodableReason::NoData);
+        }
+        Ok(())
+    }
 
-        if let Some(mut file) = viewer.get_resource_file_by_serialized_file_id_and_path(
-            self.get_serialized_file_id(),
-            &self.get_stream_data_path()?,
-        ) {
-            file.seek(SeekFrom::Start(self.get_stream_data_offset()?))?;
-            let mut data = vec![0u8; self.get_stream_data_size()? as usize];
-            file.read_exact(&mut data)?;
+    /// Streamed textures keep some or all mips in the resource named by
+    /// `m_StreamData/path`, or further on in the data section of their own
+    /// serialized file when the path is empty. Whatever is inline comes
+    /// first, as it holds the larger mips, followed by the streamed bytes.
+    fn get_image_data(&self, viewer: &UnityAssetViewer) -> ReadResult<Vec<u8>> {
+        let mut data = self.get_image_data().unwrap_or_default();
+        let stream_data_path = self.get_stream_data_path().unwrap_or_default();
+        let stream_data_size = self.get_stream_data_size().unwrap_or_default();
+        if stream_data_size == 0 {
+            if data.is_empty() && stream_data_path.is_empty() {
+                return Err(Error::Other("cannot find image data".to_owned()));
+            }
             return Ok(data);
         }
-        Err(Error::Other("cannot find image data".to_owned()))
+
+        let stream_data = viewer
+            .read_stream_data(
+                self.get_serialized_file_id(),
+                &stream_data_path,
+                self.get_stream_data_offset()?,
+                stream_data_size as usize,
+            )?
+            .ok_or_else(|| Error::Other(format!("cannot find image data in {stream_data_path}")))?;
+        data.extend(stream_data);
+        Ok(data)
     }
 }
 
@@ -21,6 +21,10 @@ pub trait TransformObject {
     fn get_father(&self) -> ReadResult<TypeTreeObjectRef>;
     fn get_local_mat(&self) -> ReadResult<Mat4>;
     fn get_children(&self) -> ReadResult<Vec<TypeTreeObjectRef>>;
+    fn get_game_object(&self) -> ReadResult<TypeTreeObjectRef>;
+    /// See [`crate::classes::game_object::GameObjectObject::get_corresponding_source_object`].
+    fn get_corresponding_source_object(&self) -> ReadResult<TypeTreeObjectRef>;
+    fn get_prefab_instance(&self) -> ReadResult<TypeTreeObjectRef>;
 }
 
 pub fn get_transform_path(viewer: &UnityAssetViewer, transform: &Transform) -> ReadResult<String> {
@@ -1,5 +1,6 @@
 use super::{Transform, TransformObject};
 
+use crate::classes::game_object;
 use crate::error::ReadResult;
 use crate::type_tree::convert::TryCastFrom;
 use crate::type_tree::TypeTreeObjectRef;
@@ -21,6 +22,18 @@ impl TransformObject for Transform<'_> {
     fn get_children(&self) -> ReadResult<Vec<TypeTreeObjectRef>> {
         self.get_children()
     }
+
+    fn get_game_object(&self) -> ReadResult<TypeTreeObjectRef> {
+        TypeTreeObjectRef::try_cast_from(self.inner, "/Base/m_GameObject")
+    }
+
+    fn get_corresponding_source_object(&self) -> ReadResult<TypeTreeObjectRef> {
+        game_object::type_tree::get_corresponding_source_object(self.inner)
+    }
+
+    fn get_prefab_instance(&self) -> ReadResult<TypeTreeObjectRef> {
+        game_object::type_tree::get_prefab_instance(self.inner)
+    }
 }
 
 impl Transform<'_> {
@@ -0,0 +1,21 @@
+pub mod type_tree;
+
+use crate::{def_unity_class, error::ReadResult, unity_asset_view::UnityAssetViewer};
+
+def_unity_class!(VideoClip);
+
+pub trait VideoClipObject {
+    fn get_original_path(&self) -> ReadResult<String>;
+    fn get_width(&self) -> ReadResult<u64>;
+    fn get_height(&self) -> ReadResult<u64>;
+    /// The movie file as imported, usually a complete `.mp4` or `.webm`.
+    fn get_movie_data(&self, viewer: &UnityAssetViewer) -> ReadResult<Vec<u8>>;
+
+    /// File extension of the original movie, e.g. `mp4`, if the path has one.
+    fn get_extension(&self) -> Option<String> {
+        let original_path = self.get_original_path().ok()?;
+        std::path::Path::new(&original_path)
+            .extension()
+            .map(|ext| ext.to_string_lossy().into_owned())
+    }
+}
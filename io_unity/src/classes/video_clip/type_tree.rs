@@ -0,0 +1,55 @@
+use super::{VideoClip, VideoClipObject};
+use crate::error::Error;
+use crate::type_tree::convert::TryCastFrom;
+use crate::unity_asset_view::UnityAssetViewer;
+use crate::{classes::SerializedFileRef, error::ReadResult};
+use std::io::{prelude::*, SeekFrom};
+
+impl VideoClipObject for VideoClip<'_> {
+    fn get_original_path(&self) -> ReadResult<String> {
+        String::try_cast_from(self.inner, "/Base/m_OriginalPath")
+    }
+
+    fn get_width(&self) -> ReadResult<u64> {
+        u64::try_cast_from(self.inner, "/Base/m_ProxyWidth")
+    }
+
+    fn get_height(&self) -> ReadResult<u64> {
+        u64::try_cast_from(self.inner, "/Base/m_ProxyHeight")
+    }
+
+    fn get_movie_data(&self, viewer: &UnityAssetViewer) -> ReadResult<Vec<u8>> {
+        let resource_source = self.get_resource_source()?;
+        let resource_offset = self.get_resource_offset()?;
+        let resource_size = self.get_resource_size()?;
+
+        if let Some(mut file) = viewer.get_resource_file_by_serialized_file_id_and_path(
+            self.get_serialized_file_id(),
+            &resource_source,
+        ) {
+            file.seek(SeekFrom::Start(resource_offset))?;
+            let mut data = vec![0u8; resource_size as usize];
+            file.read_exact(&mut data)?;
+            return Ok(data);
+        }
+        Err(Error::Other("Get movie data fail".to_owned()))
+    }
+}
+
+impl VideoClip<'_> {
+    fn get_resource_source(&self) -> ReadResult<String> {
+        String::try_cast_from(self.inner, "/Base/m_ExternalResources/m_Source")
+    }
+
+    fn get_resource_offset(&self) -> ReadResult<u64> {
+        let offset = u64::try_cast_from(self.inner, "/Base/m_ExternalResources/m_Offset");
+        if offset.is_ok() {
+            return offset;
+        }
+        Ok(usize::try_cast_from(self.inner, "/Base/m_ExternalResources/m_Offset")? as u64)
+    }
+
+    fn get_resource_size(&self) -> ReadResult<u64> {
+        u64::try_cast_from(self.inner, "/Base/m_ExternalResources/m_Size")
+    }
+}
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+#[cfg(feature = "external-class-handle-texture2d")]
+use crate::classes::texture2d::TextureFormat;
 use crate::serialized_file::Object;
 
 pub type ReadResult<T, E = Error> = core::result::Result<T, E>;
@@ -11,7 +13,7 @@ pub enum Error {
     #[error("IO error while read : {0}")]
     IOError(#[from] std::io::Error),
     #[error("Binrw error while read : {0}")]
-    BinrwError(#[from] binrw::Error),
+    BinrwError(binrw::Error),
     #[error("error while read object. data_offset: {data_offset} object : {object_meta:?} error : {source:?}")]
     ObjectReadError {
         source: Box<Error>,
@@ -39,8 +41,39 @@ pub enum Error {
     AsSliceError(&'static str),
     #[error("ArrayItemOffset use without field offset.")]
     ArrayItemOffsetError,
+    #[cfg(feature = "external-class-handle-texture2d")]
+    #[error("error while decode {texture_format} texture : {source}")]
+    TextureDecodeError {
+        texture_format: TextureFormat,
+        source: Box<Error>,
+    },
+    #[error("unsupported UnityFS compression type {0}")]
+    UnsupportedCompression(u32),
+    #[error("unsupported audio codec {0}")]
+    UnsupportedAudioCodec(String),
+    #[error("corrupt string of length {length} with {remaining} bytes left in the object")]
+    CorruptString { length: i64, remaining: u64 },
+    #[error("object {path_id} was removed and has no data")]
+    ObjectRemoved { path_id: i64 },
+    /// A file shorter than its header declares, such as a partial download.
+    #[error("truncated file of {available} bytes, {expected} expected")]
+    TruncatedFile { expected: u64, available: u64 },
     #[error("{0}")]
     Other(String),
     #[error("unknown error")]
     Unknown,
 }
+
+impl From<binrw::Error> for Error {
+    /// Errors raised by this crate from inside binrw parsers come back as
+    /// themselves rather than as an opaque binrw error.
+    fn from(err: binrw::Error) -> Self {
+        match err {
+            binrw::Error::Custom { pos, err } => match err.downcast::<Error>() {
+                Ok(err) => *err,
+                Err(err) => Error::BinrwError(binrw::Error::Custom { pos, err }),
+            },
+            err => Error::BinrwError(err),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{prelude::*, BufReader, SeekFrom};
+use std::path::Path;
+
+use crate::error::ReadResult;
+use crate::serialized_file::SerializedFileFormatVersion;
+use crate::unity_asset_view::is_resource_file;
+
+/// What kind of Unity file a file is, as told by [`identify_file`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    UnityFS,
+    SerializedFile,
+    /// A `.resS`/`.resource` stream of texture, audio or video data. These
+    /// have no header, so only [`identify_path`] tells them by extension.
+    ResourceStream,
+    /// A WebGL `.data` archive, once decompressed. This crate does not read them.
+    WebGLData,
+    Unknown,
+}
+
+impl fmt::Display for FileKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FileKind::UnityFS => "UnityFS bundle",
+            FileKind::SerializedFile => "serialized file",
+            FileKind::ResourceStream => "resource stream",
+            FileKind::WebGLData => "WebGL data archive",
+            FileKind::Unknown => "unknown file",
+        })
+    }
+}
+
+/// Tell a file's kind from its first bytes, without reading the rest. The
+/// reader is left where it was.
+pub fn identify_file<R: Read + Seek>(reader: &mut R) -> ReadResult<FileKind> {
+    let start = reader.stream_position()?;
+    let len = reader.seek(SeekFrom::End(0))?.saturating_sub(start);
+    reader.seek(SeekFrom::Start(start))?;
+    let mut head = Vec::with_capacity(48);
+    let read = reader.by_ref().take(48).read_to_end(&mut head);
+    reader.seek(SeekFrom::Start(start))?;
+    read?;
+
+    Ok(if head.starts_with(b"UnityFS\0") {
+        FileKind::UnityFS
+    } else if head.starts_with(b"UnityWebData1.0\0") {
+        FileKind::WebGLData
+    } else if is_serialized_file_header(&head, len) {
+        FileKind::SerializedFile
+    } else {
+        FileKind::Unknown
+    })
+}
+
+/// Same as [`identify_file`] for the file at `path`, naming resource
+/// streams by their extension.
+pub fn identify_path<P: AsRef<Path>>(path: P) -> ReadResult<FileKind> {
+    let path = path.as_ref();
+    if is_resource_file(path) {
+        return Ok(FileKind::ResourceStream);
+    }
+    identify_file(&mut BufReader::new(File::open(path)?))
+}
+
+/// Whether `head` starts a serialized file of `len` bytes. The header has no
+/// magic, so this checks that the version is known and the declared sizes
+/// fit together.
+fn is_serialized_file_header(head: &[u8], len: u64) -> bool {
+    let be_u32 = |offset: usize| {
+        head.get(offset..offset + 4)
+            .map(|bytes| u32::from_be_bytes(bytes.try_into().unwrap()) as u64)
+    };
+    let be_u64 = |offset: usize| {
+        head.get(offset..offset + 8)
+            .map(|bytes| u64::from_be_bytes(bytes.try_into().unwrap()))
+    };
+    let (Some(metadata_size), Some(file_size), Some(version), Some(data_offset)) =
+        (be_u32(0), be_u32(4), be_u32(8), be_u32(12))
+    else {
+        return false;
+    };
+    let Ok(version) = SerializedFileFormatVersion::try_from(version as u32) else {
+        return false;
+    };
+    if (version.clone() as u32) < SerializedFileFormatVersion::Unknown_9 as u32 {
+        // The metadata, endianness flag first, is at the end of the file.
+        return file_size <= len && metadata_size > 0 && metadata_size < file_size;
+    }
+    if !matches!(head.get(16), Some(0 | 1)) {
+        return false;
+    }
+    let (metadata_size, file_size, data_offset) =
+        if version == SerializedFileFormatVersion::LargeFilesSupport {
+            let (Some(metadata_size), Some(file_size), Some(data_offset)) =
+                (be_u32(20), be_u64(24), be_u64(32))
+            else {
+                return false;
+            };
+            (metadata_size, file_size, data_offset)
+        } else {
+            (metadata_size, file_size, data_offset)
+        };
+    file_size <= len && metadata_size < file_size && data_offset <= file_size
+}
@@ -1,6 +1,11 @@
 pub mod classes;
 pub mod error;
+pub mod identify;
+pub mod parsers;
+#[cfg(feature = "remote")]
+pub mod remote;
 pub mod serialized_file;
+pub mod split;
 pub mod type_tree;
 pub mod unity_asset_view;
 pub mod unityfs;
@@ -0,0 +1,7 @@
+//! The binrw helper types used by this crate's class and serialized file
+//! structs, for downstream structs read with the same conventions.
+
+pub use crate::until::binrw_parser::{
+    position_parser, AlignedString, PackedFloatVector, PackedIntVector, PackedQuatVector, U8Bool,
+};
+pub use crate::until::{Endian, UnityVersion};
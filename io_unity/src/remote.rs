@@ -0,0 +1,110 @@
+use std::io::{prelude::*, ErrorKind, SeekFrom};
+
+/// A `Read + Seek` view of a remote file that fetches the bytes it needs
+/// with HTTP range requests, `chunk_size` bytes at a time.
+pub struct HttpRangeReader {
+    agent: ureq::Agent,
+    url: String,
+    len: u64,
+    position: u64,
+    chunk_size: u64,
+    chunk_start: u64,
+    chunk: Vec<u8>,
+}
+
+impl HttpRangeReader {
+    pub const DEFAULT_CHUNK_SIZE: u64 = 1 << 20;
+
+    pub fn new(url: &str) -> std::io::Result<Self> {
+        Self::with_chunk_size(url, Self::DEFAULT_CHUNK_SIZE)
+    }
+
+    pub fn with_chunk_size(url: &str, chunk_size: u64) -> std::io::Result<Self> {
+        let agent = ureq::Agent::new();
+        let response = agent.head(url).call().map_err(to_io_error)?;
+        if response.header("Accept-Ranges") == Some("none") {
+            return Err(std::io::Error::new(
+                ErrorKind::Unsupported,
+                "server does not accept range requests",
+            ));
+        }
+        let len = response
+            .header("Content-Length")
+            .and_then(|len| len.parse().ok())
+            .ok_or_else(|| std::io::Error::new(ErrorKind::InvalidData, "missing Content-Length"))?;
+        Ok(HttpRangeReader {
+            agent,
+            url: url.to_owned(),
+            len,
+            position: 0,
+            chunk_size: chunk_size.max(1),
+            chunk_start: 0,
+            chunk: Vec::new(),
+        })
+    }
+
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn fetch(&mut self, start: u64, size: u64) -> std::io::Result<()> {
+        let end = (start + size).min(self.len) - 1;
+        let response = self
+            .agent
+            .get(&self.url)
+            .set("Range", &format!("bytes={start}-{end}"))
+            .call()
+            .map_err(to_io_error)?;
+        if response.status() != 206 {
+            return Err(std::io::Error::new(
+                ErrorKind::Unsupported,
+                format!("expected partial content, got status {}", response.status()),
+            ));
+        }
+        let mut chunk = Vec::with_capacity((end - start + 1) as usize);
+        response.into_reader().read_to_end(&mut chunk)?;
+        self.chunk_start = start;
+        self.chunk = chunk;
+        Ok(())
+    }
+}
+
+impl Read for HttpRangeReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() || self.position >= self.len {
+            return Ok(0);
+        }
+        let chunk_end = self.chunk_start + self.chunk.len() as u64;
+        if self.position < self.chunk_start || self.position >= chunk_end {
+            self.fetch(self.position, self.chunk_size.max(buf.len() as u64))?;
+        }
+        let offset = (self.position - self.chunk_start) as usize;
+        let size = buf.len().min(self.chunk.len() - offset);
+        buf[..size].copy_from_slice(&self.chunk[offset..offset + size]);
+        self.position += size as u64;
+        Ok(size)
+    }
+}
+
+impl Seek for HttpRangeReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(pos) => pos as i64,
+            SeekFrom::End(offset) => self.len as i64 + offset,
+            SeekFrom::Current(offset) => self.position as i64 + offset,
+        };
+        if position < 0 {
+            return Err(std::io::Error::from(ErrorKind::InvalidInput));
+        }
+        self.position = position as u64;
+        Ok(self.position)
+    }
+}
+
+fn to_io_error(err: ureq::Error) -> std::io::Error {
+    std::io::Error::other(err)
+}
@@ -19,12 +19,14 @@ pub mod version6;
 pub mod version7;
 pub mod version8;
 pub mod version9;
+pub(crate) mod writer;
 
 use std::borrow::Cow;
-use std::cell::RefCell;
+use std::cell::{OnceCell, RefCell};
 use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::io::{prelude::*, SeekFrom};
+use std::sync::Arc;
 
 use binrw::BinRead;
 use binrw::{binrw, BinResult};
@@ -32,12 +34,14 @@ use binrw::{binrw, BinResult};
 use num_enum::TryFromPrimitive;
 use once_cell::sync::Lazy;
 
-use crate::error::Error;
+use crate::error::{Error, ReadResult};
 #[cfg(feature = "type-tree-json")]
 use crate::type_tree::type_tree_json::get_type_object_args_by_version_class_id;
 use crate::type_tree::{
-    reader::TypeTreeObjectBinReadArgs, reader::TypeTreeObjectBinReadClassArgs, TypeTreeObject,
+    reader::TypeTreeObjectBinReadArgs, reader::TypeTreeObjectBinReadClassArgs, Hash128, TypeField,
+    TypeTreeNode, TypeTreeObject,
 };
+use crate::unity_asset_view::normalize_external_name;
 use crate::unityfs::UnityResource;
 use crate::until::{Endian, UnityVersion};
 
@@ -324,32 +328,360 @@ pub struct Object {
     byte_start: u64,
     byte_size: u32,
     pub class: i32,
-    type_id: usize,
+    pub(crate) type_id: usize,
+    is_destroyed: Option<u16>,
+    script_type_index: Option<i16>,
+    stripped: Option<bool>,
 }
 
-pub struct SerializedFile {
+impl Object {
+    /// Whether the entry has no data, as removed objects in patch-delta
+    /// files. Reading it fails with [`Error::ObjectRemoved`], so loops over
+    /// [`SerializedFile::get_object_map`] skip these.
+    pub fn is_removed(&self) -> bool {
+        self.byte_size == 0
+    }
+}
+
+/// An object table entry. Fields the file format version does not store are `None`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ObjectInfo {
+    pub path_id: i64,
+    /// Offset of the object data from the start of the data section.
+    pub byte_start: u64,
+    pub byte_size: u32,
+    pub class_id: i32,
+    pub type_id: usize,
+    /// Also `Some(1)` for entries without data, which Unity leaves in patch-delta files.
+    pub is_destroyed: Option<u16>,
+    /// See [`SerializedFile::script_type_index`].
+    pub script_type_index: Option<i16>,
+    pub stripped: Option<bool>,
+}
+
+impl ObjectInfo {
+    /// See [`crate::classes::class_name`].
+    pub fn class_name(&self) -> &'static str {
+        crate::classes::class_name(self.class_id)
+    }
+}
+
+/// An `m_Externals` entry with the `m_FileID` that refers to it, see
+/// [`SerializedFile::external_references`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ExternalRef {
+    /// The `m_FileID` of PPtrs into this file, counting from 1.
+    pub file_id: i64,
+    /// The path as stored, such as `archive:/CAB-xxxx/CAB-xxxx`.
+    pub path: String,
+    /// The name [`crate::unity_asset_view::UnityAssetViewer`] looks the
+    /// file up by.
+    pub name: String,
+    /// The asset GUID, zero for files referenced by path only.
+    pub guid: Hash128,
+    pub r#type: i32,
+}
+
+/// What [`SerializedFile::iter_objects`] does with an object whose class
+/// has neither an embedded TypeTree nor a database entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingTypeTreePolicy {
+    /// Yield an [`Error::ObjectReadError`], as
+    /// [`SerializedFile::iter_tt_objects`] does.
+    #[default]
+    Error,
+    /// Leave the object out.
+    SkipObject,
+    /// Yield the object's serialized bytes as [`ObjectData::RawBytes`].
+    ReturnRawBytes,
+}
+
+/// An object read by [`SerializedFile::iter_objects`].
+#[derive(Debug)]
+pub enum ObjectData {
+    TypeTree(TypeTreeObject),
+    /// The serialized bytes of an object without a TypeTree.
+    RawBytes {
+        path_id: i64,
+        class_id: i32,
+        data: Vec<u8>,
+    },
+}
+
+/// A type table entry, one per class layout used by the file's objects.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SerializedType {
+    pub class_id: i32,
+    /// Index into the script types for MonoBehaviour layouts, format 17 onwards.
+    pub script_type_index: Option<i16>,
+    /// Format 13 onwards.
+    pub type_tree_hash: Option<[u8; 16]>,
+    pub has_type_tree: bool,
+}
+
+/// A type table entry as one format stores it. The defaults fit formats
+/// before 13, which kept no hashes and always embedded TypeTrees.
+trait TypeTableEntry {
+    fn class_id(&self) -> i32;
+
+    fn script_type_index(&self) -> Option<i16> {
+        None
+    }
+
+    fn type_tree_hash(&self) -> Option<[u8; 16]> {
+        None
+    }
+
+    fn has_type_tree(&self) -> bool {
+        true
+    }
+    /// Whether the TypeTree nodes have been parsed, which formats storing
+    /// them as blobs put off until first use.
+    fn is_type_tree_decoded(&self) -> bool {
+        true
+    }
+}
+
+fn serialized_types<T: TypeTableEntry>(types: &[T]) -> Vec<SerializedType> {
+    types
+        .iter()
+        .map(|tp| SerializedType {
+            class_id: tp.class_id(),
+            script_type_index: tp.script_type_index(),
+            type_tree_hash: tp.type_tree_hash(),
+            has_type_tree: tp.has_type_tree(),
+        })
+        .collect()
+}
+
+/// An `m_RefTypes` entry, the layout of a `SerializeReference` managed
+/// reference type, format 20 onwards.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SerializedRefType {
+    pub class_id: i32,
+    pub script_type_index: i16,
+    /// The names registry entries refer to the type by. Format 20 and files
+    /// without TypeTrees do not store them, leaving them empty.
+    pub class_name: String,
+    pub name_space: String,
+    pub assembly_name: String,
+    pub has_type_tree: bool,
+}
+
+/// The header, type table and object table of a serialized file.
+struct Tables {
     content: Box<dyn Serialized + Send + Sync>,
-    file_reader: RefCell<Box<dyn UnityResource + Send + Sync>>,
+    types: Vec<SerializedType>,
+    ref_types: Vec<SerializedRefType>,
     object_map: BTreeMap<i64, Object>,
+    read_error: Option<String>,
+}
+
+/// The content of a deferred file that failed to read: no types, no
+/// objects and no externals.
+#[derive(Debug)]
+struct Unreadable;
+
+impl Serialized for Unreadable {
+    fn get_serialized_file_version(&self) -> &SerializedFileFormatVersion {
+        &SerializedFileFormatVersion::Unsupported
+    }
+
+    fn get_data_offset(&self) -> u64 {
+        0
+    }
+
+    fn get_endianess(&self) -> &Endian {
+        &Endian::Little
+    }
+
+    fn get_objects_metadata(&self) -> Vec<Object> {
+        Vec::new()
+    }
+
+    fn get_types(&self) -> Vec<SerializedType> {
+        Vec::new()
+    }
+
+    fn get_type_object_args_by_type_id(
+        &self,
+        _type_id: usize,
+    ) -> Option<TypeTreeObjectBinReadClassArgs> {
+        None
+    }
+
+    fn get_unity_version(&self) -> String {
+        "0.0.0".to_owned()
+    }
+
+    fn get_target_platform(&self) -> &BuildTarget {
+        &BuildTarget::NoTarget
+    }
+
+    fn get_enable_type_tree(&self) -> bool {
+        false
+    }
+
+    fn get_externals(&self) -> Cow<Vec<FileIdentifier>> {
+        Cow::Owned(Vec::new())
+    }
+}
+
+pub struct SerializedFile {
+    /// Set on read, or on first use for files from [`Self::read_deferred`].
+    tables: OnceCell<Tables>,
+    file_reader: RefCell<Box<dyn UnityResource + Send + Sync>>,
     serialized_file_id: i64,
+    keep_raw_bytes: bool,
     pub resource_search_path: Option<String>,
 }
 
 impl fmt::Debug for SerializedFile {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("SerializedFile")
-            .field("content", &self.content)
+            .field("content", &self.tables.get().map(|tables| &tables.content))
             .finish()
     }
 }
 
 impl SerializedFile {
+    /// Same as [`Self::read`], reading the file as `endianness` whatever its
+    /// endianness flag says. This overrides the file's own flag, for modded
+    /// or mis-flagged files that otherwise parse as garbage.
+    pub fn read_with_endianness(
+        mut reader: Box<dyn UnityResource + Send + Sync>,
+        serialized_file_id: i64,
+        resource_search_path: Option<String>,
+        endianness: binrw::Endian,
+    ) -> BinResult<Self> {
+        let Ok(head) = SerializedFileCommonHeader::read(&mut reader) else {
+            // Let `read` name the problem.
+            reader.seek(SeekFrom::Start(0))?;
+            return Self::read(reader, serialized_file_id, resource_search_path);
+        };
+        // Before format 9 the flag starts the metadata at the end of the file.
+        let flag_offset =
+            if (head.version.clone() as u32) < SerializedFileFormatVersion::Unknown_9 as u32 {
+                head.file_size.saturating_sub(head.metadata_size) as u64
+            } else {
+                16
+            };
+        reader.seek(SeekFrom::Start(0))?;
+        let reader = EndiannessOverride {
+            inner: reader,
+            position: 0,
+            flag_offset,
+            flag: match endianness {
+                binrw::Endian::Little => Endian::Little as u8,
+                binrw::Endian::Big => Endian::Big as u8,
+            },
+        };
+        Self::read(Box::new(reader), serialized_file_id, resource_search_path)
+    }
+
     pub fn read(
+        reader: Box<dyn UnityResource + Send + Sync>,
+        serialized_file_id: i64,
+        resource_search_path: Option<String>,
+    ) -> BinResult<Self> {
+        let file = Self::read_lazy(reader, serialized_file_id, resource_search_path)?;
+        file.tables().content.decode_type_trees();
+        Ok(file)
+    }
+
+    /// Same as [`Self::read`], but only reads the type table far enough to
+    /// reach the object table. TypeTree nodes are parsed when an object of
+    /// their type is first read, which saves time on huge files when only a
+    /// few objects are wanted. Formats storing TypeTrees as nested nodes
+    /// rather than blobs are always parsed in full.
+    pub fn read_lazy(
         mut reader: Box<dyn UnityResource + Send + Sync>,
         serialized_file_id: i64,
         resource_search_path: Option<String>,
     ) -> BinResult<Self> {
-        let head = SerializedFileCommonHeader::read(&mut reader)?;
+        let tables = Self::read_tables(&mut reader)?;
+        Ok(SerializedFile {
+            tables: OnceCell::from(tables),
+            file_reader: RefCell::new(reader),
+            serialized_file_id,
+            keep_raw_bytes: false,
+            resource_search_path,
+        })
+    }
+
+    /// Same as [`Self::read`], but nothing is read until the file is first
+    /// used, for when what is needed of it on load is known from elsewhere,
+    /// such as a container index. A file that fails to read then has no
+    /// objects, and [`Self::read_error`] says why.
+    pub fn read_deferred(
+        reader: Box<dyn UnityResource + Send + Sync>,
+        serialized_file_id: i64,
+        resource_search_path: Option<String>,
+    ) -> Self {
+        SerializedFile {
+            tables: OnceCell::new(),
+            file_reader: RefCell::new(reader),
+            serialized_file_id,
+            keep_raw_bytes: false,
+            resource_search_path,
+        }
+    }
+
+    /// Whether the header and tables have been read, which is on read
+    /// except for files from [`Self::read_deferred`].
+    pub fn is_read(&self) -> bool {
+        self.tables.get().is_some()
+    }
+
+    /// Why a file from [`Self::read_deferred`] could not be read.
+    pub fn read_error(&self) -> Option<&str> {
+        self.tables().read_error.as_deref()
+    }
+
+    fn tables(&self) -> &Tables {
+        self.tables.get_or_init(|| {
+            let mut reader = self.file_reader.borrow_mut();
+            match Self::read_tables(&mut reader) {
+                Ok(tables) => {
+                    tables.content.decode_type_trees();
+                    tables
+                }
+                Err(err) => Tables {
+                    content: Box::new(Unreadable),
+                    types: Vec::new(),
+                    ref_types: Vec::new(),
+                    object_map: BTreeMap::new(),
+                    read_error: Some(err.to_string()),
+                },
+            }
+        })
+    }
+
+    fn read_tables(mut reader: &mut Box<dyn UnityResource + Send + Sync>) -> BinResult<Tables> {
+        let head = SerializedFileCommonHeader::read(&mut reader).map_err(|err| {
+            // Name the version when a newer Unity bumps the format past 22.
+            let version = reader
+                .seek(SeekFrom::Start(8))
+                .and_then(|_| {
+                    let mut version = [0u8; 4];
+                    reader.read_exact(&mut version).map(|_| version)
+                })
+                .map(u32::from_be_bytes);
+            match version {
+                Ok(version) if version > SerializedFileFormatVersion::LargeFilesSupport as u32 => {
+                    binrw::Error::AssertFail {
+                        pos: 8,
+                        message: format!("unsupported serialized file format version {version}"),
+                    }
+                }
+                _ => err,
+            }
+        })?;
+        let available = reader.seek(SeekFrom::End(0))?;
+        let expected = declared_metadata_end(&mut reader, &head)?;
+        if expected > available {
+            return Err(truncated_file(expected, available));
+        }
         reader.seek(SeekFrom::Start(0))?;
         let file: Box<dyn Serialized + Send + Sync> = match head.version {
             SerializedFileFormatVersion::Unsupported => {
@@ -417,25 +749,257 @@ impl SerializedFile {
             }
         };
         let mut object_map = BTreeMap::new();
-        for obj in file.get_objects_metadata() {
-            object_map.insert(obj.path_id, obj);
+        for mut obj in file.get_objects_metadata() {
+            // Patch-delta files keep table entries for removed objects but no data.
+            if obj.byte_size == 0 {
+                obj.is_destroyed = Some(1);
+            }
+            let object_end = file.get_data_offset() + obj.byte_start + obj.byte_size as u64;
+            if object_end > available {
+                return Err(truncated_file(object_end, available));
+            }
+            if let Some(duplicate) = object_map.insert(obj.path_id, obj) {
+                return Err(binrw::Error::AssertFail {
+                    pos: 0,
+                    message: format!("duplicate path id {} in object table", duplicate.path_id),
+                });
+            }
         }
-        Ok(SerializedFile {
+        Ok(Tables {
+            types: file.get_types(),
+            ref_types: file.get_ref_types(),
             content: file,
-            file_reader: RefCell::new(reader),
             object_map,
-            serialized_file_id,
-            resource_search_path,
+            read_error: None,
         })
     }
 
+    /// The type table. A layout without a TypeTree needs a TypeTree database
+    /// to read its objects.
+    pub fn types(&self) -> &[SerializedType] {
+        &self.tables().types
+    }
+
+    /// Whether the TypeTree of the type at `type_id` has been parsed. Only
+    /// files read with [`Self::read_lazy`] have types that are not, until
+    /// an object of the type is first read.
+    pub fn is_type_tree_decoded(&self, type_id: usize) -> bool {
+        self.tables().content.is_type_tree_decoded(type_id)
+    }
+
+    /// The ref type table, which decodes the entries of a
+    /// `ManagedReferencesRegistry`. Empty before format 20.
+    pub fn ref_types(&self) -> &[SerializedRefType] {
+        &self.tables().ref_types
+    }
+
     pub fn get_object_map(&self) -> &BTreeMap<i64, Object> {
-        &self.object_map
+        &self.tables().object_map
+    }
+
+    /// Read every object, or those of `class_id`, in path id order. A failed
+    /// object is yielded as an [`Error::ObjectReadError`] carrying its path
+    /// and class id, and iteration carries on with the next one. Removed
+    /// objects, see [`Object::is_removed`], are left out.
+    pub fn iter_tt_objects(
+        &self,
+        class_id: Option<i32>,
+    ) -> impl Iterator<Item = ReadResult<TypeTreeObject>> + '_ {
+        self.tables()
+            .object_map
+            .values()
+            .filter(move |obj| class_id.is_none_or(|class_id| obj.class == class_id))
+            .filter(|obj| !obj.is_removed())
+            .map(|obj| {
+                self.get_tt_object_by_path_id(obj.path_id)?
+                    .ok_or_else(|| self.missing_type_tree_error(obj))
+            })
+    }
+
+    /// Same as [`Self::iter_tt_objects`], with objects whose class has no
+    /// TypeTree handled as `policy` says.
+    pub fn iter_objects(
+        &self,
+        class_id: Option<i32>,
+        policy: MissingTypeTreePolicy,
+    ) -> impl Iterator<Item = ReadResult<ObjectData>> + '_ {
+        self.tables()
+            .object_map
+            .values()
+            .filter(move |obj| class_id.is_none_or(|class_id| obj.class == class_id))
+            .filter(|obj| !obj.is_removed())
+            .filter_map(
+                move |obj| match self.get_tt_object_by_path_id(obj.path_id) {
+                    Ok(Some(object)) => Some(Ok(ObjectData::TypeTree(object))),
+                    Ok(None) => match policy {
+                        MissingTypeTreePolicy::Error => {
+                            Some(Err(self.missing_type_tree_error(obj)))
+                        }
+                        MissingTypeTreePolicy::SkipObject => None,
+                        MissingTypeTreePolicy::ReturnRawBytes => {
+                            Some(self.get_object_raw_bytes(obj.path_id).map(|data| {
+                                ObjectData::RawBytes {
+                                    path_id: obj.path_id,
+                                    class_id: obj.class,
+                                    data: data.unwrap_or_default(),
+                                }
+                            }))
+                        }
+                    },
+                    Err(err) => Some(Err(err)),
+                },
+            )
+    }
+
+    fn missing_type_tree_error(&self, obj: &Object) -> Error {
+        Error::ObjectReadError {
+            source: Box::new(Error::Other(format!("no TypeTree for class {}", obj.class))),
+            data_offset: self.tables().content.get_data_offset(),
+            object_meta: obj.clone(),
+        }
+    }
+
+    /// Path ids sorted by where the object data starts in the file,
+    /// which is the order Unity wrote them in.
+    pub fn objects_in_file_order(&self) -> Vec<i64> {
+        let mut objects: Vec<&Object> = self.tables().object_map.values().collect();
+        objects.sort_by_key(|obj| obj.byte_start);
+        objects.into_iter().map(|obj| obj.path_id).collect()
+    }
+
+    pub fn object_info(&self, path_id: i64) -> Option<ObjectInfo> {
+        self.tables()
+            .object_map
+            .get(&path_id)
+            .map(|obj| ObjectInfo {
+                path_id: obj.path_id,
+                byte_start: obj.byte_start,
+                byte_size: obj.byte_size,
+                class_id: obj.class,
+                type_id: obj.type_id,
+                is_destroyed: obj.is_destroyed,
+                script_type_index: self.script_type_index(path_id),
+                stripped: obj.stripped,
+            })
     }
 
+    /// Path ids, in order, of the objects whose table entry `f` accepts,
+    /// such as those over a size or of one script type. Only the object
+    /// table is read.
+    pub fn filter_objects<F: Fn(&ObjectInfo) -> bool>(&self, f: F) -> Vec<i64> {
+        self.tables()
+            .object_map
+            .keys()
+            .filter_map(|path_id| self.object_info(*path_id))
+            .filter(|info| f(info))
+            .map(|info| info.path_id)
+            .collect()
+    }
+
+    /// The object's index into the script types, naming the MonoScript of a
+    /// MonoBehaviour, to tell apart objects that all share class 114. -1 for
+    /// objects without a script. Read from the object table, or the type
+    /// table from format 17. `None` before format 11.
+    pub fn script_type_index(&self, path_id: i64) -> Option<i16> {
+        let obj = self.tables().object_map.get(&path_id)?;
+        obj.script_type_index
+            .or_else(|| self.tables().types.get(obj.type_id)?.script_type_index)
+    }
+
+    /// The serialized bytes of an object, for parsing classes without a TypeTree.
+    pub fn get_object_raw_bytes(&self, path_id: i64) -> ReadResult<Option<Vec<u8>>> {
+        let Some(obj) = self.tables().object_map.get(&path_id) else {
+            return Ok(None);
+        };
+        let mut reader = self.file_reader.borrow_mut();
+        reader.seek(SeekFrom::Start(
+            self.tables().content.get_data_offset() + obj.byte_start,
+        ))?;
+        let mut data = vec![0u8; obj.byte_size as usize];
+        reader.read_exact(&mut data)?;
+        Ok(Some(data))
+    }
+
+    /// `size` bytes of the data section from `offset`, where a StreamingInfo
+    /// with an empty path keeps its data. Fails with [`Error::TruncatedFile`]
+    /// before allocating when the range runs past the end of the file.
+    pub fn read_data(&self, offset: u64, size: usize) -> ReadResult<Vec<u8>> {
+        let start = self.get_data_offset().saturating_add(offset);
+        let mut reader = self.file_reader.borrow_mut();
+        check_range(&mut *reader, start, size)?;
+        reader.seek(SeekFrom::Start(start))?;
+        let mut data = vec![0u8; size];
+        reader.read_exact(&mut data)?;
+        Ok(data)
+    }
+
+    /// The `m_Name` of an object whose layout starts with it, read without
+    /// parsing the rest of the object. `None` for other layouts and for
+    /// classes without a TypeTree.
+    pub fn object_name(&self, path_id: i64) -> ReadResult<Option<String>> {
+        let Some(obj) = self.tables().object_map.get(&path_id) else {
+            return Ok(None);
+        };
+        let class_args = self
+            .tables()
+            .content
+            .get_type_object_args_by_type_id(obj.type_id);
+        #[cfg(feature = "type-tree-json")]
+        let class_args = class_args.or(get_type_object_args_by_version_class_id(
+            &self.tables().content.get_unity_version(),
+            obj.class,
+        ));
+        if !class_args.is_some_and(|class_args| class_args.starts_with_name()) {
+            return Ok(None);
+        }
+
+        let mut reader = self.file_reader.borrow_mut();
+        reader.seek(SeekFrom::Start(
+            self.tables().content.get_data_offset() + obj.byte_start,
+        ))?;
+        let len = i32::read_options(
+            &mut *reader,
+            self.tables().content.get_endianess().into(),
+            (),
+        )?;
+        if len < 0 || len as u64 + 4 > obj.byte_size as u64 {
+            return Err(Error::Other(format!(
+                "name of {len} bytes does not fit in a {} byte object",
+                obj.byte_size
+            )));
+        }
+        let mut name = vec![0u8; len as usize];
+        reader.read_exact(&mut name)?;
+        Ok(Some(String::from_utf8_lossy(&name).into_owned()))
+    }
+
+    /// Keep each object's original bytes on the objects read from now on,
+    /// as [`TypeTreeObject::raw_bytes`], for patching fields in place. This
+    /// holds every object's data twice, so it is off by default.
+    pub fn set_keep_raw_bytes(&mut self, keep: bool) {
+        self.keep_raw_bytes = keep;
+    }
+
+    pub fn keeps_raw_bytes(&self) -> bool {
+        self.keep_raw_bytes
+    }
+
+    fn with_raw_bytes(&self, mut object: TypeTreeObject) -> ReadResult<TypeTreeObject> {
+        if self.keep_raw_bytes {
+            object.raw_bytes = self.get_object_raw_bytes(object.path_id)?;
+        }
+        Ok(object)
+    }
+
+    /// Fails with [`Error::ObjectRemoved`] for table entries without data.
     pub fn get_tt_object_by_path_id(&self, path_id: i64) -> Result<Option<TypeTreeObject>, Error> {
-        if let Some(obj) = self.object_map.get(&path_id) {
-            self.content
+        if let Some(obj) = self.tables().object_map.get(&path_id) {
+            if obj.byte_size == 0 {
+                return Err(Error::ObjectRemoved { path_id });
+            }
+            let object = self
+                .tables()
+                .content
                 .get_type_tree_object(
                     &mut self.file_reader.borrow_mut(),
                     obj,
@@ -444,21 +1008,255 @@ impl SerializedFile {
                 )
                 .map_err(|err| Error::ObjectReadError {
                     source: err.into(),
-                    data_offset: self.content.get_data_offset(),
+                    data_offset: self.tables().content.get_data_offset(),
                     object_meta: obj.clone(),
-                })
+                })?;
+            object.map(|object| self.with_raw_bytes(object)).transpose()
         } else {
             Ok(None)
         }
     }
 
+    /// Reads an object with `nodes` in place of its embedded or database
+    /// TypeTree, for checking a layout when the known one is wrong or missing.
+    pub fn get_object_with_type_tree(
+        &self,
+        path_id: i64,
+        nodes: &[TypeTreeNode],
+    ) -> ReadResult<TypeTreeObject> {
+        let obj = self
+            .tables()
+            .object_map
+            .get(&path_id)
+            .ok_or_else(|| Error::Other(format!("cannot find object {path_id}")))?;
+        if obj.byte_size == 0 {
+            return Err(Error::ObjectRemoved { path_id });
+        }
+        let type_fields = nodes
+            .iter()
+            .map(|node| Arc::new(Box::new(node.clone()) as Box<dyn TypeField + Send + Sync>))
+            .collect();
+        let args = TypeTreeObjectBinReadArgs::new(
+            self.serialized_file_id,
+            path_id,
+            TypeTreeObjectBinReadClassArgs::new(obj.class, type_fields),
+        );
+        let object = self
+            .tables()
+            .content
+            .read_type_tree_object(&mut self.file_reader.borrow_mut(), obj, args)
+            .map_err(|err| Error::ObjectReadError {
+                source: err.into(),
+                data_offset: self.tables().content.get_data_offset(),
+                object_meta: obj.clone(),
+            })?;
+        self.with_raw_bytes(object)
+    }
+
     pub fn get_externals(&self) -> Cow<Vec<FileIdentifier>> {
-        self.content.get_externals()
+        self.tables().content.get_externals()
+    }
+
+    /// The `m_Externals` table with the file id of each entry, for mapping
+    /// a PPtr's `m_FileID` to the file it names. Resolve an entry against
+    /// the loaded files with
+    /// [`crate::unity_asset_view::UnityAssetViewer::resolve_external_reference`].
+    pub fn external_references(&self) -> Vec<ExternalRef> {
+        let endian = self.tables().content.get_endianess();
+        self.get_externals()
+            .iter()
+            .enumerate()
+            .map(|(index, external)| {
+                let mut data = [0u32; 4];
+                for (data, bytes) in data.iter_mut().zip(external.guid.chunks_exact(4)) {
+                    let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+                    *data = match endian {
+                        Endian::Big => u32::from_be_bytes(bytes),
+                        Endian::Little => u32::from_le_bytes(bytes),
+                    };
+                }
+                let path = external.path.to_string();
+                ExternalRef {
+                    file_id: index as i64 + 1,
+                    name: normalize_external_name(&path),
+                    path,
+                    guid: Hash128::from_guid_data(data),
+                    r#type: external.r#type,
+                }
+            })
+            .collect()
+    }
+
+    pub fn get_serialized_file_version(&self) -> &SerializedFileFormatVersion {
+        self.tables().content.get_serialized_file_version()
+    }
+
+    pub fn get_data_offset(&self) -> u64 {
+        self.tables().content.get_data_offset()
+    }
+
+    pub fn get_target_platform(&self) -> &BuildTarget {
+        self.tables().content.get_target_platform()
+    }
+
+    pub(crate) fn get_endianess(&self) -> &Endian {
+        self.tables().content.get_endianess()
+    }
+
+    /// The TypeTree `obj` is read with, from the file or, for files without
+    /// TypeTrees, a TypeTree database.
+    pub(crate) fn type_fields(
+        &self,
+        obj: &Object,
+    ) -> Option<Vec<Arc<Box<dyn TypeField + Send + Sync>>>> {
+        let class_args = self
+            .tables()
+            .content
+            .get_type_object_args_by_type_id(obj.type_id);
+        #[cfg(feature = "type-tree-json")]
+        let class_args = class_args.or(get_type_object_args_by_version_class_id(
+            &self.tables().content.get_unity_version(),
+            obj.class,
+        ));
+        Some(class_args?.type_fields().to_vec())
+    }
+
+    /// Whether TypeTrees are embedded in the file. When they are not, objects
+    /// can only be read with a TypeTree database matching the Unity version.
+    pub fn get_enable_type_tree(&self) -> bool {
+        self.tables().content.get_enable_type_tree()
     }
 
     pub fn get_serialized_file_id(&self) -> i64 {
         self.serialized_file_id
     }
+
+    /// The TypeTree hash of the type table entry at `type_id`, the
+    /// [`Object::type_id`] of the objects using it, to match them against
+    /// external TypeTree databases. MonoBehaviours have one entry per script,
+    /// so a class id alone does not pick a hash. Serialized file format
+    /// before 13 did not store hashes.
+    pub fn type_tree_hash(&self, type_id: usize) -> Option<[u8; 16]> {
+        self.tables().types.get(type_id)?.type_tree_hash
+    }
+
+    /// The embedded TypeTree of one class as a standalone blob, for building
+    /// TypeTree databases of only the classes needed. `script_index` picks
+    /// among the MonoBehaviour layouts by [`SerializedType::script_type_index`];
+    /// `None` takes the first layout of the class. `None` when the class has
+    /// no embedded TypeTree.
+    ///
+    /// The blob is the TypeTree as a serialized file of format 19 or later
+    /// stores it, little endian, and reads back with
+    /// [`version19::TypeTree`]: the node count and string buffer size as
+    /// `i32`, then 32 byte nodes with their type flags and the string
+    /// buffer. Every name is stored in the buffer, none as an offset into
+    /// Unity's common strings. It is not a TPK database; tools reading those
+    /// need the nodes converted.
+    pub fn export_type_tree_for_class(
+        &self,
+        class_id: i32,
+        script_index: Option<i16>,
+    ) -> Option<Vec<u8>> {
+        let type_id = self.tables().types.iter().position(|serialized_type| {
+            serialized_type.class_id == class_id
+                && serialized_type.has_type_tree
+                && script_index.is_none_or(|index| serialized_type.script_type_index == Some(index))
+        })?;
+        let class_args = self
+            .tables()
+            .content
+            .get_type_object_args_by_type_id(type_id)?;
+        Some(type_tree_blob(class_args.type_fields()))
+    }
+}
+
+/// See [`SerializedFile::export_type_tree_for_class`].
+fn type_tree_blob(fields: &[Arc<Box<dyn TypeField + Send + Sync>>]) -> Vec<u8> {
+    let mut writer = writer::Writer::new(false);
+    writer::write_type_tree(&mut writer, fields, true);
+    writer.data
+}
+
+/// Where the metadata, or the data section when it starts later, ends
+/// according to the header. Formats before 9 store the metadata at the end
+/// of the file, which the object extents check instead.
+fn declared_metadata_end<R: Read + Seek>(
+    reader: &mut R,
+    head: &SerializedFileCommonHeader,
+) -> BinResult<u64> {
+    if head.version == SerializedFileFormatVersion::LargeFilesSupport {
+        // The 64-bit sizes and offset follow the common header and endianness.
+        let mut header = [0u8; 20];
+        reader.seek(SeekFrom::Start(20))?;
+        if reader.read_exact(&mut header).is_err() {
+            return Ok(48);
+        }
+        let metadata_size = u32::from_be_bytes(header[..4].try_into().unwrap()) as u64;
+        let data_offset = u64::from_be_bytes(header[12..].try_into().unwrap());
+        return Ok((48 + metadata_size).max(data_offset));
+    }
+    if (head.version.clone() as u32) < SerializedFileFormatVersion::Unknown_9 as u32 {
+        return Ok(0);
+    }
+    Ok((20 + head.metadata_size as u64).max(head.data_offset as u64))
+}
+
+/// A reader yielding `flag` for the byte at `flag_offset`, the endianness
+/// flag of the serialized file it reads.
+struct EndiannessOverride<R> {
+    inner: R,
+    position: u64,
+    flag_offset: u64,
+    flag: u8,
+}
+
+impl<R: Read> Read for EndiannessOverride<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        if let Some(index) = self.flag_offset.checked_sub(self.position) {
+            if index < read as u64 {
+                buf[index as usize] = self.flag;
+            }
+        }
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl<R: Seek> Seek for EndiannessOverride<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.position = self.inner.seek(pos)?;
+        Ok(self.position)
+    }
+}
+
+/// Fail with [`Error::TruncatedFile`] unless `size` bytes from `start` are
+/// within `reader`, so sizes read from a file bound no allocation.
+pub(crate) fn check_range<R: Seek + ?Sized>(
+    reader: &mut R,
+    start: u64,
+    size: usize,
+) -> ReadResult<()> {
+    let available = reader.seek(SeekFrom::End(0))?;
+    let expected = start.saturating_add(size as u64);
+    if expected > available {
+        return Err(Error::TruncatedFile {
+            expected,
+            available,
+        });
+    }
+    Ok(())
+}
+
+fn truncated_file(expected: u64, available: u64) -> binrw::Error {
+    binrw::Error::Custom {
+        pos: available,
+        err: Box::new(Error::TruncatedFile {
+            expected,
+            available,
+        }),
+    }
 }
 
 pub trait Serialized: fmt::Debug {
@@ -466,6 +1264,7 @@ pub trait Serialized: fmt::Debug {
     fn get_data_offset(&self) -> u64;
     fn get_endianess(&self) -> &Endian;
     fn get_objects_metadata(&self) -> Vec<Object>;
+    fn get_types(&self) -> Vec<SerializedType>;
     fn get_type_object_args_by_type_id(
         &self,
         type_id: usize,
@@ -475,6 +1274,19 @@ pub trait Serialized: fmt::Debug {
     fn get_enable_type_tree(&self) -> bool;
     fn get_externals(&self) -> Cow<Vec<FileIdentifier>>;
 
+    fn get_ref_types(&self) -> Vec<SerializedRefType> {
+        Vec::new()
+    }
+
+    /// Parse TypeTree nodes the file was read without parsing. Formats
+    /// storing nodes as fixed-size blobs keep them raw until first use.
+    fn decode_type_trees(&self) {}
+
+    /// Whether the type at `type_id` needs no more parsing before use.
+    fn is_type_tree_decoded(&self, _type_id: usize) -> bool {
+        true
+    }
+
     fn get_metadata(&self) -> SerializedFileMetadata {
         SerializedFileMetadata {
             version: self.get_serialized_file_version().clone(),
@@ -493,10 +1305,6 @@ pub trait Serialized: fmt::Debug {
         serialized_file_id: i64,
         path_id: i64,
     ) -> Result<Option<TypeTreeObject>, Error> {
-        if self.get_enable_type_tree() {
-            return Ok(None);
-        }
-
         let class_args = self.get_type_object_args_by_type_id(obj.type_id);
 
         #[cfg(feature = "type-tree-json")]
@@ -510,21 +1318,33 @@ pub trait Serialized: fmt::Debug {
         };
 
         let args = TypeTreeObjectBinReadArgs::new(serialized_file_id, path_id, class_args);
+        self.read_type_tree_object(reader, obj, args).map(Some)
+    }
+
+    fn read_type_tree_object(
+        &self,
+        reader: &mut Box<dyn UnityResource + Send + Sync>,
+        obj: &Object,
+        args: TypeTreeObjectBinReadArgs,
+    ) -> Result<TypeTreeObject, Error> {
+        let args = args.with_byte_size(obj.byte_size as u64);
 
         reader.seek(SeekFrom::Start(self.get_data_offset() + obj.byte_start))?;
 
         let mut type_tree_object =
             TypeTreeObject::read_options(reader, self.get_endianess().into(), args)?;
-        let apos = reader.stream_position()?;
-        if apos - (self.get_data_offset() + obj.byte_start) != obj.byte_size as u64 {
-            let mut external_data = vec![
-                0u8;
-                (obj.byte_size as u64 - (apos - (self.get_data_offset() + obj.byte_start)))
-                    as usize
-            ];
+        let read_size = reader.stream_position()? - (self.get_data_offset() + obj.byte_start);
+        if read_size > obj.byte_size as u64 {
+            return Err(Error::Other(format!(
+                "TypeTree read {} bytes of a {} byte object",
+                read_size, obj.byte_size
+            )));
+        }
+        if read_size != obj.byte_size as u64 {
+            let mut external_data = vec![0u8; (obj.byte_size as u64 - read_size) as usize];
             reader.read_exact(&mut external_data)?;
             type_tree_object.external_data = Some(external_data);
         }
-        Ok(Some(type_tree_object))
+        Ok(type_tree_object)
     }
 }
@@ -3,6 +3,7 @@ use super::version17::{FileIdentifier, TypeTree, TypeTreeNode};
 use super::{BuildTarget, SerializedFileCommonHeader};
 use super::{Serialized, SerializedFileFormatVersion};
 use crate::type_tree::{reader::TypeTreeObjectBinReadClassArgs, TypeField};
+use crate::until::binrw_parser::bounded_vec_parser;
 use crate::until::Endian;
 use binrw::io::Cursor;
 use binrw::{binrw, NullString};
@@ -43,10 +44,17 @@ impl Serialized for SerializedFile {
                 byte_size: obj.byte_size,
                 class: obj.class_id as i32,
                 type_id: obj.type_id as usize,
+                is_destroyed: Some(obj.is_destroyed),
+                script_type_index: None,
+                stripped: None,
             })
             .collect()
     }
 
+    fn get_types(&self) -> Vec<super::SerializedType> {
+        super::serialized_types(&self.content.types)
+    }
+
     fn get_unity_version(&self) -> String {
         self.content.unity_version.to_string()
     }
@@ -85,6 +93,19 @@ impl Serialized for SerializedFile {
             type_fields,
         ))
     }
+    fn decode_type_trees(&self) {
+        for tp in &self.content.types {
+            tp.type_tree.type_tree_node_blobs.decode();
+        }
+    }
+
+    fn is_type_tree_decoded(&self, type_id: usize) -> bool {
+        self.content
+            .types
+            .get(type_id)
+            .is_none_or(super::TypeTableEntry::is_type_tree_decoded)
+    }
+
     fn get_externals(&self) -> Cow<Vec<FileIdentifier>> {
         return Cow::Borrowed(&self.content.externals);
     }
@@ -96,14 +117,15 @@ struct SerializedFileContent {
     unity_version: NullString,
     target_platform: BuildTarget,
     type_count: u32,
-    #[br(count = type_count)]
+    #[br(count = type_count, parse_with = bounded_vec_parser)]
     types: Vec<SerializedType>,
     big_id_enabled: i32,
     object_count: i32,
+    #[br(parse_with = bounded_vec_parser)]
     #[br(args { count: object_count as usize, inner: ObjectBinReadArgs::builder().big_id_enabled(big_id_enabled != 0).finalize() })]
     objects: Vec<Object>,
     externals_count: i32,
-    #[br(count = externals_count)]
+    #[br(count = externals_count, parse_with = bounded_vec_parser)]
     externals: Vec<FileIdentifier>,
     user_information: NullString,
 }
@@ -115,6 +137,16 @@ pub struct SerializedType {
     pub type_tree: TypeTree,
 }
 
+impl super::TypeTableEntry for SerializedType {
+    fn class_id(&self) -> i32 {
+        self.class_id
+    }
+
+    fn is_type_tree_decoded(&self) -> bool {
+        self.type_tree.type_tree_node_blobs.is_decoded()
+    }
+}
+
 #[binrw]
 #[br(import { big_id_enabled: bool})]
 #[derive(Debug, PartialEq)]
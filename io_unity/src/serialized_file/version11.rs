@@ -3,6 +3,7 @@ use super::version17::FileIdentifier;
 use super::{BuildTarget, SerializedFileCommonHeader};
 use super::{Serialized, SerializedFileFormatVersion};
 use crate::type_tree::{reader::TypeTreeObjectBinReadClassArgs, TypeField};
+use crate::until::binrw_parser::bounded_vec_parser;
 use crate::until::Endian;
 use binrw::{binrw, NullString};
 use std::borrow::Cow;
@@ -42,10 +43,17 @@ impl Serialized for SerializedFile {
                 byte_size: obj.byte_size,
                 class: obj.class_id as i32,
                 type_id: obj.type_id as usize,
+                is_destroyed: None,
+                script_type_index: Some(obj.script_type_index),
+                stripped: None,
             })
             .collect()
     }
 
+    fn get_types(&self) -> Vec<super::SerializedType> {
+        super::serialized_types(&self.content.types)
+    }
+
     fn get_unity_version(&self) -> String {
         self.content.unity_version.to_string()
     }
@@ -106,17 +114,18 @@ struct SerializedFileContent {
     unity_version: NullString,
     target_platform: BuildTarget,
     type_count: u32,
-    #[br(count = type_count)]
+    #[br(count = type_count, parse_with = bounded_vec_parser)]
     types: Vec<SerializedType>,
     big_id_enabled: i32,
     object_count: i32,
+    #[br(parse_with = bounded_vec_parser)]
     #[br(args { count: object_count as usize, inner: ObjectBinReadArgs::builder().big_id_enabled(big_id_enabled != 0).finalize() })]
     objects: Vec<Object>,
     script_count: i32,
-    #[br(count = script_count)]
+    #[br(count = script_count, parse_with = bounded_vec_parser)]
     script_types: Vec<ScriptType>,
     externals_count: i32,
-    #[br(count = externals_count)]
+    #[br(count = externals_count, parse_with = bounded_vec_parser)]
     externals: Vec<FileIdentifier>,
     user_information: NullString,
 }
@@ -128,6 +137,12 @@ pub struct SerializedType {
     pub type_tree: TypeTree,
 }
 
+impl super::TypeTableEntry for SerializedType {
+    fn class_id(&self) -> i32 {
+        self.class_id
+    }
+}
+
 #[binrw]
 #[br(import { level: i32 = 0})]
 #[derive(Debug, Clone, PartialEq)]
@@ -142,6 +157,7 @@ pub struct TypeTree {
     pub version: i32,
     pub meta_flag: i32,
     pub children_count: i32,
+    #[br(parse_with = bounded_vec_parser)]
     #[br(args { count: children_count as usize, inner: TypeTreeBinReadArgs::builder().level(level + 1).finalize() })]
     pub children: Vec<TypeTree>,
 }
@@ -174,6 +190,10 @@ impl TypeField for TypeTreeNode {
         self.type_flags & 1 > 0
     }
 
+    fn get_type_flags(&self) -> u8 {
+        self.type_flags as u8
+    }
+
     fn get_byte_size(&self) -> i32 {
         self.byte_size
     }
@@ -43,10 +43,17 @@ impl Serialized for SerializedFile {
                 byte_size: obj.byte_size,
                 class: obj.class_id as i32,
                 type_id: obj.type_id as usize,
+                is_destroyed: None,
+                script_type_index: Some(obj.script_type_index),
+                stripped: None,
             })
             .collect()
     }
 
+    fn get_types(&self) -> Vec<super::SerializedType> {
+        super::serialized_types(&self.content.types)
+    }
+
     fn get_unity_version(&self) -> String {
         self.content.unity_version.to_string()
     }
@@ -85,6 +92,21 @@ impl Serialized for SerializedFile {
             type_fields,
         ))
     }
+    fn decode_type_trees(&self) {
+        for tp in &self.content.types {
+            if let Some(type_tree) = &tp.type_tree {
+                type_tree.type_tree_node_blobs.decode();
+            }
+        }
+    }
+
+    fn is_type_tree_decoded(&self, type_id: usize) -> bool {
+        self.content
+            .types
+            .get(type_id)
+            .is_none_or(super::TypeTableEntry::is_type_tree_decoded)
+    }
+
     fn get_externals(&self) -> Cow<Vec<FileIdentifier>> {
         return Cow::Borrowed(&self.content.externals);
     }
@@ -97,17 +119,19 @@ struct SerializedFileContent {
     target_platform: BuildTarget,
     enable_type_tree: U8Bool,
     type_count: u32,
+    #[br(parse_with = bounded_vec_parser)]
     #[br(args { count: type_count as usize, inner: SerializedTypeBinReadArgs::builder().enable_type_tree(*enable_type_tree).finalize() })]
     types: Vec<SerializedType>,
     big_id_enabled: i32,
     object_count: i32,
+    #[br(parse_with = bounded_vec_parser)]
     #[br(args { count: object_count as usize, inner: ObjectBinReadArgs::builder().big_id_enabled(big_id_enabled != 0).finalize() })]
     objects: Vec<Object>,
     script_count: i32,
-    #[br(count = script_count)]
+    #[br(count = script_count, parse_with = bounded_vec_parser)]
     script_types: Vec<ScriptType>,
     externals_count: i32,
-    #[br(count = externals_count)]
+    #[br(count = externals_count, parse_with = bounded_vec_parser)]
     externals: Vec<FileIdentifier>,
     user_information: NullString,
 }
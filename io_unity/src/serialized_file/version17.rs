@@ -49,10 +49,17 @@ impl Serialized for SerializedFile {
                     .map(|t| t.class_id)
                     .unwrap_or(0),
                 type_id: obj.type_id as usize,
+                is_destroyed: None,
+                script_type_index: None,
+                stripped: None,
             })
             .collect()
     }
 
+    fn get_types(&self) -> Vec<super::SerializedType> {
+        super::serialized_types(&self.content.types)
+    }
+
     fn get_unity_version(&self) -> String {
         self.content.unity_version.to_string()
     }
@@ -87,6 +94,21 @@ impl Serialized for SerializedFile {
             type_fields,
         ))
     }
+    fn decode_type_trees(&self) {
+        for tp in &self.content.types {
+            if let Some(type_tree) = &tp.type_tree {
+                type_tree.type_tree_node_blobs.decode();
+            }
+        }
+    }
+
+    fn is_type_tree_decoded(&self, type_id: usize) -> bool {
+        self.content
+            .types
+            .get(type_id)
+            .is_none_or(super::TypeTableEntry::is_type_tree_decoded)
+    }
+
     fn get_externals(&self) -> Cow<Vec<FileIdentifier>> {
         return Cow::Borrowed(&self.content.externals);
     }
@@ -99,16 +121,17 @@ struct SerializedFileContent {
     target_platform: BuildTarget,
     enable_type_tree: U8Bool,
     type_count: u32,
+    #[br(parse_with = bounded_vec_parser)]
     #[br(args { count: type_count as usize, inner: SerializedTypeBinReadArgs { enable_type_tree:*enable_type_tree } })]
     types: Vec<SerializedType>,
     object_count: i32,
-    #[br(count = object_count)]
+    #[br(count = object_count, parse_with = bounded_vec_parser)]
     objects: Vec<Object>,
     script_count: i32,
-    #[br(count = script_count)]
+    #[br(count = script_count, parse_with = bounded_vec_parser)]
     script_types: Vec<ScriptType>,
     externals_count: i32,
-    #[br(count = externals_count)]
+    #[br(count = externals_count, parse_with = bounded_vec_parser)]
     externals: Vec<FileIdentifier>,
     user_information: NullString,
 }
@@ -127,14 +150,38 @@ pub struct SerializedType {
     type_tree: Option<TypeTree>,
 }
 
+impl super::TypeTableEntry for SerializedType {
+    fn class_id(&self) -> i32 {
+        self.class_id
+    }
+
+    fn script_type_index(&self) -> Option<i16> {
+        Some(self.script_type_index)
+    }
+
+    fn type_tree_hash(&self) -> Option<[u8; 16]> {
+        Some(self.old_type_hash)
+    }
+
+    fn has_type_tree(&self) -> bool {
+        self.type_tree.is_some()
+    }
+
+    fn is_type_tree_decoded(&self) -> bool {
+        self.type_tree
+            .as_ref()
+            .is_none_or(|type_tree| type_tree.type_tree_node_blobs.is_decoded())
+    }
+}
+
 #[binrw]
 #[derive(Clone, PartialEq)]
 pub struct TypeTree {
     number_of_nodes: i32,
     string_buffer_size: i32,
-    #[br(count = number_of_nodes)]
-    pub type_tree_node_blobs: Vec<TypeTreeNodeBlob>,
-    #[br(count = string_buffer_size)]
+    #[br(args(number_of_nodes as usize, 24))]
+    pub type_tree_node_blobs: LazyVec<TypeTreeNodeBlob>,
+    #[br(count = string_buffer_size, parse_with = bounded_vec_parser)]
     pub string_buffer: Vec<u8>,
 }
 
@@ -250,6 +297,10 @@ impl TypeField for TypeTreeNode {
         self.node.type_flags & 1 > 0
     }
 
+    fn get_type_flags(&self) -> u8 {
+        self.node.type_flags
+    }
+
     fn get_byte_size(&self) -> i32 {
         self.node.byte_size
     }
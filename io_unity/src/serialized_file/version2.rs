@@ -4,6 +4,7 @@ use super::version6::Object;
 use super::{BuildTarget, SerializedFileCommonHeader};
 use super::{Serialized, SerializedFileFormatVersion};
 use crate::type_tree::{reader::TypeTreeObjectBinReadClassArgs, TypeField};
+use crate::until::binrw_parser::bounded_vec_parser;
 use crate::until::Endian;
 use binrw::{binrw, NullString};
 use std::borrow::Cow;
@@ -43,10 +44,17 @@ impl Serialized for SerializedFile {
                 byte_size: obj.byte_size,
                 class: obj.class_id as i32,
                 type_id: obj.type_id as usize,
+                is_destroyed: Some(obj.is_destroyed),
+                script_type_index: None,
+                stripped: None,
             })
             .collect()
     }
 
+    fn get_types(&self) -> Vec<super::SerializedType> {
+        super::serialized_types(&self.content.types)
+    }
+
     fn get_unity_version(&self) -> String {
         "".to_string()
     }
@@ -116,13 +124,13 @@ impl Serialized for SerializedFile {
 #[derive(Debug, PartialEq)]
 struct SerializedFileContent {
     type_count: u32,
-    #[br(count = type_count)]
+    #[br(count = type_count, parse_with = bounded_vec_parser)]
     types: Vec<SerializedType>,
     object_count: i32,
-    #[br(count = object_count)]
+    #[br(count = object_count, parse_with = bounded_vec_parser)]
     objects: Vec<Object>,
     externals_count: i32,
-    #[br(count = externals_count)]
+    #[br(count = externals_count, parse_with = bounded_vec_parser)]
     externals: Vec<FileIdentifier>,
 }
 
@@ -133,6 +141,12 @@ pub struct SerializedType {
     pub type_tree: TypeTree,
 }
 
+impl super::TypeTableEntry for SerializedType {
+    fn class_id(&self) -> i32 {
+        self.class_id
+    }
+}
+
 #[binrw]
 #[br(import { level: i32 = 0})]
 #[derive(Debug, Clone, PartialEq)]
@@ -148,6 +162,7 @@ pub struct TypeTree {
     pub version: i32,
     pub meta_flag: i32,
     pub children_count: i32,
+    #[br(parse_with = bounded_vec_parser)]
     #[br(args { count: children_count as usize, inner: TypeTreeBinReadArgs::builder().level(level + 1).finalize() })]
     pub children: Vec<TypeTree>,
 }
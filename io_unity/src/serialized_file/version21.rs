@@ -2,7 +2,10 @@ use super::version17::{FileIdentifier, Object, ScriptType};
 use super::version19::{TypeTree, TypeTreeNode};
 use super::{BuildTarget, SerializedFileCommonHeader};
 use super::{Serialized, SerializedFileFormatVersion};
-use crate::type_tree::{reader::TypeTreeObjectBinReadClassArgs, TypeField};
+use crate::type_tree::{
+    reader::{TypeTreeObjectBinReadClassArgs, TypeTreeObjectBinReadRefTypeArgs},
+    TypeField,
+};
 use crate::until::{binrw_parser::*, Endian};
 use binrw::io::Cursor;
 use binrw::{binrw, NullString};
@@ -48,10 +51,17 @@ impl Serialized for SerializedFile {
                     .map(|t| t.class_id)
                     .unwrap_or(0),
                 type_id: obj.type_id as usize,
+                is_destroyed: None,
+                script_type_index: None,
+                stripped: None,
             })
             .collect()
     }
 
+    fn get_types(&self) -> Vec<super::SerializedType> {
+        super::serialized_types(&self.content.types)
+    }
+
     fn get_unity_version(&self) -> String {
         self.content.unity_version.to_string()
     }
@@ -81,15 +91,47 @@ impl Serialized for SerializedFile {
             }) as Box<dyn TypeField + Send + Sync>))
         }
 
-        Some(TypeTreeObjectBinReadClassArgs::new(
-            stypetree.class_id,
-            type_fields,
-        ))
+        let class_args = TypeTreeObjectBinReadClassArgs::new(stypetree.class_id, type_fields);
+        if !class_args.has_managed_references() {
+            return Some(class_args);
+        }
+        Some(
+            class_args.with_ref_types(
+                self.content
+                    .ref_types
+                    .iter()
+                    .filter_map(SerializedRefType::get_ref_type_args)
+                    .collect(),
+            ),
+        )
+    }
+
+    fn decode_type_trees(&self) {
+        let types = self.content.types.iter().map(|tp| &tp.type_tree);
+        let ref_types = self.content.ref_types.iter().map(|tp| &tp.type_tree);
+        for type_tree in types.chain(ref_types).flatten() {
+            type_tree.type_tree_node_blobs.decode();
+        }
+    }
+
+    fn is_type_tree_decoded(&self, type_id: usize) -> bool {
+        self.content
+            .types
+            .get(type_id)
+            .is_none_or(super::TypeTableEntry::is_type_tree_decoded)
     }
 
     fn get_externals(&self) -> Cow<Vec<FileIdentifier>> {
         return Cow::Borrowed(&self.content.externals);
     }
+
+    fn get_ref_types(&self) -> Vec<super::SerializedRefType> {
+        self.content
+            .ref_types
+            .iter()
+            .map(SerializedRefType::get_ref_type)
+            .collect()
+    }
 }
 
 #[binrw]
@@ -99,18 +141,20 @@ struct SerializedFileContent {
     target_platform: BuildTarget,
     enable_type_tree: U8Bool,
     type_count: u32,
+    #[br(parse_with = bounded_vec_parser)]
     #[br(args { count: type_count as usize, inner: SerializedTypeBinReadArgs { enable_type_tree:*enable_type_tree } })]
     types: Vec<SerializedType>,
     object_count: i32,
-    #[br(count = object_count)]
+    #[br(count = object_count, parse_with = bounded_vec_parser)]
     objects: Vec<Object>,
     script_count: i32,
-    #[br(count = script_count)]
+    #[br(count = script_count, parse_with = bounded_vec_parser)]
     script_types: Vec<ScriptType>,
     externals_count: i32,
-    #[br(count = externals_count)]
+    #[br(count = externals_count, parse_with = bounded_vec_parser)]
     externals: Vec<FileIdentifier>,
     ref_type_count: i32,
+    #[br(parse_with = bounded_vec_parser)]
     #[br(args { count: ref_type_count as usize, inner: SerializedRefTypeBinReadArgs { enable_type_tree:*enable_type_tree } })]
     ref_types: Vec<SerializedRefType>,
     user_information: NullString,
@@ -122,19 +166,43 @@ struct SerializedFileContent {
 pub struct SerializedType {
     pub class_id: i32,
     pub is_stripped_type: U8Bool,
-    script_type_index: i16,
+    pub script_type_index: i16,
     #[br(if(class_id < 0 || class_id == 114 || class_id == 0x7C90B5B3 || script_type_index >= 0))]
     script_id: Option<[u8; 16]>,
-    old_type_hash: [u8; 16],
+    pub old_type_hash: [u8; 16],
     #[br(if(enable_type_tree))]
     pub type_tree: Option<TypeTree>,
     #[br(if(enable_type_tree))]
     type_dependencies_count: i32,
     #[br(if(enable_type_tree))]
-    #[br(count = type_dependencies_count)]
+    #[br(count = type_dependencies_count, parse_with = bounded_vec_parser)]
     type_dependencies: Vec<u32>,
 }
 
+impl super::TypeTableEntry for SerializedType {
+    fn class_id(&self) -> i32 {
+        self.class_id
+    }
+
+    fn script_type_index(&self) -> Option<i16> {
+        Some(self.script_type_index)
+    }
+
+    fn type_tree_hash(&self) -> Option<[u8; 16]> {
+        Some(self.old_type_hash)
+    }
+
+    fn has_type_tree(&self) -> bool {
+        self.type_tree.is_some()
+    }
+
+    fn is_type_tree_decoded(&self) -> bool {
+        self.type_tree
+            .as_ref()
+            .is_none_or(|type_tree| type_tree.type_tree_node_blobs.is_decoded())
+    }
+}
+
 #[binrw]
 #[br(import { enable_type_tree: bool})]
 #[derive(Debug, PartialEq)]
@@ -146,8 +214,36 @@ pub struct SerializedRefType {
     script_id: Option<[u8; 16]>,
     old_type_hash: [u8; 16],
     #[br(if(enable_type_tree))]
-    type_tree: Option<TypeTree>,
-    class_name: NullString,
-    name_space: NullString,
-    assembly_name: NullString,
+    pub type_tree: Option<TypeTree>,
+    // Unity writes the names in place of the type dependencies, so only
+    // with a TypeTree.
+    #[br(if(enable_type_tree))]
+    class_name: Option<NullString>,
+    #[br(if(enable_type_tree))]
+    name_space: Option<NullString>,
+    #[br(if(enable_type_tree))]
+    assembly_name: Option<NullString>,
+}
+
+impl SerializedRefType {
+    pub fn get_ref_type_args(&self) -> Option<TypeTreeObjectBinReadRefTypeArgs> {
+        Some(TypeTreeObjectBinReadRefTypeArgs::new(
+            self.class_name.as_ref()?.to_string(),
+            self.name_space.as_ref()?.to_string(),
+            self.assembly_name.as_ref()?.to_string(),
+            self.type_tree.as_ref()?.get_type_fields(),
+        ))
+    }
+
+    pub fn get_ref_type(&self) -> super::SerializedRefType {
+        let name = |name: &Option<NullString>| name.as_ref().map(ToString::to_string);
+        super::SerializedRefType {
+            class_id: self.class_id,
+            script_type_index: self.script_type_index,
+            class_name: name(&self.class_name).unwrap_or_default(),
+            name_space: name(&self.name_space).unwrap_or_default(),
+            assembly_name: name(&self.assembly_name).unwrap_or_default(),
+            has_type_tree: self.type_tree.is_some(),
+        }
+    }
 }
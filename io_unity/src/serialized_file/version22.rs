@@ -62,10 +62,17 @@ impl Serialized for SerializedFile {
                     .map(|t| t.class_id)
                     .unwrap_or(0),
                 type_id: obj.type_id as usize,
+                is_destroyed: None,
+                script_type_index: None,
+                stripped: None,
             })
             .collect()
     }
 
+    fn get_types(&self) -> Vec<super::SerializedType> {
+        super::serialized_types(&self.content.types)
+    }
+
     fn get_unity_version(&self) -> String {
         self.content.unity_version.to_string()
     }
@@ -95,15 +102,47 @@ impl Serialized for SerializedFile {
             }) as Box<dyn TypeField + Send + Sync>))
         }
 
-        Some(TypeTreeObjectBinReadClassArgs::new(
-            stypetree.class_id,
-            type_fields,
-        ))
+        let class_args = TypeTreeObjectBinReadClassArgs::new(stypetree.class_id, type_fields);
+        if !class_args.has_managed_references() {
+            return Some(class_args);
+        }
+        Some(
+            class_args.with_ref_types(
+                self.content
+                    .ref_types
+                    .iter()
+                    .filter_map(SerializedRefType::get_ref_type_args)
+                    .collect(),
+            ),
+        )
+    }
+
+    fn decode_type_trees(&self) {
+        let types = self.content.types.iter().map(|tp| &tp.type_tree);
+        let ref_types = self.content.ref_types.iter().map(|tp| &tp.type_tree);
+        for type_tree in types.chain(ref_types).flatten() {
+            type_tree.type_tree_node_blobs.decode();
+        }
+    }
+
+    fn is_type_tree_decoded(&self, type_id: usize) -> bool {
+        self.content
+            .types
+            .get(type_id)
+            .is_none_or(super::TypeTableEntry::is_type_tree_decoded)
     }
 
     fn get_externals(&self) -> Cow<Vec<FileIdentifier>> {
         return Cow::Borrowed(&self.content.externals);
     }
+
+    fn get_ref_types(&self) -> Vec<super::SerializedRefType> {
+        self.content
+            .ref_types
+            .iter()
+            .map(SerializedRefType::get_ref_type)
+            .collect()
+    }
 }
 
 #[binrw]
@@ -113,18 +152,20 @@ pub struct SerializedFileContent {
     pub target_platform: BuildTarget,
     pub enable_type_tree: U8Bool,
     type_count: u32,
+    #[br(parse_with = bounded_vec_parser)]
     #[br(args { count: type_count as usize, inner:  SerializedTypeBinReadArgs::builder().enable_type_tree(*enable_type_tree).finalize() })]
     pub types: Vec<SerializedType>,
     pub object_count: i32,
-    #[br(count = object_count)]
+    #[br(count = object_count, parse_with = bounded_vec_parser)]
     pub objects: Vec<Object>,
     script_count: i32,
-    #[br(count = script_count)]
+    #[br(count = script_count, parse_with = bounded_vec_parser)]
     script_types: Vec<ScriptType>,
     externals_count: i32,
-    #[br(count = externals_count)]
+    #[br(count = externals_count, parse_with = bounded_vec_parser)]
     externals: Vec<FileIdentifier>,
     ref_type_count: i32,
+    #[br(parse_with = bounded_vec_parser)]
     #[br(args { count: ref_type_count as usize, inner: SerializedRefTypeBinReadArgs::builder().enable_type_tree(*enable_type_tree).finalize() })]
     ref_types: Vec<SerializedRefType>,
     user_information: NullString,
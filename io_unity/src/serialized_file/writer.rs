@@ -0,0 +1,249 @@
+//! Writing serialized files, for building bundles out of loaded objects.
+//! Only the formats [`SerializedFileBuilder`] lists are written, always
+//! with TypeTrees.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::type_tree::TypeField;
+
+use super::version17::FileIdentifier;
+
+/// Appends values in one byte order.
+pub(crate) struct Writer {
+    pub big_endian: bool,
+    pub data: Vec<u8>,
+}
+
+impl Writer {
+    pub fn new(big_endian: bool) -> Self {
+        Writer {
+            big_endian,
+            data: Vec::new(),
+        }
+    }
+
+    pub fn u16(&mut self, value: u16) -> &mut Self {
+        let bytes = if self.big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        };
+        self.data.extend_from_slice(&bytes);
+        self
+    }
+
+    pub fn u32(&mut self, value: u32) -> &mut Self {
+        let bytes = if self.big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        };
+        self.data.extend_from_slice(&bytes);
+        self
+    }
+
+    pub fn i32(&mut self, value: i32) -> &mut Self {
+        self.u32(value as u32)
+    }
+
+    pub fn u64(&mut self, value: u64) -> &mut Self {
+        let bytes = if self.big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        };
+        self.data.extend_from_slice(&bytes);
+        self
+    }
+
+    pub fn i64(&mut self, value: i64) -> &mut Self {
+        self.u64(value as u64)
+    }
+
+    pub fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.data.extend_from_slice(bytes);
+        self
+    }
+
+    pub fn null_string(&mut self, value: &str) -> &mut Self {
+        self.data.extend_from_slice(value.as_bytes());
+        self.data.push(0);
+        self
+    }
+
+    /// A length-prefixed string padded to 4 bytes, as TypeTree strings are.
+    pub fn aligned_string(&mut self, value: &str) -> &mut Self {
+        self.i32(value.len() as i32)
+            .bytes(value.as_bytes())
+            .align(4, 0)
+    }
+
+    /// Pad to a multiple of `alignment` counting from `base` bytes before
+    /// the start of the data, for data written after a header.
+    pub fn align(&mut self, alignment: usize, base: usize) -> &mut Self {
+        while !(base + self.data.len()).is_multiple_of(alignment) {
+            self.data.push(0);
+        }
+        self
+    }
+}
+
+/// Write a TypeTree as nodes and a string buffer holding every name.
+/// Format 19 onwards stores a ref type hash with each node.
+pub(crate) fn write_type_tree(
+    writer: &mut Writer,
+    fields: &[Arc<Box<dyn TypeField + Send + Sync>>],
+    ref_type_hashes: bool,
+) {
+    let mut strings = Vec::new();
+    let mut offsets: HashMap<String, u32> = HashMap::new();
+    let mut offset_of = |string: &String| -> u32 {
+        *offsets.entry(string.clone()).or_insert_with(|| {
+            let offset = strings.len() as u32;
+            strings.extend_from_slice(string.as_bytes());
+            strings.push(0);
+            offset
+        })
+    };
+    let offsets: Vec<(u32, u32)> = fields
+        .iter()
+        .map(|field| (offset_of(field.get_type()), offset_of(field.get_name())))
+        .collect();
+
+    writer.i32(fields.len() as i32).i32(strings.len() as i32);
+    for (field, (type_offset, name_offset)) in fields.iter().zip(offsets) {
+        writer
+            .u16(field.get_version())
+            .bytes(&[field.get_level(), field.get_type_flags()])
+            .u32(type_offset)
+            .u32(name_offset)
+            .i32(field.get_byte_size())
+            .i32(field.get_index())
+            .i32(field.get_meta_flag());
+        if ref_type_hashes {
+            writer.u64(field.get_ref_type_hash().unwrap_or(0));
+        }
+    }
+    writer.bytes(&strings);
+}
+
+/// A type table entry to write.
+pub(crate) struct WrittenType {
+    pub class_id: i32,
+    /// Index into [`SerializedFileBuilder::script_types`], -1 for none.
+    pub script_type_index: i16,
+    pub type_tree_hash: [u8; 16],
+    pub fields: Vec<Arc<Box<dyn TypeField + Send + Sync>>>,
+}
+
+pub(crate) struct WrittenObject {
+    pub path_id: i64,
+    pub type_index: usize,
+    pub data: Vec<u8>,
+}
+
+/// A serialized file of format 17, as Unity 5.5 to 2019 reads, or 22, as
+/// Unity writes from 2020.1.
+pub(crate) struct SerializedFileBuilder<'a> {
+    pub format: u32,
+    pub big_endian: bool,
+    pub unity_version: &'a str,
+    pub target_platform: i32,
+    pub types: Vec<WrittenType>,
+    pub objects: Vec<WrittenObject>,
+    /// Path ids of the MonoScripts, all in this file, the script types name.
+    pub script_types: Vec<i64>,
+    pub externals: Vec<FileIdentifier>,
+}
+
+impl SerializedFileBuilder<'_> {
+    pub fn build(&self) -> Vec<u8> {
+        assert!(matches!(self.format, 17 | 22));
+        let large_files = self.format >= 22;
+        // Format 22 follows the common header with 64-bit sizes and offsets.
+        let header_size = if large_files { 48 } else { 20 };
+
+        let mut meta = Writer::new(self.big_endian);
+        meta.null_string(self.unity_version)
+            .i32(self.target_platform)
+            .bytes(&[1]) // enable_type_tree
+            .u32(self.types.len() as u32);
+        for tp in &self.types {
+            meta.i32(tp.class_id)
+                .bytes(&[0]) // is_stripped_type
+                .u16(tp.script_type_index as u16);
+            if tp.class_id == 114 || (large_files && tp.script_type_index >= 0) {
+                meta.bytes(&[0; 16]); // script_id
+            }
+            meta.bytes(&tp.type_tree_hash);
+            write_type_tree(&mut meta, &tp.fields, large_files);
+            if large_files {
+                meta.i32(0); // type_dependencies
+            }
+        }
+
+        let mut data = Writer::new(self.big_endian);
+        let mut object_ranges = Vec::new();
+        for object in &self.objects {
+            data.align(8, 0);
+            object_ranges.push((data.data.len(), object.data.len()));
+            data.bytes(&object.data);
+        }
+
+        meta.i32(self.objects.len() as i32);
+        for (object, (start, size)) in self.objects.iter().zip(object_ranges) {
+            // Objects are aligned relative to the start of the file.
+            meta.align(4, header_size).i64(object.path_id);
+            if large_files {
+                meta.u64(start as u64);
+            } else {
+                meta.u32(start as u32);
+            }
+            meta.u32(size as u32).i32(object.type_index as i32);
+        }
+        meta.i32(self.script_types.len() as i32);
+        for path_id in &self.script_types {
+            meta.i32(0).align(4, header_size).i64(*path_id);
+        }
+        meta.i32(self.externals.len() as i32);
+        for external in &self.externals {
+            meta.null_string(&external.temp_empty.to_string())
+                .bytes(&external.guid)
+                .i32(external.r#type)
+                .null_string(&external.path.to_string());
+        }
+        if large_files {
+            meta.i32(0); // ref_types
+        }
+        meta.null_string(""); // user_information
+
+        let mut data_offset = header_size + meta.data.len();
+        data_offset = data_offset.next_multiple_of(16);
+        let file_size = data_offset + data.data.len();
+
+        let mut file = Writer::new(true);
+        if large_files {
+            // The 32-bit fields are left zero, as Unity writes them.
+            file.u32(0)
+                .u32(0)
+                .u32(22)
+                .u32(0)
+                .bytes(&[self.big_endian as u8, 0, 0, 0])
+                .u32(meta.data.len() as u32)
+                .u64(file_size as u64)
+                .u64(data_offset as u64)
+                .u64(0);
+        } else {
+            file.u32(meta.data.len() as u32)
+                .u32(file_size as u32)
+                .u32(self.format)
+                .u32(data_offset as u32)
+                .bytes(&[self.big_endian as u8, 0, 0, 0]);
+        }
+        file.bytes(&meta.data);
+        file.data.resize(data_offset, 0);
+        file.bytes(&data.data);
+        file.data
+    }
+}
@@ -0,0 +1,289 @@
+use std::fs::File;
+use std::io::{prelude::*, ErrorKind, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// A `Read + Seek` view of the parts of a file split into `.split0`,
+/// `.split1`, ... files, as Unity ships large Android bundles, read as if
+/// they were concatenated.
+pub struct SplitReader<R> {
+    parts: Vec<R>,
+    /// Offset of the start of each part in the joined file.
+    starts: Vec<u64>,
+    len: u64,
+    position: u64,
+}
+
+impl SplitReader<File> {
+    /// Open the given part files, in order.
+    pub fn open<P: AsRef<Path>>(paths: &[P]) -> std::io::Result<Self> {
+        Self::new(
+            paths
+                .iter()
+                .map(File::open)
+                .collect::<std::io::Result<_>>()?,
+        )
+    }
+}
+
+impl<R: Read + Seek> SplitReader<R> {
+    pub fn new(mut parts: Vec<R>) -> std::io::Result<Self> {
+        let mut starts = Vec::with_capacity(parts.len());
+        let mut len = 0;
+        for part in &mut parts {
+            starts.push(len);
+            len += part.seek(SeekFrom::End(0))?;
+        }
+        Ok(SplitReader {
+            parts,
+            starts,
+            len,
+            position: 0,
+        })
+    }
+
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<R: Read + Seek> Read for SplitReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() || self.position >= self.len {
+            return Ok(0);
+        }
+        // The last part starting at or before the position, skipping empty parts.
+        let index = self.starts.partition_point(|start| *start <= self.position) - 1;
+        let part_end = self.starts.get(index + 1).copied().unwrap_or(self.len);
+        let size = buf.len().min((part_end - self.position) as usize);
+        let part = &mut self.parts[index];
+        part.seek(SeekFrom::Start(self.position - self.starts[index]))?;
+        let read = part.read(&mut buf[..size])?;
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl<R: Read + Seek> Seek for SplitReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(pos) => pos as i64,
+            SeekFrom::End(offset) => self.len as i64 + offset,
+            SeekFrom::Current(offset) => self.position as i64 + offset,
+        };
+        if position < 0 {
+            return Err(std::io::Error::from(ErrorKind::InvalidInput));
+        }
+        self.position = position as u64;
+        Ok(self.position)
+    }
+}
+
+/// A `Read + Seek` view of a file, or of split parts read as one, that
+/// opens the files for each read and closes them again, so that readers
+/// kept around for rare reads hold no file descriptor. Part lengths are read
+/// once, and each read opens only the parts it covers.
+pub struct ReopeningReader {
+    paths: Vec<PathBuf>,
+    /// Offset of the start of each part in the joined file.
+    starts: Vec<u64>,
+    len: u64,
+    position: u64,
+}
+
+impl ReopeningReader {
+    /// Read the file at each of `paths` in turn, as [`SplitReader::open`].
+    pub fn new(paths: Vec<PathBuf>) -> std::io::Result<Self> {
+        let reader = SplitReader::open(&paths)?;
+        Ok(ReopeningReader {
+            paths,
+            starts: reader.starts,
+            len: reader.len,
+            position: 0,
+        })
+    }
+}
+
+impl Read for ReopeningReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut read = 0;
+        while read < buf.len() && self.position < self.len {
+            let index = self.starts.partition_point(|start| *start <= self.position) - 1;
+            let part_end = self.starts.get(index + 1).copied().unwrap_or(self.len);
+            let size = (buf.len() - read).min((part_end - self.position) as usize);
+            let mut part = File::open(&self.paths[index])?;
+            part.seek(SeekFrom::Start(self.position - self.starts[index]))?;
+            let part_read = fill(&mut part, &mut buf[read..read + size])?;
+            read += part_read;
+            self.position += part_read as u64;
+            if part_read < size {
+                break;
+            }
+        }
+        Ok(read)
+    }
+}
+
+/// Read into `buf` until it is full or `reader` ends.
+fn fill(reader: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..]) {
+            Ok(0) => break,
+            Ok(size) => read += size,
+            Err(error) if error.kind() == ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(read)
+}
+
+impl Seek for ReopeningReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(pos) => pos as i64,
+            SeekFrom::End(offset) => self.len as i64 + offset,
+            SeekFrom::Current(offset) => self.position as i64 + offset,
+        };
+        if position < 0 {
+            return Err(std::io::Error::from(ErrorKind::InvalidInput));
+        }
+        self.position = position as u64;
+        Ok(self.position)
+    }
+}
+
+/// A `Read + Seek` view of a memory-mapped file. Reads copy straight from
+/// the mapping, so only the pages read are loaded, and they are shared with
+/// the page cache rather than buffered again. The file must not shrink while
+/// mapped.
+#[cfg(all(feature = "mmap", unix))]
+pub struct MmapReader {
+    data: *mut libc::c_void,
+    len: usize,
+    position: u64,
+}
+
+// The mapping is read-only and owned by the reader.
+#[cfg(all(feature = "mmap", unix))]
+unsafe impl Send for MmapReader {}
+#[cfg(all(feature = "mmap", unix))]
+unsafe impl Sync for MmapReader {}
+
+#[cfg(all(feature = "mmap", unix))]
+impl MmapReader {
+    pub fn open<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let file = File::open(path)?;
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|_| std::io::Error::from(ErrorKind::OutOfMemory))?;
+        if len == 0 {
+            // Empty mappings are rejected, and there is nothing to map.
+            return Ok(MmapReader {
+                data: std::ptr::null_mut(),
+                len,
+                position: 0,
+            });
+        }
+        // The mapping stays valid once the file is closed.
+        let data = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if data == libc::MAP_FAILED {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(MmapReader {
+            data,
+            len,
+            position: 0,
+        })
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        if self.len == 0 {
+            return &[];
+        }
+        unsafe { std::slice::from_raw_parts(self.data as *const u8, self.len) }
+    }
+}
+
+#[cfg(all(feature = "mmap", unix))]
+impl Drop for MmapReader {
+    fn drop(&mut self) {
+        if self.len != 0 {
+            unsafe { libc::munmap(self.data, self.len) };
+        }
+    }
+}
+
+#[cfg(all(feature = "mmap", unix))]
+impl Read for MmapReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let data = self.as_slice();
+        let start = data
+            .len()
+            .min(self.position.try_into().unwrap_or(usize::MAX));
+        let read = buf.len().min(data.len() - start);
+        buf[..read].copy_from_slice(&data[start..start + read]);
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+#[cfg(all(feature = "mmap", unix))]
+impl Seek for MmapReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(pos) => pos as i64,
+            SeekFrom::End(offset) => self.len as i64 + offset,
+            SeekFrom::Current(offset) => self.position as i64 + offset,
+        };
+        if position < 0 {
+            return Err(std::io::Error::from(ErrorKind::InvalidInput));
+        }
+        self.position = position as u64;
+        Ok(self.position)
+    }
+}
+
+/// The joined file path and part index of a `<path>.split<index>` part.
+pub fn split_part_index(path: &Path) -> Option<(PathBuf, u32)> {
+    let extension = path.extension()?.to_str()?;
+    let index = extension.strip_prefix("split")?;
+    if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((path.with_extension(""), index.parse().ok()?))
+}
+
+/// Sort parts of one split file by index, failing if any part from 0 up
+/// to the last one found is missing.
+pub fn order_split_parts(
+    joined_path: &Path,
+    mut parts: Vec<(u32, PathBuf)>,
+) -> std::io::Result<Vec<PathBuf>> {
+    parts.sort();
+    for (expected, (index, _)) in parts.iter().enumerate() {
+        if *index != expected as u32 {
+            return Err(std::io::Error::new(
+                ErrorKind::NotFound,
+                format!(
+                    "split file {} is missing part {expected}",
+                    joined_path.display()
+                ),
+            ));
+        }
+    }
+    Ok(parts.into_iter().map(|(_, path)| path).collect())
+}
@@ -1,4 +1,7 @@
-use super::{ArrayFieldValue, DataOffset, Field, FieldValue, TypeTreeObject, TypeTreeObjectRef};
+use super::{
+    ArrayFieldValue, ColorRGBA, DataOffset, Field, FieldValue, Hash128, TypeTreeObject,
+    TypeTreeObjectRef,
+};
 use crate::error::{Error, ReadResult};
 use binrw::{BinRead, VecArgs};
 use std::{
@@ -55,6 +58,15 @@ pub trait TryCast<T>: Sized {
     ) -> Result<T, Self::Error>;
 }
 
+/// A type read whole from a TypeTree object, usually through
+/// `#[derive(FromTypeTree)]` with the `derive` feature.
+pub trait FromTypeTree: Sized {
+    fn from_type_tree(object: &TypeTreeObjectRef) -> ReadResult<Self>;
+}
+
+#[cfg(feature = "derive")]
+pub use io_unity_derive::FromTypeTree;
+
 pub trait TryCastFrom<T, P>: Sized {
     type Error;
 
@@ -171,11 +183,22 @@ impl TryCastFrom<&TypeTreeObjectRef, &[String]> for Vec<TypeTreeObjectRef> {
     }
 }
 
+/// A `map` field by the `Array` at `path`. Entries whose key is not a string
+/// are left out, and an array of anything but `pair`s is a
+/// [`Error::TypeMisMatch`] rather than a map of misread entries.
 impl TryCastFrom<&TypeTreeObjectRef, &[String]> for HashMap<String, TypeTreeObjectRef> {
     type Error = Error;
 
     fn try_cast_from(value: &TypeTreeObjectRef, path: &[String]) -> Result<Self, Self::Error> {
         let entites = <Vec<TypeTreeObjectRef>>::try_cast_from(value, path)?;
+        if let Some(item_type) = entites.first().and_then(TypeTreeObjectRef::get_type) {
+            if item_type != "pair" {
+                return Err(Error::TypeMisMatch {
+                    want_to_cast: std::any::type_name::<Self>(),
+                    found_type_name: item_type,
+                });
+            }
+        }
         let mut map = HashMap::new();
         for entry in entites {
             let key = String::try_cast_from(&entry, "/Base/first");
@@ -850,3 +873,114 @@ impl TryCast<glam::Vec2> for Field {
         })
     }
 }
+
+/// The named `f32` children of a composite field, in order.
+fn try_cast_f32_children<const N: usize>(
+    field: &Field,
+    names: [&str; N],
+    object_data_buff: &[u8],
+    field_cast_args: &FieldCastArgs,
+    want_to_cast: &'static str,
+) -> Result<[f32; N], Error> {
+    let FieldValue::Fields(fields) = &field.data else {
+        return Err(Error::TypeMisMatch {
+            want_to_cast,
+            found_type_name: field.field_type.get_type().to_owned(),
+        });
+    };
+    let mut values = [0f32; N];
+    for (value, name) in values.iter_mut().zip(names) {
+        *value = fields
+            .get(name)
+            .ok_or(Error::FieldNotFound(vec![name.to_owned()]))?
+            .try_cast_to(object_data_buff, field_cast_args)?;
+    }
+    Ok(values)
+}
+
+impl TryCast<glam::Vec4> for Field {
+    type Error = Error;
+
+    fn try_cast_to(
+        &self,
+        object_data_buff: &[u8],
+        field_cast_args: &FieldCastArgs,
+    ) -> Result<glam::Vec4, Self::Error> {
+        try_cast_f32_children(
+            self,
+            ["x", "y", "z", "w"],
+            object_data_buff,
+            field_cast_args,
+            std::any::type_name::<glam::Vec4>(),
+        )
+        .map(glam::Vec4::from_array)
+    }
+}
+
+impl TryCast<ColorRGBA> for Field {
+    type Error = Error;
+
+    fn try_cast_to(
+        &self,
+        object_data_buff: &[u8],
+        field_cast_args: &FieldCastArgs,
+    ) -> Result<ColorRGBA, Self::Error> {
+        // ColorRGBA32 packs the channels in one uint.
+        if let FieldValue::Fields(fields) = &self.data {
+            if let Some(rgba) = fields.get("rgba") {
+                let rgba: u32 = rgba.try_cast_to(object_data_buff, field_cast_args)?;
+                let [r, g, b, a] = rgba.to_le_bytes().map(|c| c as f32 / 255.0);
+                return Ok(ColorRGBA { r, g, b, a });
+            }
+        }
+        let [r, g, b, a] = try_cast_f32_children(
+            self,
+            ["r", "g", "b", "a"],
+            object_data_buff,
+            field_cast_args,
+            std::any::type_name::<ColorRGBA>(),
+        )?;
+        Ok(ColorRGBA { r, g, b, a })
+    }
+}
+
+impl TryCast<Hash128> for Field {
+    type Error = Error;
+
+    fn try_cast_to(
+        &self,
+        object_data_buff: &[u8],
+        field_cast_args: &FieldCastArgs,
+    ) -> Result<Hash128, Self::Error> {
+        let mismatch = || Error::TypeMisMatch {
+            want_to_cast: std::any::type_name::<Hash128>(),
+            found_type_name: self.field_type.get_type().to_owned(),
+        };
+        let FieldValue::Fields(fields) = &self.data else {
+            return Err(mismatch());
+        };
+        if self.field_type.get_type() == "GUID" {
+            let mut data = [0u32; 4];
+            for (index, data) in data.iter_mut().enumerate() {
+                let name = format!("data[{index}]");
+                *data = fields
+                    .get(&name)
+                    .ok_or(Error::FieldNotFound(vec![name]))?
+                    .try_cast_to(object_data_buff, field_cast_args)?;
+            }
+            return Ok(Hash128::from_guid_data(data));
+        }
+        let mut bytes = [0u8; 16];
+        if fields.len() != bytes.len() {
+            return Err(mismatch());
+        }
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            let name = format!("bytes[{index}]");
+            *byte = fields
+                .get(&name)
+                .ok_or(Error::FieldNotFound(vec![name]))?
+                .try_cast_to(object_data_buff, field_cast_args)?;
+        }
+        Ok(Hash128 { bytes })
+    }
+}
@@ -0,0 +1,146 @@
+//! Labels for integer fields that hold Unity enums or bit flags, keyed by
+//! the type that owns the field and the field name, since TypeTrees only
+//! store such fields as plain integers.
+
+use once_cell::sync::Lazy;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::RwLock;
+
+#[derive(Debug, Clone, Default)]
+struct EnumLabels {
+    flags: bool,
+    labels: BTreeMap<i64, String>,
+}
+
+static ENUM_LABELS: Lazy<RwLock<HashMap<(String, String), EnumLabels>>> =
+    Lazy::new(|| RwLock::new(default_enum_labels()));
+
+fn default_enum_labels() -> HashMap<(String, String), EnumLabels> {
+    const TEXTURE_WRAP_MODE: &[&str] = &["Repeat", "Clamp", "Mirror", "MirrorOnce"];
+    const FILTER_MODE: &[&str] = &["Point", "Bilinear", "Trilinear"];
+    const TEXTURE_USAGE_MODE: &[&str] = &[
+        "Default",
+        "LightmapDoubleLDR",
+        "LightmapRGBM",
+        "NormalmapDXT5nm",
+        "NormalmapPlain",
+        "RGBMEncoded",
+        "AlwaysPadded",
+        "DoubleLDR",
+        "BakedLightmapDoubleLDR",
+        "BakedLightmapRGBM",
+        "BakedLightmapFullHDR",
+        "RealtimeLightmapRGBM",
+        "NormalmapASTCnm",
+        "SingleChannelRed",
+        "SingleChannelAlpha",
+    ];
+    const COLOR_SPACE: &[&str] = &["Linear", "sRGB"];
+    const AUDIO_CLIP_LOAD_TYPE: &[&str] = &["DecompressOnLoad", "CompressedInMemory", "Streaming"];
+    const AUDIO_COMPRESSION_FORMAT: &[&str] = &[
+        "PCM", "Vorbis", "ADPCM", "MP3", "VAG", "HEVAG", "XMA", "AAC", "GCADPCM", "ATRAC9",
+    ];
+    const WRAP_MODE: &[(i64, &str)] = &[
+        (0, "Default"),
+        (1, "Once"),
+        (2, "Loop"),
+        (4, "PingPong"),
+        (8, "ClampForever"),
+    ];
+    const STATIC_EDITOR_FLAGS: &[(i64, &str)] = &[
+        (1, "ContributeGI"),
+        (2, "OccluderStatic"),
+        (4, "BatchingStatic"),
+        (8, "NavigationStatic"),
+        (16, "OccludeeStatic"),
+        (32, "OffMeshLinkGeneration"),
+        (64, "ReflectionProbeStatic"),
+    ];
+
+    let sequential = |labels: &[&str]| EnumLabels {
+        flags: false,
+        labels: (0..).zip(labels.iter().map(|s| s.to_string())).collect(),
+    };
+    let explicit = |flags: bool, labels: &[(i64, &str)]| EnumLabels {
+        flags,
+        labels: labels.iter().map(|(v, s)| (*v, s.to_string())).collect(),
+    };
+
+    let mut map = HashMap::new();
+    let mut insert = |owner: &str, field: &str, labels: EnumLabels| {
+        map.insert((owner.to_owned(), field.to_owned()), labels);
+    };
+    for field in ["m_WrapMode", "m_WrapU", "m_WrapV", "m_WrapW"] {
+        insert("GLTextureSettings", field, sequential(TEXTURE_WRAP_MODE));
+    }
+    insert("GLTextureSettings", "m_FilterMode", sequential(FILTER_MODE));
+    insert(
+        "Texture2D",
+        "m_LightmapFormat",
+        sequential(TEXTURE_USAGE_MODE),
+    );
+    insert("Texture2D", "m_ColorSpace", sequential(COLOR_SPACE));
+    insert("AudioClip", "m_LoadType", sequential(AUDIO_CLIP_LOAD_TYPE));
+    insert(
+        "AudioClip",
+        "m_CompressionFormat",
+        sequential(AUDIO_COMPRESSION_FORMAT),
+    );
+    insert("AnimationClip", "m_WrapMode", explicit(false, WRAP_MODE));
+    insert(
+        "GameObject",
+        "m_StaticEditorFlags",
+        explicit(true, STATIC_EDITOR_FLAGS),
+    );
+    map
+}
+
+/// Label the values of `field_name` in `owner_type`, replacing any labels
+/// registered before, including the defaults.
+pub fn register_enum_labels(owner_type: &str, field_name: &str, labels: &[(i64, &str)]) {
+    register(owner_type, field_name, false, labels)
+}
+
+/// Like [`register_enum_labels`] for bit flags, whose labels are per bit
+/// and joined with `|` for values with several bits set.
+pub fn register_flag_labels(owner_type: &str, field_name: &str, labels: &[(i64, &str)]) {
+    register(owner_type, field_name, true, labels)
+}
+
+fn register(owner_type: &str, field_name: &str, flags: bool, labels: &[(i64, &str)]) {
+    if let Ok(mut enum_labels) = ENUM_LABELS.write() {
+        enum_labels.insert(
+            (owner_type.to_owned(), field_name.to_owned()),
+            EnumLabels {
+                flags,
+                labels: labels.iter().map(|(v, s)| (*v, s.to_string())).collect(),
+            },
+        );
+    }
+}
+
+/// The label of `value`, or `None` when the field has no labels or the
+/// value is not one of them. A flags value of 0 is labelled `None`.
+pub fn get_enum_label(owner_type: &str, field_name: &str, value: i64) -> Option<String> {
+    let enum_labels = ENUM_LABELS.read().ok()?;
+    let EnumLabels { flags, labels } =
+        enum_labels.get(&(owner_type.to_owned(), field_name.to_owned()))?;
+    if !flags {
+        return labels.get(&value).cloned();
+    }
+    if value == 0 {
+        return Some("None".to_owned());
+    }
+    let mut names = Vec::new();
+    let mut remaining = value;
+    for (bit, name) in labels {
+        if *bit != 0 && value & bit == *bit {
+            names.push(name.as_str());
+            remaining &= !bit;
+        }
+    }
+    if remaining != 0 {
+        return None;
+    }
+    Some(names.join("|"))
+}
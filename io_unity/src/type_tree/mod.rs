@@ -1,25 +1,35 @@
 pub mod convert;
+pub mod enum_label;
 pub mod reader;
 #[cfg(feature = "type-tree-json")]
 pub mod type_tree_json;
+pub mod visitor;
 
 use std::{
     collections::HashMap,
     fmt::Debug,
+    ops::Range,
     sync::{Arc, RwLock},
 };
 
 use crate::{
+    classes::p_ptr::PPtrIds,
     error::{Error, ReadResult},
     type_tree::convert::TryCast,
 };
 
-use self::convert::FieldCastArgs;
+use self::convert::{FieldCastArgs, TryCastFrom};
 
 pub trait TypeField: Debug {
     fn get_version(&self) -> u16;
     fn get_level(&self) -> u8;
     fn is_array(&self) -> bool;
+    /// The node's type flags: 0x01 array, 0x02 managed reference, 0x04
+    /// managed reference registry, 0x08 array of managed references. Nodes
+    /// that store only whether they are arrays report that bit alone.
+    fn get_type_flags(&self) -> u8 {
+        self.is_array() as u8
+    }
     fn get_byte_size(&self) -> i32;
     fn get_index(&self) -> i32;
     fn get_meta_flag(&self) -> i32;
@@ -29,6 +39,84 @@ pub trait TypeField: Debug {
     fn get_name(&self) -> &String;
 }
 
+/// A caller-built TypeTree node, see [`crate::serialized_file::SerializedFile::get_object_with_type_tree`].
+/// Nodes are listed depth first, with `level` 0 for the root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeTreeNode {
+    pub version: u16,
+    pub level: u8,
+    pub is_array: bool,
+    pub type_name: String,
+    pub name: String,
+    /// -1 for variable sized fields.
+    pub byte_size: i32,
+    pub index: i32,
+    pub meta_flag: i32,
+}
+
+impl TypeTreeNode {
+    pub fn new(level: u8, type_name: &str, name: &str, byte_size: i32) -> Self {
+        Self {
+            version: 1,
+            level,
+            is_array: name == "Array",
+            type_name: type_name.to_owned(),
+            name: name.to_owned(),
+            byte_size,
+            index: 0,
+            meta_flag: 0,
+        }
+    }
+
+    /// Sets the align bytes flag, 0x4000.
+    pub fn aligned(mut self) -> Self {
+        self.meta_flag |= 0x4000;
+        self
+    }
+}
+
+impl TypeField for TypeTreeNode {
+    fn get_version(&self) -> u16 {
+        self.version
+    }
+
+    fn get_level(&self) -> u8 {
+        self.level
+    }
+
+    fn is_array(&self) -> bool {
+        self.is_array
+    }
+
+    fn get_byte_size(&self) -> i32 {
+        self.byte_size
+    }
+
+    fn get_index(&self) -> i32 {
+        self.index
+    }
+
+    fn get_meta_flag(&self) -> i32 {
+        self.meta_flag
+    }
+
+    fn is_align(&self) -> bool {
+        self.meta_flag & 0x4000 > 0
+    }
+
+    fn get_ref_type_hash(&self) -> Option<u64> {
+        None
+    }
+
+    fn get_type(&self) -> &String {
+        &self.type_name
+    }
+
+    fn get_name(&self) -> &String {
+        &self.name
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum FieldValue {
     DataOffset(DataOffset),
@@ -120,6 +208,18 @@ impl Field {
         Ok(&object_data_buff[pos as usize..(pos + size) as usize])
     }
 
+    /// Where the value of a fixed size field is in the object's bytes.
+    pub(super) fn value_range(&self, field_cast_args: &FieldCastArgs) -> Option<Range<usize>> {
+        let FieldValue::DataOffset(data_offset) = &self.data else {
+            return None;
+        };
+        let pos = match data_offset {
+            DataOffset::AbsDataOffset(data) => *data,
+            DataOffset::ArrayItemOffset(data) => *data + field_cast_args.field_offset? as u64,
+        } as usize;
+        Some(pos..pos + self.field_type.get_byte_size().max(0) as usize)
+    }
+
     pub fn try_get_buff_type_and_type_size(&self) -> Option<(&String, i32)> {
         if let FieldValue::Array(ar) = &self.data {
             if let ArrayFieldValue::DataOffset(_) = &ar.data {
@@ -134,77 +234,136 @@ impl Field {
         None
     }
 
-    fn display_field(
+    fn write_tree(
         &self,
-        p: &String,
-        object_data_buff: &Vec<u8>,
-        field_cast_args: &mut FieldCastArgs,
+        out: &mut String,
+        depth: usize,
+        owner_type: &str,
+        object_data_buff: &[u8],
+        field_cast_args: &FieldCastArgs,
     ) {
-        let np = p.clone() + "/" + self.field_type.get_name();
-        print!(
-            "{}/{} : {}({})",
-            p,
-            self.field_type.get_name(),
-            self.field_type.get_type(),
-            self.field_type.get_byte_size(),
-        );
+        const MAX_ARRAY_ITEMS: usize = 8;
+
+        let indent = "  ".repeat(depth);
+        let name = self.field_type.get_name();
+        let type_name = self.field_type.get_type();
+        out.push_str(&format!("{indent}{name} : {type_name}"));
+
+        if type_name == "string" {
+            if let Ok(value) =
+                TryCast::<String>::try_cast_to(self, object_data_buff, field_cast_args)
+            {
+                out.push_str(&format!(" = {value:?}\n"));
+                return;
+            }
+        }
+        if type_name.starts_with("PPtr<") {
+            if let FieldValue::Fields(fields) = &self.data {
+                let file_id = fields.get("m_FileID").and_then(|f| {
+                    TryCast::<i64>::try_cast_to(f, object_data_buff, field_cast_args).ok()
+                });
+                let path_id = fields.get("m_PathID").and_then(|f| {
+                    TryCast::<i64>::try_cast_to(f, object_data_buff, field_cast_args).ok()
+                });
+                if let (Some(file_id), Some(path_id)) = (file_id, path_id) {
+                    out.push_str(&format!(" -> (file {file_id}, path {path_id})\n"));
+                    return;
+                }
+            }
+        }
+
         match &self.data {
-            FieldValue::DataOffset(_v) => {
-                let num: Result<i64, _> = self.try_cast_to(object_data_buff, field_cast_args);
-                if let Ok(num) = num {
-                    println!(" data : {num:?}");
-                } else {
-                    let num: Result<u64, _> = self.try_cast_to(object_data_buff, field_cast_args);
-                    if let Ok(num) = num {
-                        println!(" data : {num:?}",);
-                    } else {
-                        let num: Result<f32, _> =
-                            self.try_cast_to(object_data_buff, field_cast_args);
-                        if let Ok(num) = num {
-                            println!(" data : {num:?}",);
-                        } else {
-                            println!();
-                        }
-                    }
+            FieldValue::DataOffset(_) => {
+                let label = TryCast::<i64>::try_cast_to(self, object_data_buff, field_cast_args)
+                    .or_else(|_| {
+                        TryCast::<u64>::try_cast_to(self, object_data_buff, field_cast_args)
+                            .map(|v| v as i64)
+                    })
+                    .ok()
+                    .and_then(|value| enum_label::get_enum_label(owner_type, name, value));
+                match (self.display_value(object_data_buff, field_cast_args), label) {
+                    (Some(value), Some(label)) => out.push_str(&format!(" = {value} ({label})\n")),
+                    (Some(value), None) => out.push_str(&format!(" = {value}\n")),
+                    (None, _) => out.push('\n'),
                 }
             }
-            FieldValue::Fields(fls) => {
-                println!();
-                fls.iter()
-                    .map(|(_n, f)| f.display_field(&np, object_data_buff, field_cast_args))
-                    .collect()
+            FieldValue::Fields(fields) => {
+                out.push('\n');
+                let mut fields: Vec<_> = fields.values().collect();
+                fields.sort_by_key(|f| f.field_type.get_index());
+                for field in fields {
+                    field.write_tree(out, depth + 1, type_name, object_data_buff, field_cast_args);
+                }
             }
-            FieldValue::Array(ar) => {
-                println!();
-                ar.array_size
-                    .display_field(&np, object_data_buff, field_cast_args);
-                match &ar.data {
-                    ArrayFieldValue::ArrayItems(ai) => {
-                        if let Some(aii) = ai.get(0) {
-                            aii.display_field(&np, object_data_buff, field_cast_args);
-                        } else {
-                            for item in &ar.item_type_fields {
-                                println!(
-                                    "{}/?/{} : {}({}) at level [{}]",
-                                    np,
-                                    item.get_name(),
-                                    item.get_type(),
-                                    item.get_byte_size(),
-                                    item.get_level(),
-                                );
-                            }
+            FieldValue::Array(array) => {
+                let size: i32 = array
+                    .array_size
+                    .try_cast_to(object_data_buff, field_cast_args)
+                    .unwrap_or(0);
+                if let Some(item_type) = array.item_type_fields.first() {
+                    out.push_str(&format!("<{}>", item_type.get_type()));
+                }
+                out.push_str(&format!("[{size}]\n"));
+                let shown = (size.max(0) as usize).min(MAX_ARRAY_ITEMS);
+                match &array.data {
+                    ArrayFieldValue::ArrayItems(items) => {
+                        for item in items.iter().take(shown) {
+                            item.write_tree(out, depth + 1, "", object_data_buff, field_cast_args);
                         }
                     }
-                    ArrayFieldValue::DataOffset(_) => {
-                        if let Some(item_field) = &ar.item_field {
-                            item_field.display_field(&np, object_data_buff, field_cast_args);
+                    ArrayFieldValue::DataOffset(DataOffset::AbsDataOffset(offset)) => {
+                        if let (Some(item_field), Some(item_size)) =
+                            (&array.item_field, array.item_field_size)
+                        {
+                            for index in 0..shown as u64 {
+                                let item_cast_args = FieldCastArgs {
+                                    endian: field_cast_args.endian,
+                                    field_offset: Some((offset + item_size * index) as i64),
+                                };
+                                item_field.write_tree(
+                                    out,
+                                    depth + 1,
+                                    "",
+                                    object_data_buff,
+                                    &item_cast_args,
+                                );
+                            }
                         }
                     }
+                    ArrayFieldValue::DataOffset(DataOffset::ArrayItemOffset(_)) => (),
+                }
+                if size as usize > shown {
+                    out.push_str(&format!("{indent}  ... {} more\n", size as usize - shown));
                 }
             }
         }
     }
 
+    fn display_value(
+        &self,
+        object_data_buff: &[u8],
+        field_cast_args: &FieldCastArgs,
+    ) -> Option<String> {
+        match self.field_type.get_type().as_str() {
+            "bool" => TryCast::<bool>::try_cast_to(self, object_data_buff, field_cast_args)
+                .ok()
+                .map(|v| v.to_string()),
+            "float" => TryCast::<f32>::try_cast_to(self, object_data_buff, field_cast_args)
+                .ok()
+                .map(|v| v.to_string()),
+            "double" => TryCast::<f64>::try_cast_to(self, object_data_buff, field_cast_args)
+                .ok()
+                .map(|v| v.to_string()),
+            _ => TryCast::<i64>::try_cast_to(self, object_data_buff, field_cast_args)
+                .map(|v| v.to_string())
+                .or_else(|_| {
+                    TryCast::<u64>::try_cast_to(self, object_data_buff, field_cast_args)
+                        .map(|v| v.to_string())
+                })
+                .ok(),
+        }
+    }
+
     fn get_field(
         &self,
         path: &[String],
@@ -269,6 +428,52 @@ impl Field {
     }
 }
 
+/// A `ColorRGBA` field, with `ColorRGBA32` channels scaled to `0.0..=1.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ColorRGBA {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+/// 64-bit FNV-1a of `data`, see [`TypeTreeObject::content_hash`].
+pub(crate) fn content_hash(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// A `Hash128` or `GUID` field. It displays as the 32 lowercase hex digits
+/// Unity prints, so a `GUID` reads as in `.meta` files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Hash128 {
+    pub bytes: [u8; 16],
+}
+
+impl Hash128 {
+    /// A `GUID` from its four uints. Unity prints each least significant
+    /// nibble first, so the bytes are reordered to display that way.
+    pub fn from_guid_data(data: [u32; 4]) -> Self {
+        let mut bytes = [0u8; 16];
+        for (chunk, data) in bytes.chunks_exact_mut(4).zip(data) {
+            for (byte, data) in chunk.iter_mut().zip(data.to_le_bytes()) {
+                *byte = data.rotate_left(4);
+            }
+        }
+        Hash128 { bytes }
+    }
+}
+
+impl std::fmt::Display for Hash128 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for byte in self.bytes {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
 // todo: cache get layout
 #[derive(Debug, Clone)]
 pub struct TypeTreeObject {
@@ -279,22 +484,146 @@ pub struct TypeTreeObject {
     data_layout: Field,
     data_buff: Vec<u8>,
     pub external_data: Option<Vec<u8>>,
+    pub(crate) raw_bytes: Option<Vec<u8>>,
 }
 
 impl TypeTreeObject {
-    pub fn display_tree(&self) {
-        println!("class_id : {}", self.class_id);
-        self.data_layout.display_field(
-            &"".to_string(),
+    /// Render the object as an indented tree of field names, types and
+    /// primitive values. PPtrs are shown as their file and path id, and only
+    /// the first items of long arrays are listed.
+    pub fn display_tree(&self) -> String {
+        let mut out = format!("class_id : {}\n", self.class_id);
+        self.data_layout.write_tree(
+            &mut out,
+            0,
+            "",
             &self.data_buff,
-            &mut self.get_field_cast_args(),
+            &self.get_field_cast_args(),
         );
+        out
+    }
+
+    /// See [`crate::classes::class_name`].
+    pub fn class_name(&self) -> &'static str {
+        crate::classes::class_name(self.class_id)
     }
 
     pub fn get_endian(&self) -> binrw::Endian {
         self.endian
     }
 
+    /// The object's bytes exactly as stored in the file, kept only when the
+    /// file was set to with [`crate::serialized_file::SerializedFile::set_keep_raw_bytes`].
+    pub fn raw_bytes(&self) -> Option<&[u8]> {
+        self.raw_bytes.as_deref()
+    }
+
+    /// A hash of the object's serialized bytes, equal for byte-identical
+    /// objects whatever their path id. It is stable across runs and builds.
+    pub fn content_hash(&self) -> u64 {
+        content_hash(&self.data_buff)
+    }
+
+    pub fn get_vector3_by_path(&self, path: &str) -> ReadResult<glam::Vec3> {
+        glam::Vec3::try_cast_from(self, path)
+    }
+
+    pub fn get_vector4_by_path(&self, path: &str) -> ReadResult<glam::Vec4> {
+        glam::Vec4::try_cast_from(self, path)
+    }
+
+    pub fn get_quaternion_by_path(&self, path: &str) -> ReadResult<glam::Quat> {
+        glam::Quat::try_cast_from(self, path)
+    }
+
+    pub fn get_color_by_path(&self, path: &str) -> ReadResult<ColorRGBA> {
+        ColorRGBA::try_cast_from(self, path)
+    }
+
+    /// The `Hash128` or `GUID` at `path`, or any 16 byte array.
+    pub fn get_hash128_by_path(&self, path: &str) -> ReadResult<Hash128> {
+        Hash128::try_cast_from(self, path).or_else(|error| {
+            self.try_as_slice(path)
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .map(|bytes| Hash128 { bytes })
+                .ok_or(error)
+        })
+    }
+
+    /// Label of the integer at `path` from [`enum_label`], looked up by the
+    /// type of the field owning it, such as `GLTextureSettings` for
+    /// `/Base/m_TextureSettings/m_FilterMode`.
+    pub fn get_enum_label_by_path(&self, path: &str) -> ReadResult<Option<String>> {
+        let value = i64::try_cast_from(self, path)
+            .or_else(|_| u64::try_cast_from(self, path).map(|v| v as i64))?;
+        let path: Vec<String> = path
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect();
+        let (field_name, owner_path) = path
+            .split_last()
+            .ok_or_else(|| Error::FieldNotFound(path.clone()))?;
+        let (owner, _) = self
+            .get_field_by_path_list(owner_path.get(1..).unwrap_or_default())
+            .ok_or_else(|| Error::FieldNotFound(path.clone()))?;
+        Ok(enum_label::get_enum_label(
+            owner.get_type(),
+            field_name,
+            value,
+        ))
+    }
+
+    /// The ids of every PPtr in the array at `path`, such as
+    /// `/Base/m_PreloadTable/Array`, read straight from the object data
+    /// instead of through a sub-object per item. `None` when `path` is not
+    /// an array of PPtrs.
+    pub fn get_pptr_array_by_path(&self, path: &str) -> Option<Vec<PPtrIds>> {
+        let (field, _) = self.get_field_by_path(path).ok()?;
+        let FieldValue::Array(array) = &field.data else {
+            return None;
+        };
+        let field_cast_args = self.get_field_cast_args();
+        let pptr_ids = |item: &Field, field_offset: Option<i64>| {
+            let FieldValue::Fields(fields) = &item.data else {
+                return None;
+            };
+            let item_cast_args = FieldCastArgs {
+                endian: field_cast_args.endian,
+                field_offset,
+            };
+            Some(PPtrIds {
+                serialized_file_id: self.serialized_file_id,
+                file_id: fields
+                    .get("m_FileID")?
+                    .try_cast_to(&self.data_buff, &item_cast_args)
+                    .ok()?,
+                path_id: fields
+                    .get("m_PathID")?
+                    .try_cast_to(&self.data_buff, &item_cast_args)
+                    .ok()?,
+            })
+        };
+        match &array.data {
+            ArrayFieldValue::DataOffset(DataOffset::AbsDataOffset(offset)) => {
+                let item_field = array.item_field.as_ref()?;
+                let item_size = array.item_field_size?;
+                let size: i32 = array
+                    .array_size
+                    .try_cast_to(&self.data_buff, &field_cast_args)
+                    .ok()?;
+                (0..size.max(0) as u64)
+                    .map(|index| pptr_ids(item_field, Some((offset + item_size * index) as i64)))
+                    .collect()
+            }
+            ArrayFieldValue::DataOffset(DataOffset::ArrayItemOffset(_)) => None,
+            ArrayFieldValue::ArrayItems(items) => {
+                items.iter().map(|item| pptr_ids(item, None)).collect()
+            }
+        }
+    }
+
     pub fn try_as_slice(&self, path: &str) -> ReadResult<&[u8]> {
         let (feild, offset) = self.get_field_by_path(path)?;
         let mut field_cast_args = self.get_field_cast_args();
@@ -385,7 +714,7 @@ impl TypeTreeObjectRef {
             .map(|(n, s)| (n.to_owned(), s))
     }
 
-    pub fn display_tree(&self) {
+    pub fn display_tree(&self) -> String {
         self.inner.read().unwrap().display_tree()
     }
 
@@ -393,6 +722,9 @@ impl TypeTreeObjectRef {
         self.inner.read().unwrap().endian
     }
 
+    /// The id of the serialized file owning the underlying object. Refs to
+    /// nested fields share the object, so a PPtr read at any depth resolves
+    /// its `m_FileID` against this file's externals.
     pub fn get_serialized_file_id(&self) -> i64 {
         self.inner.read().unwrap().serialized_file_id
     }
@@ -404,4 +736,25 @@ impl TypeTreeObjectRef {
     pub fn get_class_id(&self) -> i32 {
         self.inner.read().unwrap().class_id
     }
+
+    /// Look up an entry of the object's `ManagedReferencesRegistry` by `rid`,
+    /// returning its data decoded as the concrete referenced type.
+    pub fn get_managed_reference(&self, rid: i64) -> Option<TypeTreeObjectRef> {
+        let root = TypeTreeObjectRef {
+            inner: self.inner.clone(),
+            path: vec![],
+        };
+        <Vec<TypeTreeObjectRef>>::try_cast_from(&root, "/Base/references/RefIds/Array")
+            .ok()?
+            .into_iter()
+            .find(|entry| i64::try_cast_from(entry, "/Base/rid").is_ok_and(|id| id == rid))
+            .and_then(|entry| TypeTreeObjectRef::try_cast_from(&entry, "/Base/data").ok())
+    }
+
+    /// Resolve a `[SerializeReference]` field through its `rid`. Null and
+    /// missing references resolve to `None`.
+    pub fn resolve_managed_reference(&self) -> ReadResult<Option<TypeTreeObjectRef>> {
+        let rid = i64::try_cast_from(self, "/Base/rid")?;
+        Ok(self.get_managed_reference(rid))
+    }
 }
@@ -7,6 +7,7 @@ use std::{
 
 use binrw::{BinRead, BinResult, Endian, VecArgs};
 
+use crate::error::Error;
 use crate::type_tree::{
     convert::{FieldCastArgs, TryRead},
     ArrayField, ArrayFieldValue, DataOffset, FieldValue, TypeTreeObject,
@@ -19,6 +20,7 @@ pub struct TypeTreeObjectBinReadArgs {
     serialized_file_id: i64,
     path_id: i64,
     class_args: TypeTreeObjectBinReadClassArgs,
+    byte_size: Option<u64>,
 }
 
 impl TypeTreeObjectBinReadArgs {
@@ -31,14 +33,23 @@ impl TypeTreeObjectBinReadArgs {
             serialized_file_id,
             path_id,
             class_args,
+            byte_size: None,
         }
     }
+
+    /// Bound reads to `byte_size` bytes from the object start, so corrupt
+    /// lengths fail instead of running into the following objects.
+    pub fn with_byte_size(mut self, byte_size: u64) -> Self {
+        self.byte_size = Some(byte_size);
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct TypeTreeObjectBinReadClassArgs {
     class_id: i32,
     type_fields: Vec<Arc<Box<dyn TypeField + Send + Sync>>>,
+    ref_types: Arc<Vec<TypeTreeObjectBinReadRefTypeArgs>>,
 }
 
 impl TypeTreeObjectBinReadClassArgs {
@@ -46,8 +57,121 @@ impl TypeTreeObjectBinReadClassArgs {
         Self {
             class_id,
             type_fields,
+            ref_types: Arc::new(Vec::new()),
+        }
+    }
+
+    /// The TypeTree nodes, depth first.
+    pub(crate) fn type_fields(&self) -> &[Arc<Box<dyn TypeField + Send + Sync>>] {
+        &self.type_fields
+    }
+
+    /// Whether the first field is the `m_Name` string of a named object,
+    /// so the name can be read without the rest of the object.
+    pub(crate) fn starts_with_name(&self) -> bool {
+        self.type_fields.get(1).is_some_and(|field| {
+            field.get_level() == 1 && field.get_name() == "m_Name" && field.get_type() == "string"
+        })
+    }
+
+    /// Whether the TypeTree contains a `ManagedReferencesRegistry`.
+    pub fn has_managed_references(&self) -> bool {
+        self.type_fields
+            .iter()
+            .any(|field| field.get_type() == MANAGED_REFERENCES_REGISTRY)
+    }
+
+    /// The file's ref type table, used to decode `SerializeReference`
+    /// entries of a `ManagedReferencesRegistry` into their concrete type.
+    pub fn with_ref_types(mut self, ref_types: Vec<TypeTreeObjectBinReadRefTypeArgs>) -> Self {
+        self.ref_types = Arc::new(ref_types);
+        self
+    }
+}
+
+/// TypeTree of a managed reference type, keyed by the names Unity writes
+/// in each registry entry's `ReferencedManagedType`.
+#[derive(Debug, Clone)]
+pub struct TypeTreeObjectBinReadRefTypeArgs {
+    class_name: String,
+    name_space: String,
+    assembly_name: String,
+    type_fields: Vec<Arc<Box<dyn TypeField + Send + Sync>>>,
+}
+
+impl TypeTreeObjectBinReadRefTypeArgs {
+    pub fn new(
+        class_name: String,
+        name_space: String,
+        assembly_name: String,
+        type_fields: Vec<Arc<Box<dyn TypeField + Send + Sync>>>,
+    ) -> Self {
+        Self {
+            class_name,
+            name_space,
+            assembly_name,
+            type_fields,
+        }
+    }
+}
+
+const MANAGED_REFERENCES_REGISTRY: &str = "ManagedReferencesRegistry";
+const REFERENCED_MANAGED_TYPE: &str = "ReferencedManagedType";
+const REFERENCED_OBJECT_DATA: &str = "ReferencedObjectData";
+
+struct ReadContext<'a> {
+    /// Where the object data ends, bounding declared lengths.
+    end_pos: u64,
+    ref_types: &'a [TypeTreeObjectBinReadRefTypeArgs],
+    /// `(class, ns, asm)` of the registry entry being read.
+    pending_type: Option<(String, String, String)>,
+}
+
+fn peek_managed_type<R: Read + Seek>(
+    reader: &mut R,
+    endian: Endian,
+    end_pos: u64,
+) -> BinResult<(String, String, String)> {
+    fn read_aligned_string<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        end_pos: u64,
+    ) -> BinResult<String> {
+        let pos = reader.stream_position()?;
+        let len = i32::read_options(reader, endian, ())?;
+        let remaining = end_pos.saturating_sub(pos + 4);
+        if len < 0 || len as u64 > remaining {
+            return Err(binrw::Error::Custom {
+                pos,
+                err: Box::new(Error::CorruptString {
+                    length: len as i64,
+                    remaining,
+                }),
+            });
         }
+        let buf = <Vec<u8>>::read_options(
+            reader,
+            endian,
+            VecArgs {
+                count: len as usize,
+                inner: (),
+            },
+        )?;
+        let pos = reader.stream_position()?;
+        if pos % 4 != 0 {
+            reader.seek(SeekFrom::Current((4 - (pos % 4)) as i64))?;
+        }
+        Ok(String::from_utf8_lossy(&buf).into_owned())
     }
+
+    let pos = reader.stream_position()?;
+    let managed_type = (
+        read_aligned_string(reader, endian, end_pos)?,
+        read_aligned_string(reader, endian, end_pos)?,
+        read_aligned_string(reader, endian, end_pos)?,
+    );
+    reader.seek(SeekFrom::Start(pos))?;
+    Ok(managed_type)
 }
 
 impl BinRead for TypeTreeObject {
@@ -65,17 +189,60 @@ impl BinRead for TypeTreeObject {
             field_index: &mut usize,
             read_offset: &mut u64,
             is_fix_size_array_item: bool,
+            ref_context: &mut ReadContext,
         ) -> BinResult<Field> {
             let field = type_fields
                 .get(*field_index)
                 .ok_or(std::io::Error::from(ErrorKind::NotFound))?;
             let field_level = field.get_level();
-            let field_value = if field.is_array() {
+            if field.get_type() == REFERENCED_MANAGED_TYPE {
+                ref_context.pending_type =
+                    Some(peek_managed_type(reader, endian, ref_context.end_pos)?);
+            }
+            let field_value = if field.get_type() == REFERENCED_OBJECT_DATA {
+                let ref_type = ref_context
+                    .pending_type
+                    .take()
+                    .and_then(|(class, ns, asm)| {
+                        ref_context.ref_types.iter().find(|ref_type| {
+                            ref_type.class_name == class
+                                && ref_type.name_space == ns
+                                && ref_type.assembly_name == asm
+                        })
+                    });
+                // Null references and the registry terminator carry no data.
+                let data = match ref_type {
+                    Some(ref_type) => {
+                        read(
+                            reader,
+                            endian,
+                            &ref_type.type_fields,
+                            &mut 0,
+                            read_offset,
+                            false,
+                            ref_context,
+                        )?
+                        .data
+                    }
+                    None => FieldValue::Fields(HashMap::new()),
+                };
+                Field {
+                    field_type: field.clone(),
+                    data,
+                }
+            } else if field.is_array() {
                 assert!(!is_fix_size_array_item);
                 *field_index += 1;
                 let size_start_pos = reader.stream_position()?;
-                let size_field =
-                    read(reader, endian, type_fields, field_index, read_offset, false)?;
+                let size_field = read(
+                    reader,
+                    endian,
+                    type_fields,
+                    field_index,
+                    read_offset,
+                    false,
+                    ref_context,
+                )?;
                 reader.seek(SeekFrom::Start(size_start_pos))?;
                 let size: i32 = size_field
                     .try_read_to(
@@ -107,6 +274,31 @@ impl BinRead for TypeTreeObject {
                 let pos = reader.stream_position()?;
                 let is_pos_aligned = (pos % 4) == 0;
                 let fix_item_size = calc_no_array_field_size(&item_type_fields, &mut 0, &mut 0);
+                let remaining = ref_context.end_pos.saturating_sub(pos);
+                if size < 0
+                    || fix_item_size
+                        .is_some_and(|byte_size| byte_size as u64 * size as u64 > remaining)
+                {
+                    let err = if item_type_field.get_type() == "char" {
+                        Error::CorruptString {
+                            length: size as i64,
+                            remaining,
+                        }
+                    } else {
+                        Error::Other(format!(
+                            "array of {} items overruns the {} remaining object bytes",
+                            size, remaining
+                        ))
+                    };
+                    return Err(binrw::Error::Custom {
+                        pos: size_start_pos,
+                        err: Box::new(err),
+                    });
+                }
+                // Items flagged as aligned are padded one by one. A fixed item
+                // size already includes that padding, so items can be read in
+                // one go when they all start aligned; otherwise each one is
+                // read and aligned in turn below.
                 let mut buf_read_flag = false;
                 if let Some(byte_size) = fix_item_size {
                     if (is_pos_aligned && ((byte_size % 4) == 0))
@@ -128,6 +320,7 @@ impl BinRead for TypeTreeObject {
                         &mut 0,
                         &mut item_field_offset,
                         true,
+                        ref_context,
                     )?;
 
                     *read_offset += (byte_size * size as usize) as u64;
@@ -161,6 +354,7 @@ impl BinRead for TypeTreeObject {
                             field_index,
                             read_offset,
                             false,
+                            ref_context,
                         )?);
                     }
 
@@ -191,12 +385,21 @@ impl BinRead for TypeTreeObject {
                                 field_index,
                                 read_offset,
                                 is_fix_size_array_item,
+                                ref_context,
                             )?;
                             fields.insert(field_data.get_name().clone(), field_data);
                         } else if next_field.get_level() <= field_level {
                             break;
                         } else {
-                            panic!("{:#?} {:#?} ", next_field.get_level(), fields);
+                            return Err(binrw::Error::Custom {
+                                pos: reader.stream_position()?,
+                                err: Box::new(Error::Other(format!(
+                                    "TypeTree node {} at level {} follows a field at level {}",
+                                    next_field.get_name(),
+                                    next_field.get_level(),
+                                    field_level + 1
+                                ))),
+                            });
                         }
                     }
 
@@ -245,6 +448,12 @@ impl BinRead for TypeTreeObject {
         }
 
         let start_pos = reader.stream_position()?;
+        let stream_end = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(start_pos))?;
+        let end_pos = match args.byte_size {
+            Some(byte_size) => stream_end.min(start_pos + byte_size),
+            None => stream_end,
+        };
         let mut index = 0;
         let mut data_buff_offset = 0;
         let data = read(
@@ -254,6 +463,11 @@ impl BinRead for TypeTreeObject {
             &mut index,
             &mut data_buff_offset,
             false,
+            &mut ReadContext {
+                end_pos,
+                ref_types: &args.class_args.ref_types,
+                pending_type: None,
+            },
         )?;
         reader.seek(SeekFrom::Start(start_pos))?;
 
@@ -272,6 +486,7 @@ impl BinRead for TypeTreeObject {
                 },
             )?,
             external_data: None,
+            raw_bytes: None,
         })
     }
 }
@@ -283,7 +498,7 @@ fn calc_no_array_field_size(
 ) -> Option<usize> {
     let field = type_fields.get(*field_index)?;
     let field_level = field.get_level();
-    if field.is_array() {
+    if field.is_array() || field.get_type() == REFERENCED_OBJECT_DATA {
         return None;
     } else if let Some(next_field) = type_fields.get(*field_index + 1) {
         if next_field.get_level() == field_level + 1 {
@@ -294,7 +509,8 @@ fn calc_no_array_field_size(
                 } else if next_field.get_level() <= field_level {
                     break;
                 } else {
-                    panic!("{:#?} {:#?} ", next_field.get_level(), field);
+                    // Malformed, left for the reader to report.
+                    return None;
                 }
             }
         } else {
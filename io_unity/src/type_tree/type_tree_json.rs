@@ -81,6 +81,10 @@ impl TypeField for TypeTreeNode {
         self.type_flags & 1 > 0
     }
 
+    fn get_type_flags(&self) -> u8 {
+        self.type_flags
+    }
+
     fn get_byte_size(&self) -> i32 {
         self.byte_size
     }
@@ -0,0 +1,542 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use super::{
+    convert::{FieldCastArgs, TryCast},
+    ArrayFieldValue, DataOffset, Field, FieldValue, TypeTreeObject, TypeTreeObjectRef,
+};
+
+/// What a [`TypeTreeVisitor`] wants to happen after a callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visit {
+    Continue,
+    /// Do not visit the children of the struct or array just entered. Its
+    /// leave callback is still made. The same as `Continue` for leaves.
+    SkipChildren,
+    /// End the walk. No further callbacks are made, leave callbacks included.
+    Stop,
+}
+
+/// A primitive or string field value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LeafValue {
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    String(String),
+}
+
+/// Callbacks for [`TypeTreeObject::visit`], made in field order. Paths are
+/// those accepted by `try_cast_from`, such as `/Base/m_Name`, with array
+/// items as `.../Array/<index>`. Every callback defaults to doing nothing,
+/// so a visitor only implements the ones it needs.
+pub trait TypeTreeVisitor {
+    fn enter_struct(&mut self, _path: &str, _type_name: &str) -> Visit {
+        Visit::Continue
+    }
+
+    fn leave_struct(&mut self, _path: &str, _type_name: &str) {}
+
+    /// `item_type` is the type of the array items, `len` their count.
+    fn enter_array(&mut self, _path: &str, _item_type: &str, _len: usize) -> Visit {
+        Visit::Continue
+    }
+
+    fn leave_array(&mut self, _path: &str) {}
+
+    fn visit_value(&mut self, _path: &str, _type_name: &str, _value: LeafValue) -> Visit {
+        Visit::Continue
+    }
+
+    /// PPtrs are reported as one leaf rather than a struct of two fields.
+    fn visit_pptr(&mut self, _path: &str, _type_name: &str, _file_id: i64, _path_id: i64) -> Visit {
+        Visit::Continue
+    }
+}
+
+/// A leaf that differs between two objects, see
+/// [`TypeTreeObject::diff_fields`]. `None` on the side that lacks the field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    pub path: String,
+    pub old: Option<LeafValue>,
+    pub new: Option<LeafValue>,
+}
+
+/// Collects every leaf with its path, PPtrs as their two id fields.
+#[derive(Default)]
+struct LeafCollector(Vec<(String, LeafValue)>);
+
+impl TypeTreeVisitor for LeafCollector {
+    fn visit_value(&mut self, path: &str, _type_name: &str, value: LeafValue) -> Visit {
+        self.0.push((path.to_owned(), value));
+        Visit::Continue
+    }
+
+    fn visit_pptr(&mut self, path: &str, _type_name: &str, file_id: i64, path_id: i64) -> Visit {
+        self.0
+            .push((format!("{path}/m_FileID"), LeafValue::Int(file_id)));
+        self.0
+            .push((format!("{path}/m_PathID"), LeafValue::Int(path_id)));
+        Visit::Continue
+    }
+}
+
+impl TypeTreeObject {
+    /// Walk every field of the object, depth first. Returns `false` if the
+    /// visitor stopped the walk early.
+    pub fn visit<V: TypeTreeVisitor>(&self, visitor: &mut V) -> bool {
+        self.data_layout.visit(
+            "/Base",
+            visitor,
+            &self.data_buff,
+            &self.get_field_cast_args(),
+        )
+    }
+
+    /// The leaves whose value differs from those of `other` at the same
+    /// path, in this object's field order followed by the leaves only
+    /// `other` has. Array items are compared by index.
+    pub fn diff_fields(&self, other: &TypeTreeObject) -> Vec<FieldDiff> {
+        let mut old_leaves = LeafCollector::default();
+        self.visit(&mut old_leaves);
+        let mut new_leaves = LeafCollector::default();
+        other.visit(&mut new_leaves);
+
+        let mut unmatched: HashMap<&str, &LeafValue> = new_leaves
+            .0
+            .iter()
+            .map(|(path, value)| (path.as_str(), value))
+            .collect();
+        let mut diffs = Vec::new();
+        for (path, old) in old_leaves.0 {
+            let new = unmatched.remove(path.as_str());
+            if new != Some(&old) {
+                diffs.push(FieldDiff {
+                    path,
+                    old: Some(old),
+                    new: new.cloned(),
+                });
+            }
+        }
+        for (path, new) in &new_leaves.0 {
+            if unmatched.contains_key(path.as_str()) {
+                diffs.push(FieldDiff {
+                    path: path.clone(),
+                    old: None,
+                    new: Some(new.clone()),
+                });
+            }
+        }
+        diffs
+    }
+}
+
+/// Where a PPtr's two ids are in an object's bytes, see
+/// [`TypeTreeObject::pptr_slots`].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PPtrSlot {
+    pub path: String,
+    pub file_id: i64,
+    pub path_id: i64,
+    pub file_id_range: Range<usize>,
+    pub path_id_range: Range<usize>,
+}
+
+impl TypeTreeObject {
+    /// Every PPtr of the object with the byte ranges of its ids, in field
+    /// order, for rewriting them in the object's raw bytes.
+    pub(crate) fn pptr_slots(&self) -> Vec<PPtrSlot> {
+        let mut slots = Vec::new();
+        self.data_layout.collect_pptr_slots(
+            "/Base",
+            &self.data_buff,
+            &self.get_field_cast_args(),
+            &mut slots,
+        );
+        slots
+    }
+}
+
+/// A JSON container being written by [`JsonWriter`].
+#[cfg(feature = "serde")]
+enum JsonFrame {
+    Object {
+        empty: bool,
+    },
+    Array {
+        empty: bool,
+    },
+    /// A `vector`, `map` or `set`, written as the array it wraps.
+    Transparent,
+}
+
+/// Writes the fields it visits as JSON as they come, see
+/// [`TypeTreeObject::write_json`].
+#[cfg(feature = "serde")]
+struct JsonWriter<W> {
+    writer: W,
+    frames: Vec<JsonFrame>,
+    error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "serde")]
+impl<W: std::io::Write> JsonWriter<W> {
+    /// Write the separator and key, if any, of a value at `path` in the
+    /// innermost container.
+    fn begin_value(&mut self, path: &str) -> std::io::Result<()> {
+        let mut segments = path.rsplit('/');
+        let mut key = segments.next().unwrap_or_default();
+        let mut frames = self.frames.iter_mut().rev();
+        let mut frame = frames.next();
+        // The array of a transparent wrapper takes the wrapper's key.
+        if let Some(JsonFrame::Transparent) = frame {
+            key = segments.next().unwrap_or_default();
+            frame = frames.next();
+        }
+        match frame {
+            Some(JsonFrame::Object { empty }) => {
+                if !std::mem::replace(empty, false) {
+                    self.writer.write_all(b",")?;
+                }
+                serde_json::to_writer(&mut self.writer, key)?;
+                self.writer.write_all(b":")
+            }
+            Some(JsonFrame::Array { empty }) => {
+                if !std::mem::replace(empty, false) {
+                    self.writer.write_all(b",")?;
+                }
+                Ok(())
+            }
+            Some(JsonFrame::Transparent) | None => Ok(()),
+        }
+    }
+
+    /// Turn an io result into a visit, keeping the first error.
+    fn check(&mut self, result: std::io::Result<()>) -> Visit {
+        match result {
+            Ok(()) => Visit::Continue,
+            Err(err) => {
+                self.error.get_or_insert(err);
+                Visit::Stop
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<W: std::io::Write> TypeTreeVisitor for JsonWriter<W> {
+    fn enter_struct(&mut self, path: &str, type_name: &str) -> Visit {
+        if ["vector", "map", "set", "staticvector"].contains(&type_name) {
+            self.frames.push(JsonFrame::Transparent);
+            return Visit::Continue;
+        }
+        let result = self
+            .begin_value(path)
+            .and_then(|_| self.writer.write_all(b"{"));
+        self.frames.push(JsonFrame::Object { empty: true });
+        self.check(result)
+    }
+
+    fn leave_struct(&mut self, _path: &str, _type_name: &str) {
+        if let Some(JsonFrame::Object { .. }) = self.frames.pop() {
+            let result = self.writer.write_all(b"}");
+            self.check(result);
+        }
+    }
+
+    fn enter_array(&mut self, path: &str, _item_type: &str, _len: usize) -> Visit {
+        let result = self
+            .begin_value(path)
+            .and_then(|_| self.writer.write_all(b"["));
+        self.frames.push(JsonFrame::Array { empty: true });
+        self.check(result)
+    }
+
+    fn leave_array(&mut self, _path: &str) {
+        self.frames.pop();
+        let result = self.writer.write_all(b"]");
+        self.check(result);
+    }
+
+    fn visit_value(&mut self, path: &str, _type_name: &str, value: LeafValue) -> Visit {
+        let result = self.begin_value(path).and_then(|_| {
+            match value {
+                LeafValue::Bool(value) => serde_json::to_writer(&mut self.writer, &value),
+                LeafValue::Int(value) => serde_json::to_writer(&mut self.writer, &value),
+                LeafValue::UInt(value) => serde_json::to_writer(&mut self.writer, &value),
+                LeafValue::Float(value) => serde_json::to_writer(&mut self.writer, &value),
+                LeafValue::String(value) => serde_json::to_writer(&mut self.writer, &value),
+            }
+            .map_err(std::io::Error::from)
+        });
+        self.check(result)
+    }
+
+    fn visit_pptr(&mut self, path: &str, _type_name: &str, file_id: i64, path_id: i64) -> Visit {
+        let result = self.begin_value(path).and_then(|_| {
+            write!(
+                self.writer,
+                "{{\"m_FileID\":{file_id},\"m_PathID\":{path_id}}}"
+            )
+        });
+        self.check(result)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TypeTreeObject {
+    /// Write the object as one JSON object of its fields, field by field as
+    /// they are visited, so memory use stays flat however large the object
+    /// is. `vector`s and `map`s are written as the arrays they hold, PPtrs
+    /// as `{"m_FileID": .., "m_PathID": ..}` and non-finite floats as `null`.
+    pub fn write_json<W: std::io::Write>(&self, writer: W) -> crate::error::ReadResult<()> {
+        let mut json = JsonWriter {
+            writer,
+            frames: Vec::new(),
+            error: None,
+        };
+        self.visit(&mut json);
+        match json.error {
+            Some(err) => Err(err.into()),
+            None => Ok(json.writer.flush()?),
+        }
+    }
+}
+
+impl TypeTreeObjectRef {
+    /// Walk the field this ref points to, with paths relative to it as for
+    /// `try_cast_from` on this ref. See [`TypeTreeObject::visit`].
+    pub fn visit<V: TypeTreeVisitor>(&self, visitor: &mut V) -> bool {
+        let inner = self.inner.read().unwrap();
+        let Some((field, field_offset)) = inner.get_field_by_path_list(&self.path) else {
+            return true;
+        };
+        let field_cast_args = FieldCastArgs {
+            endian: inner.endian,
+            field_offset,
+        };
+        field.visit("/Base", visitor, &inner.data_buff, &field_cast_args)
+    }
+}
+
+impl Field {
+    fn visit<V: TypeTreeVisitor>(
+        &self,
+        path: &str,
+        visitor: &mut V,
+        object_data_buff: &[u8],
+        field_cast_args: &FieldCastArgs,
+    ) -> bool {
+        let type_name = self.field_type.get_type();
+
+        if type_name == "string" {
+            if let Ok(value) =
+                TryCast::<String>::try_cast_to(self, object_data_buff, field_cast_args)
+            {
+                return visitor.visit_value(path, type_name, LeafValue::String(value))
+                    != Visit::Stop;
+            }
+        }
+        if type_name.starts_with("PPtr<") {
+            if let FieldValue::Fields(fields) = &self.data {
+                let file_id = fields.get("m_FileID").and_then(|f| {
+                    TryCast::<i64>::try_cast_to(f, object_data_buff, field_cast_args).ok()
+                });
+                let path_id = fields.get("m_PathID").and_then(|f| {
+                    TryCast::<i64>::try_cast_to(f, object_data_buff, field_cast_args).ok()
+                });
+                if let (Some(file_id), Some(path_id)) = (file_id, path_id) {
+                    return visitor.visit_pptr(path, type_name, file_id, path_id) != Visit::Stop;
+                }
+            }
+        }
+
+        match &self.data {
+            FieldValue::DataOffset(_) => match self.leaf_value(object_data_buff, field_cast_args) {
+                Some(value) => visitor.visit_value(path, type_name, value) != Visit::Stop,
+                None => true,
+            },
+            FieldValue::Fields(fields) => {
+                match visitor.enter_struct(path, type_name) {
+                    Visit::Stop => return false,
+                    Visit::SkipChildren => (),
+                    Visit::Continue => {
+                        let mut fields: Vec<_> = fields.values().collect();
+                        fields.sort_by_key(|f| f.field_type.get_index());
+                        for field in fields {
+                            let field_path = format!("{path}/{}", field.get_name());
+                            if !field.visit(&field_path, visitor, object_data_buff, field_cast_args)
+                            {
+                                return false;
+                            }
+                        }
+                    }
+                }
+                visitor.leave_struct(path, type_name);
+                true
+            }
+            FieldValue::Array(array) => {
+                let size: i32 = array
+                    .array_size
+                    .try_cast_to(object_data_buff, field_cast_args)
+                    .unwrap_or(0);
+                let size = size.max(0) as usize;
+                let item_type = array
+                    .item_type_fields
+                    .first()
+                    .map_or("", |item_type| item_type.get_type().as_str());
+                match visitor.enter_array(path, item_type, size) {
+                    Visit::Stop => return false,
+                    Visit::SkipChildren => (),
+                    Visit::Continue => match &array.data {
+                        ArrayFieldValue::ArrayItems(items) => {
+                            for (index, item) in items.iter().enumerate() {
+                                let item_path = format!("{path}/{index}");
+                                if !item.visit(
+                                    &item_path,
+                                    visitor,
+                                    object_data_buff,
+                                    field_cast_args,
+                                ) {
+                                    return false;
+                                }
+                            }
+                        }
+                        ArrayFieldValue::DataOffset(DataOffset::AbsDataOffset(offset)) => {
+                            if let (Some(item_field), Some(item_size)) =
+                                (&array.item_field, array.item_field_size)
+                            {
+                                for index in 0..size {
+                                    let item_cast_args = FieldCastArgs {
+                                        endian: field_cast_args.endian,
+                                        field_offset: Some(
+                                            (offset + item_size * index as u64) as i64,
+                                        ),
+                                    };
+                                    let item_path = format!("{path}/{index}");
+                                    if !item_field.visit(
+                                        &item_path,
+                                        visitor,
+                                        object_data_buff,
+                                        &item_cast_args,
+                                    ) {
+                                        return false;
+                                    }
+                                }
+                            }
+                        }
+                        ArrayFieldValue::DataOffset(DataOffset::ArrayItemOffset(_)) => (),
+                    },
+                }
+                visitor.leave_array(path);
+                true
+            }
+        }
+    }
+
+    fn collect_pptr_slots(
+        &self,
+        path: &str,
+        object_data_buff: &[u8],
+        field_cast_args: &FieldCastArgs,
+        slots: &mut Vec<PPtrSlot>,
+    ) {
+        match &self.data {
+            FieldValue::DataOffset(_) => (),
+            FieldValue::Fields(fields) => {
+                if self.field_type.get_type().starts_with("PPtr<") {
+                    let id = |name: &str| {
+                        let field = fields.get(name)?;
+                        let value =
+                            TryCast::<i64>::try_cast_to(field, object_data_buff, field_cast_args)
+                                .ok()?;
+                        Some((value, field.value_range(field_cast_args)?))
+                    };
+                    if let (Some((file_id, file_id_range)), Some((path_id, path_id_range))) =
+                        (id("m_FileID"), id("m_PathID"))
+                    {
+                        slots.push(PPtrSlot {
+                            path: path.to_owned(),
+                            file_id,
+                            path_id,
+                            file_id_range,
+                            path_id_range,
+                        });
+                        return;
+                    }
+                }
+                let mut fields: Vec<_> = fields.values().collect();
+                fields.sort_by_key(|f| f.field_type.get_index());
+                for field in fields {
+                    let field_path = format!("{path}/{}", field.get_name());
+                    field.collect_pptr_slots(&field_path, object_data_buff, field_cast_args, slots);
+                }
+            }
+            FieldValue::Array(array) => match &array.data {
+                ArrayFieldValue::ArrayItems(items) => {
+                    for (index, item) in items.iter().enumerate() {
+                        let item_path = format!("{path}/{index}");
+                        item.collect_pptr_slots(
+                            &item_path,
+                            object_data_buff,
+                            field_cast_args,
+                            slots,
+                        );
+                    }
+                }
+                ArrayFieldValue::DataOffset(DataOffset::AbsDataOffset(offset)) => {
+                    let (Some(item_field), Some(item_size)) =
+                        (&array.item_field, array.item_field_size)
+                    else {
+                        return;
+                    };
+                    let size: i32 = array
+                        .array_size
+                        .try_cast_to(object_data_buff, field_cast_args)
+                        .unwrap_or(0);
+                    for index in 0..size.max(0) as u64 {
+                        let item_cast_args = FieldCastArgs {
+                            endian: field_cast_args.endian,
+                            field_offset: Some((offset + item_size * index) as i64),
+                        };
+                        let item_path = format!("{path}/{index}");
+                        item_field.collect_pptr_slots(
+                            &item_path,
+                            object_data_buff,
+                            &item_cast_args,
+                            slots,
+                        );
+                    }
+                }
+                ArrayFieldValue::DataOffset(DataOffset::ArrayItemOffset(_)) => (),
+            },
+        }
+    }
+
+    fn leaf_value(
+        &self,
+        object_data_buff: &[u8],
+        field_cast_args: &FieldCastArgs,
+    ) -> Option<LeafValue> {
+        match self.field_type.get_type().as_str() {
+            "bool" => TryCast::<bool>::try_cast_to(self, object_data_buff, field_cast_args)
+                .ok()
+                .map(LeafValue::Bool),
+            "float" => TryCast::<f32>::try_cast_to(self, object_data_buff, field_cast_args)
+                .ok()
+                .map(|v| LeafValue::Float(v as f64)),
+            "double" => TryCast::<f64>::try_cast_to(self, object_data_buff, field_cast_args)
+                .ok()
+                .map(LeafValue::Float),
+            _ => TryCast::<i64>::try_cast_to(self, object_data_buff, field_cast_args)
+                .map(LeafValue::Int)
+                .or_else(|_| {
+                    TryCast::<u64>::try_cast_to(self, object_data_buff, field_cast_args)
+                        .map(LeafValue::UInt)
+                })
+                .ok(),
+        }
+    }
+}
@@ -1,24 +1,61 @@
 use std::{
-    collections::{BTreeMap, HashMap},
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fs::OpenOptions,
-    io::{BufReader, Cursor},
+    io::{BufReader, Cursor, Seek, SeekFrom},
     path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
 };
 
 use walkdir::WalkDir;
 
+#[cfg(feature = "external-class-handle-texture2d")]
+use crate::classes::texture2d::{decode_image, Texture2D, Texture2DObject, TextureFormat};
+#[cfg(feature = "external-class-handle")]
+use crate::classes::{
+    audio_clip::{AudioClip, AudioClipObject},
+    mesh::{Mesh, MeshObject},
+    text_asset::{TextAsset, TextAssetObject},
+};
+use crate::error::Error;
+use crate::identify::{identify_file, FileKind};
+use crate::{
+    classes::{p_ptr::PPtrObject, SerializedFileRef},
+    type_tree::TypeTreeObjectRef,
+};
 use crate::{
-    classes::{p_ptr::PPtr, ClassIDType},
-    serialized_file::SerializedFile,
+    classes::{
+        p_ptr::{PPtr, PPtrIds},
+        ClassIDType,
+    },
+    serialized_file::{ExternalRef, MissingTypeTreePolicy, ObjectData, SerializedFile},
+    split::{order_split_parts, split_part_index, ReopeningReader, SplitReader},
+    type_tree::visitor::{FieldDiff, LeafValue, TypeTreeVisitor, Visit},
     type_tree::TypeTreeObject,
     unityfs::UnityFS,
     unityfs::UnityResource,
 };
+use crate::{error::ReadResult, type_tree::convert::TryCastFrom};
 use crate::{
-    classes::{p_ptr::PPtrObject, SerializedFileRef},
-    type_tree::TypeTreeObjectRef,
+    serialized_file::version17::FileIdentifier,
+    serialized_file::writer::{SerializedFileBuilder, Writer, WrittenObject, WrittenType},
+    type_tree::{content_hash, TypeField, TypeTreeNode},
+    unityfs::write_bundle,
+    until::Endian,
 };
-use crate::{error::ReadResult, type_tree::convert::TryCastFrom};
+#[cfg(feature = "external-class-handle-texture2d")]
+use image::RgbaImage;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "timings")]
+use std::time::Instant;
+
+/// Raw texture bytes with the format, width and height needed to decode them.
+#[cfg(feature = "external-class-handle-texture2d")]
+type TextureData = (Vec<u8>, TextureFormat, usize, usize);
 
 #[derive(Default)]
 pub struct UnityAssetViewer {
@@ -28,8 +65,405 @@ pub struct UnityAssetViewer {
     unity_fs_map: BTreeMap<i64, UnityFS>,
     unity_fs_count: i64,
     serialized_file_to_unity_fs_map: BTreeMap<i64, i64>,
-    pub container_maps: HashMap<String, Vec<(i64, TypeTreeObjectRef)>>,
+    pub container_maps: HashMap<String, Vec<ContainerEntry>>,
     container_name_maps: HashMap<i64, HashMap<i64, String>>,
+    container_raw_name_maps: HashMap<String, String>,
+    /// Key of `container_maps` by what [`normalize_container_name`] makes
+    /// of it, the first one indexed winning.
+    normalized_container_keys: HashMap<String, String>,
+    /// Serialized file id by the lowercased `m_AssetBundleName` of its
+    /// AssetBundle, indexed along with the containers.
+    asset_bundle_names: HashMap<String, i64>,
+    normalize_container_names: bool,
+    defer_container_index: bool,
+    skip_container_index: bool,
+    #[cfg(feature = "spill")]
+    spill_threshold_bytes: Option<u64>,
+    export_memory_budget_bytes: Option<u64>,
+    missing_type_tree_policy: MissingTypeTreePolicy,
+    keep_raw_bytes: bool,
+    close_bundle_files: bool,
+    /// Whether bundles being added read their CABs block by block, set
+    /// during [`Self::read_dir_lazy`].
+    lazy_bundles: bool,
+    /// Whether bundles being added leave their CABs unread until first
+    /// used, set while adding a bundle whose containers come from a loaded
+    /// index.
+    #[cfg(feature = "serde")]
+    defer_cab_reads: bool,
+    pub resource_map: HashMap<String, PathBuf>,
+    stream_search_paths: Vec<PathBuf>,
+    /// Container entries indexed since they were last shown to the
+    /// [`ScanVisitor`] of a running scan, `None` outside of one.
+    visited_containers: Option<Vec<(String, ContainerEntry)>>,
+    bundle_paths: BTreeMap<i64, PathBuf>,
+    class_name_overrides: HashMap<i32, String>,
+    #[cfg(feature = "serde")]
+    loaded_index: HashMap<PathBuf, IndexedBundle>,
+    /// Time spent per phase, by bundle id for [`TimingPhase::Decompress`]
+    /// and by serialized file id for the others.
+    #[cfg(feature = "timings")]
+    timings: HashMap<(TimingPhase, i64), Duration>,
+    #[cfg(feature = "timings")]
+    timing_hook: Option<Box<dyn FnMut(TimingPhase, Duration) + Send + Sync>>,
+}
+
+/// An object indexed under a container name, read from its AssetBundle or
+/// ResourceManager or restored from an index saved by
+/// [`UnityAssetViewer::save_index`].
+#[derive(Debug, Clone)]
+pub struct ContainerEntry {
+    /// The serialized file holding the AssetBundle or ResourceManager that
+    /// lists the container.
+    pub serialized_file_id: i64,
+    /// The ids of the object's PPtr, whose file id indexes the externals of
+    /// `serialized_file_id`. Resolve it with [`UnityAssetViewer::deref`].
+    pub pptr: PPtrIds,
+    /// Start of the object's dependencies in the AssetBundle `m_PreloadTable`.
+    /// `None` for ResourceManager containers, which have no preload table.
+    pub preload_index: Option<i32>,
+    /// Number of `m_PreloadTable` entries from `preload_index`.
+    pub preload_size: Option<i32>,
+}
+
+/// Callbacks made by [`UnityAssetViewer::read_dir_with_visitor`] while it
+/// reads, to build custom indexes in the same pass. Both default to doing
+/// nothing.
+pub trait ScanVisitor {
+    fn visit_serialized_file(&mut self, _cab_name: &str, _serialized_file: &SerializedFile) {}
+
+    /// `name` is the container name as stored in the file.
+    fn visit_container(&mut self, _name: &str, _entry: &ContainerEntry) {}
+}
+
+/// What a directory scan such as [`UnityAssetViewer::read_dir_report`] loaded.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ScanReport {
+    pub bundles_loaded: usize,
+    /// Serialized files read from the loaded bundles.
+    pub serialized_files: usize,
+    /// Objects added to the container index, counting each name once per
+    /// object it lists.
+    pub containers_indexed: usize,
+    /// Files that could not be read as a bundle, with the reason.
+    pub skipped: Vec<(PathBuf, String)>,
+    /// Time spent on each loaded bundle, in the order they were read.
+    #[cfg(feature = "timings")]
+    pub timings: Vec<BundleTimings>,
+}
+
+/// The parsed files of a viewer, taken by [`UnityAssetViewer::into_parts`].
+pub struct ViewerParts {
+    /// Every loaded serialized file, by serialized file id.
+    pub serialized_files: BTreeMap<i64, SerializedFile>,
+    /// Every loaded bundle, by bundle id.
+    pub bundles: BTreeMap<i64, UnityFS>,
+    /// The bundle id each serialized file read from a bundle came from.
+    pub serialized_file_bundles: BTreeMap<i64, i64>,
+    /// Serialized file id by CAB name.
+    pub cab_maps: HashMap<String, i64>,
+    /// Path of each bundle read from disk, by bundle id.
+    pub bundle_paths: BTreeMap<i64, PathBuf>,
+    pub container_maps: HashMap<String, Vec<ContainerEntry>>,
+    /// Container name by path id, by serialized file id.
+    pub container_names: HashMap<i64, HashMap<i64, String>>,
+    pub resource_map: HashMap<String, PathBuf>,
+}
+
+/// What [`UnityAssetViewer::dump_audio`] wrote.
+#[cfg(feature = "external-class-handle")]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AudioExportReport {
+    pub clips: Vec<AudioExport>,
+    /// Clips whose data could not be read, by the path they would have
+    /// been written to, with the reason.
+    pub skipped: Vec<(PathBuf, String)>,
+}
+
+/// One AudioClip written by [`UnityAssetViewer::dump_audio`].
+#[cfg(feature = "external-class-handle")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioExport {
+    pub path: PathBuf,
+    /// Codec of the clip's FSB5 bank, `None` when its data is not one.
+    pub codec: Option<crate::classes::audio_clip::fsb5::Fsb5Codec>,
+    /// Whether the clip was decoded to WAV rather than written as stored.
+    pub decoded: bool,
+}
+
+/// Which objects [`UnityAssetViewer::export_all`] writes. The default
+/// exports every object of a class with an exporter.
+#[cfg(feature = "external-class-handle")]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExportFilter {
+    /// Only objects of these classes. Those without an exporter are
+    /// reported as skipped.
+    pub class_ids: Option<Vec<i32>>,
+    /// Only objects whose container name or `m_Name` contains this,
+    /// ignoring case, as for [`UnityAssetViewer::find_objects`].
+    pub name_substring: Option<String>,
+}
+
+/// What [`UnityAssetViewer::export_all`] did with each object it selected,
+/// by serialized file id and path id.
+#[cfg(feature = "external-class-handle")]
+#[derive(Debug, Default)]
+pub struct ExportReport {
+    pub written: Vec<(i64, i64, PathBuf)>,
+    /// Objects that cannot be exported, with the reason.
+    pub skipped: Vec<(i64, i64, String)>,
+    pub failed: Vec<(i64, i64, Error)>,
+}
+
+/// How a container differs between two viewers, see [`UnityAssetViewer::diff`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContainerChange {
+    Added,
+    Removed,
+    /// The objects under the name hash differently. `fields` compares the
+    /// first object on each side and is only filled by
+    /// [`UnityAssetViewer::diff_with_fields`].
+    Changed {
+        fields: Option<Vec<FieldDiff>>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerDiff {
+    pub name: String,
+    pub change: ContainerChange,
+}
+
+/// The containers that differ between two viewers, sorted by name.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ViewerDiff {
+    pub containers: Vec<ContainerDiff>,
+}
+
+/// An object [`UnityAssetViewer::export_all`] has read and not yet written.
+#[cfg(feature = "external-class-handle")]
+enum PendingExport {
+    #[cfg(feature = "external-class-handle-texture2d")]
+    Texture(TextureData),
+    Data(Vec<u8>),
+}
+
+#[cfg(feature = "external-class-handle")]
+impl PendingExport {
+    /// Bytes held while the object waits to be written.
+    fn cost(&self) -> u64 {
+        match self {
+            #[cfg(feature = "external-class-handle-texture2d")]
+            PendingExport::Texture((data, _, width, height)) => {
+                (data.len() + width * height * 4) as u64
+            }
+            PendingExport::Data(data) => data.len() as u64,
+        }
+    }
+
+    fn into_bytes(self) -> ReadResult<Vec<u8>> {
+        match self {
+            #[cfg(feature = "external-class-handle-texture2d")]
+            PendingExport::Texture((data, texture_format, width, height)) => {
+                let image = decode_image(data, &texture_format, width, height).map_err(|err| {
+                    Error::TextureDecodeError {
+                        texture_format,
+                        source: Box::new(err),
+                    }
+                })?;
+                // Unity stores the bottom row first.
+                let mut png = Cursor::new(Vec::new());
+                image
+                    .flipv()
+                    .write_to(&mut png, image::ImageOutputFormat::Png)
+                    .map_err(|err| Error::Other(err.to_string()))?;
+                Ok(png.into_inner())
+            }
+            PendingExport::Data(data) => Ok(data),
+        }
+    }
+}
+
+/// A part of reading files timed with the `timings` feature.
+#[cfg(feature = "timings")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimingPhase {
+    /// Reading a bundle's header and directory and decompressing its files.
+    Decompress,
+    /// Parsing a serialized file's header, type table and object table.
+    Metadata,
+    /// Parsing objects, which reading does for the container index.
+    Objects,
+}
+
+/// Time spent reading one bundle of a directory scan.
+#[cfg(feature = "timings")]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BundleTimings {
+    pub path: PathBuf,
+    pub decompress: Duration,
+    pub metadata: Duration,
+    pub objects: Duration,
+}
+
+/// A scene file listed by [`UnityAssetViewer::scenes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SceneInfo {
+    pub cab_name: String,
+    /// Number of root transforms, counted by [`UnityAssetViewer::root_transforms`].
+    /// A scene that cannot be read counts none.
+    pub root_game_objects: usize,
+}
+
+/// Which loaded files depend on which others through their `m_Externals`,
+/// built by [`UnityAssetViewer::dependency_graph`]. Files are named as
+/// [`UnityAssetViewer::cab_names`] lists them.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DependencyGraph {
+    /// Every named file the viewer has loaded.
+    pub loaded: BTreeSet<String>,
+    /// Files named by an external reference but not loaded, such as Unity's
+    /// builtin resources. They have no dependencies of their own.
+    pub unresolved: BTreeSet<String>,
+    /// `(dependent, dependency)` pairs.
+    pub edges: BTreeSet<(String, String)>,
+}
+
+impl DependencyGraph {
+    /// The files `name` references directly.
+    pub fn dependencies<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.edges
+            .iter()
+            .filter(move |(dependent, _)| dependent == name)
+            .map(|(_, dependency)| dependency.as_str())
+    }
+
+    /// The graph in Graphviz DOT, with unresolved files drawn dashed.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph dependencies {\n");
+        for name in &self.loaded {
+            dot.push_str(&format!("    {name:?};\n"));
+        }
+        for name in &self.unresolved {
+            dot.push_str(&format!("    {name:?} [style=dashed];\n"));
+        }
+        for (dependent, dependency) in &self.edges {
+            dot.push_str(&format!("    {dependent:?} -> {dependency:?};\n"));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// An owned reference to an object of a [`UnityAssetViewer`], free of any
+/// borrow of it. Store as many as needed and read each object when it is
+/// wanted with [`UnityAssetViewer::resolve`].
+///
+/// With the `serde` feature it is stored as
+/// `{"serialized_file_id": .., "path_id": ..}`. As for
+/// [`PPtrIds`](crate::classes::p_ptr::PPtrIds), the
+/// serialized file id is only meaningful to a viewer that loaded the same
+/// files in the same order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AssetHandle {
+    pub serialized_file_id: i64,
+    pub path_id: i64,
+}
+
+impl From<&TypeTreeObject> for AssetHandle {
+    fn from(obj: &TypeTreeObject) -> Self {
+        Self {
+            serialized_file_id: obj.serialized_file_id,
+            path_id: obj.path_id,
+        }
+    }
+}
+
+/// Options applied to a [`UnityAssetViewer`] before any file is read.
+#[derive(Default)]
+pub struct UnityAssetViewerBuilder {
+    normalize_container_names: bool,
+    defer_container_index: bool,
+    skip_container_index: bool,
+    #[cfg(feature = "spill")]
+    spill_threshold_bytes: Option<u64>,
+    export_memory_budget_bytes: Option<u64>,
+    missing_type_tree_policy: MissingTypeTreePolicy,
+    keep_raw_bytes: bool,
+    close_bundle_files: bool,
+}
+
+impl UnityAssetViewerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// See [`UnityAssetViewer::set_normalize_container_names`].
+    pub fn normalize_container_names(mut self, normalize: bool) -> Self {
+        self.normalize_container_names = normalize;
+        self
+    }
+
+    /// Build the container index once after a directory is read, in parallel
+    /// with the `parallel` feature. See [`UnityAssetViewer::set_defer_container_index`].
+    pub fn defer_container_index(mut self, defer: bool) -> Self {
+        self.defer_container_index = defer;
+        self
+    }
+
+    /// See [`UnityAssetViewer::set_index_containers`].
+    pub fn index_containers(mut self, index: bool) -> Self {
+        self.skip_container_index = !index;
+        self
+    }
+
+    /// See [`UnityAssetViewer::set_spill_threshold_bytes`].
+    #[cfg(feature = "spill")]
+    pub fn spill_threshold_bytes(mut self, threshold: u64) -> Self {
+        self.spill_threshold_bytes = Some(threshold);
+        self
+    }
+
+    /// See [`UnityAssetViewer::set_export_memory_budget_bytes`].
+    pub fn export_memory_budget_bytes(mut self, budget: u64) -> Self {
+        self.export_memory_budget_bytes = Some(budget);
+        self
+    }
+
+    /// See [`UnityAssetViewer::set_missing_type_tree_policy`].
+    pub fn missing_type_tree_policy(mut self, policy: MissingTypeTreePolicy) -> Self {
+        self.missing_type_tree_policy = policy;
+        self
+    }
+
+    /// See [`UnityAssetViewer::set_keep_raw_bytes`].
+    pub fn keep_raw_bytes(mut self, keep: bool) -> Self {
+        self.keep_raw_bytes = keep;
+        self
+    }
+
+    /// See [`UnityAssetViewer::set_close_bundle_files`].
+    pub fn close_bundle_files(mut self, close: bool) -> Self {
+        self.close_bundle_files = close;
+        self
+    }
+
+    pub fn build(self) -> UnityAssetViewer {
+        UnityAssetViewer {
+            normalize_container_names: self.normalize_container_names,
+            defer_container_index: self.defer_container_index,
+            skip_container_index: self.skip_container_index,
+            #[cfg(feature = "spill")]
+            spill_threshold_bytes: self.spill_threshold_bytes,
+            export_memory_budget_bytes: self.export_memory_budget_bytes,
+            missing_type_tree_policy: self.missing_type_tree_policy,
+            keep_raw_bytes: self.keep_raw_bytes,
+            close_bundle_files: self.close_bundle_files,
+            ..Default::default()
+        }
+    }
 }
 
 impl UnityAssetViewer {
@@ -37,296 +471,2704 @@ impl UnityAssetViewer {
         Self::default()
     }
 
-    pub fn read_bundle_dir<P: AsRef<Path>>(&mut self, dir_path: P) -> ReadResult<()> {
-        for entry in WalkDir::new(dir_path).into_iter().flatten() {
-            if entry.file_type().is_file() {
-                let file = OpenOptions::new().read(true).open(entry.path())?;
-                let file = Box::new(BufReader::new(file));
-                let _unity_fs_id = self
-                    .add_bundle_file(
-                        file,
-                        Some(entry.path().parent().unwrap().to_string_lossy().to_string()),
-                    )
-                    .unwrap_or_default();
-            }
+    pub fn builder() -> UnityAssetViewerBuilder {
+        UnityAssetViewerBuilder::new()
+    }
+
+    /// Normalize container names with [`normalize_container_name`] when they
+    /// are indexed and looked up. Changing it once containers are indexed
+    /// rebuilds the index with [`Self::build_container_index`], so names
+    /// already loaded are keyed the new way too.
+    pub fn set_normalize_container_names(&mut self, normalize: bool) {
+        if normalize == self.normalize_container_names {
+            return;
+        }
+        self.normalize_container_names = normalize;
+        if !self.container_maps.is_empty() {
+            self.build_container_index();
         }
-        Ok(())
     }
 
-    pub fn add_bundle_file(
-        &mut self,
-        bundle_file_reader: Box<dyn UnityResource + Send + Sync>,
-        resource_search_path: Option<String>,
-    ) -> ReadResult<i64> {
-        let unity_fs = UnityFS::read(bundle_file_reader, resource_search_path)?;
-        let unity_fs_id = self.unity_fs_count;
-        self.unity_fs_count += 1;
-        for cab_path in unity_fs.get_cab_path() {
-            let cab_buff = unity_fs.get_file_data_by_path(&cab_path)?;
-            let cab_buff_reader = Box::new(Cursor::new(cab_buff));
-            // let cab_buff_reader = Box::new(BufReader::new(
-            //     unity_fs
-            //         .get_file_reader_by_path(&cab_path)
-            //         .ok_or(Error::Other("can not get cab reader".to_owned()))?,
-            // ));
+    /// Back CABs larger than `threshold` bytes with an anonymous temporary
+    /// file instead of memory when bundles are added.
+    #[cfg(feature = "spill")]
+    pub fn set_spill_threshold_bytes(&mut self, threshold: Option<u64>) {
+        self.spill_threshold_bytes = threshold;
+    }
 
-            let serialized_file_id = self.add_serialized_file(cab_buff_reader, None)?;
-            self.serialized_file_to_unity_fs_map
-                .insert(serialized_file_id, unity_fs_id);
-            self.cab_maps.insert(cab_path, serialized_file_id);
+    /// Bound the bytes [`Self::export_all`] holds at once, counting the
+    /// read data and decoded image of each object. `None` is 256 MiB.
+    pub fn set_export_memory_budget_bytes(&mut self, budget: Option<u64>) {
+        self.export_memory_budget_bytes = budget;
+    }
+
+    /// How [`Self::iter_objects`] handles objects whose class has no
+    /// TypeTree, such as those of release builds with stripped TypeTrees.
+    pub fn set_missing_type_tree_policy(&mut self, policy: MissingTypeTreePolicy) {
+        self.missing_type_tree_policy = policy;
+    }
+
+    /// Keep each object's original bytes on the objects read, for every
+    /// loaded file and those added afterwards. See
+    /// [`SerializedFile::set_keep_raw_bytes`].
+    pub fn set_keep_raw_bytes(&mut self, keep: bool) {
+        self.keep_raw_bytes = keep;
+        for serialized_file in self.serialized_file_map.values_mut() {
+            serialized_file.set_keep_raw_bytes(keep);
         }
-        self.unity_fs_map.insert(unity_fs_id, unity_fs);
-        Ok(unity_fs_id)
     }
 
-    pub fn add_serialized_file(
-        &mut self,
-        serialized_file_reader: Box<dyn UnityResource + Send + Sync>,
-        resource_search_path: Option<String>,
-    ) -> ReadResult<i64> {
-        let serialized_file_id = self.serialized_file_count;
-        self.serialized_file_count += 1;
+    /// Close each bundle file once the directory scans and
+    /// [`Self::read_split_bundle`] have read its CABs, reopening it only for
+    /// the rare later reads of the bundle itself, such as stream data stored
+    /// next to the CABs. Scans of more bundles than the open file limit then
+    /// succeed. CABs spilled to a temporary file keep that file open.
+    pub fn set_close_bundle_files(&mut self, close: bool) {
+        self.close_bundle_files = close;
+    }
 
-        let serialized_file = SerializedFile::read(
-            serialized_file_reader,
-            serialized_file_id,
-            resource_search_path,
-        )?;
-        if let Ok(Some(asset_bundle)) = serialized_file.get_tt_object_by_path_id(1) {
-            if let Ok(containers) = <HashMap<String, TypeTreeObjectRef>>::try_cast_from(
-                &asset_bundle.into(),
-                "/Base/m_Container/Array",
-            ) {
-                let mut name_map = HashMap::new();
-                for (name, asset_info) in containers {
-                    if let Ok(pptr) = TypeTreeObjectRef::try_cast_from(&asset_info, "/Base/asset") {
-                        if let Ok(path_id) = PPtr::new(&pptr).get_path_id() {
-                            name_map.insert(path_id, name.clone());
-                        }
+    /// Name `class_id` as `name`, taking precedence over [`ClassIDType`]. For
+    /// engine forks that renumber or add classes.
+    pub fn register_class_name(&mut self, class_id: i32, name: String) {
+        self.class_name_overrides.insert(class_id, name);
+    }
 
-                        if let Some(objs) = self.container_maps.get_mut(&name) {
-                            objs.push((serialized_file_id, pptr));
-                        } else {
-                            self.container_maps
-                                .insert(name, vec![(serialized_file_id, pptr)]);
-                        }
+    /// The name registered with [`Self::register_class_name`], else the
+    /// built-in [`ClassIDType`] name.
+    pub fn get_class_name(&self, class_id: i32) -> Option<String> {
+        if let Some(name) = self.class_name_overrides.get(&class_id) {
+            return Some(name.clone());
+        }
+        ClassIDType::try_from(class_id)
+            .ok()
+            .map(|class| class.name().to_owned())
+    }
+
+    /// Object count per class name across every serialized file. Unknown
+    /// classes are keyed by their id.
+    pub fn class_histogram(&self) -> BTreeMap<String, usize> {
+        let mut histogram = BTreeMap::new();
+        for serialized_file in self.serialized_file_map.values() {
+            for obj in serialized_file.get_object_map().values() {
+                let name = self
+                    .get_class_name(obj.class)
+                    .unwrap_or_else(|| obj.class.to_string());
+                *histogram.entry(name).or_insert(0) += 1;
+            }
+        }
+        histogram
+    }
+
+    /// [`SerializedFile::iter_tt_objects`] over every serialized file, with
+    /// the id of the file each object comes from.
+    pub fn iter_tt_objects(
+        &self,
+        class_id: Option<i32>,
+    ) -> impl Iterator<Item = (i64, ReadResult<TypeTreeObject>)> + '_ {
+        self.serialized_file_map
+            .iter()
+            .flat_map(move |(serialized_file_id, serialized_file)| {
+                serialized_file
+                    .iter_tt_objects(class_id)
+                    .map(move |object| (*serialized_file_id, object))
+            })
+    }
+
+    /// [`SerializedFile::iter_objects`] over every serialized file with the
+    /// policy set by [`Self::set_missing_type_tree_policy`], with the id of
+    /// the file each object comes from.
+    pub fn iter_objects(
+        &self,
+        class_id: Option<i32>,
+    ) -> impl Iterator<Item = (i64, ReadResult<ObjectData>)> + '_ {
+        self.serialized_file_map
+            .iter()
+            .flat_map(move |(serialized_file_id, serialized_file)| {
+                serialized_file
+                    .iter_objects(class_id, self.missing_type_tree_policy)
+                    .map(move |object| (*serialized_file_id, object))
+            })
+    }
+
+    /// The BuildSettings object, found in `globalgamemanagers`, of the first
+    /// loaded file holding one. Read it with
+    /// [`crate::classes::build_settings::BuildSettingsObject`].
+    pub fn build_settings(&self) -> ReadResult<Option<TypeTreeObject>> {
+        self.first_object_of_class(ClassIDType::BuildSettings as i32)
+    }
+
+    /// The PlayerSettings object, like [`UnityAssetViewer::build_settings`].
+    pub fn player_settings(&self) -> ReadResult<Option<TypeTreeObject>> {
+        self.first_object_of_class(ClassIDType::PlayerSettings as i32)
+    }
+
+    /// The AssetBundleManifest of the first loaded file holding one, listing
+    /// every bundle of the build and their dependencies. Read it with
+    /// [`crate::classes::asset_bundle_manifest::AssetBundleManifestObject`].
+    pub fn asset_bundle_manifest(&self) -> ReadResult<Option<TypeTreeObject>> {
+        self.first_object_of_class(ClassIDType::AssetBundleManifest as i32)
+    }
+
+    fn first_object_of_class(&self, class_id: i32) -> ReadResult<Option<TypeTreeObject>> {
+        self.iter_tt_objects(Some(class_id))
+            .next()
+            .map(|(_, object)| object)
+            .transpose()
+    }
+
+    /// Serialized file and path ids of objects of `class_id` whose container
+    /// name or `m_Name` contains `name_substring`, ignoring case. Names are
+    /// only read for layouts starting with `m_Name`. Either filter may be
+    /// left out.
+    pub fn find_objects(
+        &self,
+        class_id: Option<i32>,
+        name_substring: Option<&str>,
+    ) -> Vec<(i64, i64)> {
+        let needle = name_substring.map(str::to_lowercase);
+        let mut objects = Vec::new();
+        for (serialized_file_id, serialized_file) in &self.serialized_file_map {
+            for (path_id, obj) in serialized_file.get_object_map() {
+                if obj.is_removed() || class_id.is_some_and(|class_id| obj.class != class_id) {
+                    continue;
+                }
+                if let Some(needle) = &needle {
+                    let matches = |name: &str| name.to_lowercase().contains(needle.as_str());
+                    let container_match = self
+                        .get_container_name_by_serialized_file_id_and_path_id(
+                            *serialized_file_id,
+                            *path_id,
+                        )
+                        .is_some_and(|name| matches(name));
+                    if !container_match
+                        && !serialized_file
+                            .object_name(*path_id)
+                            .ok()
+                            .flatten()
+                            .is_some_and(|name| matches(&name))
+                    {
+                        continue;
                     }
                 }
-                self.container_name_maps
-                    .insert(serialized_file_id, name_map);
+                objects.push((*serialized_file_id, *path_id));
             }
         }
+        objects
+    }
 
-        for (path_id, obj) in serialized_file.get_object_map() {
-            if obj.class == ClassIDType::ResourceManager as i32 {
-                if let Ok(Some(resource_manager)) =
-                    serialized_file.get_tt_object_by_path_id(*path_id)
-                {
-                    if let Ok(containers) = <HashMap<String, TypeTreeObjectRef>>::try_cast_from(
-                        &resource_manager.into(),
-                        "/Base/m_Container/Array",
-                    ) {
-                        let mut name_map = HashMap::new();
-                        for (name, pptr) in containers {
-                            if let Ok(path_id) = PPtr::new(&pptr).get_path_id() {
-                                name_map.insert(path_id, name.clone());
-                            }
-
-                            if let Some(objs) = self.container_maps.get_mut(&name) {
-                                objs.push((serialized_file_id, pptr));
-                            } else {
-                                self.container_maps
-                                    .insert(name, vec![(serialized_file_id, pptr)]);
-                            }
-                        }
-                        self.container_name_maps
-                            .insert(serialized_file_id, name_map);
-                    }
+    /// Groups of byte-identical objects of the same class, as serialized
+    /// file and path ids. Objects are bucketed by
+    /// [`TypeTreeObject::content_hash`] of their raw bytes, then the bytes
+    /// within a bucket are compared so hash collisions are not reported.
+    /// Only groups of two or more are listed, each in id order.
+    pub fn find_duplicate_objects(&self) -> ReadResult<Vec<Vec<(i64, i64)>>> {
+        let raw_bytes = |(serialized_file_id, path_id): &(i64, i64)| {
+            self.serialized_file_map[serialized_file_id].get_object_raw_bytes(*path_id)
+        };
+        let mut buckets: BTreeMap<(i32, u64), Vec<(i64, i64)>> = BTreeMap::new();
+        for (serialized_file_id, serialized_file) in &self.serialized_file_map {
+            for (path_id, obj) in serialized_file.get_object_map() {
+                if obj.is_removed() {
+                    continue;
                 }
+                let Some(data) = serialized_file.get_object_raw_bytes(*path_id)? else {
+                    continue;
+                };
+                buckets
+                    .entry((obj.class, crate::type_tree::content_hash(&data)))
+                    .or_default()
+                    .push((*serialized_file_id, *path_id));
             }
         }
+        let mut groups = Vec::new();
+        for bucket in buckets.into_values().filter(|bucket| bucket.len() > 1) {
+            groups.extend(group_identical(bucket, raw_bytes)?);
+        }
+        Ok(groups)
+    }
 
-        self.serialized_file_map
-            .insert(serialized_file_id, serialized_file);
-        Ok(serialized_file_id)
+    /// The containers added, removed or changed in `other`, such as a later
+    /// build of the same game. Containers are matched by name and compared
+    /// by a hash of the fields of the objects they resolve to, in which a
+    /// PPtr counts by the name of the file it points into rather than its
+    /// `m_FileID`. An asset that moved to another bundle is then unchanged
+    /// as long as what it references is, even though its bytes differ.
+    /// Objects that cannot be read, such as ones in bundles that are not
+    /// loaded, compare as missing.
+    pub fn diff(&self, other: &UnityAssetViewer) -> ViewerDiff {
+        self.diff_containers(other, false)
     }
 
-    pub fn read_data_dir<P: AsRef<Path>>(&mut self, data_dir_path: P) -> ReadResult<()> {
-        for i in 0..u8::MAX {
-            let file_name = format!("level{i}");
-            if let Ok(file) = OpenOptions::new()
-                .read(true)
-                .open(data_dir_path.as_ref().join(&file_name))
-            {
-                let serialized_file_id = self.add_serialized_file(
-                    Box::new(BufReader::new(file)),
-                    Some(data_dir_path.as_ref().to_string_lossy().to_string()),
-                )?;
-                self.cab_maps.insert(file_name, serialized_file_id);
-            } else {
-                break;
-            }
+    /// [`Self::diff`], with the field changes of each changed container.
+    pub fn diff_with_fields(&self, other: &UnityAssetViewer) -> ViewerDiff {
+        self.diff_containers(other, true)
+    }
+
+    fn diff_containers(&self, other: &UnityAssetViewer, with_fields: bool) -> ViewerDiff {
+        let names: BTreeSet<&String> = self
+            .container_maps
+            .keys()
+            .chain(other.container_maps.keys())
+            .collect();
+        let mut containers = Vec::new();
+        for name in names {
+            let change = match (
+                self.container_maps.contains_key(name),
+                other.container_maps.contains_key(name),
+            ) {
+                (false, _) => ContainerChange::Added,
+                (_, false) => ContainerChange::Removed,
+                _ => {
+                    let old_objects = self.resolve_container_objects(name);
+                    let new_objects = other.resolve_container_objects(name);
+                    let hashes = |viewer: &UnityAssetViewer, objects: &[Option<TypeTreeObject>]| {
+                        objects
+                            .iter()
+                            .map(|object| object.as_ref().map(|object| viewer.diff_hash(object)))
+                            .collect::<Vec<_>>()
+                    };
+                    if hashes(self, &old_objects) == hashes(other, &new_objects) {
+                        continue;
+                    }
+                    let fields = match (old_objects.first(), new_objects.first()) {
+                        (Some(Some(old)), Some(Some(new))) if with_fields => {
+                            Some(old.diff_fields(new))
+                        }
+                        _ => None,
+                    };
+                    ContainerChange::Changed { fields }
+                }
+            };
+            containers.push(ContainerDiff {
+                name: name.clone(),
+                change,
+            });
         }
-        for i in 0..u8::MAX {
-            let file_name = format!("sharedassets{i}.assets");
-            if let Ok(file) = OpenOptions::new()
-                .read(true)
-                .open(data_dir_path.as_ref().join(&file_name))
-            {
-                let serialized_file_id = self.add_serialized_file(
-                    Box::new(BufReader::new(file)),
-                    Some(data_dir_path.as_ref().to_string_lossy().to_string()),
-                )?;
-                self.cab_maps.insert(file_name, serialized_file_id);
-            } else {
-                break;
-            }
+        ViewerDiff { containers }
+    }
+
+    /// The hash [`Self::diff`] compares objects by.
+    fn diff_hash(&self, object: &TypeTreeObject) -> u64 {
+        /// Collects the fields in an unambiguous encoding, PPtrs as the
+        /// name of the file they point into, empty for the object's own.
+        struct DiffEncoder<'a> {
+            externals: &'a [ExternalRef],
+            bytes: Vec<u8>,
         }
 
-        let file_names = [
-            "resources.assets",
-            "globalgamemanagers.assets",
-            "globalgamemanagers",
-        ];
-        for file_name in file_names {
-            if let Ok(file) = OpenOptions::new()
-                .read(true)
-                .open(data_dir_path.as_ref().join(file_name))
-            {
-                let serialized_file_id = self.add_serialized_file(
-                    Box::new(BufReader::new(file)),
-                    Some(data_dir_path.as_ref().to_string_lossy().to_string()),
-                )?;
-                self.cab_maps
-                    .insert(file_name.to_owned(), serialized_file_id);
+        impl TypeTreeVisitor for DiffEncoder<'_> {
+            fn enter_array(&mut self, _path: &str, _item_type: &str, len: usize) -> Visit {
+                self.bytes.push(b'a');
+                self.bytes.extend_from_slice(&(len as u64).to_le_bytes());
+                Visit::Continue
+            }
+
+            fn visit_value(&mut self, _path: &str, _type_name: &str, value: LeafValue) -> Visit {
+                match value {
+                    LeafValue::Bool(value) => self.bytes.extend([b'b', value as u8]),
+                    LeafValue::Int(value) => {
+                        self.bytes.push(b'i');
+                        self.bytes.extend_from_slice(&value.to_le_bytes());
+                    }
+                    LeafValue::UInt(value) => {
+                        self.bytes.push(b'u');
+                        self.bytes.extend_from_slice(&value.to_le_bytes());
+                    }
+                    LeafValue::Float(value) => {
+                        self.bytes.push(b'f');
+                        self.bytes.extend_from_slice(&value.to_bits().to_le_bytes());
+                    }
+                    LeafValue::String(value) => {
+                        self.bytes.push(b's');
+                        self.bytes
+                            .extend_from_slice(&(value.len() as u64).to_le_bytes());
+                        self.bytes.extend_from_slice(value.as_bytes());
+                    }
+                }
+                Visit::Continue
+            }
+
+            fn visit_pptr(
+                &mut self,
+                _path: &str,
+                _type_name: &str,
+                file_id: i64,
+                path_id: i64,
+            ) -> Visit {
+                let file = match file_id {
+                    0 => Cow::Borrowed(""),
+                    _ => self
+                        .externals
+                        .iter()
+                        .find(|external| external.file_id == file_id)
+                        .map_or(Cow::Owned(format!("#{file_id}")), |external| {
+                            Cow::Borrowed(external.name.as_str())
+                        }),
+                };
+                self.bytes.push(b'p');
+                self.bytes
+                    .extend_from_slice(&(file.len() as u64).to_le_bytes());
+                self.bytes.extend_from_slice(file.as_bytes());
+                self.bytes.extend_from_slice(&path_id.to_le_bytes());
+                Visit::Continue
             }
         }
-        Ok(())
+
+        let externals = self
+            .serialized_file_map
+            .get(&object.serialized_file_id)
+            .map(SerializedFile::external_references)
+            .unwrap_or_default();
+        let mut encoder = DiffEncoder {
+            externals: &externals,
+            bytes: Vec::new(),
+        };
+        object.visit(&mut encoder);
+        crate::type_tree::content_hash(&encoder.bytes)
     }
 
-    pub fn get_serialized_file_by_path(&self, path: &String) -> Option<&SerializedFile> {
-        if let Some(serialized_file_id) = self.cab_maps.get(path) {
-            if let Some(serialized_file) = self.serialized_file_map.get(serialized_file_id) {
-                return Some(serialized_file);
-            }
+    /// The object of each entry under the container key, `None` where it
+    /// cannot be read.
+    fn resolve_container_objects(&self, container_key: &str) -> Vec<Option<TypeTreeObject>> {
+        self.container_maps
+            .get(container_key)
+            .map_or(&[][..], Vec::as_slice)
+            .iter()
+            .map(|entry| self.deref(&entry.pptr).ok().flatten())
+            .collect()
+    }
+
+    fn container_key<'n>(&self, container_name: &'n str) -> Cow<'n, str> {
+        if self.normalize_container_names {
+            Cow::Owned(normalize_container_name(container_name))
+        } else {
+            Cow::Borrowed(container_name)
         }
-        None
     }
 
-    pub fn get_unity_fs_by_cab_path(&self, path: &String) -> Option<&UnityFS> {
-        if let Some(serialized_file_id) = self.cab_maps.get(path) {
-            if let Some(unity_fs_id) = self.serialized_file_to_unity_fs_map.get(serialized_file_id)
-            {
-                if let Some(unity_fs) = self.unity_fs_map.get(unity_fs_id) {
-                    return Some(unity_fs);
+    fn insert_container(&mut self, container_name: String, entry: ContainerEntry) {
+        if let Some(containers) = &mut self.visited_containers {
+            containers.push((container_name.clone(), entry.clone()));
+        }
+        let key = self.container_key(&container_name).into_owned();
+        self.normalized_container_keys
+            .entry(normalize_container_name(&key))
+            .or_insert_with(|| key.clone());
+        self.container_raw_name_maps
+            .entry(key.clone())
+            .or_insert(container_name);
+        self.container_maps.entry(key).or_default().push(entry);
+    }
+
+    /// The `m_PreloadTable` PPtrs an AssetBundle container entry depends on,
+    /// relative to the entry's serialized file like the entry's `pptr`.
+    pub fn get_container_preload_pptrs(
+        &self,
+        entry: &ContainerEntry,
+    ) -> ReadResult<Vec<TypeTreeObjectRef>> {
+        let (Some(preload_index), Some(preload_size)) = (entry.preload_index, entry.preload_size)
+        else {
+            return Ok(Vec::new());
+        };
+        let asset_bundle = self.container_asset_bundle(entry)?;
+        let preload_table =
+            <Vec<TypeTreeObjectRef>>::try_cast_from(&asset_bundle, "/Base/m_PreloadTable/Array")?;
+        let start = preload_index.max(0) as usize;
+        let end = start + preload_size.max(0) as usize;
+        preload_table
+            .get(start..end)
+            .map(<[TypeTreeObjectRef]>::to_vec)
+            .ok_or(Error::Other(format!(
+                "preload range {}..{} outside the {} entry preload table",
+                start,
+                end,
+                preload_table.len()
+            )))
+    }
+
+    /// The names of the bundles that must be loaded before the bundle of an
+    /// AssetBundle container entry, its AssetBundle's `m_Dependencies`.
+    /// Empty for ResourceManager entries.
+    pub fn get_container_bundle_dependencies(
+        &self,
+        entry: &ContainerEntry,
+    ) -> ReadResult<Vec<String>> {
+        if entry.preload_index.is_none() {
+            return Ok(Vec::new());
+        }
+        let asset_bundle = self.container_asset_bundle(entry)?;
+        <Vec<TypeTreeObjectRef>>::try_cast_from(&asset_bundle, "/Base/m_Dependencies/Array")?
+            .iter()
+            .map(|dependency| String::try_cast_from(dependency, "/Base"))
+            .collect()
+    }
+
+    fn container_asset_bundle(&self, entry: &ContainerEntry) -> ReadResult<TypeTreeObjectRef> {
+        Ok(self
+            .serialized_file_map
+            .get(&entry.serialized_file_id)
+            .ok_or(Error::SerializedFileNotFound)?
+            .get_tt_object_by_path_id(1)?
+            .ok_or(Error::Other("AssetBundle object not found".to_owned()))?
+            .into())
+    }
+
+    /// The `(serialized_file_id, path_id)` of every object in the preload
+    /// slices of the AssetBundle entries under a container name, which is
+    /// Unity's own list of what loading the asset loads. External PPtrs are
+    /// resolved through the entry's file `m_Externals`, failing with
+    /// [`Error::ExternalSerializedFileNotFound`] when the referenced file is
+    /// not loaded.
+    pub fn container_dependencies(&self, container_name: &str) -> ReadResult<Vec<(i64, i64)>> {
+        let mut dependencies = Vec::new();
+        for entry in self.container_entries(container_name) {
+            let serialized_file = self
+                .serialized_file_map
+                .get(&entry.serialized_file_id)
+                .ok_or(Error::SerializedFileNotFound)?;
+            for pptr in self.get_container_preload_pptrs(entry)? {
+                let pptr = PPtr::new(&pptr);
+                let serialized_file_id = self
+                    .resolve_file_id(serialized_file, pptr.get_file_id()?)
+                    .ok_or(Error::ExternalSerializedFileNotFound)?;
+                dependencies.push((serialized_file_id, pptr.get_path_id()?));
+            }
+        }
+        Ok(dependencies)
+    }
+
+    /// Every object indexed under a container name, empty if there is none.
+    pub fn container_entries(&self, container_name: &str) -> &[ContainerEntry] {
+        self.container_maps
+            .get(self.container_key(container_name).as_ref())
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Get the container name as stored in the file for a key of `container_maps`.
+    pub fn get_raw_container_name(&self, container_name: &str) -> Option<&String> {
+        self.container_raw_name_maps
+            .get(self.container_key(container_name).as_ref())
+    }
+
+    pub fn read_bundle_dir<P: AsRef<Path>>(&mut self, dir_path: P) -> ReadResult<()> {
+        self.read_dir_with_progress(dir_path, |_, _| {})?;
+        Ok(())
+    }
+
+    /// Same as [`Self::read_bundle_dir`], returning what the scan loaded and
+    /// which files it skipped.
+    pub fn read_dir_report<P: AsRef<Path>>(&mut self, dir_path: P) -> ReadResult<ScanReport> {
+        self.read_dir_with_progress(dir_path, |_, _| {})
+    }
+
+    /// Same as [`Self::read_bundle_dir`], calling `progress` with
+    /// `(bytes_done, bytes_total)` once before the first bundle and again
+    /// after each one.
+    ///
+    /// Progress is weighted by file size rather than file count: a pre-pass
+    /// sums the on-disk size of every bundle before any is opened. Resource
+    /// files are only registered, so they do not count towards the total.
+    pub fn read_dir_with_progress<P: AsRef<Path>, F: FnMut(u64, u64)>(
+        &mut self,
+        dir_path: P,
+        progress: F,
+    ) -> ReadResult<ScanReport> {
+        self.scan_dir(dir_path, progress, None)
+    }
+
+    /// Same as [`Self::read_dir_report`], showing `visitor` every serialized
+    /// file of a bundle once the bundle is read, and every container entry
+    /// once it is indexed: as each bundle is read, or after the last one when
+    /// indexing is deferred. Containers restored by [`Self::load_index`] have
+    /// no TypeTree to show and are not visited.
+    pub fn read_dir_with_visitor<P: AsRef<Path>>(
+        &mut self,
+        dir_path: P,
+        visitor: &mut dyn ScanVisitor,
+    ) -> ReadResult<ScanReport> {
+        self.scan_dir(dir_path, |_, _| {}, Some(visitor))
+    }
+
+    /// Same as [`Self::read_dir_report`], leaving the CABs compressed: only
+    /// the blocks holding a CAB's metadata are decompressed up front, and
+    /// the others as the objects in them are read. Opening a large folder
+    /// is then quick and light on memory, while the first read of an
+    /// object pays for its blocks. Container indexing reads the AssetBundle
+    /// objects, so disable it to skip those blocks as well. With the `mmap`
+    /// feature the bundles are memory-mapped rather than read through a
+    /// buffer.
+    pub fn read_dir_lazy<P: AsRef<Path>>(&mut self, dir_path: P) -> ReadResult<ScanReport> {
+        self.lazy_bundles = true;
+        let report = self.scan_dir(dir_path, |_, _| {}, None);
+        self.lazy_bundles = false;
+        report
+    }
+
+    fn scan_dir<P: AsRef<Path>, F: FnMut(u64, u64)>(
+        &mut self,
+        dir_path: P,
+        progress: F,
+        visitor: Option<&mut dyn ScanVisitor>,
+    ) -> ReadResult<ScanReport> {
+        self.visited_containers = visitor.is_some().then(Vec::new);
+        let report = self.scan_dir_with(dir_path, progress, visitor);
+        self.visited_containers = None;
+        report
+    }
+
+    fn scan_dir_with<P: AsRef<Path>, F: FnMut(u64, u64)>(
+        &mut self,
+        dir_path: P,
+        mut progress: F,
+        mut visitor: Option<&mut dyn ScanVisitor>,
+    ) -> ReadResult<ScanReport> {
+        let serialized_files_before = self.serialized_file_map.len();
+        let containers_before = self.container_entry_count();
+        #[cfg(feature = "timings")]
+        let bundles_before = self.unity_fs_count;
+        let mut report = ScanReport::default();
+        // Split parts are read as one bundle, by the path they join into.
+        let mut bundles: Vec<(PathBuf, u64, Vec<PathBuf>)> = Vec::new();
+        let mut split_parts: BTreeMap<PathBuf, (u64, Vec<(u32, PathBuf)>)> = BTreeMap::new();
+        for entry in WalkDir::new(dir_path).into_iter().flatten() {
+            if entry.file_type().is_file() {
+                if is_resource_file(entry.path()) {
+                    self.add_resource_file(entry.path());
+                    continue;
+                }
+                let size = entry.metadata().map_or(0, |metadata| metadata.len());
+                if let Some((joined_path, index)) = split_part_index(entry.path()) {
+                    let (joined_size, parts) = split_parts.entry(joined_path).or_default();
+                    *joined_size += size;
+                    parts.push((index, entry.into_path()));
+                    continue;
+                }
+                bundles.push((entry.into_path(), size, Vec::new()));
+            }
+        }
+        for (joined_path, (size, parts)) in split_parts {
+            match order_split_parts(&joined_path, parts) {
+                Ok(parts) => bundles.push((joined_path, size, parts)),
+                Err(err) => report.skipped.push((joined_path, err.to_string())),
+            }
+        }
+        let bytes_total = bundles.iter().map(|(_, size, _)| size).sum();
+        let mut bytes_done = 0;
+        progress(bytes_done, bytes_total);
+
+        for (path, size, parts) in bundles {
+            let mut file: Box<dyn UnityResource + Send + Sync> = if parts.is_empty() {
+                self.open_bundle(&path)?
+            } else {
+                Box::new(BufReader::new(SplitReader::open(&parts)?))
+            };
+            match identify_file(&mut file)? {
+                FileKind::UnityFS => {}
+                kind => {
+                    report.skipped.push((path, format!("not a bundle: {kind}")));
+                    bytes_done += size;
+                    progress(bytes_done, bytes_total);
+                    continue;
+                }
+            }
+
+            #[cfg(feature = "serde")]
+            let indexed_bundle = self.take_fresh_indexed_bundle(&path);
+            #[cfg(feature = "serde")]
+            let defer_container_index = self.defer_container_index;
+            #[cfg(feature = "serde")]
+            if indexed_bundle.is_some() {
+                self.defer_container_index = true;
+                self.defer_cab_reads = true;
+            }
+
+            let unity_fs_id = self.add_bundle_file(
+                file,
+                Some(path.parent().unwrap().to_string_lossy().to_string()),
+            );
+
+            #[cfg(feature = "serde")]
+            {
+                self.defer_container_index = defer_container_index;
+                self.defer_cab_reads = false;
+                if let (Ok(_), Some(indexed_bundle)) = (&unity_fs_id, indexed_bundle) {
+                    if !self.skip_container_index {
+                        self.apply_indexed_bundle(indexed_bundle);
+                    }
+                }
+            }
+
+            match unity_fs_id {
+                Ok(unity_fs_id) => {
+                    if self.close_bundle_files {
+                        let paths = if parts.is_empty() {
+                            vec![path.clone()]
+                        } else {
+                            parts
+                        };
+                        self.close_bundle_file(unity_fs_id, paths)?;
+                    }
+                    self.bundle_paths.insert(unity_fs_id, path);
+                    report.bundles_loaded += 1;
+                    if let Some(visitor) = visitor.as_deref_mut() {
+                        self.visit_bundle(unity_fs_id, visitor);
+                    }
+                }
+                Err(err) => report.skipped.push((path, err.to_string())),
+            }
+
+            bytes_done += size;
+            progress(bytes_done, bytes_total);
+        }
+        if self.defer_container_index && !self.skip_container_index {
+            self.build_container_index();
+        }
+        if let Some(visitor) = visitor {
+            self.visit_containers(visitor);
+        }
+        report.serialized_files = self
+            .serialized_file_map
+            .len()
+            .saturating_sub(serialized_files_before);
+        report.containers_indexed = self
+            .container_entry_count()
+            .saturating_sub(containers_before);
+        #[cfg(feature = "timings")]
+        {
+            report.timings = (bundles_before..self.unity_fs_count)
+                .filter_map(|unity_fs_id| self.bundle_timings(unity_fs_id))
+                .collect();
+        }
+        Ok(report)
+    }
+
+    fn visit_bundle(&mut self, unity_fs_id: i64, visitor: &mut dyn ScanVisitor) {
+        if let Some(unity_fs) = self.unity_fs_map.get(&unity_fs_id) {
+            for cab_path in unity_fs.cab_paths() {
+                let serialized_file = self.get_serialized_file_id_by_cab_name(cab_path).and_then(
+                    |serialized_file_id| self.serialized_file_map.get(serialized_file_id),
+                );
+                if let Some(serialized_file) = serialized_file {
+                    visitor.visit_serialized_file(cab_path, serialized_file);
+                }
+            }
+        }
+        self.visit_containers(visitor);
+    }
+
+    fn visit_containers(&mut self, visitor: &mut dyn ScanVisitor) {
+        if let Some(containers) = &mut self.visited_containers {
+            for (name, entry) in containers.drain(..) {
+                visitor.visit_container(&name, &entry);
+            }
+        }
+    }
+
+    fn container_entry_count(&self) -> usize {
+        self.container_maps.values().map(Vec::len).sum()
+    }
+
+    /// Register a `.resource`/`.resS` stream file so StreamingInfo paths
+    /// naming it resolve even when it is not next to the referencing file.
+    pub fn add_resource_file<P: AsRef<Path>>(&mut self, path: P) {
+        let path = path.as_ref();
+        if let Some(file_name) = path.file_name() {
+            self.resource_map.insert(
+                normalize_external_name(&file_name.to_string_lossy()),
+                path.to_path_buf(),
+            );
+        }
+    }
+
+    /// Read a bundle split into `<path>.split0`, `<path>.split1`, ... files,
+    /// given the path they join into or any one part. Every part from 0 up
+    /// to the last one in the directory must be present.
+    /// [`Self::read_bundle_dir`] joins split parts on its own.
+    pub fn read_split_bundle<P: AsRef<Path>>(&mut self, path: P) -> ReadResult<i64> {
+        let path = path.as_ref();
+        let joined_path = split_part_index(path)
+            .map_or_else(|| path.to_path_buf(), |(joined_path, _)| joined_path);
+        let dir = match joined_path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let mut parts = Vec::new();
+        for entry in std::fs::read_dir(dir)?.flatten() {
+            if let Some((part_joined_path, index)) = split_part_index(&entry.path()) {
+                if part_joined_path.file_name() == joined_path.file_name() {
+                    parts.push((index, entry.path()));
+                }
+            }
+        }
+        if parts.is_empty() {
+            return Err(Error::Other(format!(
+                "no split parts of {} found",
+                joined_path.display()
+            )));
+        }
+        let parts = order_split_parts(&joined_path, parts)?;
+        let unity_fs_id = self.add_bundle_file(
+            Box::new(BufReader::new(SplitReader::open(&parts)?)),
+            Some(dir.to_string_lossy().to_string()),
+        )?;
+        if self.close_bundle_files {
+            self.close_bundle_file(unity_fs_id, parts)?;
+        }
+        self.bundle_paths.insert(unity_fs_id, joined_path);
+        Ok(unity_fs_id)
+    }
+
+    /// Read a loaded bundle through a [`ReopeningReader`] of `paths`,
+    /// closing the file it was read from.
+    /// Bundles read block by block are mapped with the `mmap` feature, so
+    /// that the blocks read are not buffered twice.
+    fn open_bundle(&self, path: &Path) -> std::io::Result<Box<dyn UnityResource + Send + Sync>> {
+        #[cfg(all(feature = "mmap", unix))]
+        if self.lazy_bundles {
+            return Ok(Box::new(crate::split::MmapReader::open(path)?));
+        }
+        Ok(Box::new(BufReader::new(
+            OpenOptions::new().read(true).open(path)?,
+        )))
+    }
+
+    fn close_bundle_file(&self, unity_fs_id: i64, paths: Vec<PathBuf>) -> ReadResult<()> {
+        if let Some(unity_fs) = self.unity_fs_map.get(&unity_fs_id) {
+            unity_fs.replace_reader(Box::new(ReopeningReader::new(paths)?))?;
+        }
+        Ok(())
+    }
+
+    pub fn add_bundle_file(
+        &mut self,
+        bundle_file_reader: Box<dyn UnityResource + Send + Sync>,
+        resource_search_path: Option<String>,
+    ) -> ReadResult<i64> {
+        self.add_bundle_file_as(bundle_file_reader, resource_search_path, None)
+    }
+
+    /// Same as [`Self::add_bundle_file`], reading every CAB of the bundle
+    /// as `endianness` whatever its own endianness flag says. See
+    /// [`SerializedFile::read_with_endianness`].
+    pub fn add_bundle_with_endianness(
+        &mut self,
+        bundle_file_reader: Box<dyn UnityResource + Send + Sync>,
+        resource_search_path: Option<String>,
+        endianness: binrw::Endian,
+    ) -> ReadResult<i64> {
+        self.add_bundle_file_as(bundle_file_reader, resource_search_path, Some(endianness))
+    }
+
+    fn add_bundle_file_as(
+        &mut self,
+        bundle_file_reader: Box<dyn UnityResource + Send + Sync>,
+        resource_search_path: Option<String>,
+        endianness: Option<binrw::Endian>,
+    ) -> ReadResult<i64> {
+        let (unity_fs, mut _decompress) =
+            timed(|| UnityFS::read(bundle_file_reader, resource_search_path));
+        let unity_fs = unity_fs?;
+        let unity_fs_id = self.unity_fs_count;
+        self.unity_fs_count += 1;
+        #[cfg(feature = "serde")]
+        let defer_cab_reads = self.defer_cab_reads;
+        #[cfg(not(feature = "serde"))]
+        let defer_cab_reads = false;
+        for cab_path in unity_fs.cab_paths() {
+            #[cfg(feature = "spill")]
+            let cab_size = unity_fs
+                .peek_directory()
+                .iter()
+                .find(|node| node.path() == *cab_path)
+                .map_or(0, |node| node.size() as u64);
+            let (cab_buff_reader, elapsed) =
+                timed(|| -> ReadResult<Box<dyn UnityResource + Send + Sync>> {
+                    if self.lazy_bundles || defer_cab_reads {
+                        let node = unity_fs.get_file_reader_by_path(cab_path).ok_or_else(|| {
+                            Error::Other(format!("cannot find {cab_path} in the bundle"))
+                        })?;
+                        return Ok(Box::new(BufReader::new(node)));
+                    }
+                    #[cfg(feature = "spill")]
+                    if self
+                        .spill_threshold_bytes
+                        .is_some_and(|threshold| cab_size > threshold)
+                    {
+                        let mut file = tempfile::tempfile()?;
+                        unity_fs.write_file_by_path(cab_path, &mut file)?;
+                        file.seek(SeekFrom::Start(0))?;
+                        return Ok(Box::new(BufReader::new(file)));
+                    }
+                    Ok(Box::new(Cursor::new(
+                        unity_fs.get_file_data_by_path(cab_path)?,
+                    )))
+                });
+            _decompress += elapsed;
+            let cab_buff_reader = cab_buff_reader?;
+            // let cab_buff_reader = Box::new(BufReader::new(
+            //     unity_fs
+            //         .get_file_reader_by_path(&cab_path)
+            //         .ok_or(Error::Other("can not get cab reader".to_owned()))?,
+            // ));
+
+            let serialized_file_id = if defer_cab_reads {
+                self.add_deferred_serialized_file(cab_buff_reader)
+            } else {
+                self.add_serialized_file_as(cab_buff_reader, None, endianness)?
+            };
+            self.serialized_file_to_unity_fs_map
+                .insert(serialized_file_id, unity_fs_id);
+            self.cab_maps
+                .insert(normalize_external_name(cab_path), serialized_file_id);
+        }
+        #[cfg(feature = "timings")]
+        self.record_timing(TimingPhase::Decompress, unity_fs_id, _decompress);
+        self.unity_fs_map.insert(unity_fs_id, unity_fs);
+        Ok(unity_fs_id)
+    }
+
+    pub fn add_serialized_file(
+        &mut self,
+        serialized_file_reader: Box<dyn UnityResource + Send + Sync>,
+        resource_search_path: Option<String>,
+    ) -> ReadResult<i64> {
+        self.add_serialized_file_as(serialized_file_reader, resource_search_path, None)
+    }
+
+    /// Same as [`Self::add_serialized_file`], see
+    /// [`Self::add_bundle_with_endianness`].
+    pub fn add_serialized_file_with_endianness(
+        &mut self,
+        serialized_file_reader: Box<dyn UnityResource + Send + Sync>,
+        resource_search_path: Option<String>,
+        endianness: binrw::Endian,
+    ) -> ReadResult<i64> {
+        self.add_serialized_file_as(
+            serialized_file_reader,
+            resource_search_path,
+            Some(endianness),
+        )
+    }
+
+    fn add_serialized_file_as(
+        &mut self,
+        serialized_file_reader: Box<dyn UnityResource + Send + Sync>,
+        resource_search_path: Option<String>,
+        endianness: Option<binrw::Endian>,
+    ) -> ReadResult<i64> {
+        let serialized_file_id = self.serialized_file_count;
+        self.serialized_file_count += 1;
+
+        let (serialized_file, _elapsed) = timed(|| match endianness {
+            Some(endianness) => SerializedFile::read_with_endianness(
+                serialized_file_reader,
+                serialized_file_id,
+                resource_search_path,
+                endianness,
+            ),
+            None => SerializedFile::read(
+                serialized_file_reader,
+                serialized_file_id,
+                resource_search_path,
+            ),
+        });
+        let mut serialized_file = serialized_file?;
+        serialized_file.set_keep_raw_bytes(self.keep_raw_bytes);
+        #[cfg(feature = "timings")]
+        self.record_timing(TimingPhase::Metadata, serialized_file_id, _elapsed);
+        if !self.defer_container_index && !self.skip_container_index {
+            let (containers, _elapsed) = timed(|| read_containers(&serialized_file));
+            #[cfg(feature = "timings")]
+            self.record_timing(TimingPhase::Objects, serialized_file_id, _elapsed);
+            self.index_containers(serialized_file_id, containers);
+        }
+
+        self.serialized_file_map
+            .insert(serialized_file_id, serialized_file);
+        Ok(serialized_file_id)
+    }
+
+    /// Add a CAB to be read on first use, see [`SerializedFile::read_deferred`].
+    /// Its containers are not indexed.
+    fn add_deferred_serialized_file(
+        &mut self,
+        serialized_file_reader: Box<dyn UnityResource + Send + Sync>,
+    ) -> i64 {
+        let serialized_file_id = self.serialized_file_count;
+        self.serialized_file_count += 1;
+        let mut serialized_file =
+            SerializedFile::read_deferred(serialized_file_reader, serialized_file_id, None);
+        serialized_file.set_keep_raw_bytes(self.keep_raw_bytes);
+        self.serialized_file_map
+            .insert(serialized_file_id, serialized_file);
+        serialized_file_id
+    }
+
+    /// Skip container indexing in [`Self::add_serialized_file`], leaving it to
+    /// [`Self::build_container_index`]. The `read_*_dir` methods build the
+    /// index once all files are read.
+    pub fn set_defer_container_index(&mut self, defer: bool) {
+        self.defer_container_index = defer;
+    }
+
+    /// Whether reading files fills `container_maps`, true by default. Scans
+    /// that only extract objects by path id can turn it off to skip parsing
+    /// every AssetBundle and ResourceManager; the by-name lookups then find
+    /// nothing until [`Self::build_container_index`] is called.
+    pub fn set_index_containers(&mut self, index: bool) {
+        self.skip_container_index = !index;
+    }
+
+    /// Rebuild `container_maps` and the container names from every loaded
+    /// serialized file, reading the files in parallel when the `parallel`
+    /// feature is enabled. Entries keep the order of the serialized file ids.
+    pub fn build_container_index(&mut self) {
+        #[cfg(feature = "parallel")]
+        let containers: Vec<_> = self
+            .serialized_file_map
+            .par_iter_mut()
+            .map(|(serialized_file_id, serialized_file)| {
+                let (containers, elapsed) = timed(|| read_containers(serialized_file));
+                (*serialized_file_id, containers, elapsed)
+            })
+            .collect();
+        #[cfg(not(feature = "parallel"))]
+        let containers: Vec<_> = self
+            .serialized_file_map
+            .iter()
+            .map(|(serialized_file_id, serialized_file)| {
+                let (containers, elapsed) = timed(|| read_containers(serialized_file));
+                (*serialized_file_id, containers, elapsed)
+            })
+            .collect();
+
+        self.container_maps.clear();
+        self.container_name_maps.clear();
+        self.container_raw_name_maps.clear();
+        self.normalized_container_keys.clear();
+        self.asset_bundle_names.clear();
+        for (serialized_file_id, containers, _elapsed) in containers {
+            #[cfg(feature = "timings")]
+            self.record_timing(TimingPhase::Objects, serialized_file_id, _elapsed);
+            self.index_containers(serialized_file_id, containers);
+        }
+    }
+
+    /// Call `hook` with the time taken by each phase of reading files, as
+    /// each one finishes. [`ScanReport::timings`] has the same times summed
+    /// per bundle.
+    #[cfg(feature = "timings")]
+    pub fn set_timing_hook(
+        &mut self,
+        hook: impl FnMut(TimingPhase, Duration) + Send + Sync + 'static,
+    ) {
+        self.timing_hook = Some(Box::new(hook));
+    }
+
+    #[cfg(feature = "timings")]
+    fn record_timing(&mut self, phase: TimingPhase, id: i64, elapsed: Duration) {
+        *self.timings.entry((phase, id)).or_default() += elapsed;
+        if let Some(hook) = &mut self.timing_hook {
+            hook(phase, elapsed);
+        }
+    }
+
+    /// Everything timed so far for a bundle read from a directory, including
+    /// container indexes rebuilt after it was read.
+    #[cfg(feature = "timings")]
+    fn bundle_timings(&self, unity_fs_id: i64) -> Option<BundleTimings> {
+        let timing = |phase, id| self.timings.get(&(phase, id)).copied().unwrap_or_default();
+        let mut timings = BundleTimings {
+            path: self.bundle_paths.get(&unity_fs_id)?.clone(),
+            decompress: timing(TimingPhase::Decompress, unity_fs_id),
+            ..Default::default()
+        };
+        for (serialized_file_id, _) in self
+            .serialized_file_to_unity_fs_map
+            .iter()
+            .filter(|(_, id)| **id == unity_fs_id)
+        {
+            timings.metadata += timing(TimingPhase::Metadata, *serialized_file_id);
+            timings.objects += timing(TimingPhase::Objects, *serialized_file_id);
+        }
+        Some(timings)
+    }
+
+    fn index_containers(&mut self, serialized_file_id: i64, containers: FileContainers) {
+        if let Some(asset_bundle_name) = containers.asset_bundle_name {
+            self.asset_bundle_names
+                .insert(asset_bundle_name.to_ascii_lowercase(), serialized_file_id);
+        }
+        if containers.entries.is_empty() {
+            return;
+        }
+        let mut name_map = HashMap::new();
+        for (name, entry) in containers.entries {
+            name_map.insert(entry.pptr.path_id, name.clone());
+            self.insert_container(name, entry);
+        }
+        self.container_name_maps
+            .insert(serialized_file_id, name_map);
+    }
+
+    pub fn read_data_dir<P: AsRef<Path>>(&mut self, data_dir_path: P) -> ReadResult<()> {
+        if let Ok(entries) = std::fs::read_dir(data_dir_path.as_ref()) {
+            for entry in entries.flatten() {
+                if is_resource_file(&entry.path()) {
+                    self.add_resource_file(entry.path());
+                }
+            }
+        }
+        for i in 0..u8::MAX {
+            let file_name = format!("level{i}");
+            if let Ok(file) = OpenOptions::new()
+                .read(true)
+                .open(data_dir_path.as_ref().join(&file_name))
+            {
+                let serialized_file_id = self.add_serialized_file(
+                    Box::new(BufReader::new(file)),
+                    Some(data_dir_path.as_ref().to_string_lossy().to_string()),
+                )?;
+                self.cab_maps.insert(file_name, serialized_file_id);
+            } else {
+                break;
+            }
+        }
+        for i in 0..u8::MAX {
+            let file_name = format!("sharedassets{i}.assets");
+            if let Ok(file) = OpenOptions::new()
+                .read(true)
+                .open(data_dir_path.as_ref().join(&file_name))
+            {
+                let serialized_file_id = self.add_serialized_file(
+                    Box::new(BufReader::new(file)),
+                    Some(data_dir_path.as_ref().to_string_lossy().to_string()),
+                )?;
+                self.cab_maps.insert(file_name, serialized_file_id);
+            } else {
+                break;
+            }
+        }
+
+        let file_names = [
+            "resources.assets",
+            "globalgamemanagers.assets",
+            "globalgamemanagers",
+        ];
+        for file_name in file_names {
+            if let Ok(file) = OpenOptions::new()
+                .read(true)
+                .open(data_dir_path.as_ref().join(file_name))
+            {
+                let serialized_file_id = self.add_serialized_file(
+                    Box::new(BufReader::new(file)),
+                    Some(data_dir_path.as_ref().to_string_lossy().to_string()),
+                )?;
+                self.cab_maps
+                    .insert(file_name.to_owned(), serialized_file_id);
+            }
+        }
+        if self.defer_container_index && !self.skip_container_index {
+            self.build_container_index();
+        }
+        Ok(())
+    }
+
+    /// Names of every loaded CAB and serialized file, sorted.
+    pub fn cab_names(&self) -> Vec<&String> {
+        let mut names: Vec<&String> = self.cab_maps.keys().collect();
+        names.sort();
+        names
+    }
+
+    /// Every named loaded file with its name and id, sorted by name.
+    pub fn serialized_files(&self) -> impl Iterator<Item = (&String, i64, &SerializedFile)> {
+        let mut files: Vec<(&String, i64, &SerializedFile)> = self
+            .cab_maps
+            .iter()
+            .filter_map(|(name, serialized_file_id)| {
+                self.serialized_file_map
+                    .get(serialized_file_id)
+                    .map(|serialized_file| (name, *serialized_file_id, serialized_file))
+            })
+            .collect();
+        files.sort_by(|a, b| a.0.cmp(b.0));
+        files.into_iter()
+    }
+
+    /// The external references of every named loaded file, resolved against
+    /// the others. References to files that are not loaded become
+    /// [`DependencyGraph::unresolved`] nodes.
+    pub fn dependency_graph(&self) -> DependencyGraph {
+        let mut names: HashMap<i64, &String> = HashMap::new();
+        for (name, serialized_file_id) in &self.cab_maps {
+            names
+                .entry(*serialized_file_id)
+                .and_modify(|known| *known = (*known).min(name))
+                .or_insert(name);
+        }
+        let mut graph = DependencyGraph::default();
+        for (serialized_file_id, name) in &names {
+            let Some(serialized_file) = self.serialized_file_map.get(serialized_file_id) else {
+                continue;
+            };
+            graph.loaded.insert((*name).clone());
+            for external in serialized_file.external_references() {
+                let dependency = match self
+                    .resolve_external_reference(&external)
+                    .and_then(|id| names.get(&id))
+                {
+                    Some(dependency) => (*dependency).clone(),
+                    None => {
+                        graph.unresolved.insert(external.name.clone());
+                        external.name
+                    }
+                };
+                graph.edges.insert(((*name).clone(), dependency));
+            }
+        }
+        graph
+    }
+
+    pub fn object_count(&self, cab_name: &str) -> Option<usize> {
+        self.get_serialized_file_by_path(cab_name)
+            .map(|serialized_file| serialized_file.get_object_map().len())
+    }
+
+    /// Whether any object of the CAB is named by an AssetBundle or
+    /// ResourceManager container. Scenes and loose assets have none.
+    pub fn has_container_names(&self, cab_name: &str) -> bool {
+        self.get_serialized_file_id_by_cab_name(cab_name)
+            .and_then(|serialized_file_id| self.container_name_maps.get(serialized_file_id))
+            .is_some_and(|name_map| !name_map.is_empty())
+    }
+
+    fn get_serialized_file_id_by_cab_name(&self, cab_name: &str) -> Option<&i64> {
+        self.cab_maps
+            .get(cab_name)
+            .or_else(|| self.cab_maps.get(&normalize_external_name(cab_name)))
+    }
+
+    pub fn get_serialized_file_by_path(&self, path: &str) -> Option<&SerializedFile> {
+        if let Some(serialized_file_id) = self.get_serialized_file_id_by_cab_name(path) {
+            if let Some(serialized_file) = self.serialized_file_map.get(serialized_file_id) {
+                return Some(serialized_file);
+            }
+        }
+        None
+    }
+
+    /// Read an object of a loaded CAB. It keeps the file's id, so its PPtrs
+    /// resolve against this viewer.
+    pub fn get_object(&self, cab_name: &str, path_id: i64) -> ReadResult<Option<TypeTreeObject>> {
+        self.get_serialized_file_by_path(cab_name)
+            .ok_or(Error::SerializedFileNotFound)?
+            .get_tt_object_by_path_id(path_id)
+    }
+
+    /// Read the object a handle refers to, `None` if its file no longer has it.
+    pub fn resolve(&self, handle: AssetHandle) -> ReadResult<Option<TypeTreeObject>> {
+        self.serialized_file_map
+            .get(&handle.serialized_file_id)
+            .ok_or(Error::SerializedFileNotFound)?
+            .get_tt_object_by_path_id(handle.path_id)
+    }
+
+    /// Every object needed to fully materialize an object, found by
+    /// following its PPtrs, and theirs in turn, across the loaded files. The
+    /// object itself is included. Null PPtrs and objects in files that are
+    /// not loaded, such as Unity's builtin resources, are left out.
+    pub fn dependency_closure(
+        &self,
+        serialized_file_id: i64,
+        path_id: i64,
+    ) -> ReadResult<HashSet<(i64, i64)>> {
+        #[derive(Default)]
+        struct PPtrCollector {
+            pptrs: Vec<(i64, i64)>,
+        }
+
+        impl TypeTreeVisitor for PPtrCollector {
+            fn visit_pptr(
+                &mut self,
+                _path: &str,
+                _type_name: &str,
+                file_id: i64,
+                path_id: i64,
+            ) -> Visit {
+                if path_id != 0 {
+                    self.pptrs.push((file_id, path_id));
+                }
+                Visit::Continue
+            }
+        }
+
+        let mut closure = HashSet::new();
+        let mut pending = vec![(serialized_file_id, path_id)];
+        while let Some((serialized_file_id, path_id)) = pending.pop() {
+            if !closure.insert((serialized_file_id, path_id)) {
+                continue;
+            }
+            let serialized_file = self
+                .serialized_file_map
+                .get(&serialized_file_id)
+                .ok_or(Error::SerializedFileNotFound)?;
+            let Some(obj) = serialized_file.get_tt_object_by_path_id(path_id)? else {
+                closure.remove(&(serialized_file_id, path_id));
+                continue;
+            };
+            let mut collector = PPtrCollector::default();
+            obj.visit(&mut collector);
+            for (file_id, path_id) in collector.pptrs {
+                if let Some(target_file_id) = self.resolve_file_id(serialized_file, file_id) {
+                    if !closure.contains(&(target_file_id, path_id)) {
+                        pending.push((target_file_id, path_id));
+                    }
+                }
+            }
+        }
+        Ok(closure)
+    }
+
+    /// A self-contained bundle holding the object under `container_name` and
+    /// its [`Self::dependency_closure`] in one CAB, with an AssetBundle that
+    /// lists them all as the container's preload. `target_version` is the
+    /// Unity version the bundle is stamped with, and picks the serialized
+    /// file format: 22 from 2020.1, 17 before.
+    ///
+    /// PPtrs between the objects are rewritten to point into the new CAB,
+    /// keeping path ids where they do not clash. PPtrs into files that are
+    /// not loaded, such as Unity's builtin resources, stay external, and
+    /// those to objects missing from loaded files are nulled. Objects keep
+    /// the TypeTrees they were read with; managed reference types are not
+    /// carried over.
+    pub fn repack_closure(
+        &self,
+        container_name: &str,
+        target_version: &str,
+    ) -> ReadResult<Vec<u8>> {
+        let format = repack_format(target_version)?;
+        let entry = self
+            .container_entries(container_name)
+            .first()
+            .ok_or_else(|| Error::Other(format!("no container named {container_name}")))?;
+        let owner = self
+            .serialized_file_map
+            .get(&entry.pptr.serialized_file_id)
+            .ok_or(Error::SerializedFileNotFound)?;
+        let root = (
+            self.resolve_file_id(owner, entry.pptr.file_id)
+                .ok_or(Error::ExternalSerializedFileNotFound)?,
+            entry.pptr.path_id,
+        );
+        let mut objects: Vec<(i64, i64)> = self
+            .dependency_closure(root.0, root.1)?
+            .into_iter()
+            .collect();
+        if !objects.contains(&root) {
+            return Err(Error::Other(format!(
+                "the object of {container_name} cannot be read"
+            )));
+        }
+        objects.sort();
+        let endian = self.serialized_file_map[&root.0].get_endianess().clone();
+        let big_endian = endian == Endian::Big;
+
+        // Path id 1 is the AssetBundle's, as in bundles Unity builds, and
+        // the asset keeps its own.
+        let mut used_path_ids = HashSet::from([1]);
+        let mut path_ids = HashMap::new();
+        for object in std::iter::once(&root).chain(&objects) {
+            if used_path_ids.insert(object.1) {
+                path_ids.insert(*object, object.1);
+            }
+        }
+        let mut next_path_id = used_path_ids.iter().max().copied().unwrap_or(1) + 1;
+        for object in &objects {
+            path_ids.entry(*object).or_insert_with(|| {
+                next_path_id += 1;
+                next_path_id - 1
+            });
+        }
+
+        let mut externals: Vec<FileIdentifier> = Vec::new();
+        let mut script_types: Vec<i64> = Vec::new();
+        let mut types: Vec<WrittenType> = Vec::new();
+        let mut type_indices: HashMap<(i64, usize, i16), usize> = HashMap::new();
+        let mut written = Vec::with_capacity(objects.len() + 1);
+        for object in &objects {
+            let serialized_file = &self.serialized_file_map[&object.0];
+            if *serialized_file.get_endianess() != endian {
+                return Err(Error::Other(
+                    "objects of both byte orders cannot be repacked into one file".to_owned(),
+                ));
+            }
+            let obj = &serialized_file.get_object_map()[&object.1];
+            let tt_object = serialized_file
+                .get_tt_object_by_path_id(object.1)?
+                .ok_or_else(|| Error::Other(format!("no TypeTree for class {}", obj.class)))?;
+            let mut data = serialized_file
+                .get_object_raw_bytes(object.1)?
+                .unwrap_or_default();
+            let mut script = None;
+            for slot in tt_object.pptr_slots() {
+                let (file_id, path_id) = if slot.path_id == 0 {
+                    (0, 0)
+                } else if let Some(target_file_id) =
+                    self.resolve_file_id(serialized_file, slot.file_id)
+                {
+                    path_ids
+                        .get(&(target_file_id, slot.path_id))
+                        .map_or((0, 0), |path_id| (0, *path_id))
+                } else {
+                    let external = usize::try_from(slot.file_id - 1)
+                        .ok()
+                        .and_then(|index| serialized_file.get_externals().get(index).cloned());
+                    match external {
+                        Some(external) => {
+                            let index = match externals
+                                .iter()
+                                .position(|known| known.path == external.path)
+                            {
+                                Some(index) => index,
+                                None => {
+                                    externals.push(external);
+                                    externals.len() - 1
+                                }
+                            };
+                            (index as i64 + 1, slot.path_id)
+                        }
+                        None => (0, 0),
+                    }
+                };
+                write_int(&mut data[slot.file_id_range], file_id, big_endian);
+                write_int(&mut data[slot.path_id_range], path_id, big_endian);
+                if slot.path == "/Base/m_Script" && file_id == 0 && path_id != 0 {
+                    script = Some(path_id);
+                }
+            }
+
+            let script_type_index = match script.filter(|_| obj.class == 114) {
+                Some(script) => match script_types.iter().position(|known| *known == script) {
+                    Some(index) => index as i16,
+                    None => {
+                        script_types.push(script);
+                        script_types.len() as i16 - 1
+                    }
+                },
+                None => -1,
+            };
+            let type_index = match type_indices.get(&(object.0, obj.type_id, script_type_index)) {
+                Some(type_index) => *type_index,
+                None => {
+                    let fields = serialized_file.type_fields(obj).ok_or_else(|| {
+                        Error::Other(format!("no TypeTree for class {}", obj.class))
+                    })?;
+                    types.push(WrittenType {
+                        class_id: obj.class,
+                        script_type_index,
+                        type_tree_hash: serialized_file
+                            .type_tree_hash(obj.type_id)
+                            .unwrap_or_default(),
+                        fields,
+                    });
+                    type_indices
+                        .insert((object.0, obj.type_id, script_type_index), types.len() - 1);
+                    types.len() - 1
+                }
+            };
+            written.push(WrittenObject {
+                path_id: path_ids[object],
+                type_index,
+                data,
+            });
+        }
+
+        let bundle_name = Path::new(container_name).file_stem().map_or_else(
+            || container_name.to_lowercase(),
+            |stem| stem.to_string_lossy().to_lowercase(),
+        );
+        let mut preload_table: Vec<i64> = objects.iter().map(|object| path_ids[object]).collect();
+        preload_table.sort();
+        types.push(WrittenType {
+            class_id: ClassIDType::AssetBundle as i32,
+            script_type_index: -1,
+            type_tree_hash: [0; 16],
+            fields: repacked_asset_bundle_fields(),
+        });
+        written.push(WrittenObject {
+            path_id: 1,
+            type_index: types.len() - 1,
+            data: repacked_asset_bundle_data(
+                big_endian,
+                &bundle_name,
+                container_name,
+                &preload_table,
+                path_ids[&root],
+            ),
+        });
+        written.sort_by_key(|object| object.path_id);
+
+        let cab = SerializedFileBuilder {
+            format,
+            big_endian,
+            unity_version: target_version,
+            target_platform: self.serialized_file_map[&root.0]
+                .get_target_platform()
+                .clone() as i32,
+            types,
+            objects: written,
+            script_types,
+            externals,
+        }
+        .build();
+        let name_hash = content_hash(container_name.as_bytes());
+        let cab_name = format!(
+            "CAB-{name_hash:016x}{:016x}",
+            content_hash(&name_hash.to_le_bytes())
+        );
+        Ok(write_bundle(target_version, &[(&cab_name, &cab)]))
+    }
+
+    /// The id of the loaded serialized file an external reference names,
+    /// or `None` when it is not loaded.
+    pub fn resolve_external_reference(&self, external: &ExternalRef) -> Option<i64> {
+        self.get_serialized_file_id_by_cab_name(&external.name)
+            .copied()
+    }
+
+    /// Read the object a PPtr points to, from its own file or, for external
+    /// PPtrs, from the loaded file its `m_FileID` names. Null PPtrs give
+    /// `Ok(None)`, external ones into files the viewer has not loaded fail
+    /// with [`Error::ExternalSerializedFileNotFound`].
+    pub fn deref<P: PPtrObject + ?Sized>(&self, pptr: &P) -> ReadResult<Option<TypeTreeObject>> {
+        pptr.get_type_tree_object_in_view(self)
+    }
+
+    /// The loaded file a PPtr `m_FileID` of `serialized_file` refers to.
+    pub(crate) fn resolve_file_id(
+        &self,
+        serialized_file: &SerializedFile,
+        file_id: i64,
+    ) -> Option<i64> {
+        if file_id == 0 {
+            return Some(serialized_file.get_serialized_file_id());
+        }
+        let external = serialized_file
+            .get_externals()
+            .get(usize::try_from(file_id - 1).ok()?)?
+            .path
+            .to_string();
+        self.get_serialized_file_id_by_cab_name(&normalize_external_name(&external))
+            .copied()
+    }
+
+    /// Handles to every object of a loaded CAB, without reading any of them.
+    pub fn handles(&self, cab_name: &str) -> Vec<AssetHandle> {
+        let Some(serialized_file) = self.get_serialized_file_by_path(cab_name) else {
+            return Vec::new();
+        };
+        serialized_file
+            .get_object_map()
+            .keys()
+            .map(|path_id| AssetHandle {
+                serialized_file_id: serialized_file.get_serialized_file_id(),
+                path_id: *path_id,
+            })
+            .collect()
+    }
+
+    /// Whether the file is a scene, going by `m_IsStreamedSceneAssetBundle`
+    /// of its AssetBundle or else by the `level<N>`/`sharedassets` file names.
+    pub fn is_scene_file(&self, cab_name: &str) -> bool {
+        if let Some(serialized_file) = self.get_serialized_file_by_path(cab_name) {
+            let is_asset_bundle = serialized_file
+                .get_object_map()
+                .get(&1)
+                .is_some_and(|obj| obj.class == ClassIDType::AssetBundle as i32);
+            if is_asset_bundle {
+                if let Ok(Some(asset_bundle)) = serialized_file.get_tt_object_by_path_id(1) {
+                    let asset_bundle: TypeTreeObjectRef = asset_bundle.into();
+                    if let Ok(is_streamed_scene) =
+                        bool::try_cast_from(&asset_bundle, "/Base/m_IsStreamedSceneAssetBundle")
+                    {
+                        return is_streamed_scene;
+                    }
+                }
+            }
+        }
+
+        let file_name = normalize_external_name(cab_name).to_lowercase();
+        file_name.starts_with("sharedassets")
+            || file_name
+                .strip_prefix("level")
+                .is_some_and(|index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()))
+    }
+
+    /// Transforms and RectTransforms of the file with no parent, one per
+    /// root GameObject. Files holding several scenes have the roots of
+    /// each of them.
+    pub fn root_transforms(&self, cab_name: &str) -> ReadResult<Vec<TypeTreeObjectRef>> {
+        let serialized_file = self
+            .get_serialized_file_by_path(cab_name)
+            .ok_or(Error::SerializedFileNotFound)?;
+        let mut roots = Vec::new();
+        for (path_id, obj) in serialized_file.get_object_map() {
+            if obj.is_removed()
+                || obj.class != ClassIDType::Transform as i32
+                    && obj.class != ClassIDType::RectTransform as i32
+            {
+                continue;
+            }
+            let Some(transform) = serialized_file.get_tt_object_by_path_id(*path_id)? else {
+                continue;
+            };
+            let transform: TypeTreeObjectRef = transform.into();
+            let father = TypeTreeObjectRef::try_cast_from(&transform, "/Base/m_Father")?;
+            if PPtr::new(&father).is_null() {
+                roots.push(transform);
+            }
+        }
+        Ok(roots)
+    }
+
+    /// Every loaded scene file, see [`Self::is_scene_file`], sorted by name.
+    pub fn scenes(&self) -> Vec<SceneInfo> {
+        let mut scenes: Vec<_> = self
+            .cab_maps
+            .keys()
+            .filter(|cab_name| self.is_scene_file(cab_name))
+            .map(|cab_name| SceneInfo {
+                cab_name: cab_name.clone(),
+                root_game_objects: self
+                    .root_transforms(cab_name)
+                    .map_or(0, |roots| roots.len()),
+            })
+            .collect();
+        scenes.sort_by(|a, b| a.cab_name.cmp(&b.cab_name));
+        scenes
+    }
+
+    /// Name of the CAB or serialized file an object was read from, the
+    /// reverse of [`Self::get_serialized_file_by_path`]. `None` for files
+    /// added without a name.
+    pub fn containing_cab(&self, obj: &TypeTreeObject) -> Option<&String> {
+        self.cab_maps
+            .iter()
+            .find(|(_, serialized_file_id)| **serialized_file_id == obj.serialized_file_id)
+            .map(|(cab_name, _)| cab_name)
+    }
+
+    pub fn get_unity_fs_by_cab_path(&self, path: &str) -> Option<&UnityFS> {
+        if let Some(serialized_file_id) = self.get_serialized_file_id_by_cab_name(path) {
+            if let Some(unity_fs_id) = self.serialized_file_to_unity_fs_map.get(serialized_file_id)
+            {
+                if let Some(unity_fs) = self.unity_fs_map.get(unity_fs_id) {
+                    return Some(unity_fs);
+                }
+            }
+        }
+        None
+    }
+
+    pub fn get_unity_fs_by_serialized_file(
+        &self,
+        serialized_file: &SerializedFile,
+    ) -> Option<&UnityFS> {
+        if let Some(unity_fs_id) = self
+            .serialized_file_to_unity_fs_map
+            .get(&serialized_file.get_serialized_file_id())
+        {
+            if let Some(unity_fs) = self.unity_fs_map.get(unity_fs_id) {
+                return Some(unity_fs);
+            }
+        }
+        None
+    }
+
+    pub fn get_unity_fs_by_pptr(&self, pptr: &PPtr) -> Option<&UnityFS> {
+        let serialized_file_id = pptr.get_serialized_file_id();
+        if let Some(unity_fs_id) = self
+            .serialized_file_to_unity_fs_map
+            .get(&serialized_file_id)
+        {
+            if let Some(unity_fs) = self.unity_fs_map.get(unity_fs_id) {
+                return Some(unity_fs);
+            }
+        }
+
+        None
+    }
+
+    pub fn get_unity_fs_by_type_tree_object(
+        &self,
+        type_tree_object: &TypeTreeObject,
+    ) -> Option<&UnityFS> {
+        if let Some(unity_fs_id) = self
+            .serialized_file_to_unity_fs_map
+            .get(&type_tree_object.serialized_file_id)
+        {
+            if let Some(unity_fs) = self.unity_fs_map.get(unity_fs_id) {
+                return Some(unity_fs);
+            }
+        }
+        None
+    }
+
+    /// Take the parsed files out of the viewer, for pipelines that only use
+    /// it to scan and then process the files themselves. The ids in the
+    /// parts are the ones the viewer gave out, so a file's `PPtr`s still
+    /// resolve against `serialized_files`. Everything else the viewer keeps,
+    /// such as its options, is dropped.
+    pub fn into_parts(self) -> ViewerParts {
+        ViewerParts {
+            serialized_files: self.serialized_file_map,
+            bundles: self.unity_fs_map,
+            serialized_file_bundles: self.serialized_file_to_unity_fs_map,
+            cab_maps: self.cab_maps,
+            bundle_paths: self.bundle_paths,
+            container_maps: self.container_maps,
+            container_names: self.container_name_maps,
+            resource_map: self.resource_map,
+        }
+    }
+
+    pub fn get_container_name_by_path_id(&self, cab_name: &str, path_id: i64) -> Option<&String> {
+        if let Some(serialized_file_id) = self.get_serialized_file_id_by_cab_name(cab_name) {
+            if let Some(name_map) = self.container_name_maps.get(serialized_file_id) {
+                return name_map.get(&path_id);
+            }
+        }
+        None
+    }
+
+    pub fn get_container_name_by_serialized_file_id_and_path_id(
+        &self,
+        serialized_file_id: i64,
+        path_id: i64,
+    ) -> Option<&String> {
+        if let Some(name_map) = self.container_name_maps.get(&serialized_file_id) {
+            return name_map.get(&path_id);
+        }
+        None
+    }
+
+    pub fn get_container_name_by_pptr(&self, pptr: &PPtr) -> Option<&String> {
+        let serialized_file_id = pptr.get_serialized_file_id();
+        if let Some(name_map) = self.container_name_maps.get(&serialized_file_id) {
+            if let Ok(path_id) = pptr.get_path_id() {
+                return name_map.get(&path_id);
+            }
+        }
+        None
+    }
+
+    /// The name the bundle was given in the editor, from `m_AssetBundleName`
+    /// of the AssetBundle in the file.
+    pub fn asset_bundle_name(&self, serialized_file_id: i64) -> Option<String> {
+        let serialized_file = self.serialized_file_map.get(&serialized_file_id)?;
+        if serialized_file.get_object_map().get(&1)?.class != ClassIDType::AssetBundle as i32 {
+            return None;
+        }
+        let asset_bundle: TypeTreeObjectRef =
+            serialized_file.get_tt_object_by_path_id(1).ok()??.into();
+        String::try_cast_from(&asset_bundle, "/Base/m_AssetBundleName")
+            .ok()
+            .filter(|name| !name.is_empty())
+    }
+
+    /// The project path an object was built from, for naming exports after
+    /// the source assets. This is the object's own `m_AssetName` or
+    /// `m_AssetPath`, which some Addressables builds keep, else its
+    /// container name.
+    pub fn asset_path(&self, serialized_file_id: i64, path_id: i64) -> Option<String> {
+        let object = self
+            .serialized_file_map
+            .get(&serialized_file_id)
+            .and_then(|serialized_file| serialized_file.get_tt_object_by_path_id(path_id).ok())
+            .flatten()
+            .map(TypeTreeObjectRef::from);
+        object
+            .and_then(|object| {
+                ["/Base/m_AssetName", "/Base/m_AssetPath"]
+                    .into_iter()
+                    .find_map(|path| String::try_cast_from(&object, path).ok())
+            })
+            .filter(|name| !name.is_empty())
+            .or_else(|| {
+                self.get_container_name_by_serialized_file_id_and_path_id(
+                    serialized_file_id,
+                    path_id,
+                )
+                .cloned()
+            })
+    }
+
+    pub fn get_type_tree_object_by_container_name(
+        &self,
+        container_name: &str,
+    ) -> ReadResult<Option<TypeTreeObject>> {
+        match self.container_entries(container_name).first() {
+            Some(entry) => self.deref(&entry.pptr),
+            None => Ok(None),
+        }
+    }
+
+    /// Resolve an Addressables address, as copied from `catalog.json`, to
+    /// the object under the matching container name. The address is tried
+    /// as given, then with a leading bundle name removed: a first segment
+    /// ending in `.bundle`, or the `m_AssetBundleName` of a loaded bundle, so
+    /// `ui.bundle/Assets/Menu.prefab` and `ui/Assets/Menu.prefab` both find
+    /// `assets/menu.prefab`. Backslashes are read as `/`, and when no
+    /// container name matches exactly the names are compared as
+    /// [`normalize_container_name`] leaves them.
+    pub fn get_by_address(&self, address: &str) -> ReadResult<Option<TypeTreeObject>> {
+        let address = address.trim().replace('\\', "/");
+        let mut candidates = vec![address.as_str()];
+        if let Some((bundle, asset_path)) = address.split_once('/') {
+            if bundle.to_lowercase().ends_with(".bundle") {
+                candidates.push(asset_path);
+            }
+        }
+        for (slash, _) in address.match_indices('/') {
+            if self
+                .asset_bundle_names
+                .contains_key(&address[..slash].to_ascii_lowercase())
+            {
+                candidates.push(&address[slash + 1..]);
+            }
+        }
+
+        for candidate in &candidates {
+            if let Some(object) = self.get_type_tree_object_by_container_name(candidate)? {
+                return Ok(Some(object));
+            }
+        }
+        for candidate in candidates {
+            if let Some(container_key) = self
+                .normalized_container_keys
+                .get(&normalize_container_name(candidate))
+            {
+                return self.get_type_tree_object_by_container_name(container_key);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Resolve the objects Unity preloads for a container entry, which is the
+    /// slice `preloadIndex..preloadIndex + preloadSize` of the AssetBundle's
+    /// `m_PreloadTable`. Every entry sharing the name contributes its slice.
+    pub fn preload_for_container(&self, container_name: &str) -> ReadResult<Vec<TypeTreeObject>> {
+        let mut objects = Vec::new();
+        let container_key = self.container_key(container_name);
+        let Some(entries) = self.container_maps.get(container_key.as_ref()) else {
+            return Ok(objects);
+        };
+        let serialized_file_ids: BTreeSet<i64> = entries
+            .iter()
+            .map(|entry| entry.serialized_file_id)
+            .collect();
+
+        for serialized_file_id in serialized_file_ids {
+            let Some(serialized_file) = self.serialized_file_map.get(&serialized_file_id) else {
+                continue;
+            };
+            let Some(asset_bundle) = serialized_file.get_tt_object_by_path_id(1)? else {
+                continue;
+            };
+            if asset_bundle.class_id != ClassIDType::AssetBundle as i32 {
+                continue;
+            }
+            let asset_bundle: TypeTreeObjectRef = asset_bundle.into();
+            let containers =
+                <Vec<TypeTreeObjectRef>>::try_cast_from(&asset_bundle, "/Base/m_Container/Array")?;
+            let preload_table = <Vec<TypeTreeObjectRef>>::try_cast_from(
+                &asset_bundle,
+                "/Base/m_PreloadTable/Array",
+            )?;
+
+            for container in containers {
+                let name = String::try_cast_from(&container, "/Base/first")?;
+                if self.container_key(&name) != container_key {
+                    continue;
+                }
+                let preload_index =
+                    i64::try_cast_from(&container, "/Base/second/preloadIndex")?.max(0) as usize;
+                let preload_size =
+                    i64::try_cast_from(&container, "/Base/second/preloadSize")?.max(0) as usize;
+                for pptr in preload_table.iter().skip(preload_index).take(preload_size) {
+                    if let Some(obj) =
+                        PPtr::new(pptr).get_type_tree_object(serialized_file, Some(self))?
+                    {
+                        objects.push(obj);
+                    }
+                }
+            }
+        }
+        Ok(objects)
+    }
+
+    pub fn get_serialized_file_by_container_name(
+        &self,
+        container_name: &str,
+    ) -> Option<&SerializedFile> {
+        self.container_entries(container_name)
+            .first()
+            .and_then(|entry| self.serialized_file_map.get(&entry.serialized_file_id))
+    }
+
+    /// Write the container index of every bundle read by
+    /// [`Self::read_bundle_dir`] to `path` as JSON.
+    #[cfg(feature = "serde")]
+    pub fn save_index<P: AsRef<Path>>(&self, path: P) -> ReadResult<()> {
+        let mut cab_names = HashMap::new();
+        for (cab_name, serialized_file_id) in &self.cab_maps {
+            cab_names.insert(*serialized_file_id, cab_name);
+        }
+
+        let mut bundles = BTreeMap::new();
+        for (unity_fs_id, bundle_path) in &self.bundle_paths {
+            if let Some((size, modified)) = file_stamp(bundle_path) {
+                bundles.insert(
+                    *unity_fs_id,
+                    IndexedBundle {
+                        path: bundle_path.clone(),
+                        size,
+                        modified,
+                        containers: Vec::new(),
+                        asset_bundle_names: BTreeMap::new(),
+                    },
+                );
+            }
+        }
+
+        for (asset_bundle_name, serialized_file_id) in &self.asset_bundle_names {
+            let bundle = self
+                .serialized_file_to_unity_fs_map
+                .get(serialized_file_id)
+                .and_then(|unity_fs_id| bundles.get_mut(unity_fs_id));
+            if let (Some(bundle), Some(cab_name)) = (bundle, cab_names.get(serialized_file_id)) {
+                bundle
+                    .asset_bundle_names
+                    .insert(cab_name.to_string(), asset_bundle_name.clone());
+            }
+        }
+        for (name, entries) in &self.container_maps {
+            for entry in entries {
+                let bundle = self
+                    .serialized_file_to_unity_fs_map
+                    .get(&entry.serialized_file_id)
+                    .and_then(|unity_fs_id| bundles.get_mut(unity_fs_id));
+                let cab_name = cab_names.get(&entry.serialized_file_id);
+                if let (Some(bundle), Some(cab_name)) = (bundle, cab_name) {
+                    let name = self
+                        .get_raw_container_name(name)
+                        .cloned()
+                        .unwrap_or_else(|| name.to_owned());
+                    bundle.containers.push(IndexedContainer {
+                        cab_name: cab_name.to_string(),
+                        name,
+                        file_id: entry.pptr.file_id,
+                        path_id: entry.pptr.path_id,
+                        preload_index: entry.preload_index,
+                        preload_size: entry.preload_size,
+                    });
+                }
+            }
+        }
+
+        let index = ContainerIndex {
+            bundles: bundles.into_values().collect(),
+        };
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        serde_json::to_writer(std::io::BufWriter::new(file), &index)
+            .map_err(|e| Error::Other(e.to_string()))
+    }
+
+    /// Load an index written by [`Self::save_index`]. Bundles read afterwards
+    /// by [`Self::read_bundle_dir`] whose size and modification time still
+    /// match take their containers from the index instead of parsing them,
+    /// and leave their CABs unread until first used.
+    #[cfg(feature = "serde")]
+    pub fn load_index<P: AsRef<Path>>(&mut self, path: P) -> ReadResult<()> {
+        let file = OpenOptions::new().read(true).open(path)?;
+        let index: ContainerIndex = serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| Error::Other(e.to_string()))?;
+        for bundle in index.bundles {
+            self.loaded_index.insert(bundle.path.clone(), bundle);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    fn take_fresh_indexed_bundle(&mut self, bundle_path: &Path) -> Option<IndexedBundle> {
+        let indexed_bundle = self.loaded_index.remove(bundle_path)?;
+        let (size, modified) = file_stamp(bundle_path)?;
+        (indexed_bundle.size == size && indexed_bundle.modified == modified)
+            .then_some(indexed_bundle)
+    }
+
+    #[cfg(feature = "serde")]
+    fn apply_indexed_bundle(&mut self, indexed_bundle: IndexedBundle) {
+        for (cab_name, asset_bundle_name) in indexed_bundle.asset_bundle_names {
+            if let Some(serialized_file_id) = self.cab_maps.get(&cab_name).copied() {
+                self.asset_bundle_names
+                    .insert(asset_bundle_name, serialized_file_id);
+            }
+        }
+        for container in indexed_bundle.containers {
+            let Some(serialized_file_id) = self.cab_maps.get(&container.cab_name).copied() else {
+                continue;
+            };
+            if container.file_id == 0 {
+                self.container_name_maps
+                    .entry(serialized_file_id)
+                    .or_default()
+                    .insert(container.path_id, container.name.clone());
+            }
+            self.insert_container(
+                container.name,
+                ContainerEntry {
+                    serialized_file_id,
+                    pptr: PPtrIds {
+                        serialized_file_id,
+                        file_id: container.file_id,
+                        path_id: container.path_id,
+                    },
+                    preload_index: container.preload_index,
+                    preload_size: container.preload_size,
+                },
+            );
+        }
+    }
+
+    /// Decode every Texture2D in the loaded files.
+    /// Texture data is read in batches on the calling thread and each batch is
+    /// decoded in parallel when the `parallel` feature is enabled, so results
+    /// are yielded as soon as the batch they belong to is done.
+    /// A texture that fails to decode only fails its own item; once the format
+    /// is known the error is an [`Error::TextureDecodeError`] carrying it.
+    #[cfg(feature = "external-class-handle-texture2d")]
+    pub fn decode_all_textures(
+        &self,
+    ) -> impl Iterator<Item = (i64, i64, ReadResult<RgbaImage>)> + '_ {
+        const BATCH_SIZE: usize = 64;
+
+        let mut textures = self
+            .serialized_file_map
+            .iter()
+            .flat_map(|(serialized_file_id, serialized_file)| {
+                serialized_file
+                    .get_object_map()
+                    .iter()
+                    .filter(|(_, obj)| {
+                        obj.class == ClassIDType::Texture2D as i32 && !obj.is_removed()
+                    })
+                    .map(move |(path_id, _)| (*serialized_file_id, *path_id))
+            })
+            .peekable();
+
+        std::iter::from_fn(move || {
+            textures.peek()?;
+            let batch: Vec<_> = textures
+                .by_ref()
+                .take(BATCH_SIZE)
+                .map(|(serialized_file_id, path_id)| {
+                    (
+                        serialized_file_id,
+                        path_id,
+                        self.read_texture_data(serialized_file_id, path_id),
+                    )
+                })
+                .collect();
+
+            let decode = |(serialized_file_id, path_id, texture_data): (
+                i64,
+                i64,
+                ReadResult<TextureData>,
+            )| {
+                let image = texture_data.and_then(|(data, texture_format, width, height)| {
+                    decode_image(data, &texture_format, width, height)
+                        .map(|i| i.to_rgba8())
+                        .map_err(|err| Error::TextureDecodeError {
+                            texture_format,
+                            source: Box::new(err),
+                        })
+                });
+                (serialized_file_id, path_id, image)
+            };
+
+            #[cfg(feature = "parallel")]
+            let decoded: Vec<_> = batch.into_par_iter().map(decode).collect();
+            #[cfg(not(feature = "parallel"))]
+            let decoded: Vec<_> = batch.into_iter().map(decode).collect();
+
+            Some(decoded)
+        })
+        .flatten()
+    }
+
+    /// Decode the image of a container: a Texture2D whole, or a Sprite
+    /// cropped to its rect in its texture. `None` when the container is
+    /// missing, is another kind of object, or is a Sprite without a texture.
+    #[cfg(all(
+        feature = "external-class-handle",
+        feature = "external-class-handle-texture2d"
+    ))]
+    pub fn texture_by_container(&self, container_name: &str) -> ReadResult<Option<RgbaImage>> {
+        use crate::classes::sprite::Sprite;
+
+        let Some(obj) = self.get_type_tree_object_by_container_name(container_name)? else {
+            return Ok(None);
+        };
+        if obj.class_id == ClassIDType::Texture2D as i32 {
+            let texture = obj.into();
+            return Ok(Some(Texture2D::new(&texture).get_image(self)?.to_rgba8()));
+        }
+        if obj.class_id != ClassIDType::Sprite as i32 {
+            return Ok(None);
+        }
+
+        let sprite = obj.into();
+        self.sprite_image(&Sprite::new(&sprite))
+    }
+
+    /// Decode a Sprite cropped out of its texture. A sprite packed into a
+    /// SpriteAtlas is cut from the atlas page at its packed rect and turned
+    /// back the way it was packed. `None` when the texture is not loaded.
+    #[cfg(all(
+        feature = "external-class-handle",
+        feature = "external-class-handle-texture2d"
+    ))]
+    fn sprite_image(
+        &self,
+        sprite: &crate::classes::sprite::Sprite,
+    ) -> ReadResult<Option<RgbaImage>> {
+        use crate::classes::sprite::SpriteObject;
+        use crate::classes::sprite_atlas::{SpriteAtlas, SpriteAtlasObject};
+
+        let mut packing = None;
+        if let (Some(key), Some(atlas)) =
+            (sprite.get_render_data_key()?, sprite.get_sprite_atlas()?)
+        {
+            if let Some(atlas) = self.deref(&PPtr::new(&atlas))? {
+                let atlas = atlas.into();
+                packing = SpriteAtlas::new(&atlas)
+                    .get_render_data()?
+                    .into_iter()
+                    .find(|data| data.key == key);
+            }
+        }
+        let (image, rect, rotation) = match &packing {
+            Some(data) => (
+                self.texture_with_alpha(&data.texture, Some(&data.alpha_texture))?,
+                data.texture_rect,
+                (data.settings_raw >> 2) & 0xf,
+            ),
+            None => (
+                self.texture_with_alpha(
+                    &sprite.get_texture()?,
+                    sprite.get_alpha_texture()?.as_ref(),
+                )?,
+                sprite.get_rect()?,
+                0,
+            ),
+        };
+        let Some(image) = image else {
+            return Ok(None);
+        };
+
+        // Rects count rows from the bottom, as do the decoded rows.
+        let x = (rect.x.max(0.0) as u32).min(image.width());
+        let y = (rect.y.max(0.0) as u32).min(image.height());
+        let width = (rect.z.max(0.0) as u32).min(image.width() - x);
+        let height = (rect.w.max(0.0) as u32).min(image.height() - y);
+        let image = image::imageops::crop_imm(&image, x, y, width, height).to_image();
+        // SpritePackingRotation: flipped horizontally, vertically, turned
+        // 180 degrees or 90 degrees.
+        Ok(Some(match rotation {
+            1 => image::imageops::flip_horizontal(&image),
+            2 => image::imageops::flip_vertical(&image),
+            3 => image::imageops::rotate180(&image),
+            4 => image::imageops::rotate270(&image),
+            _ => image,
+        }))
+    }
+
+    /// Decode the Texture2D `texture` points to, taking its alpha from the
+    /// red channel of the one `alpha_texture` points to, if that is set, as
+    /// ETC1 builds split it out. `None` when `texture` is not loaded.
+    #[cfg(feature = "external-class-handle-texture2d")]
+    pub(crate) fn texture_with_alpha(
+        &self,
+        texture: &TypeTreeObjectRef,
+        alpha_texture: Option<&TypeTreeObjectRef>,
+    ) -> ReadResult<Option<RgbaImage>> {
+        let Some(texture) = self.deref(&PPtr::new(texture))? else {
+            return Ok(None);
+        };
+        let texture = texture.into();
+        let mut image = Texture2D::new(&texture).get_image(self)?.to_rgba8();
+        if let Some(alpha_texture) = alpha_texture {
+            if let Some(alpha_texture) = self.deref(&PPtr::new(alpha_texture))? {
+                let alpha_texture = alpha_texture.into();
+                let mut alpha = Texture2D::new(&alpha_texture).get_image(self)?.to_rgba8();
+                if alpha.dimensions() != image.dimensions() {
+                    alpha = image::imageops::resize(
+                        &alpha,
+                        image.width(),
+                        image.height(),
+                        image::imageops::FilterType::Nearest,
+                    );
+                }
+                for (pixel, alpha) in image.pixels_mut().zip(alpha.pixels()) {
+                    pixel[3] = alpha[0];
+                }
+            }
+        }
+        Ok(Some(image))
+    }
+
+    #[cfg(feature = "external-class-handle-texture2d")]
+    fn read_texture_data(&self, serialized_file_id: i64, path_id: i64) -> ReadResult<TextureData> {
+        let texture = self
+            .serialized_file_map
+            .get(&serialized_file_id)
+            .ok_or(Error::SerializedFileNotFound)?
+            .get_tt_object_by_path_id(path_id)?
+            .ok_or(Error::Other(format!("cannot read texture {path_id}")))?
+            .into();
+        let texture = Texture2D::new(&texture);
+        let texture_format = Texture2DObject::get_texture_format(&texture)?;
+        let image_data = texture
+            .get_image_data(self)
+            .map_err(|err| Error::TextureDecodeError {
+                texture_format: texture_format.clone(),
+                source: Box::new(err),
+            })?;
+        Ok((
+            image_data,
+            texture_format,
+            Texture2DObject::get_width(&texture)? as usize,
+            Texture2DObject::get_height(&texture)? as usize,
+        ))
+    }
+
+    /// Write the `m_Script` of every TextAsset under `out_dir`, laid out by
+    /// container name. Assets without a container are written as
+    /// `<cab name>/<path id>` and are skipped when `name_prefix` is given.
+    /// Returns the number of files written.
+    #[cfg(feature = "external-class-handle")]
+    pub fn dump_text_assets<P: AsRef<Path>>(
+        &self,
+        out_dir: P,
+        name_prefix: Option<&str>,
+    ) -> ReadResult<usize> {
+        let name_prefix = name_prefix.map(|prefix| self.container_key(prefix).into_owned());
+        let mut count = 0;
+        for (serialized_file_id, serialized_file) in &self.serialized_file_map {
+            for (path_id, obj) in serialized_file.get_object_map() {
+                if obj.class != ClassIDType::TextAsset as i32 || obj.is_removed() {
+                    continue;
+                }
+                let Some(relative_path) =
+                    self.dump_path(*serialized_file_id, *path_id, name_prefix.as_deref())
+                else {
+                    continue;
+                };
+
+                let text_asset: TypeTreeObjectRef = serialized_file
+                    .get_tt_object_by_path_id(*path_id)?
+                    .ok_or(Error::Other(format!("cannot read text asset {path_id}")))?
+                    .into();
+                let script = TextAsset::new(&text_asset).get_script()?;
+
+                let out_path = out_dir.as_ref().join(relative_path);
+                if let Some(parent) = out_path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(out_path, script)?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Write every Sprite under `out_dir` as a PNG, laid out by container
+    /// name like [`Self::dump_text_assets`] with the extension replaced by
+    /// `.png`. Sprites sharing a container, such as those cut from one
+    /// sheet, are named `<container>_<sprite name>.png`, with `_<path id>`
+    /// added to repeated names. Packed sprites are cut from their atlas
+    /// page, and separate alpha textures are merged.
+    /// Sprites whose texture is not loaded are skipped. Returns the number
+    /// of files written.
+    #[cfg(all(
+        feature = "external-class-handle",
+        feature = "external-class-handle-texture2d"
+    ))]
+    pub fn dump_sprites<P: AsRef<Path>>(
+        &self,
+        out_dir: P,
+        name_prefix: Option<&str>,
+    ) -> ReadResult<usize> {
+        use crate::classes::sprite::{Sprite, SpriteObject};
+
+        let name_prefix = name_prefix.map(|prefix| self.container_key(prefix).into_owned());
+        let mut sprites: BTreeMap<PathBuf, Vec<(i64, i64)>> = BTreeMap::new();
+        for (serialized_file_id, serialized_file) in &self.serialized_file_map {
+            for (path_id, obj) in serialized_file.get_object_map() {
+                if obj.class != ClassIDType::Sprite as i32 || obj.is_removed() {
+                    continue;
+                }
+                if let Some(relative_path) =
+                    self.dump_path(*serialized_file_id, *path_id, name_prefix.as_deref())
+                {
+                    sprites
+                        .entry(relative_path.with_extension(""))
+                        .or_default()
+                        .push((*serialized_file_id, *path_id));
+                }
+            }
+        }
+
+        let mut count = 0;
+        let mut used_paths = HashSet::new();
+        for (relative_path, ids) in sprites {
+            let shared = ids.len() > 1;
+            for (serialized_file_id, path_id) in ids {
+                let sprite: TypeTreeObjectRef = self.serialized_file_map[&serialized_file_id]
+                    .get_tt_object_by_path_id(path_id)?
+                    .ok_or(Error::Other(format!("cannot read sprite {path_id}")))?
+                    .into();
+                let sprite = Sprite::new(&sprite);
+                let Some(image) = self.sprite_image(&sprite)? else {
+                    continue;
+                };
+
+                let mut file_name = relative_path.clone().into_os_string();
+                if shared {
+                    file_name.push(format!("_{}", sprite.get_name()?));
+                }
+                // Sprites of one sheet sharing a name are told apart by path id.
+                if used_paths.contains(&file_name) {
+                    file_name.push(format!("_{path_id}"));
+                }
+                used_paths.insert(file_name.clone());
+                file_name.push(".png");
+                let out_path = out_dir.as_ref().join(file_name);
+                if let Some(parent) = out_path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                // Decoded rows run from the bottom, PNG rows from the top.
+                image::imageops::flip_vertical(&image)
+                    .save_with_format(out_path, image::ImageFormat::Png)
+                    .map_err(|err| Error::Other(err.to_string()))?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Write every AudioClip under `out_dir`, laid out by container name
+    /// like [`Self::dump_text_assets`]. With `decode`, clips whose codec
+    /// [`AudioClipObject::decode_pcm`] handles are written as `.wav`; the
+    /// others, and every clip without `decode`, are written as stored, as
+    /// `.fsb` for FSB5 banks and `.bin` otherwise, with `_<path id>` added
+    /// to the file name of a clip that would overwrite another. Clips whose
+    /// data cannot be read or decoded, such as those with a missing
+    /// `.resource` stream, are reported as skipped.
+    #[cfg(feature = "external-class-handle")]
+    pub fn dump_audio<P: AsRef<Path>>(
+        &self,
+        out_dir: P,
+        decode: bool,
+        name_prefix: Option<&str>,
+    ) -> ReadResult<AudioExportReport> {
+        use crate::classes::audio_clip::fsb5;
+
+        let name_prefix = name_prefix.map(|prefix| self.container_key(prefix).into_owned());
+        let mut report = AudioExportReport::default();
+        let mut used_paths = HashSet::new();
+        for (serialized_file_id, serialized_file) in &self.serialized_file_map {
+            for (path_id, obj) in serialized_file.get_object_map() {
+                if obj.class != ClassIDType::AudioClip as i32 || obj.is_removed() {
+                    continue;
+                }
+                let Some(relative_path) =
+                    self.dump_path(*serialized_file_id, *path_id, name_prefix.as_deref())
+                else {
+                    continue;
+                };
+
+                let audio_clip: TypeTreeObjectRef = serialized_file
+                    .get_tt_object_by_path_id(*path_id)?
+                    .ok_or(Error::Other(format!("cannot read audio clip {path_id}")))?
+                    .into();
+                let audio_clip = AudioClip::new(&audio_clip);
+                let data = match audio_clip.get_audio_data(self) {
+                    Ok(data) => data,
+                    Err(err) => {
+                        report.skipped.push((relative_path, err.to_string()));
+                        continue;
+                    }
+                };
+                let codec = fsb5::read_first_sample(&data)
+                    .ok()
+                    .map(|sample| sample.codec);
+                let wav = match (decode && codec.is_some()).then(|| fsb5::decode_pcm(&data)) {
+                    Some(Ok((samples, sample_rate, channels))) => {
+                        Some(fsb5::pcm_to_wav(&samples, sample_rate, channels))
+                    }
+                    Some(Err(Error::UnsupportedAudioCodec(_))) | None => None,
+                    Some(Err(err)) => {
+                        report.skipped.push((relative_path, err.to_string()));
+                        continue;
+                    }
+                };
+                let decoded = wav.is_some();
+                let (data, extension) = match (wav, codec) {
+                    (Some(wav), _) => (wav, "wav"),
+                    (None, Some(_)) => (data, "fsb"),
+                    (None, None) => (data, "bin"),
+                };
+
+                let mut path = out_dir
+                    .as_ref()
+                    .join(relative_path.with_extension(extension));
+                // Clips sharing a container are told apart by path id.
+                if used_paths.contains(&path) {
+                    let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+                    file_name.push(format!("_{path_id}.{extension}"));
+                    path.set_file_name(file_name);
                 }
+                used_paths.insert(path.clone());
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&path, data)?;
+                report.clips.push(AudioExport {
+                    path,
+                    codec,
+                    decoded,
+                });
             }
         }
-        None
+        Ok(report)
     }
 
-    pub fn get_unity_fs_by_serialized_file(
+    /// Where a dump writes an object under its output directory: its
+    /// container name split into directories, or `<cab name>/<path id>`
+    /// without one. `None` when the object is skipped: its container is
+    /// not under the container key `name_prefix`, or it has no container
+    /// and a prefix is given.
+    #[cfg(feature = "external-class-handle")]
+    fn dump_path(
         &self,
-        serialized_file: &SerializedFile,
-    ) -> Option<&UnityFS> {
-        if let Some(unity_fs_id) = self
-            .serialized_file_to_unity_fs_map
-            .get(&serialized_file.get_serialized_file_id())
-        {
-            if let Some(unity_fs) = self.unity_fs_map.get(unity_fs_id) {
-                return Some(unity_fs);
+        serialized_file_id: i64,
+        path_id: i64,
+        name_prefix: Option<&str>,
+    ) -> Option<PathBuf> {
+        let container_name =
+            self.get_container_name_by_serialized_file_id_and_path_id(serialized_file_id, path_id);
+        let relative_path = match (container_name, name_prefix) {
+            (Some(name), Some(prefix)) if !self.container_key(name).starts_with(prefix) => {
+                return None
             }
-        }
-        None
+            (None, Some(_)) => return None,
+            (Some(name), _) => PathBuf::from_iter(
+                name.split(['/', '\\'])
+                    .filter(|c| !c.is_empty() && *c != "." && *c != ".."),
+            ),
+            (None, None) => {
+                let cab_name = self
+                    .cab_maps
+                    .iter()
+                    .find(|(_, id)| **id == serialized_file_id)
+                    .map_or_else(|| serialized_file_id.to_string(), |(name, _)| name.clone());
+                PathBuf::from(cab_name).join(path_id.to_string())
+            }
+        };
+        (!relative_path.as_os_str().is_empty()).then_some(relative_path)
     }
 
-    pub fn get_unity_fs_by_pptr(&self, pptr: &PPtr) -> Option<&UnityFS> {
-        let serialized_file_id = pptr.get_serialized_file_id();
-        if let Some(unity_fs_id) = self
-            .serialized_file_to_unity_fs_map
-            .get(&serialized_file_id)
-        {
-            if let Some(unity_fs) = self.unity_fs_map.get(unity_fs_id) {
-                return Some(unity_fs);
+    /// Write the object behind `container_name` to `out_dir` in a format
+    /// picked from its class: PNG for textures, WAV for audio clips, OBJ
+    /// for meshes and the raw `m_Script` for text assets. The file is named
+    /// after the container, or the object's `m_Name` without one, and the
+    /// written path is returned.
+    #[cfg(feature = "external-class-handle")]
+    pub fn export_asset<P: AsRef<Path>>(
+        &self,
+        container_name: &str,
+        out_dir: P,
+    ) -> ReadResult<PathBuf> {
+        let object = self
+            .get_type_tree_object_by_container_name(container_name)?
+            .ok_or(Error::Other(format!(
+                "cannot find container {container_name}"
+            )))?;
+        let class_id = object.class_id;
+        let object: TypeTreeObjectRef = object.into();
+        let (data, extension) = match ClassIDType::try_from(class_id) {
+            #[cfg(feature = "external-class-handle-texture2d")]
+            Ok(ClassIDType::Texture2D) => {
+                // Unity stores the bottom row first.
+                let mut png = Cursor::new(Vec::new());
+                Texture2D::new(&object)
+                    .get_image(self)?
+                    .flipv()
+                    .write_to(&mut png, image::ImageOutputFormat::Png)
+                    .map_err(|err| Error::Other(err.to_string()))?;
+                (png.into_inner(), "png")
             }
-        }
+            Ok(ClassIDType::AudioClip) => (AudioClip::new(&object).get_wav(self)?, "wav"),
+            Ok(ClassIDType::Mesh) => (Mesh::new(&object).to_obj()?.into_bytes(), "obj"),
+            Ok(ClassIDType::TextAsset) => (TextAsset::new(&object).get_script()?, "txt"),
+            _ => {
+                return Err(Error::Other(format!(
+                    "cannot export {container_name} of class {}",
+                    self.get_class_name(class_id)
+                        .unwrap_or_else(|| class_id.to_string())
+                )))
+            }
+        };
 
-        None
+        let container_path = PathBuf::from(container_name.replace('\\', "/"));
+        let file_name = match container_path.file_stem() {
+            Some(stem) => stem.to_string_lossy().into_owned(),
+            None => String::try_cast_from(&object, "/Base/m_Name")?,
+        };
+        // Text assets keep their own extension, such as .json or .bytes.
+        let extension = match (container_path.extension(), extension) {
+            (Some(ext), "txt") => ext.to_string_lossy().into_owned(),
+            _ => extension.to_owned(),
+        };
+        std::fs::create_dir_all(out_dir.as_ref())?;
+        let out_path = out_dir.as_ref().join(file_name).with_extension(extension);
+        std::fs::write(&out_path, data)?;
+        Ok(out_path)
     }
 
-    pub fn get_unity_fs_by_type_tree_object(
+    /// Write every object selected by `filter` under `out_dir` in the format
+    /// [`Self::export_asset`] picks, laid out by container name or else as
+    /// `<cab name>/<path id>`. Objects are read in batches that fit the
+    /// [`Self::set_export_memory_budget_bytes`] budget, and textures of a
+    /// batch are decoded in parallel with the `parallel` feature. An object
+    /// that fails to be read, decoded or written is reported in
+    /// [`ExportReport::failed`] and does not stop the export.
+    #[cfg(feature = "external-class-handle")]
+    pub fn export_all<P: AsRef<Path>>(
         &self,
-        type_tree_object: &TypeTreeObject,
-    ) -> Option<&UnityFS> {
-        if let Some(unity_fs_id) = self
-            .serialized_file_to_unity_fs_map
-            .get(&type_tree_object.serialized_file_id)
-        {
-            if let Some(unity_fs) = self.unity_fs_map.get(unity_fs_id) {
-                return Some(unity_fs);
+        out_dir: P,
+        filter: ExportFilter,
+    ) -> ReadResult<ExportReport> {
+        const DEFAULT_BUDGET: u64 = 256 << 20;
+        let budget = self.export_memory_budget_bytes.unwrap_or(DEFAULT_BUDGET);
+
+        let mut report = ExportReport::default();
+        let mut used_paths = HashSet::new();
+        let mut batch: Vec<(i64, i64, PathBuf, PendingExport)> = Vec::new();
+        let mut batch_cost = 0;
+        let flush = |batch: &mut Vec<(i64, i64, PathBuf, PendingExport)>,
+                     report: &mut ExportReport| {
+            let encode = |(serialized_file_id, path_id, path, pending): (
+                i64,
+                i64,
+                PathBuf,
+                PendingExport,
+            )| (serialized_file_id, path_id, path, pending.into_bytes());
+            #[cfg(feature = "parallel")]
+            let encoded: Vec<_> = std::mem::take(batch).into_par_iter().map(encode).collect();
+            #[cfg(not(feature = "parallel"))]
+            let encoded: Vec<_> = std::mem::take(batch).into_iter().map(encode).collect();
+
+            for (serialized_file_id, path_id, path, data) in encoded {
+                let out_path = out_dir.as_ref().join(path);
+                let written = data.and_then(|data| {
+                    if let Some(parent) = out_path.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    Ok(std::fs::write(&out_path, data)?)
+                });
+                match written {
+                    Ok(()) => report.written.push((serialized_file_id, path_id, out_path)),
+                    Err(err) => report.failed.push((serialized_file_id, path_id, err)),
+                }
             }
-        }
-        None
-    }
+        };
 
-    pub fn get_container_name_by_path_id(
-        &self,
-        cab_name: &String,
-        path_id: i64,
-    ) -> Option<&String> {
-        if let Some(serialized_file_id) = self.cab_maps.get(cab_name) {
-            if let Some(name_map) = self.container_name_maps.get(serialized_file_id) {
-                return name_map.get(&path_id);
+        let name_substring = filter.name_substring.as_deref();
+        for (serialized_file_id, path_id) in self.find_objects(None, name_substring) {
+            let Some(obj) = self.serialized_file_map[&serialized_file_id]
+                .get_object_map()
+                .get(&path_id)
+            else {
+                continue;
+            };
+            let class_id = obj.class;
+            let requested = filter
+                .class_ids
+                .as_ref()
+                .map(|class_ids| class_ids.contains(&class_id));
+            let extension = match ClassIDType::try_from(class_id) {
+                #[cfg(feature = "external-class-handle-texture2d")]
+                Ok(ClassIDType::Texture2D) => "png",
+                Ok(ClassIDType::AudioClip) => "wav",
+                Ok(ClassIDType::Mesh) => "obj",
+                Ok(ClassIDType::TextAsset) => "txt",
+                _ => {
+                    if requested == Some(true) {
+                        report.skipped.push((
+                            serialized_file_id,
+                            path_id,
+                            format!(
+                                "no exporter for {}",
+                                self.get_class_name(class_id)
+                                    .unwrap_or_else(|| class_id.to_string())
+                            ),
+                        ));
+                    }
+                    continue;
+                }
+            };
+            if requested == Some(false) {
+                continue;
             }
-        }
-        None
-    }
 
-    pub fn get_container_name_by_serialized_file_id_and_path_id(
-        &self,
-        serialized_file_id: i64,
-        path_id: i64,
-    ) -> Option<&String> {
-        if let Some(name_map) = self.container_name_maps.get(&serialized_file_id) {
-            return name_map.get(&path_id);
+            let path = self.export_path(serialized_file_id, path_id, extension);
+            if path.as_os_str().is_empty() {
+                report.skipped.push((
+                    serialized_file_id,
+                    path_id,
+                    "container name is empty".to_owned(),
+                ));
+                continue;
+            }
+            // Objects sharing a container, such as a sprite and its texture,
+            // are told apart by path id.
+            let path = if used_paths.contains(&path) {
+                let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+                file_name.push(format!("_{path_id}"));
+                if let Some(extension) = path.extension() {
+                    file_name.push(".");
+                    file_name.push(extension);
+                }
+                path.with_file_name(file_name)
+            } else {
+                path
+            };
+            used_paths.insert(path.clone());
+
+            match self.read_export(serialized_file_id, path_id, class_id) {
+                Ok(pending) => {
+                    let cost = pending.cost();
+                    if !batch.is_empty() && batch_cost + cost > budget {
+                        flush(&mut batch, &mut report);
+                        batch_cost = 0;
+                    }
+                    batch_cost += cost;
+                    batch.push((serialized_file_id, path_id, path, pending));
+                }
+                Err(err) => report.failed.push((serialized_file_id, path_id, err)),
+            }
         }
-        None
+        flush(&mut batch, &mut report);
+        Ok(report)
     }
 
-    pub fn get_container_name_by_pptr(&self, pptr: &PPtr) -> Option<&String> {
-        let serialized_file_id = pptr.get_serialized_file_id();
-        if let Some(name_map) = self.container_name_maps.get(&serialized_file_id) {
-            if let Ok(path_id) = pptr.get_path_id() {
-                return name_map.get(&path_id);
+    /// Where [`Self::export_all`] writes an object, relative to its output
+    /// directory. Text assets keep the extension of their container name.
+    #[cfg(feature = "external-class-handle")]
+    fn export_path(&self, serialized_file_id: i64, path_id: i64, extension: &str) -> PathBuf {
+        match self.get_container_name_by_serialized_file_id_and_path_id(serialized_file_id, path_id)
+        {
+            Some(name) => {
+                let path = PathBuf::from_iter(
+                    name.split(['/', '\\'])
+                        .filter(|c| !c.is_empty() && *c != "." && *c != ".."),
+                );
+                match path.extension() {
+                    Some(_) if extension == "txt" => path,
+                    _ if path.as_os_str().is_empty() => path,
+                    _ => path.with_extension(extension),
+                }
+            }
+            None => {
+                let cab_name = self
+                    .cab_maps
+                    .iter()
+                    .find(|(_, id)| **id == serialized_file_id)
+                    .map_or_else(|| serialized_file_id.to_string(), |(name, _)| name.clone());
+                PathBuf::from(cab_name)
+                    .join(path_id.to_string())
+                    .with_extension(extension)
             }
         }
-        None
     }
 
-    pub fn get_type_tree_object_by_container_name(
+    /// Read an object for [`Self::export_all`], leaving texture decoding to
+    /// the batch.
+    #[cfg(feature = "external-class-handle")]
+    fn read_export(
         &self,
-        container_name: &String,
-    ) -> ReadResult<Option<TypeTreeObject>> {
-        if let Some(serialized_file_id) = self.container_maps.get(container_name) {
-            if let Some((serialized_file_id, pptr)) = serialized_file_id.get(0) {
-                if let Some(serialized_file) = self.serialized_file_map.get(serialized_file_id) {
-                    return PPtr::new(pptr).get_type_tree_object(serialized_file, Some(self));
-                }
-            }
+        serialized_file_id: i64,
+        path_id: i64,
+        class_id: i32,
+    ) -> ReadResult<PendingExport> {
+        #[cfg(feature = "external-class-handle-texture2d")]
+        if class_id == ClassIDType::Texture2D as i32 {
+            return Ok(PendingExport::Texture(
+                self.read_texture_data(serialized_file_id, path_id)?,
+            ));
         }
-        Ok(None)
+        let object: TypeTreeObjectRef = self
+            .serialized_file_map
+            .get(&serialized_file_id)
+            .ok_or(Error::SerializedFileNotFound)?
+            .get_tt_object_by_path_id(path_id)?
+            .ok_or(Error::Other(format!("cannot read object {path_id}")))?
+            .into();
+        let data = match ClassIDType::try_from(class_id) {
+            Ok(ClassIDType::AudioClip) => AudioClip::new(&object).get_wav(self)?,
+            Ok(ClassIDType::Mesh) => Mesh::new(&object).to_obj()?.into_bytes(),
+            Ok(ClassIDType::TextAsset) => TextAsset::new(&object).get_script()?,
+            _ => return Err(Error::Other(format!("no exporter for class {class_id}"))),
+        };
+        Ok(PendingExport::Data(data))
     }
 
-    pub fn get_serialized_file_by_container_name(
+    /// Read the `size` bytes at `offset` of the stream a StreamingInfo or
+    /// resource entry of a serialized file names. An empty `path` keeps the
+    /// data in the data section of the serialized file itself. `None` when
+    /// the stream, or the serialized file, is not loaded.
+    pub fn read_stream_data(
         &self,
-        container_name: &String,
-    ) -> Option<&SerializedFile> {
-        if let Some(serialized_file_id) = self.container_maps.get(container_name) {
-            if let Some((serialized_file_id, _pptr)) = serialized_file_id.get(0) {
-                return self.serialized_file_map.get(serialized_file_id);
-            }
+        serialized_file_id: i64,
+        path: &String,
+        offset: u64,
+        size: usize,
+    ) -> ReadResult<Option<Vec<u8>>> {
+        if path.is_empty() {
+            return self
+                .serialized_file_map
+                .get(&serialized_file_id)
+                .map(|serialized_file| serialized_file.read_data(offset, size))
+                .transpose();
         }
-        None
+        let Some(mut file) =
+            self.get_resource_file_by_serialized_file_id_and_path(serialized_file_id, path)
+        else {
+            return Ok(None);
+        };
+        crate::serialized_file::check_range(&mut file, offset, size)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut data = vec![0u8; size];
+        file.read_exact(&mut data)?;
+        Ok(Some(data))
     }
 
     pub fn get_resource_file_by_serialized_file_id_and_path(
@@ -342,6 +3184,257 @@ impl UnityAssetViewer {
                 .and_then(|fs_id| self.unity_fs_map.get(fs_id)),
             None,
         )
+        .or_else(|| {
+            let path = self.resource_map.get(&normalize_external_name(path))?;
+            let file = OpenOptions::new().read(true).open(path).ok()?;
+            Some(Box::new(BufReader::new(file)) as Box<dyn UnityResource>)
+        })
+        .or_else(|| {
+            let file_name = PathBuf::from(path).file_name()?.to_owned();
+            self.stream_search_paths.iter().find_map(|search_path| {
+                let file = OpenOptions::new()
+                    .read(true)
+                    .open(search_path.join(&file_name))
+                    .ok()?;
+                Some(Box::new(BufReader::new(file)) as Box<dyn UnityResource>)
+            })
+        })
+    }
+
+    /// Directories searched, in order, for a `.resource`/`.resS` stream by
+    /// file name once the loaded bundles, the referencing file's own
+    /// directory and registered resource files have no match. Useful when
+    /// streams live in a separate `Data/` folder from the scanned bundles.
+    pub fn set_stream_search_paths(&mut self, paths: Vec<PathBuf>) {
+        self.stream_search_paths = paths;
+    }
+
+    /// Every texture, audio, mesh and video object whose data lives in an
+    /// external stream, as serialized file id, path id and stream name.
+    pub fn objects_with_streaming_data(&self) -> Vec<(i64, i64, String)> {
+        const STREAMING_CLASSES: [ClassIDType; 8] = [
+            ClassIDType::Texture2D,
+            ClassIDType::Texture3D,
+            ClassIDType::Texture2DArray,
+            ClassIDType::Cubemap,
+            ClassIDType::CubemapArray,
+            ClassIDType::AudioClip,
+            ClassIDType::Mesh,
+            ClassIDType::VideoClip,
+        ];
+        const STREAM_PATHS: [&str; 3] = [
+            "/Base/m_StreamData/path",
+            "/Base/m_Resource/m_Source",
+            "/Base/m_ExternalResources/m_Source",
+        ];
+
+        let mut objects = Vec::new();
+        for (serialized_file_id, serialized_file) in &self.serialized_file_map {
+            for (path_id, obj) in serialized_file.get_object_map() {
+                if !STREAMING_CLASSES
+                    .iter()
+                    .any(|class| class.clone() as i32 == obj.class)
+                {
+                    continue;
+                }
+                let Ok(Some(object)) = serialized_file.get_tt_object_by_path_id(*path_id) else {
+                    continue;
+                };
+                let object: TypeTreeObjectRef = object.into();
+                let stream = STREAM_PATHS
+                    .iter()
+                    .find_map(|path| String::try_cast_from(&object, *path).ok())
+                    .filter(|stream| !stream.is_empty());
+                if let Some(stream) = stream {
+                    objects.push((*serialized_file_id, *path_id, stream));
+                }
+            }
+        }
+        objects
+    }
+
+    /// The subset of [`Self::objects_with_streaming_data`] whose stream
+    /// can't be found in the loaded bundles or resource files.
+    pub fn missing_streams(&self) -> Vec<(i64, i64, String)> {
+        self.objects_with_streaming_data()
+            .into_iter()
+            .filter(|(serialized_file_id, _, stream)| {
+                self.get_resource_file_by_serialized_file_id_and_path(*serialized_file_id, stream)
+                    .is_none()
+            })
+            .collect()
+    }
+}
+
+/// Split `ids` into the groups of two or more whose bytes are equal,
+/// keeping one copy of each group's bytes. Ids keep their order.
+fn group_identical<K>(
+    ids: Vec<K>,
+    bytes_of: impl Fn(&K) -> ReadResult<Option<Vec<u8>>>,
+) -> ReadResult<Vec<Vec<K>>> {
+    let mut groups: Vec<(Vec<u8>, Vec<K>)> = Vec::new();
+    for id in ids {
+        let Some(data) = bytes_of(&id)? else {
+            continue;
+        };
+        match groups.iter_mut().find(|(bytes, _)| *bytes == data) {
+            Some((_, group)) => group.push(id),
+            None => groups.push((data, vec![id])),
+        }
+    }
+    Ok(groups
+        .into_iter()
+        .map(|(_, group)| group)
+        .filter(|group| group.len() > 1)
+        .collect())
+}
+
+/// Container entries of the AssetBundle at path id 1 and of any
+/// ResourceManager in the file, as name and asset PPtr.
+/// What [`read_containers`] finds in a serialized file.
+struct FileContainers {
+    /// `m_AssetBundleName` of the file's AssetBundle, if it has a name.
+    asset_bundle_name: Option<String>,
+    entries: Vec<(String, ContainerEntry)>,
+}
+
+fn read_containers(serialized_file: &SerializedFile) -> FileContainers {
+    let serialized_file_id = serialized_file.get_serialized_file_id();
+    let mut entries = Vec::new();
+    let mut asset_bundle_name = None;
+    let is_asset_bundle = serialized_file
+        .get_object_map()
+        .get(&1)
+        .is_some_and(|obj| obj.class == ClassIDType::AssetBundle as i32);
+    let asset_bundle = if is_asset_bundle {
+        serialized_file.get_tt_object_by_path_id(1).ok().flatten()
+    } else {
+        None
+    };
+    if let Some(asset_bundle) = asset_bundle {
+        let asset_bundle = asset_bundle.into();
+        asset_bundle_name = String::try_cast_from(&asset_bundle, "/Base/m_AssetBundleName")
+            .ok()
+            .filter(|name| !name.is_empty());
+        if let Ok(containers) = container_pairs(&asset_bundle) {
+            for (name, asset_info) in containers {
+                let pptr = TypeTreeObjectRef::try_cast_from(&asset_info, "/Base/asset")
+                    .and_then(|pptr| PPtr::new(&pptr).ids());
+                if let Ok(pptr) = pptr {
+                    entries.push((
+                        name,
+                        ContainerEntry {
+                            serialized_file_id,
+                            pptr,
+                            preload_index: i32::try_cast_from(&asset_info, "/Base/preloadIndex")
+                                .ok(),
+                            preload_size: i32::try_cast_from(&asset_info, "/Base/preloadSize").ok(),
+                        },
+                    ));
+                }
+            }
+        }
+    }
+
+    for (path_id, obj) in serialized_file.get_object_map() {
+        if obj.class == ClassIDType::ResourceManager as i32 {
+            if let Ok(Some(resource_manager)) = serialized_file.get_tt_object_by_path_id(*path_id) {
+                if let Ok(containers) = container_pairs(&resource_manager.into()) {
+                    entries.extend(containers.into_iter().filter_map(|(name, pptr)| {
+                        Some((
+                            name,
+                            ContainerEntry {
+                                serialized_file_id,
+                                pptr: PPtr::new(&pptr).ids().ok()?,
+                                preload_index: None,
+                                preload_size: None,
+                            },
+                        ))
+                    }));
+                }
+            }
+        }
+    }
+    FileContainers {
+        asset_bundle_name,
+        entries,
+    }
+}
+
+/// The name and value of every `m_Container` pair, in file order. Names
+/// repeat for assets with several objects, such as a sheet of sprites.
+/// Entries whose key is not a string are left out, and an array of
+/// anything but `pair`s is a [`Error::TypeMisMatch`], as when the container
+/// is read as a map.
+fn container_pairs(object: &TypeTreeObjectRef) -> ReadResult<Vec<(String, TypeTreeObjectRef)>> {
+    let pairs = <Vec<TypeTreeObjectRef>>::try_cast_from(object, "/Base/m_Container/Array")?;
+    if let Some(item_type) = pairs.first().and_then(TypeTreeObjectRef::get_type) {
+        if item_type != "pair" {
+            return Err(Error::TypeMisMatch {
+                want_to_cast: std::any::type_name::<Vec<(String, TypeTreeObjectRef)>>(),
+                found_type_name: item_type,
+            });
+        }
+    }
+    Ok(pairs
+        .iter()
+        .filter_map(|pair| {
+            Some((
+                String::try_cast_from(pair, "/Base/first").ok()?,
+                TypeTreeObjectRef::try_cast_from(pair, "/Base/second").ok()?,
+            ))
+        })
+        .collect())
+}
+
+/// Run `f`, timing it when the `timings` feature is enabled.
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    #[cfg(feature = "timings")]
+    let start = Instant::now();
+    let value = f();
+    #[cfg(feature = "timings")]
+    let elapsed = start.elapsed();
+    #[cfg(not(feature = "timings"))]
+    let elapsed = Duration::ZERO;
+    (value, elapsed)
+}
+
+/// Reduce an external file path to the flat name used as key in `cab_maps`.
+///
+/// `m_Externals` entries may look like `archive:/CAB-xxxx/CAB-xxxx`,
+/// `library/unity default resources` or a plain `sharedassets0.assets`,
+/// only the last path component is kept.
+pub fn normalize_external_name(name: &str) -> String {
+    let name = name.trim().replace('\\', "/");
+    let name = name.strip_prefix("archive:").unwrap_or(&name);
+    name.rsplit('/')
+        .find(|s| !s.is_empty())
+        .unwrap_or_default()
+        .to_owned()
+}
+
+/// Whether `path` is a raw `.resource`/`.resS` stream rather than a
+/// bundle or serialized file.
+pub fn is_resource_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("resource") || ext.eq_ignore_ascii_case("ress"))
+}
+
+/// Normalize a container name so that paths differing only in casing,
+/// separators or a leading `./`, `/` or `assets/` map to the same key.
+pub fn normalize_container_name(name: &str) -> String {
+    let name = name.trim().replace('\\', "/").to_lowercase();
+    let mut name = name.as_str();
+    loop {
+        let trimmed = name
+            .strip_prefix("./")
+            .or_else(|| name.strip_prefix('/'))
+            .or_else(|| name.strip_prefix("assets/"))
+            .unwrap_or(name);
+        if trimmed == name {
+            return name.to_owned();
+        }
+        name = trimmed;
     }
 }
 
@@ -355,13 +3448,18 @@ pub fn get_resource_file_by_path(
         .file_name()
         .map(|f| f.to_string_lossy().into_owned())
     {
-        if path.starts_with("archive:/") {
-            if let Some(unityfs) = unityfs {
-                if let Some(file_reader) = unityfs.get_file_reader_by_path(&file_name) {
+        // Prefer a stream of the owning bundle, whether the path names it by
+        // its place in the archive or only by file name, so bundles sharing
+        // stream names never read each other's data.
+        if let Some(unityfs) = unityfs {
+            let archive_path = path.strip_prefix("archive:/").map(str::to_owned);
+            for candidate in archive_path.iter().chain([&file_name]) {
+                if let Some(file_reader) = unityfs.get_file_reader_by_path(candidate) {
                     return Some(Box::new(file_reader));
                 }
             }
-        } else {
+        }
+        if !path.starts_with("archive:/") {
             if let Some(search_path) = search_path {
                 let path = PathBuf::from(search_path).join(&file_name);
                 if let Ok(file) = OpenOptions::new().read(true).open(path) {
@@ -393,3 +3491,696 @@ pub fn get_resource_file_by_path(
 
     None
 }
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct ContainerIndex {
+    bundles: Vec<IndexedBundle>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct IndexedBundle {
+    path: PathBuf,
+    size: u64,
+    /// Modification time as seconds and nanoseconds since the unix epoch.
+    modified: (u64, u32),
+    containers: Vec<IndexedContainer>,
+    /// Lowercased `m_AssetBundleName` by CAB name. Missing from indexes
+    /// saved before bundle names were kept.
+    #[serde(default)]
+    asset_bundle_names: BTreeMap<String, String>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct IndexedContainer {
+    cab_name: String,
+    name: String,
+    file_id: i64,
+    path_id: i64,
+    /// Missing from indexes saved before preload slices were kept.
+    #[serde(default)]
+    preload_index: Option<i32>,
+    #[serde(default)]
+    preload_size: Option<i32>,
+}
+
+#[cfg(feature = "serde")]
+fn file_stamp(path: &Path) -> Option<(u64, (u64, u32))> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let modified = metadata
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?;
+    Some((
+        metadata.len(),
+        (modified.as_secs(), modified.subsec_nanos()),
+    ))
+}
+
+/// The serialized file format [`UnityAssetViewer::repack_closure`] writes
+/// for a Unity version.
+fn repack_format(unity_version: &str) -> ReadResult<u32> {
+    let mut parts = unity_version.split('.').map(|part| {
+        part.split(|ch: char| !ch.is_ascii_digit())
+            .next()
+            .and_then(|digits| digits.parse::<u32>().ok())
+    });
+    match (parts.next().flatten(), parts.next().flatten()) {
+        (Some(major), Some(minor)) if (major, minor) >= (2020, 1) => Ok(22),
+        (Some(major), Some(_)) if major >= 5 => Ok(17),
+        _ => Err(Error::Other(format!(
+            "cannot repack for Unity version {unity_version}"
+        ))),
+    }
+}
+
+/// Store `value` in an `int` or `SInt64` field.
+fn write_int(field: &mut [u8], value: i64, big_endian: bool) {
+    match (field.len(), big_endian) {
+        (4, false) => field.copy_from_slice(&(value as i32).to_le_bytes()),
+        (4, true) => field.copy_from_slice(&(value as i32).to_be_bytes()),
+        (8, false) => field.copy_from_slice(&value.to_le_bytes()),
+        (8, true) => field.copy_from_slice(&value.to_be_bytes()),
+        _ => (),
+    }
+}
+
+/// The AssetBundle layout of Unity 2018 to 2022.
+fn repacked_asset_bundle_fields() -> Vec<Arc<Box<dyn TypeField + Send + Sync>>> {
+    let string = |level, name| {
+        [
+            TypeTreeNode::new(level, "string", name, -1),
+            TypeTreeNode::new(level + 1, "Array", "Array", -1).aligned(),
+            TypeTreeNode::new(level + 2, "int", "size", 4),
+            TypeTreeNode::new(level + 2, "char", "data", 1),
+        ]
+    };
+    let pptr = |level, name| {
+        [
+            TypeTreeNode::new(level, "PPtr<Object>", name, 12),
+            TypeTreeNode::new(level + 1, "int", "m_FileID", 4),
+            TypeTreeNode::new(level + 1, "SInt64", "m_PathID", 8),
+        ]
+    };
+    let asset_info = |level, name| {
+        let mut nodes = vec![
+            TypeTreeNode::new(level, "AssetInfo", name, -1),
+            TypeTreeNode::new(level + 1, "int", "preloadIndex", 4),
+            TypeTreeNode::new(level + 1, "int", "preloadSize", 4),
+        ];
+        nodes.extend(pptr(level + 1, "asset"));
+        nodes
+    };
+    let array = |level, type_name, name| {
+        [
+            TypeTreeNode::new(level, type_name, name, -1),
+            TypeTreeNode::new(level + 1, "Array", "Array", -1),
+            TypeTreeNode::new(level + 2, "int", "size", 4),
+        ]
+    };
+
+    let mut nodes = vec![TypeTreeNode::new(0, "AssetBundle", "Base", -1)];
+    nodes.extend(string(1, "m_Name"));
+    nodes.extend(array(1, "vector", "m_PreloadTable"));
+    nodes.extend(pptr(3, "data"));
+    nodes.extend(array(1, "map", "m_Container"));
+    nodes.push(TypeTreeNode::new(3, "pair", "data", -1));
+    nodes.extend(string(4, "first"));
+    nodes.extend(asset_info(4, "second"));
+    nodes.extend(asset_info(1, "m_MainAsset"));
+    nodes.push(TypeTreeNode::new(
+        1,
+        "unsigned int",
+        "m_RuntimeCompatibility",
+        4,
+    ));
+    nodes.extend(string(1, "m_AssetBundleName"));
+    nodes.extend(array(1, "vector", "m_Dependencies"));
+    nodes.extend(string(3, "data"));
+    nodes.push(TypeTreeNode::new(1, "bool", "m_IsStreamedSceneAssetBundle", 1).aligned());
+    nodes.push(TypeTreeNode::new(1, "int", "m_ExplicitDataLayout", 4));
+    nodes.push(TypeTreeNode::new(1, "int", "m_PathFlags", 4));
+    nodes.extend(array(1, "map", "m_SceneHashes"));
+    nodes.push(TypeTreeNode::new(3, "pair", "data", -1));
+    nodes.extend(string(4, "first"));
+    nodes.extend(string(4, "second"));
+    nodes
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut node)| {
+            node.index = index as i32;
+            Arc::new(Box::new(node) as Box<dyn TypeField + Send + Sync>)
+        })
+        .collect()
+}
+
+/// An AssetBundle of [`repacked_asset_bundle_fields`] with one container
+/// preloading every object of the bundle.
+fn repacked_asset_bundle_data(
+    big_endian: bool,
+    bundle_name: &str,
+    container_name: &str,
+    preload_table: &[i64],
+    path_id: i64,
+) -> Vec<u8> {
+    let mut data = Writer::new(big_endian);
+    data.aligned_string(bundle_name)
+        .i32(preload_table.len() as i32);
+    for path_id in preload_table {
+        data.i32(0).i64(*path_id);
+    }
+    data.i32(1)
+        .aligned_string(container_name)
+        .i32(0)
+        .i32(preload_table.len() as i32)
+        .i32(0)
+        .i64(path_id);
+    data.i32(0)
+        .i32(0)
+        .i32(0)
+        .i64(0) // m_MainAsset
+        .u32(1) // m_RuntimeCompatibility
+        .aligned_string(bundle_name)
+        .i32(0) // m_Dependencies
+        .bytes(&[0]) // m_IsStreamedSceneAssetBundle
+        .align(4, 0)
+        .i32(0) // m_ExplicitDataLayout
+        .i32(7) // m_PathFlags
+        .i32(0); // m_SceneHashes
+    data.data
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use binrw::{BinRead, Endian};
+
+    use super::*;
+    use crate::type_tree::{
+        reader::{
+            TypeTreeObjectBinReadArgs, TypeTreeObjectBinReadClassArgs,
+            TypeTreeObjectBinReadRefTypeArgs,
+        },
+        TypeField,
+    };
+
+    #[derive(Debug)]
+    struct TestTypeField {
+        level: u8,
+        type_name: String,
+        name: String,
+        byte_size: i32,
+        is_array: bool,
+    }
+
+    impl TypeField for TestTypeField {
+        fn get_version(&self) -> u16 {
+            1
+        }
+        fn get_level(&self) -> u8 {
+            self.level
+        }
+        fn is_array(&self) -> bool {
+            self.is_array
+        }
+        fn get_byte_size(&self) -> i32 {
+            self.byte_size
+        }
+        fn get_index(&self) -> i32 {
+            0
+        }
+        fn get_meta_flag(&self) -> i32 {
+            0
+        }
+        fn is_align(&self) -> bool {
+            false
+        }
+        fn get_ref_type_hash(&self) -> Option<u64> {
+            None
+        }
+        fn get_type(&self) -> &String {
+            &self.type_name
+        }
+        fn get_name(&self) -> &String {
+            &self.name
+        }
+    }
+
+    fn type_field(
+        level: u8,
+        type_name: &str,
+        name: &str,
+        byte_size: i32,
+    ) -> Arc<Box<dyn TypeField + Send + Sync>> {
+        Arc::new(Box::new(TestTypeField {
+            level,
+            type_name: type_name.to_owned(),
+            name: name.to_owned(),
+            byte_size,
+            is_array: name == "Array",
+        }))
+    }
+
+    /// A version 17 serialized file with no types, objects or externals.
+    fn empty_serialized_file() -> Vec<u8> {
+        let mut content = Vec::new();
+        content.extend_from_slice(b"2019.4.0f1\0");
+        content.extend_from_slice(&5i32.to_le_bytes()); // StandaloneWindows
+        content.push(1); // enable_type_tree
+        content.extend_from_slice(&0u32.to_le_bytes()); // types
+        content.extend_from_slice(&0i32.to_le_bytes()); // objects
+        content.extend_from_slice(&0i32.to_le_bytes()); // script types
+        content.extend_from_slice(&0i32.to_le_bytes()); // externals
+        content.push(0); // user_information
+
+        let header_size = 20;
+        let file_size = (header_size + content.len()) as u32;
+        let mut file = Vec::new();
+        file.extend_from_slice(&(content.len() as u32).to_be_bytes());
+        file.extend_from_slice(&file_size.to_be_bytes());
+        file.extend_from_slice(&17u32.to_be_bytes());
+        file.extend_from_slice(&file_size.to_be_bytes());
+        file.extend_from_slice(&[0, 0, 0, 0]); // little endian, reserved
+        file.extend(content);
+        file
+    }
+
+    #[test]
+    fn add_serialized_file_without_objects() {
+        let mut viewer = UnityAssetViewer::new();
+        let serialized_file_id = viewer
+            .add_serialized_file(Box::new(Cursor::new(empty_serialized_file())), None)
+            .unwrap();
+
+        let serialized_file = viewer.serialized_file_map.get(&serialized_file_id).unwrap();
+        assert!(serialized_file.get_object_map().is_empty());
+        assert!(serialized_file.types().is_empty());
+        assert!(serialized_file
+            .get_tt_object_by_path_id(1)
+            .unwrap()
+            .is_none());
+        assert!(viewer.container_maps.is_empty());
+    }
+
+    #[test]
+    fn malformed_serialized_file_fails_gracefully() {
+        let file = empty_serialized_file();
+        for len in 0..file.len() {
+            assert!(
+                SerializedFile::read(Box::new(Cursor::new(file[..len].to_vec())), 0, None).is_err()
+            );
+        }
+
+        // The object count follows the header, version, platform, flag and types.
+        let object_count_at = 20 + 11 + 4 + 1 + 4;
+        let mut corrupt = file.clone();
+        corrupt[object_count_at..object_count_at + 4].copy_from_slice(&i32::MAX.to_le_bytes());
+        assert!(SerializedFile::read(Box::new(Cursor::new(corrupt)), 0, None).is_err());
+    }
+
+    #[test]
+    fn composite_floats_honor_endianness() {
+        let type_fields = vec![
+            type_field(0, "Material", "Base", -1),
+            type_field(1, "Vector3f", "m_Position", 12),
+            type_field(2, "float", "x", 4),
+            type_field(2, "float", "y", 4),
+            type_field(2, "float", "z", 4),
+            type_field(1, "Quaternionf", "m_Rotation", 16),
+            type_field(2, "float", "x", 4),
+            type_field(2, "float", "y", 4),
+            type_field(2, "float", "z", 4),
+            type_field(2, "float", "w", 4),
+            type_field(1, "ColorRGBA", "m_Color", 16),
+            type_field(2, "float", "r", 4),
+            type_field(2, "float", "g", 4),
+            type_field(2, "float", "b", 4),
+            type_field(2, "float", "a", 4),
+            type_field(1, "ColorRGBA", "m_Color32", 4),
+            type_field(2, "unsigned int", "rgba", 4),
+        ];
+        let mut data = Vec::new();
+        for value in [1f32, 2., 3., 0., 0., 0., 1., 0.5, 0.25, 0.125, 1.] {
+            data.extend_from_slice(&value.to_be_bytes());
+        }
+        data.extend_from_slice(&0xff00_00ffu32.to_be_bytes());
+
+        let args = TypeTreeObjectBinReadArgs::new(
+            0,
+            1,
+            TypeTreeObjectBinReadClassArgs::new(21, type_fields),
+        );
+        let object =
+            TypeTreeObject::read_options(&mut Cursor::new(data), Endian::Big, args).unwrap();
+
+        assert_eq!(
+            object.get_vector3_by_path("/Base/m_Position").unwrap(),
+            glam::Vec3::new(1., 2., 3.)
+        );
+        assert_eq!(
+            object.get_quaternion_by_path("/Base/m_Rotation").unwrap(),
+            glam::Quat::IDENTITY
+        );
+        assert_eq!(
+            object.get_color_by_path("/Base/m_Color").unwrap(),
+            crate::type_tree::ColorRGBA {
+                r: 0.5,
+                g: 0.25,
+                b: 0.125,
+                a: 1.
+            }
+        );
+        let color32 = object.get_color_by_path("/Base/m_Color32").unwrap();
+        assert_eq!((color32.r, color32.g, color32.a), (1., 0., 1.));
+        assert!(object.get_vector4_by_path("/Base/m_Position").is_err());
+    }
+
+    #[test]
+    fn string_key_maps_skip_malformed_entries() {
+        let type_fields = vec![
+            type_field(0, "AssetBundle", "Base", -1),
+            type_field(1, "map", "m_Empty", -1),
+            type_field(2, "Array", "Array", -1),
+            type_field(3, "int", "size", 4),
+            type_field(3, "pair", "data", -1),
+            type_field(4, "int", "first", 4),
+            type_field(4, "int", "second", 4),
+            type_field(1, "map", "m_IntKeys", -1),
+            type_field(2, "Array", "Array", -1),
+            type_field(3, "int", "size", 4),
+            type_field(3, "pair", "data", -1),
+            type_field(4, "int", "first", 4),
+            type_field(4, "int", "second", 4),
+            type_field(1, "vector", "m_Ints", -1),
+            type_field(2, "Array", "Array", -1),
+            type_field(3, "int", "size", 4),
+            type_field(3, "int", "data", 4),
+        ];
+        let mut data = Vec::new();
+        for value in [0i32, 2, 1, 10, 2, 20, 1, 5] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let args = TypeTreeObjectBinReadArgs::new(
+            0,
+            1,
+            TypeTreeObjectBinReadClassArgs::new(142, type_fields),
+        );
+        let object: TypeTreeObjectRef =
+            TypeTreeObject::read_options(&mut Cursor::new(data), Endian::Little, args)
+                .unwrap()
+                .into();
+        let map = |path| <HashMap<String, TypeTreeObjectRef>>::try_cast_from(&object, path);
+
+        assert!(map("/Base/m_Empty/Array").unwrap().is_empty());
+        assert!(map("/Base/m_IntKeys/Array").unwrap().is_empty());
+        assert!(matches!(
+            map("/Base/m_Ints/Array"),
+            Err(Error::TypeMisMatch { .. })
+        ));
+        assert!(map("/Base/m_Missing/Array").is_err());
+    }
+
+    #[test]
+    fn container_pairs_reject_non_pair_arrays() {
+        let type_fields = vec![
+            type_field(0, "AssetBundle", "Base", -1),
+            type_field(1, "vector", "m_Container", -1),
+            type_field(2, "Array", "Array", -1),
+            type_field(3, "int", "size", 4),
+            type_field(3, "int", "data", 4),
+        ];
+        let mut data = Vec::new();
+        for value in [1i32, 5] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let args = TypeTreeObjectBinReadArgs::new(
+            0,
+            1,
+            TypeTreeObjectBinReadClassArgs::new(142, type_fields),
+        );
+        let object: TypeTreeObjectRef =
+            TypeTreeObject::read_options(&mut Cursor::new(data), Endian::Little, args)
+                .unwrap()
+                .into();
+        assert!(matches!(
+            container_pairs(&object),
+            Err(Error::TypeMisMatch { .. })
+        ));
+    }
+
+    #[test]
+    fn hashes_and_guids_print_as_hex() {
+        let mut type_fields = vec![
+            type_field(0, "MonoBehaviour", "Base", -1),
+            type_field(1, "Hash128", "m_Hash", 16),
+        ];
+        for index in 0..16 {
+            type_fields.push(type_field(2, "UInt8", &format!("bytes[{index}]"), 1));
+        }
+        type_fields.push(type_field(1, "GUID", "m_Guid", 16));
+        for index in 0..4 {
+            type_fields.push(type_field(2, "unsigned int", &format!("data[{index}]"), 4));
+        }
+        let mut data: Vec<u8> = (0..16).collect();
+        for value in [0u32, 0, 0xe, 0] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let args = TypeTreeObjectBinReadArgs::new(
+            0,
+            1,
+            TypeTreeObjectBinReadClassArgs::new(114, type_fields),
+        );
+        let object =
+            TypeTreeObject::read_options(&mut Cursor::new(data), Endian::Little, args).unwrap();
+
+        assert_eq!(
+            object
+                .get_hash128_by_path("/Base/m_Hash")
+                .unwrap()
+                .to_string(),
+            "000102030405060708090a0b0c0d0e0f"
+        );
+        // The GUID of Unity's builtin extra resources.
+        assert_eq!(
+            object
+                .get_hash128_by_path("/Base/m_Guid")
+                .unwrap()
+                .to_string(),
+            "0000000000000000e000000000000000"
+        );
+    }
+
+    #[test]
+    fn registered_class_name_overrides_builtin() {
+        let mut viewer = UnityAssetViewer::new();
+        assert_eq!(viewer.get_class_name(28).as_deref(), Some("Texture2D"));
+        assert_eq!(viewer.get_class_name(90001), None);
+
+        viewer.register_class_name(28, "ForkTexture".to_owned());
+        viewer.register_class_name(90001, "ForkOnly".to_owned());
+        assert_eq!(viewer.get_class_name(28).as_deref(), Some("ForkTexture"));
+        assert_eq!(viewer.get_class_name(90001).as_deref(), Some("ForkOnly"));
+        // The free function knows only the built-in names.
+        assert_eq!(crate::classes::class_name(28), "Texture2D");
+        assert_eq!(crate::classes::class_name(90001), "UnknownType");
+    }
+
+    #[test]
+    fn truncated_string_fails_gracefully() {
+        let type_fields = vec![
+            type_field(0, "TextAsset", "Base", -1),
+            type_field(1, "string", "m_Name", -1),
+            type_field(2, "Array", "Array", -1),
+            type_field(3, "int", "size", 4),
+            type_field(3, "char", "data", 1),
+            type_field(1, "int", "m_Value", 4),
+        ];
+        let read = |data: &[u8]| {
+            let args = TypeTreeObjectBinReadArgs::new(
+                0,
+                1,
+                TypeTreeObjectBinReadClassArgs::new(49, type_fields.clone()),
+            );
+            TypeTreeObject::read_options(&mut Cursor::new(data), Endian::Little, args)
+        };
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&5i32.to_le_bytes());
+        data.extend_from_slice(b"hello");
+        data.extend_from_slice(&7i32.to_le_bytes());
+        assert!(read(&data).is_ok());
+        for len in 0..data.len() {
+            assert!(read(&data[..len]).is_err());
+        }
+
+        for length in [i32::MAX, -1] {
+            let mut data = length.to_le_bytes().to_vec();
+            data.extend_from_slice(b"trunc");
+            let err: Error = read(&data).unwrap_err().into();
+            assert!(matches!(err, Error::CorruptString { .. }), "{err}");
+        }
+    }
+
+    #[test]
+    fn nested_pptr_resolves_against_owning_file() {
+        let mut viewer = UnityAssetViewer::new();
+        let other_id = viewer
+            .add_serialized_file(Box::new(Cursor::new(empty_serialized_file())), None)
+            .unwrap();
+        let owner_id = viewer
+            .add_serialized_file(Box::new(Cursor::new(empty_serialized_file())), None)
+            .unwrap();
+        assert_ne!(other_id, owner_id);
+
+        // m_Items: vector<Item { m_Refs: vector<PPtr<Object>> }>
+        let type_fields = vec![
+            type_field(0, "MonoBehaviour", "Base", -1),
+            type_field(1, "vector", "m_Items", -1),
+            type_field(2, "Array", "Array", -1),
+            type_field(3, "int", "size", 4),
+            type_field(3, "Item", "data", -1),
+            type_field(4, "vector", "m_Refs", -1),
+            type_field(5, "Array", "Array", -1),
+            type_field(6, "int", "size", 4),
+            type_field(6, "PPtr<Object>", "data", 12),
+            type_field(7, "int", "m_FileID", 4),
+            type_field(7, "SInt64", "m_PathID", 8),
+        ];
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&2i32.to_le_bytes());
+        for refs in [&[10i64][..], &[20, 30]] {
+            data.extend_from_slice(&(refs.len() as i32).to_le_bytes());
+            for path_id in refs {
+                data.extend_from_slice(&0i32.to_le_bytes());
+                data.extend_from_slice(&path_id.to_le_bytes());
+            }
+        }
+
+        let args = TypeTreeObjectBinReadArgs::new(
+            owner_id,
+            1,
+            TypeTreeObjectBinReadClassArgs::new(114, type_fields),
+        );
+        let object: TypeTreeObjectRef =
+            TypeTreeObject::read_options(&mut Cursor::new(data), Endian::Little, args)
+                .unwrap()
+                .into();
+
+        let items =
+            <Vec<TypeTreeObjectRef>>::try_cast_from(&object, "/Base/m_Items/Array").unwrap();
+        let refs =
+            <Vec<TypeTreeObjectRef>>::try_cast_from(&items[1], "/Base/m_Refs/Array").unwrap();
+        let pptr = PPtr::new(&refs[1]);
+
+        assert_eq!(pptr.get_serialized_file_id(), owner_id);
+        assert_eq!(pptr.get_path_id().unwrap(), 30);
+        let serialized_file = pptr
+            .get_serialized_file(
+                viewer
+                    .serialized_file_map
+                    .get(&pptr.get_serialized_file_id())
+                    .unwrap(),
+                Some(&viewer),
+            )
+            .unwrap();
+        assert_eq!(serialized_file.get_serialized_file_id(), owner_id);
+
+        let tree = object.display_tree();
+        assert!(tree.contains("      data : PPtr<Object> -> (file 0, path 30)\n"));
+    }
+
+    #[test]
+    fn managed_reference_resolves_to_concrete_type() {
+        let string_fields = |level: u8, name: &str| {
+            vec![
+                type_field(level, "string", name, -1),
+                type_field(level + 1, "Array", "Array", -1),
+                type_field(level + 2, "int", "size", 4),
+                type_field(level + 2, "char", "data", 1),
+            ]
+        };
+        let mut type_fields = vec![
+            type_field(0, "MonoBehaviour", "Base", -1),
+            type_field(1, "managedReference", "m_Ref", -1),
+            type_field(2, "SInt64", "rid", 8),
+            type_field(1, "ManagedReferencesRegistry", "references", -1),
+            type_field(2, "int", "version", 4),
+            type_field(2, "vector", "RefIds", -1),
+            type_field(3, "Array", "Array", -1),
+            type_field(4, "int", "size", 4),
+            type_field(4, "ReferencedObject", "data", -1),
+            type_field(5, "SInt64", "rid", 8),
+            type_field(5, "ReferencedManagedType", "type", -1),
+        ];
+        type_fields.extend(string_fields(6, "class"));
+        type_fields.extend(string_fields(6, "ns"));
+        type_fields.extend(string_fields(6, "asm"));
+        type_fields.push(type_field(5, "ReferencedObjectData", "data", 0));
+        let ref_type = TypeTreeObjectBinReadRefTypeArgs::new(
+            "Wolf".to_owned(),
+            String::new(),
+            "Game".to_owned(),
+            vec![
+                type_field(0, "Wolf", "Base", -1),
+                type_field(1, "int", "m_Teeth", 4),
+            ],
+        );
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&7i64.to_le_bytes());
+        data.extend_from_slice(&2i32.to_le_bytes());
+        data.extend_from_slice(&2i32.to_le_bytes());
+        for (rid, class, asm) in [(7i64, "Wolf", "Game"), (-2, "", "")] {
+            data.extend_from_slice(&rid.to_le_bytes());
+            for name in [class, "", asm] {
+                data.extend_from_slice(&(name.len() as i32).to_le_bytes());
+                data.extend_from_slice(name.as_bytes());
+            }
+            if rid == 7 {
+                data.extend_from_slice(&42i32.to_le_bytes());
+            }
+        }
+
+        let args = TypeTreeObjectBinReadArgs::new(
+            0,
+            1,
+            TypeTreeObjectBinReadClassArgs::new(114, type_fields).with_ref_types(vec![ref_type]),
+        );
+        let object: TypeTreeObjectRef =
+            TypeTreeObject::read_options(&mut Cursor::new(data), Endian::Little, args)
+                .unwrap()
+                .into();
+
+        let field = TypeTreeObjectRef::try_cast_from(&object, "/Base/m_Ref").unwrap();
+        let wolf = field.resolve_managed_reference().unwrap().unwrap();
+        assert_eq!(i32::try_cast_from(&wolf, "/Base/m_Teeth").unwrap(), 42);
+        assert!(object.get_managed_reference(3).is_none());
+        assert_eq!(
+            object
+                .get_managed_reference(-2)
+                .unwrap()
+                .get_type()
+                .unwrap(),
+            "ReferencedObjectData"
+        );
+    }
+
+    #[test]
+    fn duplicates_are_confirmed_by_their_bytes() {
+        // As if all six ids shared a hash bucket. Id 5 has no raw bytes.
+        let bytes = [&b"one"[..], b"two", b"one", b"three", b"two"];
+        let groups = group_identical((0..6).collect(), |id: &usize| {
+            Ok(bytes.get(*id).map(|bytes| bytes.to_vec()))
+        })
+        .unwrap();
+        assert_eq!(groups, [vec![0, 2], vec![1, 4]]);
+    }
+}
@@ -1,11 +1,12 @@
-use crate::until::binrw_parser::position_parser;
+use crate::error::Error;
+use crate::serialized_file::SerializedFile;
+use crate::until::binrw_parser::{bounded_vec_parser, position_parser};
 use binrw::{binrw, BinResult, Endian, NullString};
 use binrw::{io::Cursor, BinRead};
 use lz4::block::decompress;
 use modular_bitfield::specifiers::{B22, B9};
 use modular_bitfield::{bitfield, BitfieldSpecifier};
 use num_enum::TryFromPrimitive;
-use std::collections::BTreeMap;
 use std::io::{prelude::*, ErrorKind, SeekFrom};
 use std::sync::{Arc, Mutex};
 
@@ -13,6 +14,16 @@ pub trait UnityResource: std::io::Read + std::io::Seek {}
 
 impl<T: std::io::Read + std::io::Seek> UnityResource for T {}
 
+/// Placement of the blocks info, which lists the data blocks and the files
+/// in them. The data blocks follow the header either way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlocksInfoLocation {
+    /// Right after the header, before the data blocks.
+    AfterHeader,
+    /// In the last bytes of the file, after the data blocks.
+    AtTheEnd,
+}
+
 #[bitfield]
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[binrw]
@@ -39,16 +50,44 @@ pub struct ArchiveFlags {
 pub struct StorageBlockFlags {
     #[bits = 6]
     compression_type: CompressionType,
-    #[allow(dead_code)]
+    /// Set on blocks compressed as part of one stream with the blocks
+    /// before them, which may then refer back into their data.
     streamed: bool,
     #[skip]
     __: B9,
 }
 
-#[derive(Debug, Eq, PartialEq, TryFromPrimitive, BitfieldSpecifier)]
+impl ArchiveFlags {
+    /// The compression of the blocks info, failing with
+    /// [`Error::UnsupportedCompression`] for values this crate does not know.
+    fn compression(&self) -> std::io::Result<CompressionType> {
+        self.compression_type_or_err()
+            .map_err(|err| unsupported_compression(err.invalid_bytes() as u32))
+    }
+}
+
+impl StorageBlockFlags {
+    /// See [`ArchiveFlags::compression`].
+    fn compression(&self) -> std::io::Result<CompressionType> {
+        self.compression_type_or_err()
+            .map_err(|err| unsupported_compression(err.invalid_bytes() as u32))
+    }
+}
+
+fn unsupported_compression(compression_type: u32) -> std::io::Error {
+    std::io::Error::new(
+        ErrorKind::Unsupported,
+        Error::UnsupportedCompression(compression_type),
+    )
+}
+
+/// LZMA and LZHAM are named so that bundles using them fail with
+/// [`Error::UnsupportedCompression`] instead of being misread; only
+/// uncompressed and LZ4 blocks are decoded.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, TryFromPrimitive, BitfieldSpecifier)]
 #[repr(u32)]
 #[bits = 6]
-enum CompressionType {
+pub enum CompressionType {
     None = 0,
     Lzma,
     Lz4,
@@ -62,21 +101,71 @@ pub struct UnityFS {
     file_reader: Arc<Mutex<Box<dyn UnityResource + Send>>>,
     pub resource_search_path: Option<String>,
     storage_blocks_start_positions: Vec<(u64, u64)>,
+    cab_paths: Vec<String>,
+    trailer: Vec<u8>,
 }
 
+/// The fixed fields at the start of a bundle, as stored.
 #[binrw]
 #[brw(big)]
 #[brw(magic = b"UnityFS\0")]
 #[derive(Clone, Debug, PartialEq)]
-pub struct UnityFSFile {
+pub struct UnityFSHeader {
     version: u32,
     unity_version: NullString,
     unity_revision: NullString,
     size: i64,
+    /// The compressed size comes first in every UnityFS version, 6 to 8.
+    /// Only the older UnityWeb and UnityRaw archives lay sizes out
+    /// differently, and those are not read by this crate.
     compressed_blocks_info_size: u32,
     uncompressed_blocks_info_size: u32,
     flags: ArchiveFlags,
-    #[br(parse_with = blocks_info_parser, args (version, compressed_blocks_info_size,uncompressed_blocks_info_size,flags))]
+}
+
+impl UnityFSHeader {
+    /// Always `UnityFS`: files with any other signature fail to read.
+    pub fn signature(&self) -> &'static str {
+        "UnityFS"
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn unity_version(&self) -> String {
+        self.unity_version.to_string()
+    }
+
+    pub fn unity_revision(&self) -> String {
+        self.unity_revision.to_string()
+    }
+
+    /// Size of the whole bundle in bytes, as declared.
+    pub fn size(&self) -> i64 {
+        self.size
+    }
+
+    pub fn compressed_blocks_info_size(&self) -> u32 {
+        self.compressed_blocks_info_size
+    }
+
+    pub fn uncompressed_blocks_info_size(&self) -> u32 {
+        self.uncompressed_blocks_info_size
+    }
+
+    /// The raw archive flags, whose low bits are the blocks info compression.
+    pub fn flags(&self) -> u32 {
+        u32::from_le_bytes(self.flags.into_bytes())
+    }
+}
+
+#[binrw]
+#[brw(big)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnityFSFile {
+    header: UnityFSHeader,
+    #[br(parse_with = blocks_info_parser, args (header.version, header.compressed_blocks_info_size, header.uncompressed_blocks_info_size, header.flags, header.size))]
     blocks_info: BlocksInfo,
     #[br(parse_with = position_parser)]
     #[bw(ignore)]
@@ -101,7 +190,7 @@ impl UnityFS {
                     storage_blocks: self.content.blocks_info.storage_blocks.clone(),
                     storage_blocks_start_positions: self.storage_blocks_start_positions.clone(),
                     storage_block_position: self.content.position,
-                    storage_blocks_cache: BTreeMap::new(),
+                    storage_blocks_cache: Vec::new(),
                     node_info: node.clone(),
                     current_position: 0,
                 });
@@ -110,53 +199,264 @@ impl UnityFS {
         None
     }
 
+    /// Write a file of the bundle to `writer` one decompressed block at a
+    /// time, so it never has to be held in memory as a whole.
+    pub fn write_file_by_path<W: Write>(
+        &self,
+        path: &String,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        for node in &self.content.blocks_info.directory_info {
+            if path == &node.path() {
+                return self.write_file_by_node(node, writer).map(|_| ());
+            }
+        }
+        Err(std::io::Error::from(ErrorKind::NotFound))
+    }
+
+    /// Like [`UnityFS::write_file_by_path`], giving the number of bytes
+    /// written, such as for copying a `.resS` out of the bundle to disk.
+    pub fn extract_file_to<W: Write>(&self, path: &str, writer: &mut W) -> std::io::Result<u64> {
+        for node in &self.content.blocks_info.directory_info {
+            if path == node.path() {
+                return self.write_file_by_node(node, writer);
+            }
+        }
+        Err(std::io::Error::from(ErrorKind::NotFound))
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    fn get_file_by_node(&self, node: &Node) -> std::io::Result<Vec<u8>> {
+        let mut file_block = Vec::with_capacity(node.size as usize);
+        self.write_file_by_node(node, &mut file_block)?;
+        Ok(file_block)
+    }
+
+    /// Blocks are independent (see [`StorageBlock::decompress`]), so they
+    /// are decompressed in parallel once their compressed bytes are read,
+    /// then joined in order.
+    #[cfg(feature = "parallel")]
     fn get_file_by_node(&self, node: &Node) -> std::io::Result<Vec<u8>> {
+        use rayon::prelude::*;
+
+        let blocks = self
+            .node_blocks(node)?
+            .into_iter()
+            .map(|block| Ok((self.read_storage_block(&block)?, block)))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        let parts = blocks
+            .into_par_iter()
+            .map(|(compressed, block)| {
+                let mut data = block.storage_block.decompress(compressed)?;
+                data.truncate(block.end);
+                data.drain(..block.start);
+                Ok(data)
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+        Ok(parts.concat())
+    }
+
+    fn write_file_by_node<W: Write>(&self, node: &Node, writer: &mut W) -> std::io::Result<u64> {
+        let mut written = 0;
+        for block in self.node_blocks(node)? {
+            let blocks_info_uncompressedd_stream = block
+                .storage_block
+                .decompress(self.read_storage_block(&block)?)?;
+            writer.write_all(&blocks_info_uncompressedd_stream[block.start..block.end])?;
+            written += (block.end - block.start) as u64;
+        }
+        Ok(written)
+    }
+
+    /// The storage blocks overlapping `node`, with the range of each
+    /// uncompressed block that belongs to it.
+    fn node_blocks(&self, node: &Node) -> std::io::Result<Vec<NodeBlock>> {
+        let node_start = node.offset as u64;
+        let node_end = node_start + node.size as u64;
         let mut compressed_data_offset = 0u64;
         let mut uncompressed_data_offset = 0u64;
-        let mut file_block = Vec::new();
+        let mut covered = 0u64;
+        let mut blocks = Vec::new();
         for sb in &self.content.blocks_info.storage_blocks {
-            if (uncompressed_data_offset + (sb.uncompressed_size as u64)) >= node.offset as u64 {
-                let mut blocks_infocompressedd_stream = vec![0u8; sb.compressed_size as usize];
-                if let Ok(mut file_reader) = self.file_reader.lock() {
-                    file_reader.seek(SeekFrom::Start(
-                        compressed_data_offset + self.content.position,
-                    ))?;
-                    file_reader.read_exact(&mut blocks_infocompressedd_stream)?;
-                } else {
-                    return Err(std::io::Error::from(ErrorKind::BrokenPipe));
-                }
-
-                let mut blocks_info_uncompressedd_stream = block_uncompressed(
-                    sb.uncompressed_size as u64,
-                    sb.flags.compression_type(),
-                    blocks_infocompressedd_stream,
-                )?;
-                if uncompressed_data_offset < node.offset as u64 {
-                    blocks_info_uncompressedd_stream = blocks_info_uncompressedd_stream
-                        [(node.offset as u64 - uncompressed_data_offset) as usize..]
-                        .to_vec();
-                }
-                file_block.extend(blocks_info_uncompressedd_stream);
-                if file_block.len() >= node.size as usize {
-                    file_block.truncate(node.size as usize);
-                    return Ok(file_block);
-                }
+            let block_end = uncompressed_data_offset + sb.uncompressed_size as u64;
+            if block_end > node_start && uncompressed_data_offset < node_end {
+                let start = node_start.saturating_sub(uncompressed_data_offset) as usize;
+                let end = (node_end.min(block_end) - uncompressed_data_offset) as usize;
+                blocks.push(NodeBlock {
+                    storage_block: sb.clone(),
+                    compressed_data_offset,
+                    start,
+                    end,
+                });
+                covered += (end - start) as u64;
+            }
+            if block_end >= node_end {
+                break;
             }
             compressed_data_offset += sb.compressed_size as u64;
             uncompressed_data_offset += sb.uncompressed_size as u64;
         }
-        Err(std::io::Error::from(ErrorKind::NotFound))
+        if covered != node.size as u64 {
+            return Err(std::io::Error::from(ErrorKind::NotFound));
+        }
+        Ok(blocks)
     }
 
-    pub fn get_cab_path(&self) -> Vec<String> {
-        let mut paths = vec![];
-        for file in &self.content.blocks_info.directory_info {
-            let path = file.path();
-            if path.starts_with("CAB-") && (path.len() == 36) {
-                paths.push(path);
+    fn read_storage_block(&self, block: &NodeBlock) -> std::io::Result<Vec<u8>> {
+        let mut blocks_infocompressedd_stream =
+            vec![0u8; block.storage_block.compressed_size as usize];
+        if let Ok(mut file_reader) = self.file_reader.lock() {
+            file_reader.seek(SeekFrom::Start(
+                block.compressed_data_offset + self.content.position,
+            ))?;
+            file_reader.read_exact(&mut blocks_infocompressedd_stream)?;
+        } else {
+            return Err(std::io::Error::from(ErrorKind::BrokenPipe));
+        }
+        Ok(blocks_infocompressedd_stream)
+    }
+
+    /// Decompress every block and check it against its declared size, then
+    /// check every directory entry lies within the data and every serialized
+    /// file among them parses. All problems found are returned, not only
+    /// the first.
+    pub fn verify(&self) -> Result<(), Vec<BundleIssue>> {
+        let mut issues = Vec::new();
+        let mut uncompressed_total = 0u64;
+        for (index, (sb, (compressed_data_offset, _))) in self
+            .content
+            .blocks_info
+            .storage_blocks
+            .iter()
+            .zip(&self.storage_blocks_start_positions)
+            .enumerate()
+        {
+            let block = NodeBlock {
+                storage_block: sb.clone(),
+                compressed_data_offset: *compressed_data_offset,
+                start: 0,
+                end: sb.uncompressed_size as usize,
+            };
+            if let Err(err) = self
+                .read_storage_block(&block)
+                .and_then(|compressed| sb.decompress(compressed))
+            {
+                issues.push(BundleIssue::Block {
+                    index,
+                    message: err.to_string(),
+                });
             }
+            uncompressed_total += sb.uncompressed_size as u64;
+        }
+
+        for node in &self.content.blocks_info.directory_info {
+            let path = node.path();
+            if node.offset < 0 || node.size < 0 {
+                issues.push(BundleIssue::File {
+                    path,
+                    message: format!("negative offset {} or size {}", node.offset, node.size),
+                });
+                continue;
+            }
+            let end = node.offset as u64 + node.size as u64;
+            if end > uncompressed_total {
+                issues.push(BundleIssue::File {
+                    path,
+                    message: format!(
+                        "ends at {} past the {} bytes of the blocks",
+                        end, uncompressed_total
+                    ),
+                });
+                continue;
+            }
+            if node.flags & NODE_FLAG_SERIALIZED_FILE == 0 {
+                continue;
+            }
+            let result = self
+                .get_file_by_node(node)
+                .map_err(|err| err.to_string())
+                .and_then(|data| {
+                    SerializedFile::read(Box::new(Cursor::new(data)), 0, None)
+                        .map_err(|err| err.to_string())
+                });
+            if let Err(message) = result {
+                issues.push(BundleIssue::File { path, message });
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+
+    /// Compression of the blocks info header and of each data block, in order.
+    pub fn compression_info(&self) -> (CompressionType, Vec<CompressionType>) {
+        (
+            self.content.header.flags.compression_type(),
+            self.content
+                .blocks_info
+                .storage_blocks
+                .iter()
+                .map(|sb| sb.flags.compression_type())
+                .collect(),
+        )
+    }
+
+    pub fn header(&self) -> &UnityFSHeader {
+        &self.content.header
+    }
+
+    /// Bytes stored after the archive, past the size its header declares,
+    /// such as a hash appended by a distribution pipeline. Empty for most
+    /// bundles.
+    pub fn trailer(&self) -> &[u8] {
+        &self.trailer
+    }
+
+    /// The full version of the Unity editor that built the bundle, like
+    /// `2021.3.16f1`, for picking a matching TypeTree database. Unlike
+    /// [`UnityFSHeader::unity_version`], which is often just `5.x.x`.
+    pub fn unity_revision(&self) -> &str {
+        std::str::from_utf8(&self.content.header.unity_revision).unwrap_or_default()
+    }
+
+    /// Where the blocks info was stored in the file.
+    pub fn blocks_info_location(&self) -> BlocksInfoLocation {
+        if self.content.header.flags.blocks_info_at_the_end() {
+            BlocksInfoLocation::AtTheEnd
+        } else {
+            BlocksInfoLocation::AfterHeader
         }
-        paths
+    }
+
+    /// Entries of the bundle directory. Only the header and blocks info are
+    /// parsed by [`UnityFS::read`], so this never touches the data blocks.
+    pub fn peek_directory(&self) -> &[Node] {
+        &self.content.blocks_info.directory_info
+    }
+
+    pub fn get_cab_path(&self) -> Vec<String> {
+        self.cab_paths.clone()
+    }
+
+    /// Same as [`Self::get_cab_path`], borrowing the paths collected when
+    /// the bundle was read.
+    pub fn cab_paths(&self) -> &[String] {
+        &self.cab_paths
+    }
+
+    /// Read the bundle through `reader` from now on, dropping the one it was
+    /// read with. `reader` must read the same bytes. Fails if a reader
+    /// panicked while holding the current one.
+    pub fn replace_reader(&self, reader: Box<dyn UnityResource + Send>) -> std::io::Result<()> {
+        let mut file_reader = self
+            .file_reader
+            .lock()
+            .map_err(|_| std::io::Error::from(ErrorKind::BrokenPipe))?;
+        *file_reader = reader;
+        Ok(())
     }
 
     pub fn get_file_paths(&self) -> Vec<String> {
@@ -167,11 +467,27 @@ impl UnityFS {
         paths
     }
 
+    /// Read a bundle over HTTP, fetching only the ranges that are read.
+    #[cfg(feature = "remote")]
+    pub fn read_remote(url: &str) -> BinResult<UnityFS> {
+        let reader = crate::remote::HttpRangeReader::new(url)?;
+        UnityFS::read(Box::new(reader), None)
+    }
+
     pub fn read(
         mut file: Box<dyn UnityResource + Send>,
         resource_search_path: Option<String>,
     ) -> BinResult<UnityFS> {
         let content = UnityFSFile::read(&mut file)?;
+        let stream_size = file.seek(SeekFrom::End(0))?;
+        let archive_end = archive_end(content.header.size, stream_size);
+        let data_size = archive_end.saturating_sub(content.position);
+        check_blocks_info(&content.blocks_info, data_size).map_err(|message| {
+            binrw::Error::AssertFail {
+                pos: content.position,
+                message,
+            }
+        })?;
         let storage_blocks_start_positions = {
             let mut compressed_data_offset = 0;
             let mut uncompressed_data_offset = 0;
@@ -183,15 +499,106 @@ impl UnityFS {
             }
             storage_blocks_positions
         };
+        let cab_paths = content
+            .blocks_info
+            .directory_info
+            .iter()
+            .map(Node::path)
+            .filter(|path| path.starts_with("CAB-") && (path.len() == 36))
+            .collect();
+        let mut trailer = vec![0u8; (stream_size - archive_end) as usize];
+        if !trailer.is_empty() {
+            file.seek(SeekFrom::Start(archive_end))?;
+            file.read_exact(&mut trailer)?;
+        }
         Ok(UnityFS {
             content,
             file_reader: Arc::new(Mutex::new(file)),
             resource_search_path,
             storage_blocks_start_positions,
+            cab_paths,
+            trailer,
         })
     }
 }
 
+/// Where the archive ends in a stream of `stream_size` bytes: at its
+/// declared size, so that bytes appended after it are not taken for the
+/// blocks info, or at the end of the stream when the declared size does
+/// not fit in it.
+fn archive_end(declared_size: i64, stream_size: u64) -> u64 {
+    match u64::try_from(declared_size) {
+        Ok(size) if size > 0 && size <= stream_size => size,
+        _ => stream_size,
+    }
+}
+
+/// LZ4 never expands data by more than this factor, which bounds the
+/// allocation for a declared uncompressed size.
+const MAX_LZ4_RATIO: u64 = 255;
+
+/// Whether a block of `compressed_size` bytes can hold `uncompressed_size`
+/// bytes, checked before its uncompressed buffer is allocated.
+fn check_block_size(
+    compressed_size: u64,
+    uncompressed_size: u64,
+    flag: CompressionType,
+) -> Result<(), String> {
+    let max_size = match flag {
+        CompressionType::None => compressed_size,
+        _ => compressed_size
+            .saturating_mul(MAX_LZ4_RATIO)
+            .saturating_add(16),
+    };
+    if uncompressed_size > max_size || uncompressed_size > i32::MAX as u64 {
+        return Err(format!(
+            "block of {} bytes cannot hold {} uncompressed bytes",
+            compressed_size, uncompressed_size
+        ));
+    }
+    Ok(())
+}
+
+/// Blocks must fit in the `data_size` bytes following the blocks info, and
+/// files in the uncompressed blocks.
+fn check_blocks_info(blocks_info: &BlocksInfo, data_size: u64) -> Result<(), String> {
+    let mut compressed_total = 0u64;
+    let mut uncompressed_total = 0u64;
+    for sb in &blocks_info.storage_blocks {
+        if sb.compressed_size < 0 {
+            return Err(format!("negative block size {}", sb.compressed_size));
+        }
+        check_block_size(
+            sb.compressed_size as u64,
+            sb.uncompressed_size as u64,
+            sb.flags.compression().map_err(|err| err.to_string())?,
+        )?;
+        compressed_total += sb.compressed_size as u64;
+        uncompressed_total += sb.uncompressed_size as u64;
+    }
+    if compressed_total > data_size {
+        return Err(format!(
+            "blocks of {} bytes exceed the {} bytes of data",
+            compressed_total, data_size
+        ));
+    }
+    for node in &blocks_info.directory_info {
+        if node.offset < 0
+            || node.size < 0
+            || (node.offset as u64).saturating_add(node.size as u64) > uncompressed_total
+        {
+            return Err(format!(
+                "file {} at {} of {} bytes is outside the {} bytes of blocks",
+                node.path(),
+                node.offset,
+                node.size,
+                uncompressed_total
+            ));
+        }
+    }
+    Ok(())
+}
+
 fn block_uncompressed(
     uncompressed_size: u64,
     flag: CompressionType,
@@ -199,13 +606,24 @@ fn block_uncompressed(
 ) -> std::io::Result<Vec<u8>> {
     let blocks_info_uncompressedd_stream = match flag {
         CompressionType::None => blocks_infocompressedd_stream,
-        CompressionType::Lzma => todo!(),
         CompressionType::Lz4 | CompressionType::Lz4HC => decompress(
             &blocks_infocompressedd_stream,
             Some(uncompressed_size as i32),
         )?,
-        CompressionType::Lzham => todo!(),
+        CompressionType::Lzma | CompressionType::Lzham => {
+            return Err(unsupported_compression(flag as u32))
+        }
     };
+    if blocks_info_uncompressedd_stream.len() as u64 != uncompressed_size {
+        return Err(std::io::Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "block decompressed to {} bytes instead of {}",
+                blocks_info_uncompressedd_stream.len(),
+                uncompressed_size
+            ),
+        ));
+    }
     Ok(blocks_info_uncompressedd_stream)
 }
 
@@ -215,13 +633,34 @@ fn block_uncompressed(
 struct BlocksInfo {
     uncompressed_data_hash: [u8; 16],
     blocks_info_count: u32,
-    #[br(count = blocks_info_count)]
+    #[br(count = blocks_info_count, parse_with = bounded_vec_parser)]
     storage_blocks: Vec<StorageBlock>,
     nodes_count: u32,
-    #[br(count = nodes_count)]
+    #[br(count = nodes_count, parse_with = bounded_vec_parser)]
     directory_info: Vec<Node>,
 }
 
+/// Directory entry flag Unity sets on serialized files.
+const NODE_FLAG_SERIALIZED_FILE: u32 = 4;
+
+/// A problem found by [`UnityFS::verify`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum BundleIssue {
+    /// A data block could not be read or did not decompress to its size.
+    Block { index: usize, message: String },
+    /// A directory entry lies outside the data, or is a serialized file
+    /// that does not parse.
+    File { path: String, message: String },
+}
+
+/// The part of a storage block that belongs to one directory node.
+struct NodeBlock {
+    storage_block: StorageBlock,
+    compressed_data_offset: u64,
+    start: usize,
+    end: usize,
+}
+
 #[binrw]
 #[br(big)]
 #[derive(Clone, Debug, PartialEq)]
@@ -231,6 +670,31 @@ struct StorageBlock {
     flags: StorageBlockFlags,
 }
 
+impl StorageBlock {
+    /// Decompress the block on its own. Unity compresses every LZ4 block
+    /// independently, which lets blocks be read in any order and in
+    /// parallel. A block flagged as streamed may depend on the output of
+    /// the blocks before it; decoding it alone fails rather than produce
+    /// corrupt data, and the error says why.
+    fn decompress(&self, compressed: Vec<u8>) -> std::io::Result<Vec<u8>> {
+        block_uncompressed(
+            self.uncompressed_size as u64,
+            self.flags.compression()?,
+            compressed,
+        )
+        .map_err(|err| {
+            if self.flags.streamed() {
+                std::io::Error::new(
+                    ErrorKind::Unsupported,
+                    format!("streamed block depends on the blocks before it: {err}"),
+                )
+            } else {
+                err
+            }
+        })
+    }
+}
+
 #[binrw]
 #[br(big)]
 #[derive(Clone, Debug, PartialEq)]
@@ -245,15 +709,30 @@ impl Node {
     pub fn path(&self) -> String {
         self.path.clone().to_string()
     }
+
+    /// Offset of the file in the uncompressed data of all blocks.
+    pub fn offset(&self) -> i64 {
+        self.offset
+    }
+
+    pub fn size(&self) -> i64 {
+        self.size
+    }
+
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
 }
 
 fn blocks_info_parser<R: Read + Seek>(
     reader: &mut R,
     _endian: Endian,
-    flags: (u32, u32, u32, ArchiveFlags),
+    flags: (u32, u32, u32, ArchiveFlags, i64),
 ) -> BinResult<BlocksInfo> {
-    let (version, compressed_blocks_info_size, uncompressed_blocks_info_size, flags) = flags;
+    let (version, compressed_blocks_info_size, uncompressed_blocks_info_size, flags, size) = flags;
 
+    // Version 7 bundles, from Unity 2019.4 on, align the blocks info to 16
+    // bytes, or where it would be when it is stored at the end.
     if version >= 7 {
         let pos = reader.stream_position()?;
         if pos % 16 != 0 {
@@ -261,17 +740,41 @@ fn blocks_info_parser<R: Read + Seek>(
         }
     }
 
+    let compression = flags.compression()?;
+    let pos = reader.stream_position()?;
+    let stream_size = archive_end(size, reader.seek(SeekFrom::End(0))?);
+    reader.seek(SeekFrom::Start(pos))?;
+    check_block_size(
+        compressed_blocks_info_size as u64,
+        uncompressed_blocks_info_size as u64,
+        compression,
+    )
+    .and_then(|_| {
+        if compressed_blocks_info_size as u64 > stream_size {
+            Err(format!(
+                "blocks info of {} bytes exceeds the file size {}",
+                compressed_blocks_info_size, stream_size
+            ))
+        } else {
+            Ok(())
+        }
+    })
+    .map_err(|message| binrw::Error::AssertFail { pos, message })?;
+
     let mut blocks_infocompressedd_stream = vec![0u8; compressed_blocks_info_size as usize];
 
     if flags.blocks_info_at_the_end() {
         let pos = reader.stream_position()?;
-        reader.seek(SeekFrom::End(-(compressed_blocks_info_size as i64)))?;
+        reader.seek(SeekFrom::Start(
+            stream_size - compressed_blocks_info_size as u64,
+        ))?;
         reader.read_exact(&mut blocks_infocompressedd_stream)?;
         reader.seek(SeekFrom::Start(pos))?;
     } else {
         reader.read_exact(&mut blocks_infocompressedd_stream)?;
     }
 
+    // The data blocks are aligned in turn when the flag asks for it.
     if flags.block_info_need_padding_at_start() {
         let pos = reader.stream_position()?;
         if pos % 16 != 0 {
@@ -281,7 +784,7 @@ fn blocks_info_parser<R: Read + Seek>(
 
     let blocks_info_uncompressedd_stream = block_uncompressed(
         uncompressed_blocks_info_size as u64,
-        flags.compression_type(),
+        compression,
         blocks_infocompressedd_stream,
     )?;
 
@@ -289,6 +792,10 @@ fn blocks_info_parser<R: Read + Seek>(
     BlocksInfo::read(&mut blocks_info_reader)
 }
 
+/// How many decompressed blocks a [`UnityFSNode`] keeps, so that reading a
+/// whole CAB through one holds a few blocks at a time rather than all of them.
+const CACHED_STORAGE_BLOCKS: usize = 4;
+
 #[derive(Clone)]
 pub struct UnityFSNode {
     file_reader: Arc<Mutex<Box<dyn UnityResource + Send>>>,
@@ -297,11 +804,20 @@ pub struct UnityFSNode {
     storage_block_position: u64,
     node_info: Node,
     current_position: u64,
-    storage_blocks_cache: BTreeMap<u64, Vec<u8>>,
+    /// Decompressed blocks by uncompressed offset, least recently used
+    /// first, at most [`CACHED_STORAGE_BLOCKS`] of them.
+    storage_blocks_cache: Vec<(u64, Vec<u8>)>,
 }
 
 impl Read for UnityFSNode {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        // Stop at the end of the file rather than reading into the next one.
+        let remaining = (self.node_info.size.max(0) as u64).saturating_sub(self.current_position);
+        if remaining == 0 || buf.is_empty() {
+            return Ok(0);
+        }
+        let len = buf.len().min(remaining as usize);
+        let buf = &mut buf[..len];
         let uncompressed_data_read_start_offset =
             (self.node_info.offset as u64) + self.current_position;
         let storage_blocks_index = match self.storage_blocks_start_positions.binary_search_by_key(
@@ -309,19 +825,21 @@ impl Read for UnityFSNode {
             |&(_compressed_data_offset, uncompressed_data_offset)| uncompressed_data_offset,
         ) {
             Ok(index) => index,
+            Err(0) => return Ok(0),
             Err(rindex) => rindex - 1,
         };
         let (mut compressed_data_offset, mut uncompressed_data_offset) =
             self.storage_blocks_start_positions[storage_blocks_index];
-        let mut file_block = Vec::new();
         for sb in &self.storage_blocks[storage_blocks_index..] {
             if (uncompressed_data_offset + (sb.uncompressed_size as u64))
                 >= ((self.node_info.offset as u64) + self.current_position)
             {
-                let blocks_info_uncompressedd_stream = if let Some(cache_block) =
-                    self.storage_blocks_cache.get(&uncompressed_data_offset)
-                {
-                    cache_block
+                let cached = self
+                    .storage_blocks_cache
+                    .iter()
+                    .position(|(offset, _)| *offset == uncompressed_data_offset);
+                let cache_block = if let Some(cached) = cached {
+                    self.storage_blocks_cache.remove(cached)
                 } else {
                     let mut blocks_infocompressedd_stream = vec![0u8; sb.compressed_size as usize];
                     if let Ok(mut file_reader) = self.file_reader.lock() {
@@ -333,45 +851,36 @@ impl Read for UnityFSNode {
                         return Err(std::io::Error::from(ErrorKind::BrokenPipe));
                     }
 
-                    let blocks_info_uncompressedd_stream = block_uncompressed(
-                        sb.uncompressed_size as u64,
-                        sb.flags.compression_type(),
-                        blocks_infocompressedd_stream,
-                    )?;
-                    self.storage_blocks_cache
-                        .insert(uncompressed_data_offset, blocks_info_uncompressedd_stream);
-                    self.storage_blocks_cache
-                        .get(&uncompressed_data_offset)
-                        .unwrap()
+                    if self.storage_blocks_cache.len() >= CACHED_STORAGE_BLOCKS {
+                        self.storage_blocks_cache.remove(0);
+                    }
+                    (
+                        uncompressed_data_offset,
+                        sb.decompress(blocks_infocompressedd_stream)?,
+                    )
                 };
+                self.storage_blocks_cache.push(cache_block);
+                let (_, blocks_info_uncompressedd_stream) =
+                    self.storage_blocks_cache.last().unwrap();
 
-                if uncompressed_data_offset
-                    < ((self.node_info.offset as u64) + self.current_position)
-                {
-                    file_block.extend_from_slice(
-                        &blocks_info_uncompressedd_stream[(((self.node_info.offset as u64)
-                            + self.current_position)
-                            - uncompressed_data_offset)
-                            as usize..],
-                    );
-                } else {
-                    file_block.extend(blocks_info_uncompressedd_stream);
-                }
-                if file_block.len() >= buf.len() {
-                    let buf_len = buf.len();
-                    buf[0..buf_len].copy_from_slice(&file_block[0..buf_len]);
-                    self.current_position += buf_len as u64;
-                    return Ok(buf_len);
+                let block_start = ((self.node_info.offset as u64) + self.current_position)
+                    .saturating_sub(uncompressed_data_offset)
+                    as usize;
+                let block = blocks_info_uncompressedd_stream
+                    .get(block_start..)
+                    .unwrap_or_default();
+                // Return at most the rest of this block, so that a read
+                // never decompresses blocks past the ones it returns.
+                let read = block.len().min(buf.len());
+                if read > 0 {
+                    buf[..read].copy_from_slice(&block[..read]);
+                    self.current_position += read as u64;
+                    return Ok(read);
                 }
             }
             compressed_data_offset += sb.compressed_size as u64;
             uncompressed_data_offset += sb.uncompressed_size as u64;
         }
-        if !file_block.is_empty() && file_block.len() <= buf.len() {
-            buf[0..file_block.len()].copy_from_slice(&file_block[0..file_block.len()]);
-            self.current_position += file_block.len() as u64;
-            return Ok(file_block.len());
-        }
         Err(std::io::Error::from(ErrorKind::NotFound))
     }
 }
@@ -400,3 +909,450 @@ impl Seek for UnityFSNode {
         Ok(self.current_position)
     }
 }
+
+/// Size of the data blocks [`write_bundle`] compresses, as Unity uses.
+const WRITTEN_BLOCK_SIZE: usize = 128 * 1024;
+
+/// A version 6 bundle holding `files`, all flagged as serialized files, with
+/// the data in LZ4 blocks and the blocks info uncompressed after the header.
+pub(crate) fn write_bundle(unity_version: &str, files: &[(&str, &[u8])]) -> Vec<u8> {
+    use crate::serialized_file::writer::Writer;
+
+    let data: Vec<u8> = files.iter().flat_map(|(_, data)| data.to_vec()).collect();
+    let blocks: Vec<(usize, Vec<u8>)> = data
+        .chunks(WRITTEN_BLOCK_SIZE)
+        .map(|block| {
+            // Compressing a block held in memory cannot fail.
+            let compressed = lz4::block::compress(block, None, false).unwrap();
+            (block.len(), compressed)
+        })
+        .collect();
+
+    let mut blocks_info = Writer::new(true);
+    blocks_info.bytes(&[0; 16]).u32(blocks.len() as u32);
+    for (uncompressed_size, block) in &blocks {
+        blocks_info
+            .u32(*uncompressed_size as u32)
+            .u32(block.len() as u32)
+            .u16(CompressionType::Lz4 as u16);
+    }
+    blocks_info.u32(files.len() as u32);
+    let mut offset = 0;
+    for (path, data) in files {
+        blocks_info
+            .i64(offset as i64)
+            .i64(data.len() as i64)
+            .u32(4)
+            .null_string(path);
+        offset += data.len();
+    }
+
+    let mut file = Writer::new(true);
+    file.bytes(b"UnityFS\0")
+        .u32(6)
+        .null_string("5.x.x")
+        .null_string(unity_version);
+    let header_size = file.data.len() + 8 + 4 + 4 + 4;
+    let blocks_size: usize = blocks.iter().map(|(_, block)| block.len()).sum();
+    let size = header_size + blocks_info.data.len() + blocks_size;
+    file.i64(size as i64)
+        .u32(blocks_info.data.len() as u32)
+        .u32(blocks_info.data.len() as u32)
+        .u32(0x40) // blocks and directory info combined, uncompressed
+        .bytes(&blocks_info.data);
+    for (_, block) in blocks {
+        file.bytes(&block);
+    }
+    file.data
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// An uncompressed version 6 bundle storing `data` in `block_size` blocks.
+    fn bundle(data: &[u8], block_size: usize, files: &[(&str, usize, usize)]) -> Vec<u8> {
+        let blocks: Vec<_> = data
+            .chunks(block_size)
+            .map(|block| (block.to_vec(), block.len(), 0))
+            .collect();
+        bundle_with_blocks(&blocks, files)
+    }
+
+    /// A version 6 bundle of already compressed blocks, each given with its
+    /// uncompressed size and storage block flags.
+    fn bundle_with_blocks(
+        blocks: &[(Vec<u8>, usize, u16)],
+        files: &[(&str, usize, usize)],
+    ) -> Vec<u8> {
+        bundle_with_layout(blocks, files, BlocksInfoLocation::AfterHeader)
+    }
+
+    fn bundle_with_layout(
+        blocks: &[(Vec<u8>, usize, u16)],
+        files: &[(&str, usize, usize)],
+        location: BlocksInfoLocation,
+    ) -> Vec<u8> {
+        bundle_with_header(blocks, files, location, 6, false)
+    }
+
+    /// Version 7 onwards aligns the blocks info to 16 bytes, and
+    /// `padding_at_start` also aligns the data blocks after it.
+    fn bundle_with_header(
+        blocks: &[(Vec<u8>, usize, u16)],
+        files: &[(&str, usize, usize)],
+        location: BlocksInfoLocation,
+        version: u32,
+        padding_at_start: bool,
+    ) -> Vec<u8> {
+        let align = |file: &mut Vec<u8>| file.resize(file.len().next_multiple_of(16), 0);
+        let mut blocks_info = vec![0u8; 16];
+        blocks_info.extend_from_slice(&(blocks.len() as u32).to_be_bytes());
+        for (block, uncompressed_size, flags) in blocks {
+            blocks_info.extend_from_slice(&(*uncompressed_size as u32).to_be_bytes());
+            blocks_info.extend_from_slice(&(block.len() as i32).to_be_bytes());
+            blocks_info.extend_from_slice(&flags.to_be_bytes());
+        }
+        blocks_info.extend_from_slice(&(files.len() as u32).to_be_bytes());
+        for (path, offset, size) in files {
+            blocks_info.extend_from_slice(&(*offset as i64).to_be_bytes());
+            blocks_info.extend_from_slice(&(*size as i64).to_be_bytes());
+            blocks_info.extend_from_slice(&4u32.to_be_bytes());
+            blocks_info.extend_from_slice(path.as_bytes());
+            blocks_info.push(0);
+        }
+
+        let mut file = b"UnityFS\0".to_vec();
+        file.extend_from_slice(&version.to_be_bytes());
+        file.extend_from_slice(b"5.x.x\0");
+        file.extend_from_slice(b"2018.4.0f1\0");
+        let data: Vec<u8> = blocks
+            .iter()
+            .flat_map(|(block, ..)| block.clone())
+            .collect();
+        let mut flags = 0;
+        if location == BlocksInfoLocation::AtTheEnd {
+            flags |= 0x80;
+        }
+        if padding_at_start {
+            flags |= 0x200;
+        }
+        // The size is patched in once the layout is known.
+        let size_offset = file.len();
+        file.extend_from_slice(&0i64.to_be_bytes());
+        file.extend_from_slice(&(blocks_info.len() as u32).to_be_bytes());
+        file.extend_from_slice(&(blocks_info.len() as u32).to_be_bytes());
+        file.extend_from_slice(&(flags as u32).to_be_bytes());
+        if version >= 7 {
+            align(&mut file);
+        }
+        if location == BlocksInfoLocation::AfterHeader {
+            file.extend_from_slice(&blocks_info);
+        }
+        if padding_at_start {
+            align(&mut file);
+        }
+        file.extend(data);
+        if location == BlocksInfoLocation::AtTheEnd {
+            file.extend(blocks_info);
+        }
+        let size = file.len() as i64;
+        file[size_offset..size_offset + 8].copy_from_slice(&size.to_be_bytes());
+        file
+    }
+
+    #[test]
+    fn header_fields_are_exposed() {
+        let file = bundle_with_layout(
+            &[(vec![1, 2, 3], 3, 0)],
+            &[("a", 0, 3)],
+            BlocksInfoLocation::AtTheEnd,
+        );
+        let unity_fs = UnityFS::read(Box::new(Cursor::new(file.clone())), None).unwrap();
+        let header = unity_fs.header();
+        assert_eq!(header.signature(), "UnityFS");
+        assert_eq!(header.version(), 6);
+        assert_eq!(header.unity_version(), "5.x.x");
+        assert_eq!(header.unity_revision(), "2018.4.0f1");
+        assert_eq!(unity_fs.unity_revision(), "2018.4.0f1");
+        assert_eq!(header.size(), file.len() as i64);
+        assert_eq!(header.compressed_blocks_info_size(), 56);
+        assert_eq!(header.uncompressed_blocks_info_size(), 56);
+        assert_eq!(header.flags(), 0x80);
+    }
+
+    #[test]
+    fn cab_paths_leave_out_other_files() {
+        const CAB: &str = "CAB-0123456789abcdef0123456789abcdef";
+        let resource = format!("{CAB}.resS");
+        let file = bundle_with_layout(
+            &[(vec![0; 6], 6, 0)],
+            &[("a", 0, 2), (CAB, 2, 2), (&resource, 4, 2)],
+            BlocksInfoLocation::AfterHeader,
+        );
+        let unity_fs = UnityFS::read(Box::new(Cursor::new(file)), None).unwrap();
+        assert_eq!(unity_fs.cab_paths(), [CAB]);
+        assert_eq!(unity_fs.get_cab_path(), unity_fs.cab_paths());
+    }
+
+    #[test]
+    fn blocks_info_is_read_from_the_end() {
+        let data: Vec<u8> = (0..300u32).map(|i| i as u8).collect();
+        let blocks: Vec<_> = data
+            .chunks(128)
+            .map(|block| (block.to_vec(), block.len(), 0))
+            .collect();
+        let files = [("a", 0, 100), ("b", 100, 200)];
+        for location in [
+            BlocksInfoLocation::AfterHeader,
+            BlocksInfoLocation::AtTheEnd,
+        ] {
+            let unity_fs = UnityFS::read(
+                Box::new(Cursor::new(bundle_with_layout(&blocks, &files, location))),
+                None,
+            )
+            .unwrap();
+            assert_eq!(unity_fs.blocks_info_location(), location);
+            assert_eq!(
+                unity_fs.get_file_data_by_path(&"a".to_owned()).unwrap(),
+                data[..100]
+            );
+            assert_eq!(
+                unity_fs.get_file_data_by_path(&"b".to_owned()).unwrap(),
+                data[100..]
+            );
+        }
+    }
+
+    #[test]
+    fn bytes_after_the_archive_are_kept_as_a_trailer() {
+        let hash: Vec<u8> = (0..32).collect();
+        for location in [
+            BlocksInfoLocation::AfterHeader,
+            BlocksInfoLocation::AtTheEnd,
+        ] {
+            let mut file = bundle_with_layout(&[(vec![1, 2, 3], 3, 0)], &[("a", 0, 3)], location);
+            file.extend_from_slice(&hash);
+            let unity_fs = UnityFS::read(Box::new(Cursor::new(file)), None).unwrap();
+            assert_eq!(unity_fs.trailer(), hash);
+            assert_eq!(
+                unity_fs.get_file_data_by_path(&"a".to_owned()).unwrap(),
+                [1, 2, 3]
+            );
+        }
+
+        let unity_fs = UnityFS::read(
+            Box::new(Cursor::new(bundle_with_layout(
+                &[(vec![1, 2, 3], 3, 0)],
+                &[("a", 0, 3)],
+                BlocksInfoLocation::AtTheEnd,
+            ))),
+            None,
+        )
+        .unwrap();
+        assert!(unity_fs.trailer().is_empty());
+    }
+
+    #[test]
+    fn version_7_aligns_blocks_info_and_data() {
+        let data: Vec<u8> = (0..300u32).map(|i| i as u8).collect();
+        let blocks: Vec<_> = data
+            .chunks(128)
+            .map(|block| (block.to_vec(), block.len(), 0))
+            .collect();
+        let files = [("a", 0, 100), ("b", 100, 200)];
+        for location in [
+            BlocksInfoLocation::AfterHeader,
+            BlocksInfoLocation::AtTheEnd,
+        ] {
+            for (version, padding_at_start) in [(6, true), (7, false), (8, true)] {
+                let file = bundle_with_header(&blocks, &files, location, version, padding_at_start);
+                let unity_fs = UnityFS::read(Box::new(Cursor::new(file)), None).unwrap();
+                assert_eq!(
+                    unity_fs.get_file_data_by_path(&"a".to_owned()).unwrap(),
+                    data[..100]
+                );
+                assert_eq!(
+                    unity_fs.get_file_data_by_path(&"b".to_owned()).unwrap(),
+                    data[100..]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn lz4_blocks_decompress_independently() {
+        let data: Vec<u8> = (0..1024u32).map(|i| (i % 7 * 31 + i / 100) as u8).collect();
+        let blocks: Vec<_> = data
+            .chunks(100)
+            .map(|block| {
+                let compressed = lz4::block::compress(block, None, false).unwrap();
+                (compressed, block.len(), CompressionType::Lz4 as u16)
+            })
+            .collect();
+        let unity_fs = UnityFS::read(
+            Box::new(Cursor::new(bundle_with_blocks(
+                &blocks,
+                &[("a", 0, 250), ("b", 250, 774)],
+            ))),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            unity_fs.get_file_data_by_path(&"a".to_owned()).unwrap(),
+            data[..250]
+        );
+        assert_eq!(
+            unity_fs.get_file_data_by_path(&"b".to_owned()).unwrap(),
+            data[250..]
+        );
+        let mut b = Vec::new();
+        unity_fs
+            .write_file_by_path(&"b".to_owned(), &mut b)
+            .unwrap();
+        assert_eq!(b, data[250..]);
+        let mut a = Vec::new();
+        assert_eq!(unity_fs.extract_file_to("a", &mut a).unwrap(), 250);
+        assert_eq!(a, data[..250]);
+    }
+
+    #[test]
+    fn unsupported_compression_is_named() {
+        let lzham = bundle_with_blocks(
+            &[(vec![0; 8], 8, CompressionType::Lzham as u16)],
+            &[("a", 0, 8)],
+        );
+        let unity_fs = UnityFS::read(Box::new(Cursor::new(lzham)), None).unwrap();
+        let err = unity_fs.get_file_data_by_path(&"a".to_owned()).unwrap_err();
+        assert!(matches!(
+            err.get_ref().and_then(|err| err.downcast_ref::<Error>()),
+            Some(Error::UnsupportedCompression(4))
+        ));
+
+        let unknown = bundle_with_blocks(&[(vec![0; 8], 8, 5)], &[("a", 0, 8)]);
+        let Err(err) = UnityFS::read(Box::new(Cursor::new(unknown)), None) else {
+            panic!("read a bundle of unknown compression");
+        };
+        assert!(
+            err.to_string()
+                .contains("unsupported UnityFS compression type 5"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn streamed_blocks_referring_back_are_rejected() {
+        let first = b"abcdabcdabcd".to_vec();
+        // A match four bytes back, before the start of this block, then
+        // five literals to end it.
+        let second = [0x00, 0x04, 0x00, 0x50, b'e', b'f', b'g', b'h', b'i'].to_vec();
+        let lz4_streamed = CompressionType::Lz4 as u16 | 0x40;
+        let unity_fs = UnityFS::read(
+            Box::new(Cursor::new(bundle_with_blocks(
+                &[(first.clone(), first.len(), 0), (second, 9, lz4_streamed)],
+                &[("a", 0, 21)],
+            ))),
+            None,
+        )
+        .unwrap();
+
+        let err = unity_fs.get_file_data_by_path(&"a".to_owned()).unwrap_err();
+        assert!(err.to_string().contains("streamed block"), "{err}");
+    }
+
+    #[test]
+    fn verify_reports_every_unparsable_serialized_file() {
+        let data: Vec<u8> = (0..=255).collect();
+        let unity_fs = UnityFS::read(
+            Box::new(Cursor::new(bundle(
+                &data,
+                100,
+                &[("a", 0, 150), ("b", 150, 106)],
+            ))),
+            None,
+        )
+        .unwrap();
+
+        let issues = unity_fs.verify().unwrap_err();
+        let paths: Vec<_> = issues
+            .iter()
+            .map(|issue| match issue {
+                BundleIssue::File { path, .. } => path.as_str(),
+                BundleIssue::Block { .. } => panic!("unexpected {issue:?}"),
+            })
+            .collect();
+        assert_eq!(paths, ["a", "b"]);
+    }
+
+    #[test]
+    fn read_files_across_blocks() {
+        let data: Vec<u8> = (0..=255).collect();
+        let unity_fs = UnityFS::read(
+            Box::new(Cursor::new(bundle(
+                &data,
+                100,
+                &[("a", 0, 150), ("b", 150, 106)],
+            ))),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(unity_fs.get_file_paths(), vec!["a", "b"]);
+        assert_eq!(
+            unity_fs.get_file_data_by_path(&"a".to_owned()).unwrap(),
+            data[..150]
+        );
+        let mut b = Vec::new();
+        unity_fs
+            .write_file_by_path(&"b".to_owned(), &mut b)
+            .unwrap();
+        assert_eq!(b, data[150..]);
+    }
+
+    #[test]
+    fn file_readers_keep_a_few_blocks() {
+        let data: Vec<u8> = (0..=255).collect();
+        let unity_fs = UnityFS::read(
+            Box::new(Cursor::new(bundle(&data, 16, &[("a", 0, 256)]))),
+            None,
+        )
+        .unwrap();
+        let mut node = unity_fs.get_file_reader_by_path(&"a".to_owned()).unwrap();
+
+        let mut read = Vec::new();
+        node.read_to_end(&mut read).unwrap();
+        assert_eq!(read, data);
+        assert_eq!(node.storage_blocks_cache.len(), CACHED_STORAGE_BLOCKS);
+
+        // Evicted blocks are decompressed again.
+        let mut start = [0u8; 4];
+        node.seek(SeekFrom::Start(0)).unwrap();
+        node.read_exact(&mut start).unwrap();
+        assert_eq!(start, [0, 1, 2, 3]);
+        assert_eq!(node.storage_blocks_cache.len(), CACHED_STORAGE_BLOCKS);
+        assert_eq!(node.storage_blocks_cache.last().unwrap().0, 0);
+    }
+
+    #[test]
+    fn malformed_bundles_fail_gracefully() {
+        let data: Vec<u8> = (0..=255).collect();
+        let file = bundle(&data, 100, &[("a", 0, 150), ("b", 150, 106)]);
+        for len in 0..file.len() {
+            assert!(UnityFS::read(Box::new(Cursor::new(file[..len].to_vec())), None).is_err());
+        }
+
+        // Block count, first block size and first file size in the blocks info.
+        let blocks_info_start = file.len() - data.len() - (16 + 4 + 3 * 10 + 4 + 2 * 22);
+        for (offset, value) in [(16, u32::MAX), (20, u32::MAX), (24, i32::MIN as u32)] {
+            let mut corrupt = file.clone();
+            let at = blocks_info_start + offset;
+            corrupt[at..at + 4].copy_from_slice(&value.to_be_bytes());
+            assert!(UnityFS::read(Box::new(Cursor::new(corrupt)), None).is_err());
+        }
+        let mut corrupt = file.clone();
+        let file_size_at = blocks_info_start + 16 + 4 + 3 * 10 + 4 + 8;
+        corrupt[file_size_at..file_size_at + 8].copy_from_slice(&i64::MAX.to_be_bytes());
+        assert!(UnityFS::read(Box::new(Cursor::new(corrupt)), None).is_err());
+    }
+}
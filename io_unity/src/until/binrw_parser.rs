@@ -1,8 +1,9 @@
 use std::fmt;
-use std::io::prelude::*;
+use std::io::{prelude::*, Cursor, SeekFrom};
 use std::ops::Deref;
+use std::sync::OnceLock;
 
-use binrw::{binrw, BinResult, Endian};
+use binrw::{binrw, BinResult, Endian, VecArgs};
 use binrw::{BinRead, BinWrite};
 
 // reading/writing utilities
@@ -13,6 +14,139 @@ pub fn position_parser() -> BinResult<u64> {
     Ok(reader.stream_position()?)
 }
 
+/// Read a `Vec` like binrw's `count`, but fail before allocating when the
+/// stream has fewer bytes left than the declared item count.
+pub fn bounded_vec_parser<'a, R, T>(
+    reader: &mut R,
+    endian: Endian,
+    args: VecArgs<T::Args<'a>>,
+) -> BinResult<Vec<T>>
+where
+    R: Read + Seek,
+    T: BinRead + 'static,
+    T::Args<'a>: Clone,
+{
+    let pos = reader.stream_position()?;
+    let remaining = reader.seek(SeekFrom::End(0))?.saturating_sub(pos);
+    reader.seek(SeekFrom::Start(pos))?;
+    if args.count as u64 > remaining {
+        return Err(binrw::Error::AssertFail {
+            pos,
+            message: format!(
+                "count of {} items exceeds the {} bytes left",
+                args.count, remaining
+            ),
+        });
+    }
+    binrw::helpers::count_with(args.count, T::read_options)(reader, endian, args.inner)
+}
+
+/// A table of fixed-size items kept as their raw bytes and only parsed on
+/// first access, so skipping past a table that is never used costs a copy.
+/// Read with the item count and the size of one item.
+pub struct LazyVec<T> {
+    endian: Endian,
+    item_size: usize,
+    data: Vec<u8>,
+    items: OnceLock<Vec<T>>,
+}
+
+impl<T: for<'a> BinRead<Args<'a> = ()>> LazyVec<T> {
+    /// Parse the items now if they have not been yet.
+    pub fn decode(&self) -> &[T] {
+        self.items.get_or_init(|| {
+            let mut reader = Cursor::new(&self.data);
+            (0..self.data.len() / self.item_size.max(1))
+                .map_while(|_| T::read_options(&mut reader, self.endian, ()).ok())
+                .collect()
+        })
+    }
+
+    pub fn is_decoded(&self) -> bool {
+        self.items.get().is_some()
+    }
+}
+
+impl<T: for<'a> BinRead<Args<'a> = ()>> Deref for LazyVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.decode()
+    }
+}
+
+impl<'b, T: for<'a> BinRead<Args<'a> = ()>> IntoIterator for &'b LazyVec<T> {
+    type Item = &'b T;
+    type IntoIter = std::slice::Iter<'b, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.decode().iter()
+    }
+}
+
+impl<T: Clone> Clone for LazyVec<T> {
+    fn clone(&self) -> Self {
+        LazyVec {
+            endian: self.endian,
+            item_size: self.item_size,
+            data: self.data.clone(),
+            items: self.items.clone(),
+        }
+    }
+}
+
+impl<T> PartialEq for LazyVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.endian == other.endian && self.data == other.data
+    }
+}
+
+impl<T> fmt::Debug for LazyVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LazyVec({} bytes)", self.data.len())
+    }
+}
+
+impl<T> BinRead for LazyVec<T> {
+    type Args<'a> = (usize, usize);
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (count, item_size): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let size = count.saturating_mul(item_size);
+        let data = bounded_vec_parser(
+            reader,
+            endian,
+            VecArgs {
+                count: size,
+                inner: (),
+            },
+        )?;
+        Ok(LazyVec {
+            endian,
+            item_size,
+            data,
+            items: OnceLock::new(),
+        })
+    }
+}
+
+impl<T> BinWrite for LazyVec<T> {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        _endian: Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        writer.write_all(&self.data)?;
+        Ok(())
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct U8Bool(bool);
 
@@ -24,6 +158,12 @@ impl Deref for U8Bool {
     }
 }
 
+impl From<bool> for U8Bool {
+    fn from(value: bool) -> Self {
+        U8Bool(value)
+    }
+}
+
 impl BinRead for U8Bool {
     type Args<'a> = ();
 
@@ -61,6 +201,12 @@ pub struct AlignedString {
     string: Vec<u8>,
 }
 
+impl AlignedString {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.string
+    }
+}
+
 impl fmt::Display for AlignedString {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", String::from_utf8(self.string.clone()).unwrap())
@@ -86,6 +232,28 @@ pub struct PackedFloatVector {
     bit_size: u8,
 }
 
+impl PackedFloatVector {
+    pub fn num_items(&self) -> u32 {
+        self.num_items
+    }
+
+    pub fn range(&self) -> f32 {
+        self.range
+    }
+
+    pub fn start(&self) -> f32 {
+        self.start
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn bit_size(&self) -> u8 {
+        self.bit_size
+    }
+}
+
 #[binrw]
 #[derive(Debug)]
 pub struct PackedIntVector {
@@ -97,6 +265,20 @@ pub struct PackedIntVector {
     bit_size: u8,
 }
 
+impl PackedIntVector {
+    pub fn num_items(&self) -> u32 {
+        self.num_items
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn bit_size(&self) -> u8 {
+        self.bit_size
+    }
+}
+
 #[binrw]
 #[derive(Debug)]
 pub struct PackedQuatVector {
@@ -105,3 +287,13 @@ pub struct PackedQuatVector {
     #[br(count(num_data), align_after(4))]
     data: Vec<u8>,
 }
+
+impl PackedQuatVector {
+    pub fn num_items(&self) -> u32 {
+        self.num_items
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
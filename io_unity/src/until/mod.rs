@@ -21,6 +21,7 @@ impl UnityVersion {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(version: &str) -> Result<Self, Box<dyn Error>> {
         static BUILD_TYPE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"([^\d.])").unwrap());
         static VERSION_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\D").unwrap());
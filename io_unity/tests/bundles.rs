@@ -0,0 +1,426 @@
+mod fixtures;
+
+use fixtures::*;
+use io_unity::type_tree::{convert::TryCastFrom, TypeTreeObjectRef};
+
+#[test]
+fn read_text_assets_from_bundles() {
+    for compression in [Compression::None, Compression::Lz4] {
+        for big_endian in [false, true] {
+            let viewer = load_bundle(text_asset_bundle(compression, big_endian, true, 3));
+            for path_id in 1..=3 {
+                let object: TypeTreeObjectRef = viewer
+                    .get_object(CAB_NAME, path_id)
+                    .unwrap()
+                    .unwrap_or_else(|| panic!("{compression:?} {big_endian} {path_id}"))
+                    .into();
+                assert_eq!(
+                    String::try_cast_from(&object, "/Base/m_Name").unwrap(),
+                    format!("text{path_id}")
+                );
+                assert_eq!(
+                    String::try_cast_from(&object, "/Base/m_Script").unwrap(),
+                    format!("script of {path_id}")
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn generated_bundles_verify() {
+    for compression in [Compression::None, Compression::Lz4] {
+        let unity_fs = io_unity::unityfs::UnityFS::read(
+            Box::new(std::io::Cursor::new(text_asset_bundle(
+                compression,
+                false,
+                true,
+                3,
+            ))),
+            None,
+        )
+        .unwrap();
+        assert_eq!(unity_fs.verify(), Ok(()));
+    }
+}
+
+#[test]
+fn directory_progress_is_weighted_by_bytes() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let small = text_asset_bundle(Compression::None, false, true, 1);
+    let large = text_asset_bundle(Compression::None, false, true, 40);
+    std::fs::write(temp_dir.path().join("small.bundle"), &small).unwrap();
+    std::fs::write(temp_dir.path().join("large.bundle"), &large).unwrap();
+    let bytes_total = (small.len() + large.len()) as u64;
+
+    let mut reports = Vec::new();
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+    viewer
+        .read_dir_with_progress(temp_dir.path(), |done, total| reports.push((done, total)))
+        .unwrap();
+
+    assert_eq!(reports.len(), 3);
+    assert_eq!(reports[0], (0, bytes_total));
+    assert_eq!(reports[2], (bytes_total, bytes_total));
+    assert!([small.len() as u64, large.len() as u64].contains(&reports[1].0));
+}
+
+#[test]
+fn directory_scans_report_what_they_loaded() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        temp_dir.path().join("with_containers.bundle"),
+        text_asset_bundle_with_containers(&["assets/a.txt", "assets/b.txt"]),
+    )
+    .unwrap();
+    std::fs::write(
+        temp_dir.path().join("plain.bundle"),
+        text_asset_bundle(Compression::Lz4, false, true, 2),
+    )
+    .unwrap();
+    std::fs::write(temp_dir.path().join("notes.txt"), b"not a bundle").unwrap();
+
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+    let report = viewer.read_dir_report(temp_dir.path()).unwrap();
+
+    assert_eq!(report.bundles_loaded, 2);
+    assert_eq!(report.serialized_files, 2);
+    assert_eq!(report.containers_indexed, 2);
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(report.skipped[0].0, temp_dir.path().join("notes.txt"));
+    assert_eq!(report.skipped[0].1, "not a bundle: unknown file");
+}
+
+#[test]
+fn lazy_directory_reads_decompress_blocks_on_demand() {
+    use io_unity::unity_asset_view::UnityAssetViewer;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut bundle = text_asset_bundle(Compression::Lz4, false, true, 20);
+    // Break the last block, which only holds the data of the last objects.
+    let len = bundle.len();
+    bundle[len - 6..].fill(0xff);
+    std::fs::write(temp_dir.path().join("text.bundle"), bundle).unwrap();
+
+    let report = UnityAssetViewer::new()
+        .read_dir_report(temp_dir.path())
+        .unwrap();
+    assert_eq!(report.bundles_loaded, 0);
+
+    let mut viewer = UnityAssetViewer::new();
+    let report = viewer.read_dir_lazy(temp_dir.path()).unwrap();
+    assert_eq!(report.bundles_loaded, 1);
+    for path_id in 1..=3 {
+        let object: TypeTreeObjectRef = viewer
+            .get_object(CAB_NAME, path_id)
+            .unwrap()
+            .unwrap()
+            .into();
+        assert_eq!(
+            String::try_cast_from(&object, "/Base/m_Script").unwrap(),
+            format!("script of {path_id}")
+        );
+    }
+    assert!(viewer.get_object(CAB_NAME, 20).is_err());
+}
+
+#[cfg(all(feature = "mmap", unix))]
+#[test]
+fn mapped_files_read_and_seek_like_files() {
+    use io_unity::split::MmapReader;
+    use std::io::{Read, Seek, SeekFrom};
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let data: Vec<u8> = (0..=255).collect();
+    std::fs::write(temp_dir.path().join("data"), &data).unwrap();
+    std::fs::write(temp_dir.path().join("empty"), []).unwrap();
+
+    let mut reader = MmapReader::open(temp_dir.path().join("data")).unwrap();
+    assert_eq!(reader.as_slice(), data);
+    let mut read = [0u8; 4];
+    reader.seek(SeekFrom::End(-4)).unwrap();
+    reader.read_exact(&mut read).unwrap();
+    assert_eq!(read, [252, 253, 254, 255]);
+    assert_eq!(reader.read(&mut read).unwrap(), 0);
+    reader.seek(SeekFrom::Start(1000)).unwrap();
+    assert_eq!(reader.read(&mut read).unwrap(), 0);
+
+    let mut reader = MmapReader::open(temp_dir.path().join("empty")).unwrap();
+    assert_eq!(reader.read(&mut read).unwrap(), 0);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn closed_bundle_files_leave_no_handles_open() {
+    use io_unity::unity_asset_view::UnityAssetViewer;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    for index in 0..40 {
+        std::fs::write(
+            temp_dir.path().join(format!("{index}.bundle")),
+            text_asset_bundle(Compression::Lz4, false, true, 1),
+        )
+        .unwrap();
+    }
+    let open_handles = || {
+        std::fs::read_dir("/proc/self/fd")
+            .unwrap()
+            .flatten()
+            .filter_map(|fd| std::fs::read_link(fd.path()).ok())
+            .filter(|target| target.starts_with(temp_dir.path()))
+            .count()
+    };
+
+    let mut viewer = UnityAssetViewer::new();
+    viewer.read_bundle_dir(temp_dir.path()).unwrap();
+    assert_eq!(open_handles(), 40);
+    drop(viewer);
+
+    let mut viewer = UnityAssetViewer::builder().close_bundle_files(true).build();
+    let report = viewer.read_dir_report(temp_dir.path()).unwrap();
+    assert_eq!(report.bundles_loaded, 40);
+    assert_eq!(open_handles(), 0);
+
+    // The bundle is reopened for reads that need it, and closed again.
+    let unity_fs = viewer.get_unity_fs_by_cab_path(CAB_NAME).unwrap();
+    assert!(unity_fs.verify().is_ok());
+    assert_eq!(
+        unity_fs
+            .get_file_data_by_path(&CAB_NAME.to_string())
+            .unwrap(),
+        text_asset_file(false, true, 1)
+    );
+    assert_eq!(open_handles(), 0);
+}
+
+#[test]
+fn files_are_identified_by_their_header() {
+    use io_unity::identify::{identify_file, identify_path, FileKind};
+    use std::io::{Cursor, Seek};
+
+    let large_file = SerializedFileFixture {
+        format_version: 22,
+        big_endian: true,
+        enable_type_tree: true,
+        types: vec![text_asset_type()],
+        objects: vec![FixtureObject {
+            path_id: 1,
+            type_index: 0,
+            data: text_asset_data(true, "text", "script"),
+        }],
+    }
+    .build();
+    let files = [
+        (
+            text_asset_bundle(Compression::Lz4, false, true, 1),
+            FileKind::UnityFS,
+        ),
+        (text_asset_file(false, true, 1), FileKind::SerializedFile),
+        (text_asset_file(true, false, 1), FileKind::SerializedFile),
+        (large_file, FileKind::SerializedFile),
+        (b"UnityWebData1.0\0\x10\0\0\0".to_vec(), FileKind::WebGLData),
+        (b"not a bundle".to_vec(), FileKind::Unknown),
+        (Vec::new(), FileKind::Unknown),
+    ];
+    for (file, kind) in files {
+        // Identification starts, and leaves the reader, where it is.
+        let mut reader = Cursor::new([b"skip".as_slice(), &file].concat());
+        reader.set_position(4);
+        assert_eq!(identify_file(&mut reader).unwrap(), kind);
+        assert_eq!(reader.stream_position().unwrap(), 4);
+    }
+
+    // A resource stream has no header, only its extension.
+    let temp_dir = tempfile::tempdir().unwrap();
+    let stream = temp_dir.path().join("texture.resS");
+    std::fs::write(&stream, [0u8; 16]).unwrap();
+    assert_eq!(identify_path(&stream).unwrap(), FileKind::ResourceStream);
+}
+
+#[test]
+fn split_bundle_parts_read_as_one_bundle() {
+    let data = text_asset_bundle(Compression::Lz4, false, true, 2);
+    let temp_dir = tempfile::tempdir().unwrap();
+    let part_size = data.len() / 3 + 1;
+    for (index, part) in data.chunks(part_size).enumerate() {
+        std::fs::write(
+            temp_dir.path().join(format!("data.bundle.split{index}")),
+            part,
+        )
+        .unwrap();
+    }
+
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+    let report = viewer.read_dir_report(temp_dir.path()).unwrap();
+    assert_eq!(report.bundles_loaded, 1);
+    assert!(report.skipped.is_empty());
+    assert!(viewer.get_object(CAB_NAME, 2).unwrap().is_some());
+
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+    viewer
+        .read_split_bundle(temp_dir.path().join("data.bundle.split1"))
+        .unwrap();
+    assert!(viewer.get_object(CAB_NAME, 1).unwrap().is_some());
+
+    // Closed, the parts are reopened for reads spanning them.
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::builder()
+        .close_bundle_files(true)
+        .build();
+    viewer
+        .read_split_bundle(temp_dir.path().join("data.bundle"))
+        .unwrap();
+    let unity_fs = viewer.get_unity_fs_by_cab_path(CAB_NAME).unwrap();
+    assert!(unity_fs.verify().is_ok());
+    assert_eq!(
+        unity_fs
+            .get_file_data_by_path(&CAB_NAME.to_string())
+            .unwrap(),
+        text_asset_file(false, true, 2)
+    );
+
+    std::fs::remove_file(temp_dir.path().join("data.bundle.split1")).unwrap();
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+    let err = viewer
+        .read_split_bundle(temp_dir.path().join("data.bundle"))
+        .unwrap_err();
+    assert!(err.to_string().contains("missing part 1"), "{err}");
+    let report = viewer.read_dir_report(temp_dir.path()).unwrap();
+    assert_eq!(report.bundles_loaded, 0);
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(report.skipped[0].0, temp_dir.path().join("data.bundle"));
+}
+
+#[test]
+fn raw_scans_skip_the_container_index() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        temp_dir.path().join("with_containers.bundle"),
+        text_asset_bundle_with_containers(&["assets/a.txt"]),
+    )
+    .unwrap();
+
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewerBuilder::new()
+        .index_containers(false)
+        .build();
+    let report = viewer.read_dir_report(temp_dir.path()).unwrap();
+    assert_eq!(report.bundles_loaded, 1);
+    assert_eq!(report.containers_indexed, 0);
+    assert!(viewer.container_maps.is_empty());
+    assert!(viewer.get_object(CAB_NAME, 2).unwrap().is_some());
+
+    viewer.build_container_index();
+    assert_eq!(viewer.container_entries("assets/a.txt").len(), 1);
+}
+
+#[test]
+fn scan_visitors_see_files_and_containers() {
+    use io_unity::serialized_file::SerializedFile;
+    use io_unity::unity_asset_view::{ContainerEntry, ScanVisitor, UnityAssetViewerBuilder};
+
+    #[derive(Default)]
+    struct Names {
+        files: Vec<(String, usize)>,
+        containers: Vec<String>,
+    }
+
+    impl ScanVisitor for Names {
+        fn visit_serialized_file(&mut self, cab_name: &str, serialized_file: &SerializedFile) {
+            self.files
+                .push((cab_name.to_owned(), serialized_file.get_object_map().len()));
+        }
+
+        fn visit_container(&mut self, name: &str, _entry: &ContainerEntry) {
+            self.containers.push(name.to_owned());
+        }
+    }
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        temp_dir.path().join("with_containers.bundle"),
+        text_asset_bundle_with_containers(&["assets/a.txt", "assets/b.txt"]),
+    )
+    .unwrap();
+
+    for defer in [false, true] {
+        let mut viewer = UnityAssetViewerBuilder::new()
+            .defer_container_index(defer)
+            .build();
+        let mut names = Names::default();
+        viewer
+            .read_dir_with_visitor(temp_dir.path(), &mut names)
+            .unwrap();
+
+        assert_eq!(names.files, [(CAB_NAME.to_owned(), 3)], "defer {defer}");
+        names.containers.sort();
+        assert_eq!(
+            names.containers,
+            ["assets/a.txt", "assets/b.txt"],
+            "defer {defer}"
+        );
+    }
+}
+
+#[cfg(feature = "timings")]
+#[test]
+fn scans_time_each_bundle() {
+    use io_unity::unity_asset_view::TimingPhase;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let paths = [
+        temp_dir.path().join("a.bundle"),
+        temp_dir.path().join("b.bundle"),
+    ];
+    std::fs::write(
+        &paths[0],
+        text_asset_bundle_with_containers(&["assets/a.txt"]),
+    )
+    .unwrap();
+    std::fs::write(
+        &paths[1],
+        text_asset_bundle(Compression::Lz4, false, true, 2),
+    )
+    .unwrap();
+
+    let recorded = Arc::new(Mutex::new(Vec::new()));
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+    viewer.set_timing_hook({
+        let recorded = recorded.clone();
+        move |phase, elapsed| recorded.lock().unwrap().push((phase, elapsed))
+    });
+    let report = viewer.read_dir_report(temp_dir.path()).unwrap();
+
+    let mut timed_paths: Vec<_> = report.timings.iter().map(|t| t.path.clone()).collect();
+    timed_paths.sort();
+    assert_eq!(timed_paths, paths);
+
+    let recorded = recorded.lock().unwrap();
+    let total = |phase| -> Duration {
+        recorded
+            .iter()
+            .filter(|(p, _)| *p == phase)
+            .map(|(_, elapsed)| *elapsed)
+            .sum()
+    };
+    // Each of the two bundles is read, parsed and scanned for containers once.
+    for (phase, count) in [
+        (TimingPhase::Decompress, 2),
+        (TimingPhase::Metadata, 2),
+        (TimingPhase::Objects, 2),
+    ] {
+        assert_eq!(recorded.iter().filter(|(p, _)| *p == phase).count(), count);
+    }
+    assert_eq!(
+        total(TimingPhase::Decompress),
+        report.timings.iter().map(|t| t.decompress).sum()
+    );
+    assert_eq!(
+        total(TimingPhase::Metadata),
+        report.timings.iter().map(|t| t.metadata).sum()
+    );
+    assert_eq!(
+        total(TimingPhase::Objects),
+        report.timings.iter().map(|t| t.objects).sum()
+    );
+}
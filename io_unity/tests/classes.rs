@@ -0,0 +1,341 @@
+#![cfg(feature = "external-class-handle")]
+
+mod fixtures;
+
+use fixtures::*;
+use io_unity::type_tree::TypeTreeObjectRef;
+
+#[test]
+fn particle_systems_and_line_renderers_read_their_basics() {
+    use io_unity::classes::line_renderer::{LineRenderer, LineRendererObject};
+    use io_unity::classes::particle_system::{MinMaxCurve, ParticleSystem, ParticleSystemObject};
+    use io_unity::type_tree::ColorRGBA;
+
+    let (viewer, serialized_file_id) = load_serialized_file(
+        SerializedFileFixture {
+            format_version: 17,
+            big_endian: false,
+            enable_type_tree: true,
+            types: vec![particle_system_type(), line_renderer_type()],
+            objects: vec![
+                FixtureObject {
+                    path_id: 1,
+                    type_index: 0,
+                    data: particle_system_data(),
+                },
+                FixtureObject {
+                    path_id: 2,
+                    type_index: 1,
+                    data: line_renderer_data(&[[0.0, 0.0, 0.0], [1.0, 2.0, 3.0]], 0.25),
+                },
+            ],
+        }
+        .build(),
+    );
+    let serialized_file = &viewer.serialized_file_map[&serialized_file_id];
+
+    let particles: TypeTreeObjectRef = serialized_file
+        .get_tt_object_by_path_id(1)
+        .unwrap()
+        .unwrap()
+        .into();
+    let particles = ParticleSystem::new(&particles);
+    assert_eq!(particles.get_duration().unwrap(), 5.0);
+    assert!(particles.get_looping().unwrap());
+    assert_eq!(particles.get_max_particles().unwrap(), 1000);
+    assert_eq!(particles.get_start_lifetime().unwrap().scalar, 3.0);
+    assert_eq!(particles.get_start_speed().unwrap().scalar, 4.5);
+    assert_eq!(
+        particles.get_start_size().unwrap(),
+        MinMaxCurve {
+            mode: 3,
+            scalar: 2.0,
+            min_scalar: 0.5,
+        }
+    );
+    let start_color = particles.get_start_color().unwrap();
+    assert_eq!(start_color.mode, 0);
+    assert_eq!(
+        start_color.max_color,
+        ColorRGBA {
+            r: 1.0,
+            g: 0.5,
+            b: 0.0,
+            a: 1.0,
+        }
+    );
+    assert!(particles.get_emission_enabled().unwrap());
+    assert_eq!(particles.get_rate_over_time().unwrap().scalar, 10.0);
+
+    let line: TypeTreeObjectRef = serialized_file
+        .get_tt_object_by_path_id(2)
+        .unwrap()
+        .unwrap()
+        .into();
+    let line = LineRenderer::new(&line);
+    assert_eq!(
+        line.get_positions().unwrap(),
+        [glam::Vec3::ZERO, glam::Vec3::new(1.0, 2.0, 3.0)]
+    );
+    assert!(line.get_use_world_space().unwrap());
+    assert!(!line.get_loop().unwrap());
+    assert_eq!(line.get_width_multiplier().unwrap(), 0.25);
+}
+
+#[test]
+fn global_game_managers_name_the_game() {
+    use io_unity::classes::build_settings::{BuildSettings, BuildSettingsObject};
+    use io_unity::classes::player_settings::{PlayerSettings, PlayerSettingsObject};
+
+    let (viewer, _) = load_serialized_file(single_object_file(
+        text_asset_type(),
+        text_asset_data(false, "a", "b"),
+    ));
+    assert!(viewer.build_settings().unwrap().is_none());
+
+    let (mut viewer, _) = load_serialized_file(
+        SerializedFileFixture {
+            format_version: 17,
+            big_endian: false,
+            enable_type_tree: true,
+            types: vec![player_settings_type(), build_settings_type()],
+            objects: vec![
+                FixtureObject {
+                    path_id: 1,
+                    type_index: 0,
+                    data: player_settings_data("Studio", "Game", "1.0.3"),
+                },
+                FixtureObject {
+                    path_id: 11,
+                    type_index: 1,
+                    data: build_settings_data(
+                        &["Assets/Scenes/Title.unity", "Assets/Scenes/Main.unity"],
+                        UNITY_VERSION,
+                    ),
+                },
+            ],
+        }
+        .build(),
+    );
+    viewer
+        .add_serialized_file(
+            Box::new(std::io::Cursor::new(single_object_file(
+                build_settings_type(),
+                build_settings_data(&[], UNITY_VERSION),
+            ))),
+            None,
+        )
+        .unwrap();
+
+    let build_settings: TypeTreeObjectRef = viewer.build_settings().unwrap().unwrap().into();
+    let build_settings = BuildSettings::new(&build_settings);
+    assert_eq!(
+        build_settings.get_scenes().unwrap(),
+        ["Assets/Scenes/Title.unity", "Assets/Scenes/Main.unity"]
+    );
+    assert_eq!(build_settings.get_version().unwrap(), UNITY_VERSION);
+
+    let player_settings: TypeTreeObjectRef = viewer.player_settings().unwrap().unwrap().into();
+    let player_settings = PlayerSettings::new(&player_settings);
+    assert_eq!(player_settings.get_company_name().unwrap(), "Studio");
+    assert_eq!(player_settings.get_product_name().unwrap(), "Game");
+    assert_eq!(player_settings.get_bundle_version().unwrap(), "1.0.3");
+}
+
+#[test]
+fn shader_sub_programs_name_their_keywords() {
+    use io_unity::classes::shader::{Shader, ShaderObject, ShaderStage};
+
+    let keywords = ["FOG_LINEAR", "SHADOWS_SCREEN", "_EMISSION"];
+    for modern in [false, true] {
+        let shader = read_single_object(
+            shader_type(modern),
+            shader_data(
+                modern,
+                "Custom/Lit",
+                &keywords,
+                [
+                    &[(0, vec![], vec![]), (1, vec![0, 1], vec![])],
+                    &[(2, vec![1], vec![2, 7])],
+                ],
+            ),
+        );
+        let shader = Shader::new(&shader);
+
+        assert_eq!(shader.get_name().unwrap(), "Custom/Lit");
+        assert_eq!(
+            shader.get_keyword_names().unwrap().len(),
+            if modern { 3 } else { 0 }
+        );
+        let sub_programs = shader.get_sub_programs().unwrap();
+        let variants: Vec<_> = sub_programs
+            .iter()
+            .map(|sub_program| {
+                (
+                    sub_program.stage,
+                    sub_program.blob_index,
+                    sub_program.keywords.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            variants,
+            [
+                (ShaderStage::Vertex, 0, vec![]),
+                (
+                    ShaderStage::Vertex,
+                    1,
+                    vec!["FOG_LINEAR".to_owned(), "SHADOWS_SCREEN".to_owned()]
+                ),
+                (
+                    ShaderStage::Fragment,
+                    2,
+                    vec![
+                        "SHADOWS_SCREEN".to_owned(),
+                        "_EMISSION".to_owned(),
+                        "7".to_owned()
+                    ]
+                ),
+            ],
+            "modern: {modern}"
+        );
+        assert!(sub_programs.iter().all(|sub_program| (
+            sub_program.sub_shader,
+            sub_program.pass,
+            sub_program.gpu_program_type
+        ) == (0, 0, 13)));
+    }
+}
+
+#[test]
+fn asset_bundle_manifests_list_bundles_and_dependencies() {
+    use io_unity::classes::asset_bundle_manifest::{
+        AssetBundleManifest, AssetBundleManifestObject,
+    };
+
+    let file = single_object_file(
+        asset_bundle_manifest_type(),
+        asset_bundle_manifest_data(
+            &[
+                (1, "ui", &[0]),
+                (0, "shared", &[]),
+                (2, "levels.hd", &[1, 0]),
+            ],
+            &[2],
+        ),
+    );
+    let (viewer, _) = load_serialized_file(file);
+    let manifest = viewer.asset_bundle_manifest().unwrap().unwrap().into();
+    let manifest = AssetBundleManifest::new(&manifest);
+
+    assert_eq!(
+        manifest.all_asset_bundles().unwrap(),
+        ["shared", "ui", "levels.hd"]
+    );
+    assert_eq!(
+        manifest.asset_bundles_with_variant().unwrap(),
+        ["levels.hd"]
+    );
+    assert_eq!(
+        manifest.dependencies_for("levels.hd").unwrap(),
+        ["ui", "shared"]
+    );
+    assert!(manifest.dependencies_for("shared").unwrap().is_empty());
+    assert!(manifest.dependencies_for("missing").is_err());
+    assert_eq!(
+        manifest.asset_bundle_hash("ui").unwrap().unwrap().bytes,
+        [1; 16]
+    );
+    assert_eq!(manifest.asset_bundle_hash("missing").unwrap(), None);
+}
+
+#[test]
+fn animator_controllers_read_their_states_and_transitions() {
+    use io_unity::classes::animator_controller::{
+        AnimatorCondition, AnimatorController, AnimatorControllerObject, AnimatorTransition,
+    };
+
+    let states = [
+        AnimatorStateFixture {
+            name_hash: 10,
+            clip_ids: &[0],
+            transitions: &[(1, &[(3, 20, 0.5, 0.0)])],
+        },
+        AnimatorStateFixture {
+            name_hash: 11,
+            clip_ids: &[1, u32::MAX, 0, 1],
+            transitions: &[(0, &[(4, 20, 0.1, 0.0), (5, 30, 0.0, 0.75)]), (2, &[])],
+        },
+    ];
+    let controller = read_single_object(
+        animator_controller_type(),
+        animator_controller_data(
+            &[(1, 0), (2, 5)],
+            &states,
+            &[(1, "Base Layer"), (10, "Idle"), (20, "Speed")],
+            &[7, 8],
+        ),
+    );
+    let controller = AnimatorController::new(&controller);
+
+    assert_eq!(controller.get_name().unwrap(), "controller");
+    assert_eq!(controller.get_animation_clips().unwrap().len(), 2);
+    assert!(controller
+        .get_state_machine_behaviours()
+        .unwrap()
+        .is_empty());
+    let layers = controller.get_layers().unwrap();
+    assert_eq!(layers.len(), 2);
+    assert_eq!(layers[0].name, "Base Layer");
+    // Names missing from m_TOS fall back to their hash.
+    assert_eq!(layers[1].name, "2");
+    // A state machine index past the end leaves the layer without states.
+    assert!(layers[1].states.is_empty());
+
+    let [idle, run] = &layers[0].states[..] else {
+        panic!("expected two states, got {:?}", layers[0].states);
+    };
+    assert_eq!((&idle.name[..], idle.name_hash), ("Idle", 10));
+    assert_eq!(idle.clips, [0]);
+    assert_eq!(
+        idle.transitions,
+        [AnimatorTransition {
+            destination_state: 1,
+            conditions: vec![AnimatorCondition {
+                mode: 3,
+                parameter: "Speed".to_owned(),
+                threshold: 0.5,
+                exit_time: 0.0,
+            }],
+        }]
+    );
+    assert_eq!((&run.name[..], run.name_hash), ("11", 11));
+    // Empty blend tree nodes are skipped and clips listed once.
+    assert_eq!(run.clips, [1, 0]);
+    assert_eq!(
+        run.transitions,
+        [
+            AnimatorTransition {
+                destination_state: 0,
+                conditions: vec![
+                    AnimatorCondition {
+                        mode: 4,
+                        parameter: "Speed".to_owned(),
+                        threshold: 0.1,
+                        exit_time: 0.0,
+                    },
+                    AnimatorCondition {
+                        mode: 5,
+                        parameter: "30".to_owned(),
+                        threshold: 0.0,
+                        exit_time: 0.75,
+                    },
+                ],
+            },
+            AnimatorTransition {
+                destination_state: 2,
+                conditions: Vec::new(),
+            },
+        ]
+    );
+}
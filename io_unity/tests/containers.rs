@@ -0,0 +1,712 @@
+mod fixtures;
+
+use fixtures::*;
+use io_unity::type_tree::{convert::TryCastFrom, TypeTreeNode};
+
+#[test]
+fn normalizing_container_names_rekeys_loaded_containers() {
+    let mut viewer = load_bundle(text_asset_bundle_with_containers(&[
+        "Assets/Config/Settings.json",
+    ]));
+    let found = |viewer: &io_unity::unity_asset_view::UnityAssetViewer, name: &str| {
+        !viewer.container_entries(name).is_empty()
+    };
+    assert!(found(&viewer, "Assets/Config/Settings.json"));
+    assert!(!found(&viewer, "config/settings.json"));
+
+    viewer.set_normalize_container_names(true);
+    assert!(found(&viewer, "config/settings.json"));
+    assert!(found(&viewer, "Assets/Config/Settings.json"));
+    assert_eq!(
+        viewer
+            .get_raw_container_name("config/settings.json")
+            .unwrap(),
+        "Assets/Config/Settings.json"
+    );
+
+    viewer.set_normalize_container_names(false);
+    assert!(found(&viewer, "Assets/Config/Settings.json"));
+    assert!(!found(&viewer, "config/settings.json"));
+}
+
+#[test]
+fn container_names_depend_on_an_asset_bundle() {
+    let viewer = load_bundle(text_asset_bundle_with_containers(&["assets/a.txt"]));
+    assert!(viewer.has_container_names(CAB_NAME));
+
+    let viewer = load_bundle(text_asset_bundle(Compression::None, false, true, 1));
+    assert!(!viewer.has_container_names(CAB_NAME));
+    assert!(!viewer.has_container_names("CAB-missing"));
+}
+
+#[test]
+fn viewer_parts_keep_the_files_it_read() {
+    let viewer = load_bundle(text_asset_bundle(Compression::None, false, true, 2));
+    let serialized_file_id = viewer.cab_maps[CAB_NAME];
+    let parts = viewer.into_parts();
+
+    let bundle_id = parts.serialized_file_bundles[&serialized_file_id];
+    assert_eq!(parts.bundles[&bundle_id].cab_paths(), [CAB_NAME]);
+    let serialized_file = &parts.serialized_files[&serialized_file_id];
+    assert!(serialized_file
+        .get_tt_object_by_path_id(2)
+        .unwrap()
+        .is_some());
+    assert!(parts.bundle_paths.is_empty());
+}
+
+#[test]
+fn asset_paths_prefer_the_name_an_object_keeps() {
+    let mut asset_bundle = asset_bundle_type();
+    asset_bundle
+        .nodes
+        .extend(string_nodes(1, "m_AssetBundleName"));
+    let mut asset_bundle_data = asset_bundle_data(
+        false,
+        &[
+            ("assets/notes.txt", 2),
+            ("0f1e2d3c4b5a69788796a5b4c3d2e1f0", 3),
+        ],
+    );
+    let mut tail = Writer::new(false);
+    tail.aligned_string("ui/menus");
+    asset_bundle_data.extend(tail.data);
+
+    let mut named = vec![TypeTreeNode::new(0, "MonoBehaviour", "Base", -1)];
+    named.extend(string_nodes(1, "m_Name"));
+    named.extend(string_nodes(1, "m_AssetName"));
+    let mut named_data = Writer::new(false);
+    named_data
+        .aligned_string("menu")
+        .aligned_string("Assets/UI/Menu.prefab");
+
+    let cab = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![
+            asset_bundle,
+            text_asset_type(),
+            FixtureType {
+                class_id: 114,
+                nodes: named,
+            },
+        ],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: asset_bundle_data,
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 1,
+                data: text_asset_data(false, "notes", "text"),
+            },
+            FixtureObject {
+                path_id: 3,
+                type_index: 2,
+                data: named_data.data,
+            },
+        ],
+    }
+    .build();
+    let viewer = load_bundle(bundle(&[(CAB_NAME, &cab)], Compression::None, 256));
+    let serialized_file_id = *viewer.serialized_file_map.keys().next().unwrap();
+
+    assert_eq!(
+        viewer.asset_bundle_name(serialized_file_id).as_deref(),
+        Some("ui/menus")
+    );
+    assert_eq!(
+        viewer.asset_path(serialized_file_id, 2).as_deref(),
+        Some("assets/notes.txt")
+    );
+    assert_eq!(
+        viewer.asset_path(serialized_file_id, 3).as_deref(),
+        Some("Assets/UI/Menu.prefab")
+    );
+    assert_eq!(viewer.asset_path(serialized_file_id, 4), None);
+
+    let address = |address: &str| {
+        viewer
+            .get_by_address(address)
+            .unwrap()
+            .map(|object| object.path_id)
+    };
+    assert_eq!(address("assets/notes.txt"), Some(2));
+    assert_eq!(address("ui_assets_all.bundle/Assets/Notes.txt"), Some(2));
+    assert_eq!(address("UI/Menus/assets\\notes.txt"), Some(2));
+    assert_eq!(address("other/assets/notes.txt"), None);
+
+    // Restored from an index, the bundle name is known without parsing the
+    // AssetBundle.
+    #[cfg(feature = "serde")]
+    {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp_dir.path().join("menus.bundle"),
+            bundle(&[(CAB_NAME, &cab)], Compression::None, 256),
+        )
+        .unwrap();
+        let index_path = temp_dir.path().join("index.json");
+        let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+        viewer.read_bundle_dir(temp_dir.path()).unwrap();
+        viewer.save_index(&index_path).unwrap();
+
+        let index = std::fs::read_to_string(&index_path).unwrap();
+        std::fs::write(
+            &index_path,
+            index.replace("assets/notes.txt", "assets/indexed.txt"),
+        )
+        .unwrap();
+        let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+        viewer.load_index(&index_path).unwrap();
+        viewer.read_bundle_dir(temp_dir.path()).unwrap();
+        let object = viewer
+            .get_by_address("UI/Menus/Assets/Indexed.txt")
+            .unwrap();
+        assert_eq!(object.map(|object| object.path_id), Some(2));
+    }
+}
+
+#[test]
+fn resource_manager_containers_name_loose_file_objects() {
+    let file = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![resource_manager_type(), text_asset_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: resource_manager_data(&[("ui/title", 3), ("ui/credits", 2)]),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 1,
+                data: text_asset_data(false, "credits", "thanks"),
+            },
+            FixtureObject {
+                path_id: 3,
+                type_index: 1,
+                data: text_asset_data(false, "title", "hello"),
+            },
+        ],
+    }
+    .build();
+    let (viewer, _) = load_serialized_file(file);
+
+    let title = viewer
+        .get_type_tree_object_by_container_name("ui/title")
+        .unwrap()
+        .unwrap();
+    assert_eq!(title.path_id, 3);
+    let entry = &viewer.container_entries("ui/credits")[0];
+    assert_eq!((entry.preload_index, entry.preload_size), (None, None));
+    assert!(viewer
+        .container_dependencies("ui/credits")
+        .unwrap()
+        .is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn containers_restored_from_a_saved_index_are_seen_by_every_lookup() {
+    let cab = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![preload_asset_bundle_type(), text_asset_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: preload_asset_bundle_data(&[("assets/b", 0, 2, 3)], &[(0, 3), (0, 2)], &[]),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 1,
+                data: text_asset_data(false, "a", "a"),
+            },
+            FixtureObject {
+                path_id: 3,
+                type_index: 1,
+                data: text_asset_data(false, "b", "b"),
+            },
+        ],
+    }
+    .build();
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        temp_dir.path().join("b.bundle"),
+        bundle(&[(CAB_NAME, &cab)], Compression::None, 64),
+    )
+    .unwrap();
+    let index_path = temp_dir.path().join("index.json");
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+    viewer.read_bundle_dir(temp_dir.path()).unwrap();
+    viewer.save_index(&index_path).unwrap();
+
+    // Renamed in the index, so the name is only known from it.
+    let index = std::fs::read_to_string(&index_path).unwrap();
+    std::fs::write(&index_path, index.replace("assets/b", "assets/indexed")).unwrap();
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+    viewer.load_index(&index_path).unwrap();
+    viewer.read_bundle_dir(temp_dir.path()).unwrap();
+
+    let cab_id = viewer.cab_maps[CAB_NAME];
+    let entry = &viewer.container_entries("assets/indexed")[0];
+    assert_eq!(
+        (entry.preload_index, entry.preload_size),
+        (Some(0), Some(2))
+    );
+    assert_eq!(
+        viewer
+            .get_type_tree_object_by_container_name("assets/indexed")
+            .unwrap()
+            .unwrap()
+            .path_id,
+        3
+    );
+    assert_eq!(
+        viewer.container_dependencies("assets/indexed").unwrap(),
+        [(cab_id, 3), (cab_id, 2)]
+    );
+    assert!(viewer.get_by_address("assets/indexed").unwrap().is_some());
+    let parts = viewer.into_parts();
+    assert_eq!(parts.container_names[&cab_id][&3], "assets/indexed");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn bundles_with_a_fresh_index_entry_read_their_cab_on_first_use() {
+    let cab = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![asset_bundle_type(), text_asset_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: asset_bundle_data(false, &[("assets/notes.txt", 2)]),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 1,
+                data: text_asset_data(false, "notes", "hello"),
+            },
+        ],
+    }
+    .build();
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        temp_dir.path().join("notes.bundle"),
+        bundle(&[(CAB_NAME, &cab)], Compression::Lz4, 64),
+    )
+    .unwrap();
+    let index_path = temp_dir.path().join("index.json");
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+    viewer.read_bundle_dir(temp_dir.path()).unwrap();
+    assert!(viewer.serialized_file_map[&viewer.cab_maps[CAB_NAME]].is_read());
+    viewer.save_index(&index_path).unwrap();
+
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+    viewer.load_index(&index_path).unwrap();
+    let report = viewer.read_dir_report(temp_dir.path()).unwrap();
+    assert_eq!((report.bundles_loaded, report.serialized_files), (1, 1));
+    let file = &viewer.serialized_file_map[&viewer.cab_maps[CAB_NAME]];
+    assert!(!file.is_read());
+
+    let object = viewer
+        .get_type_tree_object_by_container_name("assets/notes.txt")
+        .unwrap()
+        .unwrap();
+    assert_eq!(object.path_id, 2);
+    let file = &viewer.serialized_file_map[&viewer.cab_maps[CAB_NAME]];
+    assert!(file.is_read());
+    assert_eq!(file.read_error(), None);
+
+    // A CAB that turns out unreadable has no objects rather than failing.
+    let file = io_unity::serialized_file::SerializedFile::read_deferred(
+        Box::new(std::io::Cursor::new(vec![0u8; 8])),
+        0,
+        None,
+    );
+    assert!(file.get_object_map().is_empty());
+    assert!(file.read_error().is_some());
+}
+
+#[test]
+fn container_entries_keep_their_preload_range_and_bundle_dependencies() {
+    let cab = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![preload_asset_bundle_type(), text_asset_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: preload_asset_bundle_data(
+                    &[("assets/a", 1, 2, 2), ("assets/broken", 2, 5, 3)],
+                    &[(0, 2), (0, 3), (1, 4)],
+                    &["shared.bundle", "textures.bundle"],
+                ),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 1,
+                data: text_asset_data(false, "a", "a"),
+            },
+            FixtureObject {
+                path_id: 3,
+                type_index: 1,
+                data: text_asset_data(false, "b", "b"),
+            },
+        ],
+    }
+    .build();
+    let viewer = load_bundle(bundle(&[(CAB_NAME, &cab)], Compression::None, 64));
+
+    let entry = &viewer.container_entries("assets/a")[0];
+    assert_eq!(
+        (entry.preload_index, entry.preload_size),
+        (Some(1), Some(2))
+    );
+    let preload: Vec<_> = viewer
+        .get_container_preload_pptrs(entry)
+        .unwrap()
+        .iter()
+        .map(|pptr| {
+            (
+                i32::try_cast_from(pptr, "/Base/m_FileID").unwrap(),
+                i64::try_cast_from(pptr, "/Base/m_PathID").unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(preload, [(0, 3), (1, 4)]);
+    assert_eq!(
+        viewer.get_container_bundle_dependencies(entry).unwrap(),
+        ["shared.bundle", "textures.bundle"]
+    );
+
+    // Five entries from index 2 run past the three entry table.
+    let broken = &viewer.container_entries("assets/broken")[0];
+    assert!(viewer.get_container_preload_pptrs(broken).is_err());
+}
+
+#[test]
+fn container_dependencies_resolve_the_preload_slice_across_files() {
+    const SHARED: &str = "CAB-22222222222222222222222222222222";
+    let shared = text_asset_file(false, true, 2);
+    let cab = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![preload_asset_bundle_type(), text_asset_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: preload_asset_bundle_data(
+                    &[("assets/a", 0, 1, 2), ("assets/b", 1, 2, 3)],
+                    &[(0, 2), (0, 3), (1, 2)],
+                    &[],
+                ),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 1,
+                data: text_asset_data(false, "a", "a"),
+            },
+            FixtureObject {
+                path_id: 3,
+                type_index: 1,
+                data: text_asset_data(false, "b", "b"),
+            },
+        ],
+    }
+    .build_with_externals(&[&format!("archive:/{SHARED}/{SHARED}")]);
+    let viewer = load_bundle(bundle(
+        &[(SHARED, &shared), (CAB_NAME, &cab)],
+        Compression::None,
+        64,
+    ));
+    let shared_id = viewer
+        .get_serialized_file_by_path(SHARED)
+        .unwrap()
+        .get_serialized_file_id();
+    let cab_id = viewer
+        .get_serialized_file_by_path(CAB_NAME)
+        .unwrap()
+        .get_serialized_file_id();
+
+    let asset_bundle = viewer.get_object(CAB_NAME, 1).unwrap().unwrap();
+    let preload_table: Vec<_> = asset_bundle
+        .get_pptr_array_by_path("/Base/m_PreloadTable/Array")
+        .unwrap()
+        .into_iter()
+        .map(|ids| (ids.serialized_file_id, ids.file_id, ids.path_id))
+        .collect();
+    assert_eq!(
+        preload_table,
+        [(cab_id, 0, 2), (cab_id, 0, 3), (cab_id, 1, 2)]
+    );
+    assert!(asset_bundle
+        .get_pptr_array_by_path("/Base/m_Container/Array")
+        .is_none());
+
+    let entry = &viewer.container_entries("assets/b")[0];
+    assert_eq!(
+        (entry.preload_index, entry.preload_size),
+        (Some(1), Some(2))
+    );
+    assert_eq!(
+        viewer.container_dependencies("assets/a").unwrap(),
+        [(cab_id, 2)]
+    );
+    assert_eq!(
+        viewer.container_dependencies("assets/b").unwrap(),
+        [(cab_id, 3), (shared_id, 2)]
+    );
+    assert!(viewer
+        .container_dependencies("assets/missing")
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn viewer_diff_lists_changed_containers() {
+    use io_unity::type_tree::visitor::{FieldDiff, LeafValue};
+    use io_unity::unity_asset_view::{ContainerChange, ContainerDiff};
+
+    let old = load_bundle(text_asset_bundle_with_containers(&[
+        "assets/a.txt",
+        "assets/b.txt",
+        "assets/c.txt",
+    ]));
+    // `c` holds "text3" here and "text4" in `old`.
+    let new = load_bundle(text_asset_bundle_with_containers(&[
+        "assets/a.txt",
+        "assets/c.txt",
+        "assets/d.txt",
+    ]));
+
+    let kinds: Vec<_> = old
+        .diff(&new)
+        .containers
+        .into_iter()
+        .map(|diff| (diff.name, diff.change))
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            ("assets/b.txt".to_owned(), ContainerChange::Removed),
+            (
+                "assets/c.txt".to_owned(),
+                ContainerChange::Changed { fields: None }
+            ),
+            ("assets/d.txt".to_owned(), ContainerChange::Added),
+        ]
+    );
+    assert!(old.diff(&old).containers.is_empty());
+
+    let string = |value: &str| Some(LeafValue::String(value.to_owned()));
+    let diff = old.diff_with_fields(&new);
+    assert_eq!(
+        diff.containers[1],
+        ContainerDiff {
+            name: "assets/c.txt".to_owned(),
+            change: ContainerChange::Changed {
+                fields: Some(vec![
+                    FieldDiff {
+                        path: "/Base/m_Name".to_owned(),
+                        old: string("text4"),
+                        new: string("text3"),
+                    },
+                    FieldDiff {
+                        path: "/Base/m_Script".to_owned(),
+                        old: string("script of 4"),
+                        new: string("script of 3"),
+                    },
+                ]),
+            },
+        }
+    );
+}
+
+#[test]
+fn viewer_diff_follows_moved_assets_by_what_they_reference() {
+    use io_unity::unity_asset_view::ContainerChange;
+
+    const SHARED: &str = "CAB-55555555555555555555555555555555";
+    const OTHER: &str = "CAB-66666666666666666666666666666666";
+    // "assets/pointer" at `path_id`, referencing path id `target` through
+    // the external `file_id`.
+    let pointer_bundle = |externals: &[&str], path_id: i64, file_id: i32, target: i64| {
+        let externals: Vec<_> = externals
+            .iter()
+            .map(|name| format!("archive:/{name}/{name}"))
+            .collect();
+        let cab = SerializedFileFixture {
+            format_version: 17,
+            big_endian: false,
+            enable_type_tree: true,
+            types: vec![asset_bundle_type(), pointer_type()],
+            objects: vec![
+                FixtureObject {
+                    path_id: 1,
+                    type_index: 0,
+                    data: asset_bundle_data(false, &[("assets/pointer", path_id)]),
+                },
+                FixtureObject {
+                    path_id,
+                    type_index: 1,
+                    data: pointer_data(file_id, target),
+                },
+            ],
+        }
+        .build_with_externals(&externals.iter().map(String::as_str).collect::<Vec<_>>());
+        load_bundle(bundle(&[(CAB_NAME, &cab)], Compression::None, 256))
+    };
+    let old = pointer_bundle(&[SHARED], 2, 1, 5);
+    // Moved to a bundle where the same target is the second external.
+    let moved = pointer_bundle(&[OTHER, SHARED], 9, 2, 5);
+    // The same ids, now pointing into another file.
+    let retargeted = pointer_bundle(&[OTHER, SHARED], 9, 1, 5);
+
+    let content_hash = |viewer: &io_unity::unity_asset_view::UnityAssetViewer| {
+        viewer
+            .get_type_tree_object_by_container_name("assets/pointer")
+            .unwrap()
+            .unwrap()
+            .content_hash()
+    };
+    assert_ne!(content_hash(&old), content_hash(&moved));
+    assert!(old.diff(&moved).containers.is_empty());
+    assert_eq!(
+        old.diff(&retargeted).containers[0].change,
+        ContainerChange::Changed { fields: None }
+    );
+}
+
+#[test]
+fn scenes_count_their_root_game_objects() {
+    let scene = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![scene_asset_bundle_type(), transform_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: scene_asset_bundle_data(true),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 1,
+                data: transform_data(10, 0),
+            },
+            FixtureObject {
+                path_id: 3,
+                type_index: 1,
+                data: transform_data(11, 2),
+            },
+            FixtureObject {
+                path_id: 4,
+                type_index: 1,
+                data: transform_data(12, 0),
+            },
+        ],
+    }
+    .build();
+    const ASSETS: &str = "CAB-44444444444444444444444444444444";
+    let assets = text_asset_file(false, true, 1);
+    let viewer = load_bundle(bundle(
+        &[(CAB_NAME, &scene), (ASSETS, &assets)],
+        Compression::None,
+        1 << 16,
+    ));
+
+    let scenes = viewer.scenes();
+    assert_eq!(scenes.len(), 1);
+    assert_eq!(scenes[0].cab_name, CAB_NAME);
+    assert_eq!(scenes[0].root_game_objects, 2);
+}
+
+#[test]
+fn find_objects_by_class_and_name() {
+    let viewer = load_bundle(text_asset_bundle_with_containers(&[
+        "assets/UI/Icon.json",
+        "assets/notes",
+    ]));
+    let serialized_file_id = *viewer.serialized_file_map.keys().next().unwrap();
+    let found = |class_id, name| -> Vec<i64> {
+        viewer
+            .find_objects(class_id, name)
+            .into_iter()
+            .map(|(id, path_id)| {
+                assert_eq!(id, serialized_file_id);
+                path_id
+            })
+            .collect()
+    };
+
+    assert_eq!(found(None, None), [1, 2, 3]);
+    assert_eq!(found(Some(49), None), [2, 3]);
+    // By container name, then by m_Name.
+    assert_eq!(found(Some(49), Some("ICON")), [2]);
+    assert_eq!(found(None, Some("Text3")), [3]);
+    assert_eq!(found(None, Some("bund")), [1]);
+    assert!(found(Some(142), Some("text")).is_empty());
+}
+
+#[test]
+fn byte_identical_objects_are_duplicates() {
+    let texts = [("a", "same"), ("b", "other"), ("a", "same"), ("a", "same")];
+    let mut objects: Vec<_> = texts
+        .iter()
+        .enumerate()
+        .map(|(index, (name, script))| FixtureObject {
+            path_id: index as i64 + 1,
+            type_index: 0,
+            data: text_asset_data(false, name, script),
+        })
+        .collect();
+    // The same bytes under another class are not a duplicate.
+    objects.push(FixtureObject {
+        path_id: 5,
+        type_index: 1,
+        data: text_asset_data(false, "a", "same"),
+    });
+    let mut other_class = text_asset_type();
+    other_class.class_id = 48;
+    let file = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![text_asset_type(), other_class],
+        objects,
+    }
+    .build();
+    let (viewer, id) = load_serialized_file(file);
+    let serialized_file = &viewer.serialized_file_map[&id];
+
+    let hash = |path_id| {
+        serialized_file
+            .get_tt_object_by_path_id(path_id)
+            .unwrap()
+            .unwrap()
+            .content_hash()
+    };
+    assert_eq!(hash(1), hash(3));
+    assert_ne!(hash(1), hash(2));
+    assert_eq!(
+        viewer.find_duplicate_objects().unwrap(),
+        [vec![(id, 1), (id, 3), (id, 4)]]
+    );
+}
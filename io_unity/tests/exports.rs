@@ -0,0 +1,383 @@
+#![cfg(feature = "external-class-handle")]
+
+mod fixtures;
+
+use fixtures::*;
+
+#[test]
+fn export_text_asset_by_container_name() {
+    let viewer = load_bundle(text_asset_bundle_with_containers(&[
+        "assets/config/settings.json",
+        "assets/notes",
+    ]));
+    let temp_dir = tempfile::tempdir().unwrap();
+    let out_dir = temp_dir.path().join("export");
+
+    let path = viewer
+        .export_asset("assets/config/settings.json", &out_dir)
+        .unwrap();
+    assert_eq!(path, out_dir.join("settings.json"));
+    assert_eq!(std::fs::read(&path).unwrap(), b"script of 2");
+
+    let path = viewer.export_asset("assets/notes", &out_dir).unwrap();
+    assert_eq!(path, out_dir.join("notes.txt"));
+
+    assert!(viewer.export_asset("assets/missing", &out_dir).is_err());
+}
+
+#[cfg(feature = "external-class-handle-texture2d")]
+#[test]
+fn exported_textures_are_written_top_row_first() {
+    let viewer = load_bundle(alpha8_texture_bundle("assets/tex", [10, 20]));
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let path = viewer.export_asset("assets/tex", temp_dir.path()).unwrap();
+    let alphas: Vec<_> = image::open(path)
+        .unwrap()
+        .to_rgba8()
+        .pixels()
+        .map(|pixel| pixel[3])
+        .collect();
+    assert_eq!(alphas, [20, 10]);
+}
+
+#[test]
+fn export_all_writes_each_selected_object() {
+    use io_unity::unity_asset_view::ExportFilter;
+
+    let mut viewer = load_bundle(text_asset_bundle_with_containers(&[
+        "assets/a.json",
+        "./assets/a.json",
+        "assets/notes",
+    ]));
+    // Every object is over budget, so each is written on its own.
+    viewer.set_export_memory_budget_bytes(Some(1));
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let report = viewer
+        .export_all(temp_dir.path(), ExportFilter::default())
+        .unwrap();
+    let written: Vec<_> = report
+        .written
+        .iter()
+        .map(|(_, path_id, path)| (*path_id, path.strip_prefix(temp_dir.path()).unwrap()))
+        .collect();
+    let expected = [
+        (2, "assets/a.json"),
+        (3, "assets/a_3.json"),
+        (4, "assets/notes.txt"),
+    ];
+    assert_eq!(written.len(), expected.len());
+    for ((path_id, path), (expected_id, expected_path)) in written.into_iter().zip(expected) {
+        assert_eq!(
+            (path_id, path),
+            (expected_id, std::path::Path::new(expected_path))
+        );
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join(path)).unwrap(),
+            format!("script of {path_id}")
+        );
+    }
+    assert!(report.skipped.is_empty() && report.failed.is_empty());
+
+    let report = viewer
+        .export_all(
+            temp_dir.path(),
+            ExportFilter {
+                class_ids: Some(vec![49, 142]),
+                name_substring: Some("NOTES".to_owned()),
+            },
+        )
+        .unwrap();
+    assert_eq!(report.written.len(), 1);
+    assert!(report.skipped.is_empty());
+
+    let report = viewer
+        .export_all(
+            temp_dir.path(),
+            ExportFilter {
+                class_ids: Some(vec![142]),
+                name_substring: None,
+            },
+        )
+        .unwrap();
+    assert!(report.written.is_empty());
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(report.skipped[0].2, "no exporter for AssetBundle");
+}
+
+#[test]
+fn export_all_reports_objects_it_cannot_write() {
+    use io_unity::unity_asset_view::ExportFilter;
+
+    let viewer = load_bundle(text_asset_bundle_with_containers(&[
+        "assets/a.json",
+        "notes",
+    ]));
+    let temp_dir = tempfile::tempdir().unwrap();
+    // A file where the assets directory should go.
+    std::fs::write(temp_dir.path().join("assets"), b"").unwrap();
+
+    let report = viewer
+        .export_all(temp_dir.path(), ExportFilter::default())
+        .unwrap();
+    let failed: Vec<_> = report
+        .failed
+        .iter()
+        .map(|(_, path_id, _)| *path_id)
+        .collect();
+    assert_eq!(failed, [2]);
+    let written: Vec<_> = report
+        .written
+        .iter()
+        .map(|(_, path_id, path)| (*path_id, path.strip_prefix(temp_dir.path()).unwrap()))
+        .collect();
+    assert_eq!(written, [(3, std::path::Path::new("notes.txt"))]);
+}
+
+#[cfg(feature = "external-class-handle-texture2d")]
+#[test]
+fn export_all_writes_textures_top_row_first() {
+    use io_unity::unity_asset_view::ExportFilter;
+
+    let viewer = load_bundle(alpha8_texture_bundle("assets/tex", [10, 20]));
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let report = viewer
+        .export_all(temp_dir.path(), ExportFilter::default())
+        .unwrap();
+    assert_eq!(report.written.len(), 1);
+    let alphas: Vec<_> = image::open(&report.written[0].2)
+        .unwrap()
+        .to_rgba8()
+        .pixels()
+        .map(|pixel| pixel[3])
+        .collect();
+    assert_eq!(alphas, [20, 10]);
+}
+
+#[cfg(feature = "external-class-handle-texture2d")]
+#[test]
+fn sprite_dumps_cut_sprites_from_textures_and_atlases() {
+    // A 2x2 texture, cut into a loose sprite of its bottom row, two
+    // sprites of a sheet packed by an atlas, the left column flipped, and
+    // two same named sprites of its corners.
+    let pixels: Vec<u8> = (0..16).collect();
+    let entries = [
+        AtlasEntry {
+            key: 1,
+            texture_path_id: 2,
+            rect: [0.0, 0.0, 1.0, 2.0],
+            settings_raw: 1 | 2 << 2, // FlipVertical
+        },
+        AtlasEntry {
+            key: 2,
+            texture_path_id: 2,
+            rect: [1.0, 0.0, 1.0, 2.0],
+            settings_raw: 1,
+        },
+    ];
+    let cab = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![
+            asset_bundle_type(),
+            texture2d_type(),
+            sprite_type(),
+            atlas_sprite_type(),
+            sprite_atlas_type(),
+        ],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: asset_bundle_data(
+                    false,
+                    &[
+                        ("assets/ui/icon.png", 3),
+                        ("assets/ui/sheet.png", 4),
+                        ("assets/ui/sheet.png", 5),
+                        ("assets/hud/bar.png", 7),
+                        ("assets/ui/twins.png", 8),
+                        ("assets/ui/twins.png", 9),
+                    ],
+                ),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 1,
+                data: texture2d_data(
+                    false,
+                    &TextureFixture {
+                        width: 2,
+                        height: 2,
+                        texture_format: 4, // RGBA32
+                        mip_count: 1,
+                        image_data: &pixels,
+                        stream_offset: 0,
+                        stream_size: 0,
+                        stream_path: "",
+                    },
+                ),
+            },
+            FixtureObject {
+                path_id: 3,
+                type_index: 2,
+                data: sprite_data([0.0, 0.0, 2.0, 1.0], 2, 0),
+            },
+            FixtureObject {
+                path_id: 4,
+                type_index: 3,
+                data: atlas_sprite_data("left", 1, 6),
+            },
+            FixtureObject {
+                path_id: 5,
+                type_index: 3,
+                data: atlas_sprite_data("right", 2, 6),
+            },
+            FixtureObject {
+                path_id: 6,
+                type_index: 4,
+                data: sprite_atlas_data(&[(4, "left"), (5, "right")], &entries),
+            },
+            FixtureObject {
+                path_id: 7,
+                type_index: 2,
+                data: sprite_data([0.0, 0.0, 1.0, 1.0], 2, 0),
+            },
+            FixtureObject {
+                path_id: 8,
+                type_index: 2,
+                data: sprite_data([0.0, 0.0, 1.0, 1.0], 2, 0),
+            },
+            FixtureObject {
+                path_id: 9,
+                type_index: 2,
+                data: sprite_data([1.0, 1.0, 1.0, 1.0], 2, 0),
+            },
+        ],
+    }
+    .build();
+    let viewer = load_bundle(bundle(&[(CAB_NAME, &cab)], Compression::None, 1 << 16));
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let count = viewer
+        .dump_sprites(temp_dir.path(), Some("assets/ui/"))
+        .unwrap();
+    assert_eq!(count, 5);
+    let pixels_of = |name: &str| {
+        image::open(temp_dir.path().join("assets/ui").join(name))
+            .unwrap()
+            .to_rgba8()
+            .into_raw()
+    };
+    // Rows are written top first.
+    assert_eq!(pixels_of("icon.png"), (0..8).collect::<Vec<u8>>());
+    assert_eq!(pixels_of("sheet_left.png"), [0, 1, 2, 3, 8, 9, 10, 11]);
+    assert_eq!(pixels_of("sheet_right.png"), [12, 13, 14, 15, 4, 5, 6, 7]);
+    // Sprites of one container sharing a name both get a file.
+    assert_eq!(pixels_of("twins_sprite.png"), [0, 1, 2, 3]);
+    assert_eq!(pixels_of("twins_sprite_9.png"), [12, 13, 14, 15]);
+    assert!(!temp_dir.path().join("assets/hud").exists());
+}
+
+#[test]
+fn audio_dumps_decode_what_they_can_and_pass_the_rest_through() {
+    use io_unity::classes::audio_clip::fsb5::Fsb5Codec;
+
+    let pcm = fsb5_bank(2, 2, &[1, 0, 2, 0]);
+    let mpeg = fsb5_bank(11, 1, &[0xff, 0xfb, 0, 0]);
+    let opaque = b"not a bank".to_vec();
+    let resource: Vec<u8> = [&pcm[..], &mpeg, &opaque].concat();
+    let source = format!("archive:/{CAB_NAME}/{CAB_NAME}.resource");
+    let clips = [
+        ("hit", &source[..], 0, pcm.len()),
+        ("music", &source[..], pcm.len(), mpeg.len()),
+        ("raw", &source[..], pcm.len() + mpeg.len(), opaque.len()),
+        ("gone", "archive:/missing/missing.resource", 0, 4),
+        ("hit again", &source[..], 0, pcm.len()),
+    ];
+    let mut objects = vec![FixtureObject {
+        path_id: 1,
+        type_index: 0,
+        data: asset_bundle_data(
+            false,
+            &[
+                ("assets/sfx/hit.wav", 2),
+                ("assets/sfx/music.mp3", 3),
+                ("assets/sfx/raw.ogg", 4),
+                ("assets/sfx/gone.wav", 5),
+                ("assets/sfx/hit.wav", 6),
+            ],
+        ),
+    }];
+    objects.extend(
+        clips
+            .iter()
+            .enumerate()
+            .map(|(index, (name, source, offset, size))| FixtureObject {
+                path_id: index as i64 + 2,
+                type_index: 1,
+                data: audio_clip_data(name, source, *offset as u64, *size as u64),
+            }),
+    );
+    let cab = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![asset_bundle_type(), audio_clip_type()],
+        objects,
+    }
+    .build();
+    let viewer = load_bundle(bundle(
+        &[
+            (CAB_NAME, &cab),
+            (&format!("{CAB_NAME}.resource"), &resource),
+        ],
+        Compression::None,
+        1 << 16,
+    ));
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let sfx = temp_dir.path().join("assets/sfx");
+    let mut report = viewer.dump_audio(temp_dir.path(), true, None).unwrap();
+    report.clips.sort_by(|a, b| a.path.cmp(&b.path));
+    let clips: Vec<_> = report
+        .clips
+        .iter()
+        .map(|clip| (clip.path.clone(), clip.codec, clip.decoded))
+        .collect();
+    assert_eq!(
+        clips,
+        [
+            (sfx.join("hit.wav"), Some(Fsb5Codec::Pcm16), true),
+            (sfx.join("hit_6.wav"), Some(Fsb5Codec::Pcm16), true),
+            (sfx.join("music.fsb"), Some(Fsb5Codec::Mpeg), false),
+            (sfx.join("raw.bin"), None, false),
+        ]
+    );
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(
+        report.skipped[0].0,
+        std::path::Path::new("assets/sfx/gone.wav")
+    );
+    let wav = std::fs::read(sfx.join("hit.wav")).unwrap();
+    assert_eq!(&wav[..4], b"RIFF");
+    assert_eq!(&wav[wav.len() - 4..], [1, 0, 2, 0]);
+    assert_eq!(std::fs::read(sfx.join("music.fsb")).unwrap(), mpeg);
+    assert_eq!(std::fs::read(sfx.join("raw.bin")).unwrap(), opaque);
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let report = viewer
+        .dump_audio(temp_dir.path(), false, Some("assets/sfx/hit"))
+        .unwrap();
+    assert_eq!(report.clips.len(), 2);
+    assert!(report.clips.iter().all(|clip| !clip.decoded));
+    for name in ["hit.fsb", "hit_6.fsb"] {
+        assert_eq!(
+            std::fs::read(temp_dir.path().join("assets/sfx").join(name)).unwrap(),
+            pcm
+        );
+    }
+}
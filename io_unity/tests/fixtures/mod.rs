@@ -0,0 +1,1976 @@
+//! Minimal hand-built UnityFS bundles and serialized files for regression
+//! tests. Everything is generated in code so fixtures stay reviewable and
+//! cover each compression mode, endianness and TypeTree setting.
+#![allow(dead_code)]
+
+use std::io::Cursor;
+
+use io_unity::type_tree::{TypeTreeNode, TypeTreeObjectRef};
+use io_unity::unity_asset_view::UnityAssetViewer;
+
+pub const CAB_NAME: &str = "CAB-0123456789abcdef0123456789abcdef";
+pub const UNITY_VERSION: &str = "2019.4.0f1";
+
+pub struct FixtureType {
+    pub class_id: i32,
+    pub nodes: Vec<TypeTreeNode>,
+}
+
+/// An `m_RefTypes` entry, written by format 22 files only.
+pub struct FixtureRefType {
+    pub class_name: &'static str,
+    pub name_space: &'static str,
+    pub assembly_name: &'static str,
+    pub nodes: Vec<TypeTreeNode>,
+}
+
+pub struct FixtureObject {
+    pub path_id: i64,
+    /// Index into [`SerializedFileFixture::types`].
+    pub type_index: i32,
+    pub data: Vec<u8>,
+}
+
+/// A serialized file of format 13 (Unity 5.0 to 5.4), 17 (5.5 to 2018.4)
+/// or 22 (2020.1 on). Format 13 files enable 64-bit path ids only when an
+/// object's path id does not fit in 32 bits, and store class ids in place
+/// of type indices.
+pub struct SerializedFileFixture {
+    pub format_version: u32,
+    pub big_endian: bool,
+    pub enable_type_tree: bool,
+    pub types: Vec<FixtureType>,
+    pub objects: Vec<FixtureObject>,
+}
+
+/// Writes values in the fixture's endianness.
+pub struct Writer {
+    pub big_endian: bool,
+    pub data: Vec<u8>,
+}
+
+impl Writer {
+    pub fn new(big_endian: bool) -> Self {
+        Writer {
+            big_endian,
+            data: Vec::new(),
+        }
+    }
+
+    pub fn u16(&mut self, value: u16) -> &mut Self {
+        let bytes = if self.big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        };
+        self.data.extend_from_slice(&bytes);
+        self
+    }
+
+    pub fn u32(&mut self, value: u32) -> &mut Self {
+        let bytes = if self.big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        };
+        self.data.extend_from_slice(&bytes);
+        self
+    }
+
+    pub fn i32(&mut self, value: i32) -> &mut Self {
+        self.u32(value as u32)
+    }
+
+    pub fn i64(&mut self, value: i64) -> &mut Self {
+        let bytes = if self.big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        };
+        self.data.extend_from_slice(&bytes);
+        self
+    }
+
+    pub fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.data.extend_from_slice(bytes);
+        self
+    }
+
+    pub fn null_string(&mut self, value: &str) -> &mut Self {
+        self.data.extend_from_slice(value.as_bytes());
+        self.data.push(0);
+        self
+    }
+
+    /// A length-prefixed string padded to 4 bytes, as TypeTree strings are.
+    pub fn aligned_string(&mut self, value: &str) -> &mut Self {
+        self.i32(value.len() as i32)
+            .bytes(value.as_bytes())
+            .align(4)
+    }
+
+    pub fn align(&mut self, alignment: usize) -> &mut Self {
+        while !self.data.len().is_multiple_of(alignment) {
+            self.data.push(0);
+        }
+        self
+    }
+}
+
+impl SerializedFileFixture {
+    pub fn build(&self) -> Vec<u8> {
+        self.build_with_externals(&[])
+    }
+
+    /// Builds the file with an external reference to each of the given
+    /// paths, in order, so that `m_FileID` 1 refers to the first.
+    pub fn build_with_externals(&self, externals: &[&str]) -> Vec<u8> {
+        self.build_with(externals, &[])
+    }
+
+    /// Builds a format 22 file with the given ref types.
+    pub fn build_with_ref_types(&self, ref_types: &[FixtureRefType]) -> Vec<u8> {
+        assert_eq!(self.format_version, 22);
+        self.build_with(&[], ref_types)
+    }
+
+    fn build_with(&self, externals: &[&str], ref_types: &[FixtureRefType]) -> Vec<u8> {
+        assert!(matches!(self.format_version, 13 | 17 | 22));
+        let legacy = self.format_version < 14;
+        let large_files = self.format_version >= 22;
+        let big_ids = !legacy
+            || self
+                .objects
+                .iter()
+                .any(|object| i32::try_from(object.path_id).is_err());
+        // Format 22 follows the common header with 64-bit sizes and offsets.
+        let header_size = if large_files { 48 } else { 20 };
+
+        let mut meta = Writer::new(self.big_endian);
+        meta.null_string(UNITY_VERSION)
+            .i32(5) // StandaloneWindows
+            .bytes(&[self.enable_type_tree as u8])
+            .u32(self.types.len() as u32);
+        let write_type_tree = |meta: &mut Writer, nodes: &[TypeTreeNode]| {
+            let mut strings = Vec::new();
+            let mut offset_of = |s: &str| {
+                let offset = strings.len() as u32;
+                strings.extend_from_slice(s.as_bytes());
+                strings.push(0);
+                offset
+            };
+            let offsets: Vec<(u32, u32)> = nodes
+                .iter()
+                .map(|node| (offset_of(&node.type_name), offset_of(&node.name)))
+                .collect();
+            meta.i32(nodes.len() as i32).i32(strings.len() as i32);
+            for (index, (node, (type_offset, name_offset))) in nodes.iter().zip(offsets).enumerate()
+            {
+                meta.u16(node.version)
+                    .bytes(&[node.level, node.is_array as u8])
+                    .u32(type_offset)
+                    .u32(name_offset)
+                    .i32(node.byte_size)
+                    .i32(index as i32)
+                    .i32(node.meta_flag);
+                if large_files {
+                    meta.i64(0); // ref_type_hash
+                }
+            }
+            meta.bytes(&strings);
+        };
+        // MonoBehaviour layouts each get the next script type index.
+        let script_type_index = |type_index: usize| {
+            let types = &self.types[..=type_index];
+            if types[type_index].class_id == 114 {
+                types.iter().filter(|tp| tp.class_id == 114).count() as u16 - 1
+            } else {
+                u16::MAX
+            }
+        };
+        for (type_index, tp) in self.types.iter().enumerate() {
+            meta.i32(tp.class_id);
+            if !legacy {
+                meta.bytes(&[0]) // is_stripped_type
+                    .u16(script_type_index(type_index));
+                if tp.class_id == 114 {
+                    meta.bytes(&[0; 16]); // script_id
+                }
+            }
+            meta.bytes(&[type_index as u8; 16]); // old_type_hash, distinct per type
+            if self.enable_type_tree {
+                write_type_tree(&mut meta, &tp.nodes);
+                if large_files {
+                    meta.i32(0); // type_dependencies
+                }
+            }
+        }
+
+        let mut data = Writer::new(self.big_endian);
+        let mut object_ranges = Vec::new();
+        for object in &self.objects {
+            data.align(8);
+            object_ranges.push((data.data.len(), object.data.len()));
+            data.bytes(&object.data);
+        }
+
+        if legacy {
+            meta.i32(big_ids as i32);
+        }
+        meta.i32(self.objects.len() as i32);
+        for (object, (start, size)) in self.objects.iter().zip(object_ranges) {
+            if legacy {
+                if big_ids {
+                    meta.i64(object.path_id);
+                } else {
+                    meta.i32(object.path_id as i32);
+                }
+                let class_id = self.types[object.type_index as usize].class_id;
+                meta.u32(start as u32)
+                    .u32(size as u32)
+                    .i32(class_id)
+                    .u16(class_id as u16)
+                    .u16(script_type_index(object.type_index as usize));
+                continue;
+            }
+            // Objects are aligned relative to the start of the file.
+            while !(header_size + meta.data.len()).is_multiple_of(4) {
+                meta.data.push(0);
+            }
+            meta.i64(object.path_id);
+            if large_files {
+                meta.i64(start as i64);
+            } else {
+                meta.u32(start as u32);
+            }
+            meta.u32(size as u32).i32(object.type_index);
+        }
+        meta.i32(0) // script types
+            .i32(externals.len() as i32);
+        for path in externals {
+            meta.null_string("") // temp_empty
+                .bytes(&[0; 16]) // guid
+                .i32(0) // type
+                .null_string(path);
+        }
+        if large_files {
+            meta.i32(ref_types.len() as i32);
+            for ref_type in ref_types {
+                meta.i32(114)
+                    .bytes(&[0]) // is_stripped_type
+                    .u16(0) // script_type_index
+                    .bytes(&[0; 16]) // script_id
+                    .bytes(&[0; 16]); // old_type_hash
+                if self.enable_type_tree {
+                    write_type_tree(&mut meta, &ref_type.nodes);
+                    meta.null_string(ref_type.class_name)
+                        .null_string(ref_type.name_space)
+                        .null_string(ref_type.assembly_name);
+                }
+            }
+        }
+        meta.null_string(""); // user_information
+
+        let mut data_offset = header_size + meta.data.len();
+        data_offset += (16 - data_offset % 16) % 16;
+        let file_size = data_offset + data.data.len();
+
+        let mut file = Writer::new(true);
+        if large_files {
+            // The 32-bit fields are left zero, as Unity writes them.
+            file.u32(0)
+                .u32(0)
+                .u32(22)
+                .u32(0)
+                .bytes(&[self.big_endian as u8, 0, 0, 0])
+                .u32(meta.data.len() as u32)
+                .i64(file_size as i64)
+                .i64(data_offset as i64)
+                .i64(0);
+        } else {
+            file.u32(meta.data.len() as u32)
+                .u32(file_size as u32)
+                .u32(self.format_version)
+                .u32(data_offset as u32)
+                .bytes(&[self.big_endian as u8, 0, 0, 0]);
+        }
+        file.bytes(&meta.data);
+        file.data.resize(data_offset, 0);
+        file.bytes(&data.data);
+        file.data
+    }
+}
+
+pub fn text_asset_type() -> FixtureType {
+    let mut nodes = vec![TypeTreeNode::new(0, "TextAsset", "Base", -1)];
+    nodes.extend(string_nodes(1, "m_Name"));
+    nodes.extend(string_nodes(1, "m_Script"));
+    FixtureType {
+        class_id: 49,
+        nodes,
+    }
+}
+
+pub fn text_asset_data(big_endian: bool, name: &str, script: &str) -> Vec<u8> {
+    let mut data = Writer::new(big_endian);
+    data.aligned_string(name).aligned_string(script);
+    data.data
+}
+
+/// A serialized file with TextAssets named `text{path_id}` at path ids 1..=count.
+pub fn text_asset_file(big_endian: bool, enable_type_tree: bool, count: i64) -> Vec<u8> {
+    SerializedFileFixture {
+        format_version: 17,
+        big_endian,
+        enable_type_tree,
+        types: vec![text_asset_type()],
+        objects: (1..=count)
+            .map(|path_id| FixtureObject {
+                path_id,
+                type_index: 0,
+                data: text_asset_data(
+                    big_endian,
+                    &format!("text{path_id}"),
+                    &format!("script of {path_id}"),
+                ),
+            })
+            .collect(),
+    }
+    .build()
+}
+
+pub fn string_nodes(level: u8, name: &'static str) -> [TypeTreeNode; 4] {
+    [
+        TypeTreeNode::new(level, "string", name, -1),
+        TypeTreeNode::new(level + 1, "Array", "Array", -1).aligned(),
+        TypeTreeNode::new(level + 2, "int", "size", 4),
+        TypeTreeNode::new(level + 2, "char", "data", 1),
+    ]
+}
+
+pub fn asset_bundle_type() -> FixtureType {
+    let mut nodes = vec![TypeTreeNode::new(0, "AssetBundle", "Base", -1)];
+    nodes.extend(string_nodes(1, "m_Name"));
+    nodes.extend([
+        TypeTreeNode::new(1, "map", "m_Container", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1).aligned(),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "pair", "data", -1),
+    ]);
+    nodes.extend(string_nodes(4, "first"));
+    nodes.extend([
+        TypeTreeNode::new(4, "AssetInfo", "second", -1),
+        TypeTreeNode::new(5, "int", "preloadIndex", 4),
+        TypeTreeNode::new(5, "int", "preloadSize", 4),
+        TypeTreeNode::new(5, "PPtr<Object>", "asset", 12),
+        TypeTreeNode::new(6, "int", "m_FileID", 4),
+        TypeTreeNode::new(6, "SInt64", "m_PathID", 8),
+    ]);
+    FixtureType {
+        class_id: 142,
+        nodes,
+    }
+}
+
+/// An AssetBundle mapping container names to path ids in the same file.
+pub fn asset_bundle_data(big_endian: bool, containers: &[(&str, i64)]) -> Vec<u8> {
+    let mut data = Writer::new(big_endian);
+    data.aligned_string("bundle").i32(containers.len() as i32);
+    for (name, path_id) in containers {
+        data.aligned_string(name).i32(0).i32(0).i32(0).i64(*path_id);
+    }
+    data.data
+}
+
+pub fn material_type() -> FixtureType {
+    let mut nodes = vec![TypeTreeNode::new(0, "Material", "Base", -1)];
+    nodes.extend(string_nodes(1, "m_Name"));
+    nodes.extend([
+        TypeTreeNode::new(1, "PPtr<Shader>", "m_Shader", 12),
+        TypeTreeNode::new(2, "int", "m_FileID", 4),
+        TypeTreeNode::new(2, "SInt64", "m_PathID", 8),
+        TypeTreeNode::new(1, "UnityPropertySheet", "m_SavedProperties", -1),
+        TypeTreeNode::new(2, "map", "m_TexEnvs", -1),
+        TypeTreeNode::new(3, "Array", "Array", -1).aligned(),
+        TypeTreeNode::new(4, "int", "size", 4),
+        TypeTreeNode::new(4, "pair", "data", -1),
+    ]);
+    nodes.extend(string_nodes(5, "first"));
+    nodes.extend([
+        TypeTreeNode::new(5, "UnityTexEnv", "second", -1),
+        TypeTreeNode::new(6, "PPtr<Texture>", "m_Texture", 12),
+        TypeTreeNode::new(7, "int", "m_FileID", 4),
+        TypeTreeNode::new(7, "SInt64", "m_PathID", 8),
+    ]);
+    FixtureType {
+        class_id: 21,
+        nodes,
+    }
+}
+
+/// A Material with a shader at path id 0 and the given texture properties,
+/// each a path id in the same file.
+pub fn material_data(big_endian: bool, tex_envs: &[(&str, i64)]) -> Vec<u8> {
+    let mut data = Writer::new(big_endian);
+    data.aligned_string("material")
+        .i32(0)
+        .i64(0)
+        .i32(tex_envs.len() as i32);
+    for (name, path_id) in tex_envs {
+        data.aligned_string(name).i32(0).i64(*path_id);
+    }
+    data.data
+}
+
+/// An object with a single PPtr field, `m_Ref`.
+pub fn pointer_type() -> FixtureType {
+    FixtureType {
+        class_id: 1,
+        nodes: vec![
+            TypeTreeNode::new(0, "GameObject", "Base", -1),
+            TypeTreeNode::new(1, "PPtr<Object>", "m_Ref", 12),
+            TypeTreeNode::new(2, "int", "m_FileID", 4),
+            TypeTreeNode::new(2, "SInt64", "m_PathID", 8),
+        ],
+    }
+}
+
+pub fn pointer_data(file_id: i32, path_id: i64) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.i32(file_id).i64(path_id);
+    data.data
+}
+
+/// A bundle whose CAB has an AssetBundle at path id 1 followed by the
+/// TextAssets of [`text_asset_file`], each under the given container name.
+pub fn resource_manager_type() -> FixtureType {
+    let mut nodes = vec![
+        TypeTreeNode::new(0, "ResourceManager", "Base", -1),
+        TypeTreeNode::new(1, "map", "m_Container", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1).aligned(),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "pair", "data", -1),
+    ];
+    nodes.extend(string_nodes(4, "first"));
+    nodes.extend([
+        TypeTreeNode::new(4, "PPtr<Object>", "second", 12),
+        TypeTreeNode::new(5, "int", "m_FileID", 4),
+        TypeTreeNode::new(5, "SInt64", "m_PathID", 8),
+    ]);
+    FixtureType {
+        class_id: 147,
+        nodes,
+    }
+}
+
+/// A ResourceManager mapping resource names to path ids in the same file.
+pub fn resource_manager_data(containers: &[(&str, i64)]) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.i32(containers.len() as i32);
+    for (name, path_id) in containers {
+        data.aligned_string(name).i32(0).i64(*path_id);
+    }
+    data.data
+}
+
+/// An AssetBundle with an `m_PreloadTable` and `m_Dependencies`, which
+/// [`asset_bundle_type`] leaves out.
+pub fn preload_asset_bundle_type() -> FixtureType {
+    let mut asset_bundle = asset_bundle_type();
+    asset_bundle.nodes.splice(
+        5..5,
+        [
+            TypeTreeNode::new(1, "vector", "m_PreloadTable", -1),
+            TypeTreeNode::new(2, "Array", "Array", -1).aligned(),
+            TypeTreeNode::new(3, "int", "size", 4),
+            TypeTreeNode::new(3, "PPtr<Object>", "data", 12),
+            TypeTreeNode::new(4, "int", "m_FileID", 4),
+            TypeTreeNode::new(4, "SInt64", "m_PathID", 8),
+        ],
+    );
+    asset_bundle.nodes.extend([
+        TypeTreeNode::new(1, "vector", "m_Dependencies", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1).aligned(),
+        TypeTreeNode::new(3, "int", "size", 4),
+    ]);
+    asset_bundle.nodes.extend(string_nodes(3, "data"));
+    asset_bundle
+}
+
+/// Containers of `(name, preload_index, preload_size, path_id)` over a
+/// preload table of `(file_id, path_id)`, depending on the named bundles.
+pub fn preload_asset_bundle_data(
+    containers: &[(&str, i32, i32, i64)],
+    preload_table: &[(i32, i64)],
+    dependencies: &[&str],
+) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.aligned_string("bundle")
+        .i32(preload_table.len() as i32);
+    for (file_id, path_id) in preload_table {
+        data.i32(*file_id).i64(*path_id);
+    }
+    data.i32(containers.len() as i32);
+    for (name, preload_index, preload_size, path_id) in containers {
+        data.aligned_string(name)
+            .i32(*preload_index)
+            .i32(*preload_size)
+            .i32(0)
+            .i64(*path_id);
+    }
+    data.i32(dependencies.len() as i32);
+    for dependency in dependencies {
+        data.aligned_string(dependency);
+    }
+    data.data
+}
+
+pub fn text_asset_bundle_with_containers(containers: &[&str]) -> Vec<u8> {
+    let mut objects = vec![FixtureObject {
+        path_id: 1,
+        type_index: 0,
+        data: asset_bundle_data(
+            false,
+            &containers
+                .iter()
+                .enumerate()
+                .map(|(index, name)| (*name, index as i64 + 2))
+                .collect::<Vec<_>>(),
+        ),
+    }];
+    objects.extend(
+        (2..containers.len() as i64 + 2).map(|path_id| FixtureObject {
+            path_id,
+            type_index: 1,
+            data: text_asset_data(
+                false,
+                &format!("text{path_id}"),
+                &format!("script of {path_id}"),
+            ),
+        }),
+    );
+    let cab = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![asset_bundle_type(), text_asset_type()],
+        objects,
+    }
+    .build();
+    bundle(&[(CAB_NAME, &cab)], Compression::None, 256)
+}
+
+pub fn texture2d_type() -> FixtureType {
+    let mut nodes = vec![TypeTreeNode::new(0, "Texture2D", "Base", -1)];
+    nodes.extend(string_nodes(1, "m_Name"));
+    for name in ["m_Width", "m_Height", "m_TextureFormat", "m_MipCount"] {
+        nodes.push(TypeTreeNode::new(1, "int", name, 4));
+    }
+    nodes.extend([
+        // TypelessData is itself the array, without an Array child.
+        TypeTreeNode {
+            is_array: true,
+            ..TypeTreeNode::new(1, "TypelessData", "image data", -1).aligned()
+        },
+        TypeTreeNode::new(2, "int", "size", 4),
+        TypeTreeNode::new(2, "UInt8", "data", 1),
+        TypeTreeNode::new(1, "StreamingInfo", "m_StreamData", -1),
+        TypeTreeNode::new(2, "unsigned int", "offset", 4),
+        TypeTreeNode::new(2, "unsigned int", "size", 4),
+    ]);
+    nodes.extend(string_nodes(2, "path"));
+    FixtureType {
+        class_id: 28,
+        nodes,
+    }
+}
+
+pub struct TextureFixture<'a> {
+    pub width: i32,
+    pub height: i32,
+    pub texture_format: i32,
+    pub mip_count: i32,
+    pub image_data: &'a [u8],
+    pub stream_offset: u32,
+    pub stream_size: u32,
+    pub stream_path: &'a str,
+}
+
+pub fn texture2d_data(big_endian: bool, texture: &TextureFixture) -> Vec<u8> {
+    let mut data = Writer::new(big_endian);
+    data.aligned_string("texture")
+        .i32(texture.width)
+        .i32(texture.height)
+        .i32(texture.texture_format)
+        .i32(texture.mip_count)
+        .i32(texture.image_data.len() as i32)
+        .bytes(texture.image_data)
+        .align(4)
+        .u32(texture.stream_offset)
+        .u32(texture.stream_size)
+        .aligned_string(texture.stream_path);
+    data.data
+}
+
+/// A bundle whose AssetBundle lists one 1x2 Alpha8 Texture2D under
+/// `container`, its bottom row `pixels[0]` as Unity stores it.
+pub fn alpha8_texture_bundle(container: &str, pixels: [u8; 2]) -> Vec<u8> {
+    let cab = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![asset_bundle_type(), texture2d_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: asset_bundle_data(false, &[(container, 2)]),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 1,
+                data: texture2d_data(
+                    false,
+                    &TextureFixture {
+                        width: 1,
+                        height: 2,
+                        texture_format: 1, // Alpha8
+                        mip_count: 1,
+                        image_data: &pixels,
+                        stream_offset: 0,
+                        stream_size: 0,
+                        stream_path: "",
+                    },
+                ),
+            },
+        ],
+    }
+    .build();
+    bundle(&[(CAB_NAME, &cab)], Compression::None, 256)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Compression {
+    None,
+    Lz4,
+}
+
+impl Compression {
+    fn flag(self) -> u32 {
+        match self {
+            Compression::None => 0,
+            Compression::Lz4 => 2,
+        }
+    }
+
+    fn compress(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Compression::None => data.to_vec(),
+            Compression::Lz4 => lz4::block::compress(data, None, false).unwrap(),
+        }
+    }
+}
+
+/// A version 6 UnityFS bundle holding `files` back to back, split into
+/// `block_size` blocks. The blocks info and blocks are compressed alike.
+pub fn bundle(files: &[(&str, &[u8])], compression: Compression, block_size: usize) -> Vec<u8> {
+    let data: Vec<u8> = files.iter().flat_map(|(_, data)| data.to_vec()).collect();
+    let blocks: Vec<(usize, Vec<u8>)> = data
+        .chunks(block_size)
+        .map(|block| (block.len(), compression.compress(block)))
+        .collect();
+
+    let mut blocks_info = Writer::new(true);
+    blocks_info.bytes(&[0; 16]).u32(blocks.len() as u32);
+    for (uncompressed_size, block) in &blocks {
+        blocks_info
+            .u32(*uncompressed_size as u32)
+            .u32(block.len() as u32)
+            .u16(compression.flag() as u16);
+    }
+    blocks_info.u32(files.len() as u32);
+    let mut offset = 0;
+    for (path, data) in files {
+        blocks_info
+            .i64(offset as i64)
+            .i64(data.len() as i64)
+            .u32(4)
+            .null_string(path);
+        offset += data.len();
+    }
+    let compressed_blocks_info = compression.compress(&blocks_info.data);
+
+    let mut file = Writer::new(true);
+    file.bytes(b"UnityFS\0")
+        .u32(6)
+        .null_string("5.x.x")
+        .null_string(UNITY_VERSION);
+    let header_size = file.data.len() + 8 + 4 + 4 + 4;
+    let blocks_size: usize = blocks.iter().map(|(_, block)| block.len()).sum();
+    let size = header_size + compressed_blocks_info.len() + blocks_size;
+    file.i64(size as i64)
+        .u32(compressed_blocks_info.len() as u32)
+        .u32(blocks_info.data.len() as u32)
+        .u32(compression.flag())
+        .bytes(&compressed_blocks_info);
+    for (_, block) in blocks {
+        file.bytes(&block);
+    }
+    file.data
+}
+
+/// A bundle with one CAB of [`text_asset_file`].
+pub fn text_asset_bundle(
+    compression: Compression,
+    big_endian: bool,
+    enable_type_tree: bool,
+    count: i64,
+) -> Vec<u8> {
+    let cab = text_asset_file(big_endian, enable_type_tree, count);
+    bundle(&[(CAB_NAME, &cab)], compression, 64)
+}
+
+pub fn load_bundle(bundle: Vec<u8>) -> UnityAssetViewer {
+    let mut viewer = UnityAssetViewer::new();
+    viewer
+        .add_bundle_file(Box::new(Cursor::new(bundle)), None)
+        .unwrap();
+    viewer
+}
+
+pub fn load_serialized_file(file: Vec<u8>) -> (UnityAssetViewer, i64) {
+    let mut viewer = UnityAssetViewer::new();
+    let serialized_file_id = viewer
+        .add_serialized_file(Box::new(Cursor::new(file)), None)
+        .unwrap();
+    (viewer, serialized_file_id)
+}
+
+/// A format 17 little-endian file with TypeTrees holding one object of
+/// `tp` at path id 1.
+pub fn single_object_file(tp: FixtureType, data: Vec<u8>) -> Vec<u8> {
+    SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![tp],
+        objects: vec![FixtureObject {
+            path_id: 1,
+            type_index: 0,
+            data,
+        }],
+    }
+    .build()
+}
+
+/// A bundle with one CAB of [`single_object_file`].
+pub fn single_object_bundle(tp: FixtureType, data: Vec<u8>) -> Vec<u8> {
+    bundle(
+        &[(CAB_NAME, &single_object_file(tp, data))],
+        Compression::None,
+        64,
+    )
+}
+
+/// Load a [`single_object_file`] and read its object back.
+pub fn read_single_object(tp: FixtureType, data: Vec<u8>) -> TypeTreeObjectRef {
+    let (viewer, serialized_file_id) = load_serialized_file(single_object_file(tp, data));
+    viewer.serialized_file_map[&serialized_file_id]
+        .get_tt_object_by_path_id(1)
+        .unwrap()
+        .unwrap()
+        .into()
+}
+
+/// An AssetBundle with only the fields [`UnityAssetViewer::is_scene_file`]
+/// reads.
+pub fn scene_asset_bundle_type() -> FixtureType {
+    let mut nodes = vec![TypeTreeNode::new(0, "AssetBundle", "Base", -1)];
+    nodes.extend(string_nodes(1, "m_Name"));
+    nodes.push(TypeTreeNode::new(1, "bool", "m_IsStreamedSceneAssetBundle", 1).aligned());
+    FixtureType {
+        class_id: 142,
+        nodes,
+    }
+}
+
+pub fn scene_asset_bundle_data(is_streamed_scene: bool) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.aligned_string("scene")
+        .bytes(&[is_streamed_scene as u8])
+        .align(4);
+    data.data
+}
+
+/// A Transform with only its GameObject and parent.
+pub fn transform_type() -> FixtureType {
+    FixtureType {
+        class_id: 4,
+        nodes: vec![
+            TypeTreeNode::new(0, "Transform", "Base", -1),
+            TypeTreeNode::new(1, "PPtr<GameObject>", "m_GameObject", 12),
+            TypeTreeNode::new(2, "int", "m_FileID", 4),
+            TypeTreeNode::new(2, "SInt64", "m_PathID", 8),
+            TypeTreeNode::new(1, "PPtr<Transform>", "m_Father", 12),
+            TypeTreeNode::new(2, "int", "m_FileID", 4),
+            TypeTreeNode::new(2, "SInt64", "m_PathID", 8),
+        ],
+    }
+}
+
+pub fn transform_data(game_object: i64, father: i64) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.i32(0).i64(game_object).i32(0).i64(father);
+    data.data
+}
+
+/// A GameObject with its components and name.
+pub fn game_object_type() -> FixtureType {
+    let mut nodes = vec![
+        TypeTreeNode::new(0, "GameObject", "Base", -1),
+        TypeTreeNode::new(1, "vector", "m_Component", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "ComponentPair", "data", 12),
+        TypeTreeNode::new(4, "PPtr<Component>", "component", 12),
+        TypeTreeNode::new(5, "int", "m_FileID", 4),
+        TypeTreeNode::new(5, "SInt64", "m_PathID", 8),
+    ];
+    nodes.extend(string_nodes(1, "m_Name"));
+    FixtureType { class_id: 1, nodes }
+}
+
+pub fn game_object_data(name: &str, components: &[i64]) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.i32(components.len() as i32);
+    for component in components {
+        data.i32(0).i64(*component);
+    }
+    data.aligned_string(name);
+    data.data
+}
+
+/// A Transform with its local pose and children.
+pub fn posed_transform_type() -> FixtureType {
+    let mut nodes = vec![
+        TypeTreeNode::new(0, "Transform", "Base", -1),
+        TypeTreeNode::new(1, "PPtr<GameObject>", "m_GameObject", 12),
+        TypeTreeNode::new(2, "int", "m_FileID", 4),
+        TypeTreeNode::new(2, "SInt64", "m_PathID", 8),
+        TypeTreeNode::new(1, "Quaternionf", "m_LocalRotation", 16),
+    ];
+    nodes.extend(["x", "y", "z", "w"].map(|name| TypeTreeNode::new(2, "float", name, 4)));
+    for vector in ["m_LocalPosition", "m_LocalScale"] {
+        nodes.push(TypeTreeNode::new(1, "Vector3f", vector, 12));
+        nodes.extend(["x", "y", "z"].map(|name| TypeTreeNode::new(2, "float", name, 4)));
+    }
+    nodes.extend([
+        TypeTreeNode::new(1, "vector", "m_Children", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "PPtr<Transform>", "data", 12),
+        TypeTreeNode::new(4, "int", "m_FileID", 4),
+        TypeTreeNode::new(4, "SInt64", "m_PathID", 8),
+        TypeTreeNode::new(1, "PPtr<Transform>", "m_Father", 12),
+        TypeTreeNode::new(2, "int", "m_FileID", 4),
+        TypeTreeNode::new(2, "SInt64", "m_PathID", 8),
+    ]);
+    FixtureType { class_id: 4, nodes }
+}
+
+/// An unrotated, unscaled Transform at `position`.
+pub fn posed_transform_data(
+    game_object: i64,
+    position: [f32; 3],
+    children: &[i64],
+    father: i64,
+) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.i32(0).i64(game_object);
+    for value in [0.0f32, 0.0, 0.0, 1.0]
+        .iter()
+        .chain(&position)
+        .chain(&[1.0; 3])
+    {
+        data.u32(value.to_bits());
+    }
+    data.i32(children.len() as i32);
+    for child in children {
+        data.i32(0).i64(*child);
+    }
+    data.i32(0).i64(father);
+    data.data
+}
+
+pub fn skinned_mesh_renderer_type() -> FixtureType {
+    FixtureType {
+        class_id: 137,
+        nodes: vec![
+            TypeTreeNode::new(0, "SkinnedMeshRenderer", "Base", -1),
+            TypeTreeNode::new(1, "PPtr<GameObject>", "m_GameObject", 12),
+            TypeTreeNode::new(2, "int", "m_FileID", 4),
+            TypeTreeNode::new(2, "SInt64", "m_PathID", 8),
+            TypeTreeNode::new(1, "PPtr<Mesh>", "m_Mesh", 12),
+            TypeTreeNode::new(2, "int", "m_FileID", 4),
+            TypeTreeNode::new(2, "SInt64", "m_PathID", 8),
+            TypeTreeNode::new(1, "vector", "m_Bones", -1),
+            TypeTreeNode::new(2, "Array", "Array", -1),
+            TypeTreeNode::new(3, "int", "size", 4),
+            TypeTreeNode::new(3, "PPtr<Transform>", "data", 12),
+            TypeTreeNode::new(4, "int", "m_FileID", 4),
+            TypeTreeNode::new(4, "SInt64", "m_PathID", 8),
+        ],
+    }
+}
+
+pub fn skinned_mesh_renderer_data(game_object: i64, mesh: i64, bones: &[i64]) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.i32(0).i64(game_object).i32(0).i64(mesh);
+    data.i32(bones.len() as i32);
+    for bone in bones {
+        data.i32(0).i64(*bone);
+    }
+    data.data
+}
+
+/// A Sprite with only its name, rect and texture.
+pub fn sprite_type() -> FixtureType {
+    let mut nodes = vec![TypeTreeNode::new(0, "Sprite", "Base", -1)];
+    nodes.extend(string_nodes(1, "m_Name"));
+    nodes.push(TypeTreeNode::new(1, "Rectf", "m_Rect", 16));
+    for name in ["x", "y", "width", "height"] {
+        nodes.push(TypeTreeNode::new(2, "float", name, 4));
+    }
+    nodes.extend([
+        TypeTreeNode::new(1, "SpriteRenderData", "m_RD", -1),
+        TypeTreeNode::new(2, "PPtr<Texture2D>", "texture", 12),
+        TypeTreeNode::new(3, "int", "m_FileID", 4),
+        TypeTreeNode::new(3, "SInt64", "m_PathID", 8),
+        TypeTreeNode::new(2, "PPtr<Texture2D>", "alphaTexture", 12),
+        TypeTreeNode::new(3, "int", "m_FileID", 4),
+        TypeTreeNode::new(3, "SInt64", "m_PathID", 8),
+    ]);
+    FixtureType {
+        class_id: 213,
+        nodes,
+    }
+}
+
+/// `alpha_texture_path_id` 0 leaves the alpha texture PPtr null.
+pub fn sprite_data(rect: [f32; 4], texture_path_id: i64, alpha_texture_path_id: i64) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.aligned_string("sprite");
+    for value in rect {
+        data.u32(value.to_bits());
+    }
+    data.i32(0)
+        .i64(texture_path_id)
+        .i32(0)
+        .i64(alpha_texture_path_id);
+    data.data
+}
+
+fn render_data_key_nodes(level: u8, name: &str) -> Vec<TypeTreeNode> {
+    let mut nodes = vec![
+        TypeTreeNode::new(level, "pair", name, 24),
+        TypeTreeNode::new(level + 1, "GUID", "first", 16),
+    ];
+    for index in 0..4 {
+        nodes.push(TypeTreeNode::new(
+            level + 2,
+            "unsigned int",
+            &format!("data[{index}]"),
+            4,
+        ));
+    }
+    nodes.push(TypeTreeNode::new(level + 1, "SInt64", "second", 8));
+    nodes
+}
+
+fn pptr_nodes(level: u8, type_name: &str, name: &str) -> [TypeTreeNode; 3] {
+    [
+        TypeTreeNode::new(level, type_name, name, 12),
+        TypeTreeNode::new(level + 1, "int", "m_FileID", 4),
+        TypeTreeNode::new(level + 1, "SInt64", "m_PathID", 8),
+    ]
+}
+
+/// A Sprite packed into an atlas, [`sprite_type`] with its `m_RenderDataKey`.
+pub fn packed_sprite_type() -> FixtureType {
+    let mut sprite = sprite_type();
+    sprite
+        .nodes
+        .extend(render_data_key_nodes(1, "m_RenderDataKey"));
+    sprite
+}
+
+/// The sprite keyed by a GUID of `key` and a file id of 21300000.
+pub fn packed_sprite_data(name: &str, key: u32) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.aligned_string(name);
+    for value in [0.0f32; 4] {
+        data.u32(value.to_bits());
+    }
+    data.i32(0).i64(0).i32(0).i64(0);
+    data.u32(key).u32(0).u32(0).u32(0).i64(21300000);
+    data.data
+}
+
+/// [`packed_sprite_type`] with the `m_SpriteAtlas` it is packed into.
+pub fn atlas_sprite_type() -> FixtureType {
+    let mut sprite = packed_sprite_type();
+    sprite
+        .nodes
+        .extend(pptr_nodes(1, "PPtr<SpriteAtlas>", "m_SpriteAtlas"));
+    sprite
+}
+
+pub fn atlas_sprite_data(name: &str, key: u32, atlas_path_id: i64) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.bytes(&packed_sprite_data(name, key))
+        .i32(0)
+        .i64(atlas_path_id);
+    data.data
+}
+
+/// A SpriteAtlas with its packed sprites and render data map.
+pub fn sprite_atlas_type() -> FixtureType {
+    let mut nodes = vec![TypeTreeNode::new(0, "SpriteAtlas", "Base", -1)];
+    nodes.extend(string_nodes(1, "m_Name"));
+    nodes.extend([
+        TypeTreeNode::new(1, "vector", "m_PackedSprites", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1),
+        TypeTreeNode::new(3, "int", "size", 4),
+    ]);
+    nodes.extend(pptr_nodes(3, "PPtr<Sprite>", "data"));
+    nodes.extend([
+        TypeTreeNode::new(1, "vector", "m_PackedSpriteNamesToIndex", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1),
+        TypeTreeNode::new(3, "int", "size", 4),
+    ]);
+    nodes.extend(string_nodes(3, "data"));
+    nodes.extend([
+        TypeTreeNode::new(1, "map", "m_RenderDataMap", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "pair", "data", -1),
+    ]);
+    nodes.extend(render_data_key_nodes(4, "first"));
+    nodes.push(TypeTreeNode::new(4, "SpriteAtlasData", "second", -1));
+    nodes.extend(pptr_nodes(5, "PPtr<Texture2D>", "texture"));
+    nodes.extend(pptr_nodes(5, "PPtr<Texture2D>", "alphaTexture"));
+    nodes.push(TypeTreeNode::new(5, "Rectf", "textureRect", 16));
+    for name in ["x", "y", "width", "height"] {
+        nodes.push(TypeTreeNode::new(6, "float", name, 4));
+    }
+    nodes.push(TypeTreeNode::new(5, "unsigned int", "settingsRaw", 4));
+    FixtureType {
+        class_id: 687078895,
+        nodes,
+    }
+}
+
+/// A sprite packed into an atlas page, by its render data key.
+pub struct AtlasEntry {
+    pub key: u32,
+    pub texture_path_id: i64,
+    pub rect: [f32; 4],
+    pub settings_raw: u32,
+}
+
+/// `packed` lists the path id and name of each packed sprite.
+pub fn sprite_atlas_data(packed: &[(i64, &str)], entries: &[AtlasEntry]) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.aligned_string("atlas").i32(packed.len() as i32);
+    for (path_id, _) in packed {
+        data.i32(0).i64(*path_id);
+    }
+    data.i32(packed.len() as i32);
+    for (_, name) in packed {
+        data.aligned_string(name);
+    }
+    data.i32(entries.len() as i32);
+    for entry in entries {
+        data.u32(entry.key)
+            .u32(0)
+            .u32(0)
+            .u32(0)
+            .i64(21300000)
+            .i32(0)
+            .i64(entry.texture_path_id)
+            .i32(0)
+            .i64(0);
+        for value in entry.rect {
+            data.u32(value.to_bits());
+        }
+        data.u32(entry.settings_raw);
+    }
+    data.data
+}
+
+/// A MeshRenderer with only the `Renderer` fields its wrapper reads.
+pub fn mesh_renderer_type() -> FixtureType {
+    FixtureType {
+        class_id: 23,
+        nodes: vec![
+            TypeTreeNode::new(0, "MeshRenderer", "Base", -1),
+            TypeTreeNode::new(1, "PPtr<GameObject>", "m_GameObject", 12),
+            TypeTreeNode::new(2, "int", "m_FileID", 4),
+            TypeTreeNode::new(2, "SInt64", "m_PathID", 8),
+            TypeTreeNode::new(1, "bool", "m_Enabled", 1),
+            TypeTreeNode::new(1, "UInt8", "m_CastShadows", 1),
+            TypeTreeNode::new(1, "UInt8", "m_ReceiveShadows", 1).aligned(),
+            TypeTreeNode::new(1, "vector", "m_Materials", -1),
+            TypeTreeNode::new(2, "Array", "Array", -1),
+            TypeTreeNode::new(3, "int", "size", 4),
+            TypeTreeNode::new(3, "PPtr<Material>", "data", 12),
+            TypeTreeNode::new(4, "int", "m_FileID", 4),
+            TypeTreeNode::new(4, "SInt64", "m_PathID", 8),
+        ],
+    }
+}
+
+pub fn mesh_renderer_data(
+    enabled: bool,
+    cast_shadows: u8,
+    receive_shadows: bool,
+    materials: &[i64],
+) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.i32(0)
+        .i64(0)
+        .bytes(&[enabled as u8, cast_shadows, receive_shadows as u8])
+        .align(4)
+        .i32(materials.len() as i32);
+    for path_id in materials {
+        data.i32(0).i64(*path_id);
+    }
+    data.data
+}
+
+/// A Mesh with only its sub meshes, index buffer and vertex data.
+pub struct MeshFixture<'a> {
+    /// `firstByte`, `indexCount`, `topology`, `baseVertex`, `firstVertex`
+    /// and `vertexCount` of each sub mesh.
+    pub sub_meshes: &'a [[u32; 6]],
+    pub index_format: i32,
+    pub index_buffer: &'a [u8],
+    pub vertex_count: u32,
+    /// `stream`, `offset`, `format` and `dimension` of each channel.
+    pub channels: &'a [[u8; 4]],
+    /// `offset` and `stride` of each stream, for [`mesh_type_with_streams`].
+    pub streams: Option<&'a [[u32; 2]]>,
+    pub vertex_data: &'a [u8],
+    /// Each bind pose's `e00` to `e33`, row by row.
+    pub bind_poses: &'a [[f32; 16]],
+}
+
+pub fn mesh_type() -> FixtureType {
+    mesh_type_for(false)
+}
+
+/// A Mesh as before 5.0, with `m_Streams` after the channels.
+pub fn mesh_type_with_streams() -> FixtureType {
+    mesh_type_for(true)
+}
+
+fn mesh_type_for(with_streams: bool) -> FixtureType {
+    let mut nodes = vec![TypeTreeNode::new(0, "Mesh", "Base", -1)];
+    nodes.extend(string_nodes(1, "m_Name"));
+    nodes.extend([
+        TypeTreeNode::new(1, "vector", "m_SubMeshes", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "SubMesh", "data", 24),
+    ]);
+    for (name, type_name) in [
+        ("firstByte", "unsigned int"),
+        ("indexCount", "unsigned int"),
+        ("topology", "int"),
+        ("baseVertex", "unsigned int"),
+        ("firstVertex", "unsigned int"),
+        ("vertexCount", "unsigned int"),
+    ] {
+        nodes.push(TypeTreeNode::new(4, type_name, name, 4));
+    }
+    nodes.extend([
+        TypeTreeNode::new(1, "int", "m_IndexFormat", 4),
+        TypeTreeNode::new(1, "vector", "m_IndexBuffer", -1).aligned(),
+        TypeTreeNode::new(2, "Array", "Array", -1),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "UInt8", "data", 1),
+        TypeTreeNode::new(1, "VertexData", "m_VertexData", -1),
+        TypeTreeNode::new(2, "unsigned int", "m_VertexCount", 4),
+        TypeTreeNode::new(2, "vector", "m_Channels", -1),
+        TypeTreeNode::new(3, "Array", "Array", -1),
+        TypeTreeNode::new(4, "int", "size", 4),
+        TypeTreeNode::new(4, "ChannelInfo", "data", 4),
+        TypeTreeNode::new(5, "UInt8", "stream", 1),
+        TypeTreeNode::new(5, "UInt8", "offset", 1),
+        TypeTreeNode::new(5, "UInt8", "format", 1),
+        TypeTreeNode::new(5, "UInt8", "dimension", 1),
+    ]);
+    if with_streams {
+        nodes.extend([
+            TypeTreeNode::new(2, "vector", "m_Streams", -1),
+            TypeTreeNode::new(3, "Array", "Array", -1),
+            TypeTreeNode::new(4, "int", "size", 4),
+            TypeTreeNode::new(4, "StreamInfo", "data", 12),
+            TypeTreeNode::new(5, "unsigned int", "channelMask", 4),
+            TypeTreeNode::new(5, "unsigned int", "offset", 4),
+            TypeTreeNode::new(5, "UInt8", "stride", 1),
+            TypeTreeNode::new(5, "UInt8", "dividerOp", 1),
+            TypeTreeNode::new(5, "UInt16", "frequency", 2),
+        ]);
+    }
+    nodes.extend([
+        TypeTreeNode {
+            is_array: true,
+            ..TypeTreeNode::new(2, "TypelessData", "m_DataSize", -1).aligned()
+        },
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "UInt8", "data", 1),
+        TypeTreeNode::new(1, "vector", "m_BindPose", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "Matrix4x4f", "data", 64),
+    ]);
+    for row in 0..4 {
+        for col in 0..4 {
+            nodes.push(TypeTreeNode::new(4, "float", &format!("e{row}{col}"), 4));
+        }
+    }
+    FixtureType {
+        class_id: 43,
+        nodes,
+    }
+}
+
+pub fn mesh_data(mesh: &MeshFixture) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.aligned_string("mesh")
+        .i32(mesh.sub_meshes.len() as i32);
+    for sub_mesh in mesh.sub_meshes {
+        for value in sub_mesh {
+            data.u32(*value);
+        }
+    }
+    data.i32(mesh.index_format)
+        .i32(mesh.index_buffer.len() as i32)
+        .bytes(mesh.index_buffer)
+        .align(4)
+        .u32(mesh.vertex_count)
+        .i32(mesh.channels.len() as i32);
+    for channel in mesh.channels {
+        data.bytes(channel);
+    }
+    if let Some(streams) = mesh.streams {
+        data.i32(streams.len() as i32);
+        for [offset, stride] in streams {
+            data.u32(0).u32(*offset).bytes(&[*stride as u8, 0]).u16(0);
+        }
+    }
+    data.i32(mesh.vertex_data.len() as i32)
+        .bytes(mesh.vertex_data)
+        .align(4)
+        .i32(mesh.bind_poses.len() as i32);
+    for value in mesh.bind_poses.iter().flatten() {
+        data.u32(value.to_bits());
+    }
+    data.data
+}
+
+/// A Mesh with only its name and `m_Shapes`.
+pub fn blend_shape_mesh_type() -> FixtureType {
+    let mut nodes = vec![TypeTreeNode::new(0, "Mesh", "Base", -1)];
+    nodes.extend(string_nodes(1, "m_Name"));
+    nodes.extend([
+        TypeTreeNode::new(1, "BlendShapeData", "m_Shapes", -1),
+        TypeTreeNode::new(2, "vector", "vertices", -1),
+        TypeTreeNode::new(3, "Array", "Array", -1),
+        TypeTreeNode::new(4, "int", "size", 4),
+        TypeTreeNode::new(4, "BlendShapeVertex", "data", 40),
+    ]);
+    for vector in ["vertex", "normal", "tangent"] {
+        nodes.push(TypeTreeNode::new(5, "Vector3f", vector, 12));
+        nodes.extend(["x", "y", "z"].map(|name| TypeTreeNode::new(6, "float", name, 4)));
+    }
+    nodes.extend([
+        TypeTreeNode::new(5, "unsigned int", "index", 4),
+        TypeTreeNode::new(2, "vector", "shapes", -1),
+        TypeTreeNode::new(3, "Array", "Array", -1),
+        TypeTreeNode::new(4, "int", "size", 4),
+        TypeTreeNode::new(4, "MeshBlendShape", "data", 12),
+        TypeTreeNode::new(5, "unsigned int", "firstVertex", 4),
+        TypeTreeNode::new(5, "unsigned int", "vertexCount", 4),
+        TypeTreeNode::new(5, "bool", "hasNormals", 1),
+        TypeTreeNode::new(5, "bool", "hasTangents", 1).aligned(),
+        TypeTreeNode::new(2, "vector", "channels", -1),
+        TypeTreeNode::new(3, "Array", "Array", -1),
+        TypeTreeNode::new(4, "int", "size", 4),
+        TypeTreeNode::new(4, "MeshBlendShapeChannel", "data", -1),
+    ]);
+    nodes.extend(string_nodes(5, "name"));
+    nodes.extend([
+        TypeTreeNode::new(5, "unsigned int", "nameHash", 4),
+        TypeTreeNode::new(5, "int", "frameIndex", 4),
+        TypeTreeNode::new(5, "int", "frameCount", 4),
+        TypeTreeNode::new(2, "vector", "fullWeights", -1),
+        TypeTreeNode::new(3, "Array", "Array", -1),
+        TypeTreeNode::new(4, "int", "size", 4),
+        TypeTreeNode::new(4, "float", "data", 4),
+    ]);
+    FixtureType {
+        class_id: 43,
+        nodes,
+    }
+}
+
+/// A "smile" channel with two frames, at weights 50 and 100, moving vertex 1
+/// and then vertices 1 and 2 up by an increasing amount. The second frame
+/// has normals.
+pub fn blend_shape_mesh_data() -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.aligned_string("face").i32(3);
+    for (index, up, normal) in [(1, 0.5, 0.0), (1, 1.0, 1.0), (2, 2.0, 1.0)] {
+        for value in [0.0, up, 0.0, 0.0, 0.0, normal, 0.0, 0.0, 0.0] {
+            data.u32(f32::to_bits(value));
+        }
+        data.u32(index);
+    }
+    data.i32(2)
+        .u32(0)
+        .u32(1)
+        .bytes(&[0, 0, 0, 0])
+        .u32(1)
+        .u32(2)
+        .bytes(&[1, 0, 0, 0])
+        .i32(1)
+        .aligned_string("smile")
+        .u32(0x1234)
+        .i32(0)
+        .i32(2)
+        .i32(2)
+        .u32(50f32.to_bits())
+        .u32(100f32.to_bits());
+    data.data
+}
+
+/// A BuildSettings with only its scene list and version.
+pub fn build_settings_type() -> FixtureType {
+    let mut nodes = vec![
+        TypeTreeNode::new(0, "BuildSettings", "Base", -1),
+        TypeTreeNode::new(1, "vector", "m_Scenes", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1),
+        TypeTreeNode::new(3, "int", "size", 4),
+    ];
+    nodes.extend(string_nodes(3, "data"));
+    nodes.extend(string_nodes(1, "m_Version"));
+    FixtureType {
+        class_id: 141,
+        nodes,
+    }
+}
+
+pub fn build_settings_data(scenes: &[&str], version: &str) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.i32(scenes.len() as i32);
+    for scene in scenes {
+        data.aligned_string(scene);
+    }
+    data.aligned_string(version);
+    data.data
+}
+
+/// A PlayerSettings with only its names and version.
+pub fn player_settings_type() -> FixtureType {
+    let mut nodes = vec![TypeTreeNode::new(0, "PlayerSettings", "Base", -1)];
+    nodes.extend(string_nodes(1, "companyName"));
+    nodes.extend(string_nodes(1, "productName"));
+    nodes.extend(string_nodes(1, "bundleVersion"));
+    FixtureType {
+        class_id: 129,
+        nodes,
+    }
+}
+
+pub fn player_settings_data(company_name: &str, product_name: &str, version: &str) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.aligned_string(company_name)
+        .aligned_string(product_name)
+        .aligned_string(version);
+    data.data
+}
+
+/// A subprogram as blob index, global and local keyword indices.
+pub type ShaderSubProgramFixture = (u32, Vec<u16>, Vec<u16>);
+
+fn u16_vector_nodes(level: u8, name: &'static str) -> [TypeTreeNode; 4] {
+    [
+        TypeTreeNode::new(level, "vector", name, -1),
+        TypeTreeNode::new(level + 1, "Array", "Array", -1),
+        TypeTreeNode::new(level + 2, "int", "size", 4),
+        TypeTreeNode::new(level + 2, "UInt16", "data", 2),
+    ]
+}
+
+/// A Shader whose parsed form has one sub shader of one pass with vertex
+/// and fragment subprograms. `modern` gives the 2021.2 layout, with a
+/// shader-wide keyword table and one keyword index list per subprogram.
+pub fn shader_type(modern: bool) -> FixtureType {
+    let mut nodes = vec![
+        TypeTreeNode::new(0, "Shader", "Base", -1),
+        TypeTreeNode::new(1, "SerializedShader", "m_ParsedForm", -1),
+    ];
+    nodes.extend(string_nodes(2, "m_Name"));
+    nodes.extend([
+        TypeTreeNode::new(2, "vector", "m_SubShaders", -1),
+        TypeTreeNode::new(3, "Array", "Array", -1),
+        TypeTreeNode::new(4, "int", "size", 4),
+        TypeTreeNode::new(4, "SerializedSubShader", "data", -1),
+        TypeTreeNode::new(5, "vector", "m_Passes", -1),
+        TypeTreeNode::new(6, "Array", "Array", -1),
+        TypeTreeNode::new(7, "int", "size", 4),
+        TypeTreeNode::new(7, "SerializedPass", "data", -1),
+        TypeTreeNode::new(8, "map", "m_NameIndices", -1),
+        TypeTreeNode::new(9, "Array", "Array", -1),
+        TypeTreeNode::new(10, "int", "size", 4),
+        TypeTreeNode::new(10, "pair", "data", -1),
+    ]);
+    nodes.extend(string_nodes(11, "first"));
+    nodes.push(TypeTreeNode::new(11, "int", "second", 4));
+    for stage in ["progVertex", "progFragment"] {
+        nodes.extend([
+            TypeTreeNode::new(8, "SerializedProgram", stage, -1),
+            TypeTreeNode::new(9, "vector", "m_SubPrograms", -1),
+            TypeTreeNode::new(10, "Array", "Array", -1),
+            TypeTreeNode::new(11, "int", "size", 4),
+            TypeTreeNode::new(11, "SerializedSubProgram", "data", -1),
+            TypeTreeNode::new(12, "unsigned int", "m_BlobIndex", 4),
+        ]);
+        if modern {
+            nodes.extend(u16_vector_nodes(12, "m_KeywordIndices"));
+        } else {
+            nodes.extend(u16_vector_nodes(12, "m_GlobalKeywordIndices"));
+            nodes.extend(u16_vector_nodes(12, "m_LocalKeywordIndices"));
+        }
+        nodes.push(TypeTreeNode::new(12, "SInt8", "m_GpuProgramType", 1));
+    }
+    if modern {
+        nodes.extend([
+            TypeTreeNode::new(2, "vector", "m_KeywordNames", -1),
+            TypeTreeNode::new(3, "Array", "Array", -1),
+            TypeTreeNode::new(4, "int", "size", 4),
+        ]);
+        nodes.extend(string_nodes(4, "data"));
+    }
+    FixtureType {
+        class_id: 48,
+        nodes,
+    }
+}
+
+/// Data for [`shader_type`]. `keywords` fill `m_KeywordNames` when
+/// `modern`, and `m_NameIndices` by position otherwise.
+pub fn shader_data(
+    modern: bool,
+    name: &str,
+    keywords: &[&str],
+    stages: [&[ShaderSubProgramFixture]; 2],
+) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.aligned_string(name).i32(1).i32(1);
+    let name_indices: &[&str] = if modern { &[] } else { keywords };
+    data.i32(name_indices.len() as i32);
+    for (index, keyword) in name_indices.iter().enumerate() {
+        data.aligned_string(keyword).i32(index as i32);
+    }
+    for sub_programs in stages {
+        data.i32(sub_programs.len() as i32);
+        for (blob_index, global, local) in sub_programs {
+            data.u32(*blob_index);
+            let lists = if modern {
+                vec![[global.as_slice(), local.as_slice()].concat()]
+            } else {
+                vec![global.clone(), local.clone()]
+            };
+            for list in lists {
+                data.i32(list.len() as i32);
+                for index in list {
+                    data.u16(index);
+                }
+            }
+            data.bytes(&[13]); // m_GpuProgramType, DX11 vertex SM 4.0
+        }
+    }
+    if modern {
+        data.i32(keywords.len() as i32);
+        for keyword in keywords {
+            data.aligned_string(keyword);
+        }
+    }
+    data.data
+}
+
+/// A MonoBehaviour with one `SerializeReference` field, `m_Ref`, and the
+/// registry of the objects it refers to.
+pub fn managed_reference_behaviour_type() -> FixtureType {
+    let mut nodes = vec![
+        TypeTreeNode::new(0, "MonoBehaviour", "Base", -1),
+        TypeTreeNode::new(1, "managedReference", "m_Ref", -1),
+        TypeTreeNode::new(2, "SInt64", "rid", 8),
+        TypeTreeNode::new(1, "ManagedReferencesRegistry", "references", -1),
+        TypeTreeNode::new(2, "int", "version", 4),
+        TypeTreeNode::new(2, "vector", "RefIds", -1),
+        TypeTreeNode::new(3, "Array", "Array", -1),
+        TypeTreeNode::new(4, "int", "size", 4),
+        TypeTreeNode::new(4, "ReferencedObject", "data", -1),
+        TypeTreeNode::new(5, "SInt64", "rid", 8),
+        TypeTreeNode::new(5, "ReferencedManagedType", "type", -1),
+    ];
+    nodes.extend(string_nodes(6, "class"));
+    nodes.extend(string_nodes(6, "ns"));
+    nodes.extend(string_nodes(6, "asm"));
+    nodes.push(TypeTreeNode::new(5, "ReferencedObjectData", "data", 0));
+    FixtureType {
+        class_id: 114,
+        nodes,
+    }
+}
+
+/// The `Wolf` ref type [`managed_reference_behaviour_data`] refers to.
+pub fn wolf_ref_type() -> FixtureRefType {
+    FixtureRefType {
+        class_name: "Wolf",
+        name_space: "Pack",
+        assembly_name: "Game",
+        nodes: vec![
+            TypeTreeNode::new(0, "Wolf", "Base", -1),
+            TypeTreeNode::new(1, "int", "m_Teeth", 4),
+        ],
+    }
+}
+
+/// `m_Ref` pointing at a [`wolf_ref_type`] with `teeth`, followed by the
+/// registry terminator.
+pub fn managed_reference_behaviour_data(big_endian: bool, teeth: i32) -> Vec<u8> {
+    let mut data = Writer::new(big_endian);
+    data.i64(1).i32(2).i32(2);
+    data.i64(1)
+        .aligned_string("Wolf")
+        .aligned_string("Pack")
+        .aligned_string("Game")
+        .i32(teeth);
+    data.i64(-2)
+        .aligned_string("")
+        .aligned_string("")
+        .aligned_string("");
+    data.data
+}
+
+fn min_max_curve_nodes(level: u8, name: &'static str) -> [TypeTreeNode; 4] {
+    [
+        TypeTreeNode::new(level, "MinMaxCurve", name, -1),
+        TypeTreeNode::new(level + 1, "SInt16", "minMaxState", 2).aligned(),
+        TypeTreeNode::new(level + 1, "float", "scalar", 4),
+        TypeTreeNode::new(level + 1, "float", "minScalar", 4),
+    ]
+}
+
+fn min_max_curve_data(data: &mut Writer, mode: i16, scalar: f32, min_scalar: f32) {
+    data.u16(mode as u16)
+        .align(4)
+        .u32(scalar.to_bits())
+        .u32(min_scalar.to_bits());
+}
+
+/// A ParticleSystem with the main module's start values and the emission
+/// rate, leaving out its other modules.
+pub fn particle_system_type() -> FixtureType {
+    let mut nodes = vec![
+        TypeTreeNode::new(0, "ParticleSystem", "Base", -1),
+        TypeTreeNode::new(1, "float", "lengthInSec", 4),
+        TypeTreeNode::new(1, "bool", "looping", 1).aligned(),
+        TypeTreeNode::new(1, "InitialModule", "InitialModule", -1),
+    ];
+    nodes.extend(min_max_curve_nodes(2, "startLifetime"));
+    nodes.extend(min_max_curve_nodes(2, "startSpeed"));
+    nodes.extend([
+        TypeTreeNode::new(2, "MinMaxGradient", "startColor", -1),
+        TypeTreeNode::new(3, "SInt16", "minMaxState", 2).aligned(),
+    ]);
+    for color in ["maxColor", "minColor"] {
+        nodes.push(TypeTreeNode::new(3, "ColorRGBA", color, 16));
+        nodes.extend(["r", "g", "b", "a"].map(|name| TypeTreeNode::new(4, "float", name, 4)));
+    }
+    nodes.extend(min_max_curve_nodes(2, "startSize"));
+    nodes.extend([
+        TypeTreeNode::new(2, "int", "maxNumParticles", 4),
+        TypeTreeNode::new(1, "EmissionModule", "EmissionModule", -1),
+        TypeTreeNode::new(2, "bool", "enabled", 1).aligned(),
+    ]);
+    nodes.extend(min_max_curve_nodes(2, "rateOverTime"));
+    FixtureType {
+        class_id: 198,
+        nodes,
+    }
+}
+
+/// A looping 5 second ParticleSystem of up to 1000 particles with random
+/// start sizes between 0.5 and 2, starting orange, emitting 10 a second.
+pub fn particle_system_data() -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.u32(5.0f32.to_bits()).bytes(&[1]).align(4);
+    min_max_curve_data(&mut data, 0, 3.0, 0.0);
+    min_max_curve_data(&mut data, 0, 4.5, 0.0);
+    data.u16(0).align(4);
+    for value in [1.0f32, 0.5, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0] {
+        data.u32(value.to_bits());
+    }
+    min_max_curve_data(&mut data, 3, 2.0, 0.5);
+    data.i32(1000).bytes(&[1]).align(4);
+    min_max_curve_data(&mut data, 0, 10.0, 0.0);
+    data.data
+}
+
+/// A LineRenderer with only its points and width.
+pub fn line_renderer_type() -> FixtureType {
+    FixtureType {
+        class_id: 120,
+        nodes: vec![
+            TypeTreeNode::new(0, "LineRenderer", "Base", -1),
+            TypeTreeNode::new(1, "vector", "m_Positions", -1),
+            TypeTreeNode::new(2, "Array", "Array", -1),
+            TypeTreeNode::new(3, "int", "size", 4),
+            TypeTreeNode::new(3, "Vector3f", "data", 12),
+            TypeTreeNode::new(4, "float", "x", 4),
+            TypeTreeNode::new(4, "float", "y", 4),
+            TypeTreeNode::new(4, "float", "z", 4),
+            TypeTreeNode::new(1, "LineParameters", "m_Parameters", -1),
+            TypeTreeNode::new(2, "float", "widthMultiplier", 4),
+            TypeTreeNode::new(1, "bool", "m_UseWorldSpace", 1).aligned(),
+        ],
+    }
+}
+
+pub fn line_renderer_data(positions: &[[f32; 3]], width_multiplier: f32) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.i32(positions.len() as i32);
+    for value in positions.iter().flatten() {
+        data.u32(value.to_bits());
+    }
+    data.u32(width_multiplier.to_bits()).bytes(&[1]).align(4);
+    data.data
+}
+
+/// A MonoBehaviour nesting collections in collections, with a field after
+/// them that only reads right when every nested size does.
+pub fn nested_collections_type() -> FixtureType {
+    let mut nodes = vec![TypeTreeNode::new(0, "MonoBehaviour", "Base", -1)];
+    nodes.extend(string_nodes(1, "m_Name"));
+    nodes.extend([
+        // vector<vector<int>>
+        TypeTreeNode::new(1, "vector", "m_Grid", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "vector", "data", -1),
+        TypeTreeNode::new(4, "Array", "Array", -1),
+        TypeTreeNode::new(5, "int", "size", 4),
+        TypeTreeNode::new(5, "int", "data", 4),
+        // map<int, vector<float>>
+        TypeTreeNode::new(1, "map", "m_Curves", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1).aligned(),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "pair", "data", -1),
+        TypeTreeNode::new(4, "int", "first", 4),
+        TypeTreeNode::new(4, "vector", "second", -1),
+        TypeTreeNode::new(5, "Array", "Array", -1),
+        TypeTreeNode::new(6, "int", "size", 4),
+        TypeTreeNode::new(6, "float", "data", 4),
+        // vector<Item> with Item { vector<UInt8> m_Bytes; int m_Id; }
+        TypeTreeNode::new(1, "vector", "m_Items", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "Item", "data", -1),
+        TypeTreeNode::new(4, "vector", "m_Bytes", -1).aligned(),
+        TypeTreeNode::new(5, "Array", "Array", -1),
+        TypeTreeNode::new(6, "int", "size", 4),
+        TypeTreeNode::new(6, "UInt8", "data", 1),
+        TypeTreeNode::new(4, "int", "m_Id", 4),
+        TypeTreeNode::new(1, "int", "m_Tail", 4),
+    ]);
+    FixtureType {
+        class_id: 114,
+        nodes,
+    }
+}
+
+/// `m_Grid` [[1, 2], [], [3]], `m_Curves` {1: [0.5], 2: [1, 2]}, `m_Items`
+/// [([7], 10), ([8, 9, 10, 11, 12], 20)] and `m_Tail` 99.
+pub fn nested_collections_data() -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.aligned_string("nested");
+    data.i32(3).i32(2).i32(1).i32(2).i32(0).i32(1).i32(3);
+    data.i32(2)
+        .i32(1)
+        .i32(1)
+        .u32(0.5f32.to_bits())
+        .i32(2)
+        .i32(2)
+        .u32(1f32.to_bits())
+        .u32(2f32.to_bits());
+    data.i32(2).i32(1).bytes(&[7]).align(4).i32(10);
+    data.i32(5).bytes(&[8, 9, 10, 11, 12]).align(4).i32(20);
+    data.i32(99);
+    data.data
+}
+
+pub fn asset_bundle_manifest_type() -> FixtureType {
+    let mut nodes = vec![TypeTreeNode::new(0, "AssetBundleManifest", "Base", -1)];
+    nodes.extend(string_nodes(1, "m_Name"));
+    nodes.extend([
+        TypeTreeNode::new(1, "map", "AssetBundleNames", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "pair", "data", -1),
+        TypeTreeNode::new(4, "int", "first", 4),
+    ]);
+    nodes.extend(string_nodes(4, "second"));
+    nodes.extend([
+        TypeTreeNode::new(1, "vector", "AssetBundlesWithVariant", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "int", "data", 4),
+        TypeTreeNode::new(1, "map", "AssetBundleInfos", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "pair", "data", -1),
+        TypeTreeNode::new(4, "int", "first", 4),
+        TypeTreeNode::new(4, "AssetBundleInfo", "second", -1),
+        TypeTreeNode::new(5, "Hash128", "AssetBundleHash", 16),
+    ]);
+    for index in 0..16 {
+        nodes.push(TypeTreeNode::new(6, "UInt8", &format!("bytes[{index}]"), 1));
+    }
+    nodes.extend([
+        TypeTreeNode::new(5, "vector", "AssetBundleDependencies", -1),
+        TypeTreeNode::new(6, "Array", "Array", -1),
+        TypeTreeNode::new(7, "int", "size", 4),
+        TypeTreeNode::new(7, "int", "data", 4),
+    ]);
+    FixtureType {
+        class_id: 290,
+        nodes,
+    }
+}
+
+/// Bundles listed by index with a hash of their index repeated, and the
+/// indices of the bundles each depends on.
+pub fn asset_bundle_manifest_data(
+    bundles: &[(i32, &str, &[i32])],
+    with_variant: &[i32],
+) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.aligned_string("AssetBundleManifest")
+        .i32(bundles.len() as i32);
+    for (index, name, _) in bundles {
+        data.i32(*index).aligned_string(name);
+    }
+    data.i32(with_variant.len() as i32);
+    for index in with_variant {
+        data.i32(*index);
+    }
+    data.i32(bundles.len() as i32);
+    for (index, _, dependencies) in bundles {
+        data.i32(*index)
+            .bytes(&[*index as u8; 16])
+            .i32(dependencies.len() as i32);
+        for dependency in *dependencies {
+            data.i32(*dependency);
+        }
+    }
+    data.data
+}
+
+/// An AudioClip with only its name and the `m_Resource` its bank is read from.
+pub fn audio_clip_type() -> FixtureType {
+    let mut nodes = vec![TypeTreeNode::new(0, "AudioClip", "Base", -1)];
+    nodes.extend(string_nodes(1, "m_Name"));
+    nodes.push(TypeTreeNode::new(1, "StreamedResource", "m_Resource", -1));
+    nodes.extend(string_nodes(2, "m_Source"));
+    nodes.extend([
+        TypeTreeNode::new(2, "UInt64", "m_Offset", 8),
+        TypeTreeNode::new(2, "UInt64", "m_Size", 8),
+    ]);
+    FixtureType {
+        class_id: 83,
+        nodes,
+    }
+}
+
+pub fn audio_clip_data(name: &str, source: &str, offset: u64, size: u64) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.aligned_string(name)
+        .aligned_string(source)
+        .i64(offset as i64)
+        .i64(size as i64);
+    data.data
+}
+
+/// A one sample FSB5 bank of `mode`, mono at 44100 Hz, holding `frames`
+/// frames of `data`.
+pub fn fsb5_bank(mode: u32, frames: u64, data: &[u8]) -> Vec<u8> {
+    let sample_header: u64 = (8 << 1) | (frames << 34);
+    let mut bank = Vec::new();
+    bank.extend_from_slice(b"FSB5");
+    for field in [1u32, 1, 8, 0, data.len() as u32, mode] {
+        bank.extend_from_slice(&field.to_le_bytes());
+    }
+    bank.extend_from_slice(&[0; 0x3C - 0x1C]);
+    bank.extend_from_slice(&sample_header.to_le_bytes());
+    bank.extend_from_slice(data);
+    bank
+}
+
+/// A `vector` of `OffsetPtr`s to `type_name`, whose fields go at `level + 5`.
+fn offset_ptr_array_nodes(level: u8, name: &'static str, type_name: &str) -> [TypeTreeNode; 5] {
+    [
+        TypeTreeNode::new(level, "vector", name, -1),
+        TypeTreeNode::new(level + 1, "Array", "Array", -1).aligned(),
+        TypeTreeNode::new(level + 2, "int", "size", 4),
+        TypeTreeNode::new(level + 2, "OffsetPtr", "data", -1),
+        TypeTreeNode::new(level + 3, type_name, "data", -1),
+    ]
+}
+
+/// An AnimatorController with the parts of `m_Controller` that
+/// [`io_unity::classes::animator_controller`] reads.
+pub fn animator_controller_type() -> FixtureType {
+    let mut nodes = vec![TypeTreeNode::new(0, "AnimatorController", "Base", -1)];
+    nodes.extend(string_nodes(1, "m_Name"));
+    nodes.push(TypeTreeNode::new(
+        1,
+        "ControllerConstant",
+        "m_Controller",
+        -1,
+    ));
+    nodes.extend(offset_ptr_array_nodes(2, "m_LayerArray", "LayerConstant"));
+    nodes.extend([
+        TypeTreeNode::new(6, "unsigned int", "m_StateMachineIndex", 4),
+        TypeTreeNode::new(6, "unsigned int", "m_Binding", 4),
+    ]);
+    nodes.extend(offset_ptr_array_nodes(
+        2,
+        "m_StateMachineArray",
+        "StateMachineConstant",
+    ));
+    nodes.extend(offset_ptr_array_nodes(
+        6,
+        "m_StateConstantArray",
+        "StateConstant",
+    ));
+    nodes.extend(offset_ptr_array_nodes(
+        10,
+        "m_TransitionConstantArray",
+        "TransitionConstant",
+    ));
+    nodes.extend(offset_ptr_array_nodes(
+        14,
+        "m_ConditionConstantArray",
+        "ConditionConstant",
+    ));
+    nodes.extend([
+        TypeTreeNode::new(18, "unsigned int", "m_ConditionMode", 4),
+        TypeTreeNode::new(18, "unsigned int", "m_EventID", 4),
+        TypeTreeNode::new(18, "float", "m_EventThreshold", 4),
+        TypeTreeNode::new(18, "float", "m_ExitTime", 4),
+        TypeTreeNode::new(14, "unsigned int", "m_DestinationState", 4),
+    ]);
+    nodes.extend(offset_ptr_array_nodes(
+        10,
+        "m_BlendTreeConstantArray",
+        "BlendTreeConstant",
+    ));
+    nodes.extend(offset_ptr_array_nodes(
+        14,
+        "m_NodeArray",
+        "BlendTreeNodeConstant",
+    ));
+    nodes.extend([
+        TypeTreeNode::new(18, "unsigned int", "m_ClipID", 4),
+        TypeTreeNode::new(10, "unsigned int", "m_NameID", 4),
+        TypeTreeNode::new(1, "map", "m_TOS", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1).aligned(),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "pair", "data", -1),
+        TypeTreeNode::new(4, "unsigned int", "first", 4),
+    ]);
+    nodes.extend(string_nodes(4, "second"));
+    nodes.extend([
+        TypeTreeNode::new(1, "vector", "m_AnimationClips", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1).aligned(),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "PPtr<AnimationClip>", "data", 12),
+        TypeTreeNode::new(4, "int", "m_FileID", 4),
+        TypeTreeNode::new(4, "SInt64", "m_PathID", 8),
+    ]);
+    FixtureType {
+        class_id: 91,
+        nodes,
+    }
+}
+
+/// `(mode, event, threshold, exit_time)` of a transition condition.
+pub type AnimatorConditionFixture = (u32, u32, f32, f32);
+
+/// A state of the one state machine of [`animator_controller_data`].
+pub struct AnimatorStateFixture<'a> {
+    pub name_hash: u32,
+    /// `m_ClipID` of each node of the state's one blend tree.
+    pub clip_ids: &'a [u32],
+    /// Destination state and conditions of each transition.
+    pub transitions: &'a [(u32, &'a [AnimatorConditionFixture])],
+}
+
+/// Layers of `(binding, state_machine_index)` over one state machine,
+/// with the `m_TOS` names and the path ids of the animation clips.
+pub fn animator_controller_data(
+    layers: &[(u32, u32)],
+    states: &[AnimatorStateFixture],
+    tos: &[(u32, &str)],
+    clip_path_ids: &[i64],
+) -> Vec<u8> {
+    let mut data = Writer::new(false);
+    data.aligned_string("controller").i32(layers.len() as i32);
+    for (binding, state_machine_index) in layers {
+        data.u32(*state_machine_index).u32(*binding);
+    }
+    data.i32(1).i32(states.len() as i32);
+    for state in states {
+        data.i32(state.transitions.len() as i32);
+        for (destination_state, conditions) in state.transitions {
+            data.i32(conditions.len() as i32);
+            for (mode, event, threshold, exit_time) in *conditions {
+                data.u32(*mode)
+                    .u32(*event)
+                    .u32(threshold.to_bits())
+                    .u32(exit_time.to_bits());
+            }
+            data.u32(*destination_state);
+        }
+        data.i32(1).i32(state.clip_ids.len() as i32);
+        for clip_id in state.clip_ids {
+            data.u32(*clip_id);
+        }
+        data.u32(state.name_hash);
+    }
+    data.i32(tos.len() as i32);
+    for (hash, name) in tos {
+        data.u32(*hash).aligned_string(name);
+    }
+    data.i32(clip_path_ids.len() as i32);
+    for path_id in clip_path_ids {
+        data.i32(0).i64(*path_id);
+    }
+    data.data
+}
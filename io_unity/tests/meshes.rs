@@ -0,0 +1,415 @@
+#![cfg(feature = "external-class-handle")]
+
+mod fixtures;
+
+use fixtures::*;
+use io_unity::type_tree::TypeTreeObjectRef;
+
+#[test]
+fn renderers_list_their_materials_by_slot() {
+    use io_unity::classes::renderer::{Renderer, RendererObject, ShadowCastingMode};
+
+    let file = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![mesh_renderer_type(), material_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: mesh_renderer_data(false, 2, true, &[0, 2]),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 1,
+                data: material_data(false, &[]),
+            },
+        ],
+    }
+    .build();
+    let (viewer, serialized_file_id) = load_serialized_file(file);
+    let renderer: TypeTreeObjectRef = viewer.serialized_file_map[&serialized_file_id]
+        .get_tt_object_by_path_id(1)
+        .unwrap()
+        .unwrap()
+        .into();
+    let renderer = Renderer::new(&renderer);
+
+    assert!(!renderer.get_enabled().unwrap());
+    assert_eq!(
+        renderer.get_cast_shadows().unwrap(),
+        ShadowCastingMode::TwoSided
+    );
+    assert!(renderer.get_receive_shadows().unwrap());
+    assert!(renderer.get_bounds().unwrap().is_none());
+
+    let materials = renderer.get_materials(&viewer).unwrap();
+    assert_eq!(materials.len(), 2);
+    assert!(materials[0].is_none());
+    assert_eq!(materials[1].as_ref().unwrap().get_path_id(), 2);
+}
+
+#[test]
+fn sub_meshes_report_index_ranges_and_topology() {
+    use io_unity::classes::mesh::{GfxPrimitiveType, Mesh, MeshObject};
+
+    let mesh = read_single_object(
+        mesh_type(),
+        mesh_data(&MeshFixture {
+            sub_meshes: &[[0, 6, 0, 0, 0, 4], [24, 4, 3, 4, 4, 2]],
+            index_format: 1,
+            index_buffer: &[0; 40],
+            vertex_count: 0,
+            channels: &[],
+            streams: None,
+            vertex_data: &[],
+            bind_poses: &[],
+        }),
+    );
+    let sub_meshes = Mesh::new(&mesh).sub_meshes().unwrap();
+
+    assert_eq!(sub_meshes.len(), 2);
+    assert_eq!(sub_meshes[0].topology, GfxPrimitiveType::Triangles);
+    assert_eq!(
+        (sub_meshes[0].first_index, sub_meshes[0].index_count),
+        (0, 6)
+    );
+    assert_eq!(sub_meshes[1].topology, GfxPrimitiveType::Lines);
+    assert_eq!(
+        (sub_meshes[1].first_index, sub_meshes[1].index_count),
+        (6, 4)
+    );
+    assert_eq!(sub_meshes[1].base_vertex, 4);
+    assert_eq!(sub_meshes[1].material_index, 1);
+}
+
+#[test]
+fn sub_mesh_indices_are_offset_by_their_base_vertex() {
+    use io_unity::classes::mesh::{Mesh, MeshObject};
+
+    // Two triangles in one 16 bit buffer, the second stored relative to
+    // base vertex 3.
+    let mut index_buffer = Writer::new(false);
+    for index in [0u16, 1, 2, 0, 2, 1] {
+        index_buffer.u16(index);
+    }
+    let mut vertex_data = Writer::new(false);
+    for value in 0..18 {
+        vertex_data.u32((value as f32).to_bits());
+    }
+    let mesh = read_single_object(
+        mesh_type(),
+        mesh_data(&MeshFixture {
+            sub_meshes: &[[0, 3, 0, 0, 0, 3], [6, 3, 0, 3, 3, 3]],
+            index_format: 0,
+            index_buffer: &index_buffer.data,
+            vertex_count: 6,
+            channels: &[[0, 0, 0, 3]],
+            streams: None,
+            vertex_data: &vertex_data.data,
+            bind_poses: &[],
+        }),
+    );
+    let mesh = Mesh::new(&mesh);
+
+    assert_eq!(mesh.get_index_buff(1).unwrap(), [0, 2, 1]);
+    assert_eq!(mesh.attributes().unwrap().indices, [0, 1, 2, 3, 5, 4]);
+    assert!(mesh.to_obj().unwrap().ends_with("f 4 6 5\n"));
+}
+
+#[test]
+fn obj_export_follows_the_sub_mesh_topology() {
+    use io_unity::classes::mesh::{Mesh, MeshObject};
+
+    let mut index_buffer = Writer::new(false);
+    for index in [0u16, 1, 2, 3] {
+        index_buffer.u16(index);
+    }
+    let mut vertex_data = Writer::new(false);
+    for value in 0..12 {
+        vertex_data.u32((value as f32).to_bits());
+    }
+    let expected = [
+        (0, "f 1 2 3\n"),
+        (1, "f 1 2 3\nf 3 2 4\n"),
+        (2, "f 1 2 3 4\n"),
+        (3, "l 1 2\nl 3 4\n"),
+        (4, "l 1 2 3 4\n"),
+        (5, "p 1 2 3 4\n"),
+    ];
+    for (topology, elements) in expected {
+        let mesh = read_single_object(
+            mesh_type(),
+            mesh_data(&MeshFixture {
+                sub_meshes: &[[0, if topology == 0 { 3 } else { 4 }, topology, 0, 0, 4]],
+                index_format: 0,
+                index_buffer: &index_buffer.data,
+                vertex_count: 4,
+                channels: &[[0, 0, 0, 3]],
+                streams: None,
+                vertex_data: &vertex_data.data,
+                bind_poses: &[],
+            }),
+        );
+
+        let obj = Mesh::new(&mesh).to_obj().unwrap();
+        let elements_start = obj.find("v 9 10 11\n").unwrap() + "v 9 10 11\n".len();
+        assert_eq!(&obj[elements_start..], elements, "topology {topology}");
+    }
+}
+
+#[test]
+fn meshes_with_explicit_streams_read_each_stream() {
+    use io_unity::classes::mesh::{Mesh, MeshObject};
+
+    // Positions in stream 0, normals and UVs interleaved in stream 1. The
+    // stored offset of stream 1 is not the 16 byte aligned one later
+    // versions imply.
+    let mut vertex_data = Writer::new(false);
+    for value in [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0] {
+        vertex_data.u32(value.to_bits());
+    }
+    for value in [7.0f32, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0] {
+        vertex_data.u32(value.to_bits());
+    }
+    let mesh = read_single_object(
+        mesh_type_with_streams(),
+        mesh_data(&MeshFixture {
+            sub_meshes: &[],
+            index_format: 0,
+            index_buffer: &[],
+            vertex_count: 2,
+            // Vertex, normal, color, uv0, uv1 and tangent, as before 5.0.
+            channels: &[
+                [0, 0, 0, 3],
+                [1, 0, 0, 3],
+                [0, 0, 0, 0],
+                [1, 12, 0, 2],
+                [0, 0, 0, 0],
+                [0, 0, 0, 0],
+            ],
+            streams: Some(&[[0, 12], [24, 20]]),
+            vertex_data: &vertex_data.data,
+            bind_poses: &[],
+        }),
+    );
+    let attributes = Mesh::new(&mesh).attributes().unwrap();
+
+    assert_eq!(attributes.positions, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(attributes.normals, [7.0, 8.0, 9.0, 12.0, 13.0, 14.0]);
+    assert_eq!(attributes.uvs[0], [10.0, 11.0, 15.0, 16.0]);
+    assert!(attributes.tangents.is_empty());
+    assert!(attributes.uvs[2].is_empty());
+}
+
+#[test]
+fn vertex_colors_read_from_byte_and_float_channels() {
+    use io_unity::classes::mesh::{Mesh, MeshObject};
+
+    // One triangle, colored by 8 bit colors in the first mesh and by floats
+    // in the second.
+    let colors = [[255u8, 0, 0, 255], [0, 255, 0, 51], [0, 0, 255, 0]];
+    let mut byte_data = Writer::new(false);
+    let mut float_data = Writer::new(false);
+    for (index, color) in colors.iter().enumerate() {
+        for writer in [&mut byte_data, &mut float_data] {
+            for value in [index as f32, 0.0, 0.0] {
+                writer.u32(value.to_bits());
+            }
+        }
+        byte_data.data.extend_from_slice(color);
+        for value in color {
+            float_data.u32((*value as f32 / 255.0).to_bits());
+        }
+    }
+    let mesh = |color_format: u8, vertex_data: &[u8]| {
+        mesh_data(&MeshFixture {
+            sub_meshes: &[[0, 3, 0, 0, 0, 3]],
+            index_format: 0,
+            index_buffer: &[0, 0, 1, 0, 2, 0],
+            vertex_count: 3,
+            // Vertex, normal, tangent and color, as since 2019.
+            channels: &[
+                [0, 0, 0, 3],
+                [0, 0, 0, 0],
+                [0, 0, 0, 0],
+                [0, 12, color_format, 4],
+            ],
+            streams: None,
+            vertex_data,
+            bind_poses: &[],
+        })
+    };
+    let file = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![mesh_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: mesh(2, &byte_data.data),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 0,
+                data: mesh(0, &float_data.data),
+            },
+        ],
+    }
+    .build();
+    let (viewer, serialized_file_id) = load_serialized_file(file);
+
+    let expected = [
+        [1.0, 0.0, 0.0, 1.0],
+        [0.0, 1.0, 0.0, 0.2],
+        [0.0, 0.0, 1.0, 0.0],
+    ];
+    for path_id in [1, 2] {
+        let mesh: TypeTreeObjectRef = viewer.serialized_file_map[&serialized_file_id]
+            .get_tt_object_by_path_id(path_id)
+            .unwrap()
+            .unwrap()
+            .into();
+        let mesh = Mesh::new(&mesh);
+        assert_eq!(mesh.colors().unwrap(), expected);
+        assert_eq!(mesh.attributes().unwrap().colors.len(), 12);
+        assert!(mesh.to_obj().unwrap().contains("v 1 0 0 0 1 0\n"));
+    }
+}
+
+#[test]
+fn mesh_blend_shapes_group_frames_by_channel() {
+    use glam::Vec3;
+    use io_unity::classes::mesh::{Mesh, MeshObject};
+
+    let mesh = read_single_object(blend_shape_mesh_type(), blend_shape_mesh_data());
+
+    let channels = Mesh::new(&mesh).get_blend_shapes().unwrap();
+    assert_eq!(channels.len(), 1);
+    assert_eq!(
+        (channels[0].name.as_str(), channels[0].name_hash),
+        ("smile", 0x1234)
+    );
+    let frames = &channels[0].frames;
+    assert_eq!(
+        frames
+            .iter()
+            .map(|frame| (frame.weight, frame.has_normals, frame.vertices.len()))
+            .collect::<Vec<_>>(),
+        [(50.0, false, 1), (100.0, true, 2)]
+    );
+    assert_eq!(frames[1].vertices[1].index, 2);
+    assert_eq!(frames[1].vertices[1].position, Vec3::new(0.0, 2.0, 0.0));
+    assert_eq!(frames[1].vertices[1].normal, Vec3::new(0.0, 0.0, 1.0));
+}
+
+#[test]
+fn skinned_meshes_export_to_fbx_with_their_skin() {
+    use io_unity::classes::game_object::GameObject;
+    use io_unity::classes::skinned_mesh_renderer::fbx::export_fbx;
+
+    // One triangle, the first two vertices weighted to the only bone.
+    let mut vertex_data = Writer::new(false);
+    for (position, weight) in [
+        ([1.0f32, 0.0, 0.0], 1.0f32),
+        ([0.0, 1.0, 0.0], 0.5),
+        ([0.0, 0.0, 1.0], 0.0),
+    ] {
+        for value in position.iter().chain(&[weight]) {
+            vertex_data.u32(value.to_bits());
+        }
+        vertex_data.i32(0);
+    }
+    // Position, then 11 absent channels, blend weight and SInt32 blend indices.
+    let mut channels = vec![[0u8, 0, 0, 3]];
+    channels.extend([[0, 0, 0, 0]; 11]);
+    channels.extend([[0, 12, 0, 1], [0, 16, 11, 1]]);
+
+    let cab = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![
+            game_object_type(),
+            posed_transform_type(),
+            skinned_mesh_renderer_type(),
+            mesh_type(),
+        ],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: game_object_data("Body", &[2, 3]),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 1,
+                data: posed_transform_data(1, [0.0; 3], &[5], 0),
+            },
+            FixtureObject {
+                path_id: 3,
+                type_index: 2,
+                data: skinned_mesh_renderer_data(1, 6, &[5]),
+            },
+            FixtureObject {
+                path_id: 4,
+                type_index: 0,
+                data: game_object_data("Hip", &[5]),
+            },
+            FixtureObject {
+                path_id: 5,
+                type_index: 1,
+                data: posed_transform_data(4, [1.0, 2.0, 3.0], &[], 2),
+            },
+            FixtureObject {
+                path_id: 6,
+                type_index: 3,
+                data: mesh_data(&MeshFixture {
+                    sub_meshes: &[[0, 3, 0, 0, 0, 3]],
+                    index_format: 0,
+                    index_buffer: &[0, 0, 1, 0, 2, 0],
+                    vertex_count: 3,
+                    channels: &channels,
+                    streams: None,
+                    vertex_data: &vertex_data.data,
+                    // The inverse of the hip's world matrix.
+                    bind_poses: &[[
+                        1.0, 0.0, 0.0, -1.0, //
+                        0.0, 1.0, 0.0, -2.0, //
+                        0.0, 0.0, 1.0, -3.0, //
+                        0.0, 0.0, 0.0, 1.0,
+                    ]],
+                }),
+            },
+        ],
+    }
+    .build();
+    let viewer = load_bundle(bundle(&[(CAB_NAME, &cab)], Compression::None, 1 << 16));
+    let body: TypeTreeObjectRef = viewer.get_object(CAB_NAME, 1).unwrap().unwrap().into();
+    let fbx = export_fbx(&viewer, &GameObject::new(&body)).unwrap();
+
+    let array = |name: &str, values: &str| {
+        let count = values.split(',').count();
+        format!("{name}: *{count} {{\n\t\t\ta: {values}\n")
+    };
+    assert!(fbx.starts_with("; FBX 7.4.0 project file\n"));
+    assert!(fbx.contains("\"Model::Body\", \"Mesh\""));
+    assert!(fbx.contains("\"Model::Hip\", \"LimbNode\""));
+    // Mirrored on x, with the winding reversed.
+    assert!(fbx.contains(&array("Vertices", "-1,0,0,0,1,0,0,0,1")));
+    assert!(fbx.contains(&array("PolygonVertexIndex", "0,2,-2")));
+    assert!(fbx.contains("P: \"Lcl Translation\", \"Lcl Translation\", \"\", \"A\",-1,2,3\n"));
+    assert!(fbx.contains(&array("Indexes", "0,1")));
+    assert!(fbx.contains(&array("Weights", "1,0.5")));
+    assert!(fbx.contains(&array("Transform", "1,0,0,0,0,1,0,0,0,0,1,0,1,-2,-3,1")));
+    assert!(fbx.contains(&array("TransformLink", "1,0,0,0,0,1,0,0,0,0,1,0,-1,2,3,1")));
+    assert!(fbx.contains("NbPoseNodes: 2\n"));
+    for (object_type, count) in [("Model", 2), ("Geometry", 1), ("Deformer", 2), ("Pose", 1)] {
+        assert!(fbx.contains(&format!(
+            "ObjectType: \"{object_type}\" {{\n\t\tCount: {count}\n"
+        )));
+    }
+}
@@ -0,0 +1,534 @@
+mod fixtures;
+
+use fixtures::*;
+use io_unity::error::Error;
+use io_unity::serialized_file::SerializedFileFormatVersion;
+use io_unity::type_tree::{convert::TryCastFrom, TypeTreeObjectRef};
+
+#[test]
+fn handles_resolve_after_the_objects_are_dropped() {
+    use io_unity::unity_asset_view::AssetHandle;
+
+    let viewer = load_bundle(text_asset_bundle(Compression::Lz4, false, true, 3));
+    let handles = viewer.handles(CAB_NAME);
+    assert_eq!(handles.len(), 3);
+
+    let object = viewer.get_object(CAB_NAME, 2).unwrap().unwrap();
+    assert_eq!(AssetHandle::from(&object), handles[1]);
+    drop(object);
+
+    for (handle, path_id) in handles.into_iter().zip(1..) {
+        let object: TypeTreeObjectRef = viewer.resolve(handle).unwrap().unwrap().into();
+        assert_eq!(
+            String::try_cast_from(&object, "/Base/m_Name").unwrap(),
+            format!("text{path_id}")
+        );
+    }
+    assert!(viewer
+        .resolve(AssetHandle {
+            serialized_file_id: 99,
+            path_id: 1
+        })
+        .is_err());
+}
+
+#[test]
+fn null_pptrs_resolve_to_nothing() {
+    use io_unity::classes::p_ptr::{PPtr, PPtrObject};
+
+    let file = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![pointer_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: pointer_data(3, 0),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 0,
+                data: pointer_data(3, 7),
+            },
+        ],
+    }
+    .build();
+    let (viewer, serialized_file_id) = load_serialized_file(file);
+    let serialized_file = &viewer.serialized_file_map[&serialized_file_id];
+
+    let null: TypeTreeObjectRef = serialized_file
+        .get_tt_object_by_path_id(1)
+        .unwrap()
+        .unwrap()
+        .into();
+    let null = <TypeTreeObjectRef>::try_cast_from(&null, "/Base/m_Ref").unwrap();
+    let null = PPtr::new(&null);
+    assert!(null.is_null());
+    assert!(null
+        .get_type_tree_object_in_view(&viewer)
+        .unwrap()
+        .is_none());
+
+    let dangling: TypeTreeObjectRef = serialized_file
+        .get_tt_object_by_path_id(2)
+        .unwrap()
+        .unwrap()
+        .into();
+    let dangling = <TypeTreeObjectRef>::try_cast_from(&dangling, "/Base/m_Ref").unwrap();
+    let dangling = PPtr::new(&dangling);
+    assert!(!dangling.is_null());
+    assert!(matches!(
+        dangling.get_type_tree_object_in_view(&viewer),
+        Err(Error::ExternalSerializedFileNotFound)
+    ));
+}
+
+#[test]
+fn external_references_name_their_files() {
+    const TARGET: &str = "CAB-33333333333333333333333333333333";
+    let file = |externals: &[&str]| {
+        SerializedFileFixture {
+            format_version: 22,
+            big_endian: false,
+            enable_type_tree: true,
+            types: vec![pointer_type()],
+            objects: vec![FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: pointer_data(0, 1),
+            }],
+        }
+        .build_with_externals(externals)
+    };
+    let target = file(&[]);
+    let referrer = file(&[
+        "archive:/CAB-missing/CAB-missing",
+        &format!("archive:/{TARGET}/{TARGET}"),
+    ]);
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+    viewer
+        .add_bundle_file(
+            Box::new(std::io::Cursor::new(bundle(
+                &[(TARGET, &target), (CAB_NAME, &referrer)],
+                Compression::None,
+                64,
+            ))),
+            None,
+        )
+        .unwrap();
+
+    let external_references = viewer
+        .get_serialized_file_by_path(CAB_NAME)
+        .unwrap()
+        .external_references();
+    let names: Vec<_> = external_references
+        .iter()
+        .map(|external| (external.file_id, external.name.as_str()))
+        .collect();
+    assert_eq!(names, [(1, "CAB-missing"), (2, TARGET)]);
+    assert_eq!(
+        external_references[1].path,
+        format!("archive:/{TARGET}/{TARGET}")
+    );
+    assert_eq!(external_references[1].guid.to_string(), "0".repeat(32));
+
+    assert_eq!(
+        viewer.resolve_external_reference(&external_references[0]),
+        None
+    );
+    assert_eq!(
+        viewer.resolve_external_reference(&external_references[1]),
+        Some(
+            viewer
+                .get_serialized_file_by_path(TARGET)
+                .unwrap()
+                .get_serialized_file_id()
+        )
+    );
+}
+
+#[test]
+fn same_file_pptrs_resolve_without_a_viewer() {
+    use io_unity::classes::p_ptr::{PPtr, PPtrObject};
+    use io_unity::serialized_file::SerializedFile;
+
+    let file = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![pointer_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: pointer_data(0, 2),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 0,
+                data: pointer_data(1, 1),
+            },
+        ],
+    }
+    .build();
+    let serialized_file =
+        SerializedFile::read(Box::new(std::io::Cursor::new(file)), 0, None).unwrap();
+    let pptr = |path_id| -> TypeTreeObjectRef {
+        let object: TypeTreeObjectRef = serialized_file
+            .get_tt_object_by_path_id(path_id)
+            .unwrap()
+            .unwrap()
+            .into();
+        <TypeTreeObjectRef>::try_cast_from(&object, "/Base/m_Ref").unwrap()
+    };
+
+    let target = PPtr::new(&pptr(1))
+        .get_type_tree_object(&serialized_file, None)
+        .unwrap()
+        .unwrap();
+    assert_eq!(target.path_id, 2);
+    assert!(matches!(
+        PPtr::new(&pptr(2)).get_type_tree_object(&serialized_file, None),
+        Err(Error::ExternalSerializedFileNotFound)
+    ));
+}
+
+#[test]
+fn dependency_graph_links_cabs_through_their_externals() {
+    const SHARED: &str = "CAB-22222222222222222222222222222222";
+    let cab = |externals: &[&str]| {
+        SerializedFileFixture {
+            format_version: 17,
+            big_endian: false,
+            enable_type_tree: true,
+            types: vec![text_asset_type()],
+            objects: Vec::new(),
+        }
+        .build_with_externals(externals)
+    };
+    let shared = cab(&["library/unity default resources"]);
+    let dependent = cab(&[
+        &format!("archive:/{SHARED}/{SHARED}"),
+        "archive:/CAB-missing/CAB-missing",
+    ]);
+    let viewer = load_bundle(bundle(
+        &[(SHARED, &shared), (CAB_NAME, &dependent)],
+        Compression::None,
+        64,
+    ));
+
+    let graph = viewer.dependency_graph();
+    assert_eq!(graph.loaded.iter().collect::<Vec<_>>(), [CAB_NAME, SHARED]);
+    assert_eq!(
+        graph.unresolved.iter().collect::<Vec<_>>(),
+        ["CAB-missing", "unity default resources"]
+    );
+    assert_eq!(
+        graph.dependencies(CAB_NAME).collect::<Vec<_>>(),
+        [SHARED, "CAB-missing"]
+    );
+    assert_eq!(
+        graph.dependencies(SHARED).collect::<Vec<_>>(),
+        ["unity default resources"]
+    );
+    let dot = graph.to_dot();
+    assert!(dot.contains(&format!("\"{CAB_NAME}\" -> \"{SHARED}\";")));
+    assert!(dot.contains("\"CAB-missing\" [style=dashed];"));
+}
+
+#[test]
+fn pptrs_compare_by_the_object_they_reach() {
+    use io_unity::classes::p_ptr::PPtr;
+
+    const FIRST: &str = "CAB-11111111111111111111111111111111";
+    const SECOND: &str = "CAB-22222222222222222222222222222222";
+    let pointers = |pointers: &[(i32, i64)]| SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![pointer_type()],
+        objects: pointers
+            .iter()
+            .enumerate()
+            .map(|(index, (file_id, path_id))| FixtureObject {
+                path_id: index as i64 + 1,
+                type_index: 0,
+                data: pointer_data(*file_id, *path_id),
+            })
+            .collect(),
+    };
+    // The first CAB points at its own objects, the second reaches the first
+    // through its second external.
+    let first = pointers(&[(0, 1), (0, 2)]).build();
+    let second = pointers(&[(2, 1), (1, 1), (0, 0)])
+        .build_with_externals(&["archive:/CAB-missing/CAB-missing", FIRST]);
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+    viewer
+        .add_bundle_file(
+            Box::new(std::io::Cursor::new(bundle(
+                &[(FIRST, &first), (SECOND, &second)],
+                Compression::None,
+                64,
+            ))),
+            None,
+        )
+        .unwrap();
+
+    let objects: Vec<Vec<TypeTreeObjectRef>> = [(FIRST, 2), (SECOND, 3)]
+        .into_iter()
+        .map(|(cab_name, count)| {
+            (1..=count)
+                .map(|path_id| {
+                    viewer
+                        .get_object(cab_name, path_id)
+                        .unwrap()
+                        .unwrap()
+                        .into()
+                })
+                .collect()
+        })
+        .collect();
+    let pptrs: Vec<Vec<TypeTreeObjectRef>> = objects
+        .iter()
+        .map(|objects| {
+            objects
+                .iter()
+                .map(|object| <TypeTreeObjectRef>::try_cast_from(object, "/Base/m_Ref").unwrap())
+                .collect()
+        })
+        .collect();
+    let pptr = |file: usize, index: usize| PPtr::new(&pptrs[file][index]);
+
+    assert!(pptr(0, 0).points_to_same(&pptr(1, 0), &viewer));
+    assert!(pptr(1, 0).points_to_same(&pptr(0, 0), &viewer));
+    assert!(!pptr(0, 1).points_to_same(&pptr(1, 0), &viewer));
+    // Unloaded files and null PPtrs match nothing, not even themselves.
+    assert!(!pptr(1, 1).points_to_same(&pptr(1, 1), &viewer));
+    assert!(!pptr(1, 2).points_to_same(&pptr(1, 2), &viewer));
+
+    let first_id = viewer
+        .get_serialized_file_by_path(FIRST)
+        .unwrap()
+        .get_serialized_file_id();
+    let target = |pptr: PPtr| {
+        viewer
+            .deref(&pptr)
+            .unwrap()
+            .map(|object| (object.serialized_file_id, object.path_id))
+    };
+    assert_eq!(target(pptr(0, 1)), Some((first_id, 2)));
+    assert_eq!(target(pptr(1, 0)), Some((first_id, 1)));
+    assert!(target(pptr(1, 2)).is_none());
+    assert!(matches!(
+        viewer.deref(&pptr(1, 1)),
+        Err(Error::ExternalSerializedFileNotFound)
+    ));
+}
+
+#[test]
+fn dependency_closure_follows_pptrs_through_cycles() {
+    let pointers = [(1, 2), (2, 3), (3, 1), (4, 0), (5, 99)];
+    let mut objects: Vec<_> = pointers
+        .iter()
+        .map(|(path_id, target)| FixtureObject {
+            path_id: *path_id,
+            type_index: 0,
+            data: pointer_data(0, *target),
+        })
+        .collect();
+    objects.push(FixtureObject {
+        path_id: 6,
+        type_index: 0,
+        data: pointer_data(3, 1),
+    });
+    let file = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![pointer_type()],
+        objects,
+    }
+    .build();
+    let (viewer, id) = load_serialized_file(file);
+
+    let closure = |path_id| {
+        let mut closure: Vec<_> = viewer
+            .dependency_closure(id, path_id)
+            .unwrap()
+            .into_iter()
+            .collect();
+        closure.sort();
+        closure
+    };
+    assert_eq!(closure(2), [(id, 1), (id, 2), (id, 3)]);
+    assert_eq!(closure(4), [(id, 4)]);
+    assert_eq!(closure(5), [(id, 5)]);
+    assert_eq!(closure(6), [(id, 6)]);
+}
+
+#[test]
+fn repacked_closures_read_back_as_one_bundle() {
+    const SHARED: &str = "CAB-22222222222222222222222222222222";
+    const BUILTIN: &str = "library/unity default resources";
+    // Path id 2 in both CABs, so one of them is renumbered.
+    let shared = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![pointer_type()],
+        objects: vec![FixtureObject {
+            path_id: 2,
+            type_index: 0,
+            data: pointer_data(1, 7),
+        }],
+    }
+    .build_with_externals(&[BUILTIN]);
+    let cab = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![asset_bundle_type(), pointer_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: asset_bundle_data(false, &[("assets/root.asset", 2)]),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 1,
+                data: pointer_data(1, 2),
+            },
+            FixtureObject {
+                path_id: 3,
+                type_index: 1,
+                data: pointer_data(0, 0),
+            },
+        ],
+    }
+    .build_with_externals(&[&format!("archive:/{SHARED}/{SHARED}")]);
+    let viewer = load_bundle(bundle(
+        &[(SHARED, &shared), (CAB_NAME, &cab)],
+        Compression::None,
+        64,
+    ));
+
+    for (target_version, format_version) in [
+        ("2019.4.31f1", SerializedFileFormatVersion::RefactorTypeData),
+        ("2021.3.5f1", SerializedFileFormatVersion::LargeFilesSupport),
+    ] {
+        let repacked = load_bundle(
+            viewer
+                .repack_closure("assets/root.asset", target_version)
+                .unwrap(),
+        );
+        assert_eq!(repacked.serialized_file_map.len(), 1);
+        let file = repacked.serialized_file_map.values().next().unwrap();
+        assert_eq!(file.get_serialized_file_version(), &format_version);
+        assert_eq!(file.get_object_map().len(), 3);
+        let externals = file.external_references();
+        assert_eq!(
+            externals
+                .iter()
+                .map(|external| &external.path)
+                .collect::<Vec<_>>(),
+            [BUILTIN]
+        );
+
+        let root: TypeTreeObjectRef = repacked
+            .get_type_tree_object_by_container_name("assets/root.asset")
+            .unwrap()
+            .unwrap()
+            .into();
+        assert_eq!(root.get_path_id(), 2);
+        assert_eq!(
+            i64::try_cast_from(&root, "/Base/m_Ref/m_FileID").unwrap(),
+            0
+        );
+        let shared_path_id = i64::try_cast_from(&root, "/Base/m_Ref/m_PathID").unwrap();
+        assert_ne!(shared_path_id, 2);
+        let shared: TypeTreeObjectRef = file
+            .get_tt_object_by_path_id(shared_path_id)
+            .unwrap()
+            .unwrap()
+            .into();
+        assert_eq!(
+            (
+                i64::try_cast_from(&shared, "/Base/m_Ref/m_FileID").unwrap(),
+                i64::try_cast_from(&shared, "/Base/m_Ref/m_PathID").unwrap(),
+            ),
+            (1, 7)
+        );
+
+        let asset_bundle = file.get_tt_object_by_path_id(1).unwrap().unwrap();
+        let preload_table: Vec<_> = asset_bundle
+            .get_pptr_array_by_path("/Base/m_PreloadTable/Array")
+            .unwrap()
+            .into_iter()
+            .map(|ids| ids.path_id)
+            .collect();
+        assert_eq!(preload_table, [2, shared_path_id]);
+        let entry = &repacked.container_entries("assets/root.asset")[0];
+        assert_eq!(
+            (entry.preload_index, entry.preload_size),
+            (Some(0), Some(2))
+        );
+    }
+
+    assert!(viewer
+        .repack_closure("assets/missing", "2021.3.5f1")
+        .is_err());
+    assert!(viewer
+        .repack_closure("assets/root.asset", "latest")
+        .is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn pptrs_round_trip_through_json() {
+    use io_unity::classes::p_ptr::{PPtr, PPtrIds, PPtrObject};
+    use io_unity::unity_asset_view::AssetHandle;
+
+    let file = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![pointer_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: pointer_data(0, 2),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 0,
+                data: pointer_data(0, 0),
+            },
+        ],
+    }
+    .build();
+    let (viewer, serialized_file_id) = load_serialized_file(file);
+    let object: TypeTreeObjectRef = viewer.serialized_file_map[&serialized_file_id]
+        .get_tt_object_by_path_id(1)
+        .unwrap()
+        .unwrap()
+        .into();
+    let pptr = <TypeTreeObjectRef>::try_cast_from(&object, "/Base/m_Ref").unwrap();
+
+    let json = serde_json::to_string(&PPtr::new(&pptr)).unwrap();
+    assert_eq!(
+        json,
+        format!(r#"{{"serialized_file_id":{serialized_file_id},"file_id":0,"path_id":2}}"#)
+    );
+    let ids: PPtrIds = serde_json::from_str(&json).unwrap();
+    assert_eq!(ids, PPtr::from_ids(serialized_file_id, 0, 2));
+    let target = ids.get_type_tree_object_in_view(&viewer).unwrap().unwrap();
+    assert_eq!(target.path_id, 2);
+
+    let handle = AssetHandle::from(&target);
+    let json = serde_json::to_string(&handle).unwrap();
+    assert_eq!(serde_json::from_str::<AssetHandle>(&json).unwrap(), handle);
+}
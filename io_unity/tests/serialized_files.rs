@@ -0,0 +1,622 @@
+mod fixtures;
+
+use fixtures::*;
+use io_unity::error::Error;
+use io_unity::serialized_file::SerializedFileFormatVersion;
+use io_unity::type_tree::{convert::TryCastFrom, TypeTreeNode, TypeTreeObjectRef};
+
+#[test]
+fn serialized_file_without_type_tree_keeps_raw_data() {
+    let (viewer, serialized_file_id) = load_serialized_file(text_asset_file(false, false, 2));
+    let serialized_file = &viewer.serialized_file_map[&serialized_file_id];
+
+    assert!(serialized_file
+        .types()
+        .iter()
+        .all(|tp| tp.class_id == 49 && !tp.has_type_tree));
+    assert_eq!(
+        serialized_file.get_object_raw_bytes(2).unwrap().unwrap(),
+        text_asset_data(false, "text2", "script of 2")
+    );
+}
+
+#[test]
+fn objects_keep_their_raw_bytes_when_asked() {
+    use io_unity::unity_asset_view::UnityAssetViewer;
+
+    let mut viewer = UnityAssetViewer::builder().keep_raw_bytes(true).build();
+    let serialized_file_id = viewer
+        .add_serialized_file(
+            Box::new(std::io::Cursor::new(text_asset_file(true, true, 2))),
+            None,
+        )
+        .unwrap();
+    let serialized_file = &viewer.serialized_file_map[&serialized_file_id];
+    let object = serialized_file
+        .get_tt_object_by_path_id(2)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        object.raw_bytes().unwrap(),
+        text_asset_data(true, "text2", "script of 2")
+    );
+
+    viewer.set_keep_raw_bytes(false);
+    let serialized_file = &viewer.serialized_file_map[&serialized_file_id];
+    let object = serialized_file
+        .get_tt_object_by_path_id(2)
+        .unwrap()
+        .unwrap();
+    assert!(object.raw_bytes().is_none());
+}
+
+#[test]
+fn missing_type_tree_policy_chooses_what_iteration_yields() {
+    use io_unity::serialized_file::{MissingTypeTreePolicy, ObjectData};
+    use io_unity::unity_asset_view::UnityAssetViewer;
+
+    let read = |policy| {
+        let mut viewer = UnityAssetViewer::builder()
+            .missing_type_tree_policy(policy)
+            .build();
+        viewer
+            .add_serialized_file(
+                Box::new(std::io::Cursor::new(text_asset_file(false, false, 2))),
+                None,
+            )
+            .unwrap();
+        viewer
+            .iter_objects(None)
+            .map(|(_, object)| object)
+            .collect::<Vec<_>>()
+    };
+
+    let objects = read(MissingTypeTreePolicy::Error);
+    assert_eq!(objects.len(), 2);
+    assert!(objects
+        .iter()
+        .all(|object| matches!(object, Err(Error::ObjectReadError { .. }))));
+    assert!(read(MissingTypeTreePolicy::SkipObject).is_empty());
+    let objects = read(MissingTypeTreePolicy::ReturnRawBytes);
+    match &objects[1] {
+        Ok(ObjectData::RawBytes {
+            path_id,
+            class_id,
+            data,
+        }) => {
+            assert_eq!((*path_id, *class_id), (2, 49));
+            assert_eq!(data, &text_asset_data(false, "text2", "script of 2"));
+        }
+        object => panic!("{object:?}"),
+    }
+}
+
+#[test]
+fn forced_endianness_overrides_a_wrong_flag() {
+    let mut cab = text_asset_file(false, true, 2);
+    // Flag the little endian file as big endian.
+    cab[16] = 1;
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+    assert!(viewer
+        .add_bundle_file(
+            Box::new(std::io::Cursor::new(bundle(
+                &[(CAB_NAME, &cab)],
+                Compression::None,
+                64
+            ))),
+            None,
+        )
+        .is_err());
+
+    viewer
+        .add_bundle_with_endianness(
+            Box::new(std::io::Cursor::new(bundle(
+                &[(CAB_NAME, &cab)],
+                Compression::None,
+                64,
+            ))),
+            None,
+            binrw::Endian::Little,
+        )
+        .unwrap();
+    let text: TypeTreeObjectRef = viewer.get_object(CAB_NAME, 2).unwrap().unwrap().into();
+    assert_eq!(
+        String::try_cast_from(&text, "/Base/m_Script").unwrap(),
+        "script of 2"
+    );
+}
+
+#[test]
+fn objects_without_data_are_removed() {
+    let file = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![text_asset_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: Vec::new(),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 0,
+                data: text_asset_data(false, "kept", ""),
+            },
+        ],
+    }
+    .build();
+    let (viewer, serialized_file_id) = load_serialized_file(file);
+    let serialized_file = &viewer.serialized_file_map[&serialized_file_id];
+
+    assert_eq!(
+        serialized_file.object_info(1).unwrap().is_destroyed,
+        Some(1)
+    );
+    assert!(matches!(
+        serialized_file.get_tt_object_by_path_id(1),
+        Err(Error::ObjectRemoved { path_id: 1 })
+    ));
+    // Loops over every object skip it rather than fail.
+    assert!(serialized_file.get_object_map()[&1].is_removed());
+    let read: Vec<i64> = serialized_file
+        .iter_tt_objects(None)
+        .map(|object| object.unwrap().path_id)
+        .collect();
+    assert_eq!(read, [2]);
+    assert_eq!(viewer.find_objects(None, None), [(serialized_file_id, 2)]);
+    assert!(serialized_file
+        .get_tt_object_by_path_id(2)
+        .unwrap()
+        .is_some());
+}
+
+#[test]
+fn reinterpret_object_with_caller_type_tree() {
+    let (viewer, serialized_file_id) = load_serialized_file(text_asset_file(true, false, 1));
+    let serialized_file = &viewer.serialized_file_map[&serialized_file_id];
+
+    let nodes = [
+        TypeTreeNode::new(0, "TextAsset", "Base", -1),
+        TypeTreeNode::new(1, "string", "m_Name", -1),
+        TypeTreeNode::new(2, "Array", "Array", -1).aligned(),
+        TypeTreeNode::new(3, "int", "size", 4),
+        TypeTreeNode::new(3, "char", "data", 1),
+    ];
+    let object = serialized_file
+        .get_object_with_type_tree(1, &nodes)
+        .unwrap();
+    assert!(object.external_data.is_some());
+    let object: TypeTreeObjectRef = object.into();
+    assert_eq!(
+        String::try_cast_from(&object, "/Base/m_Name").unwrap(),
+        "text1"
+    );
+}
+
+#[test]
+fn exported_type_trees_read_the_objects_of_their_class() {
+    let (viewer, serialized_file_id) = load_serialized_file(text_asset_file(false, true, 1));
+    let serialized_file = &viewer.serialized_file_map[&serialized_file_id];
+    assert_eq!(serialized_file.export_type_tree_for_class(1, None), None);
+    assert_eq!(
+        serialized_file.export_type_tree_for_class(49, Some(0)),
+        None
+    );
+    let blob = serialized_file
+        .export_type_tree_for_class(49, None)
+        .unwrap();
+
+    let int = |offset: usize| i32::from_le_bytes(blob[offset..offset + 4].try_into().unwrap());
+    let node_count = int(0) as usize;
+    let strings = &blob[8 + node_count * 32..];
+    assert_eq!(strings.len(), int(4) as usize);
+    let string = |offset: i32| {
+        let string = &strings[offset as usize..];
+        String::from_utf8(string[..string.iter().position(|b| *b == 0).unwrap()].to_vec()).unwrap()
+    };
+    let nodes: Vec<TypeTreeNode> = blob[8..8 + node_count * 32]
+        .chunks_exact(32)
+        .map(|node| TypeTreeNode {
+            version: u16::from_le_bytes([node[0], node[1]]),
+            level: node[2],
+            is_array: node[3] & 1 != 0,
+            type_name: string(i32::from_le_bytes(node[4..8].try_into().unwrap())),
+            name: string(i32::from_le_bytes(node[8..12].try_into().unwrap())),
+            byte_size: i32::from_le_bytes(node[12..16].try_into().unwrap()),
+            index: i32::from_le_bytes(node[16..20].try_into().unwrap()),
+            meta_flag: i32::from_le_bytes(node[20..24].try_into().unwrap()),
+        })
+        .collect();
+    // The fixture writer numbers the nodes as it stores them.
+    let expected: Vec<TypeTreeNode> = text_asset_type()
+        .nodes
+        .into_iter()
+        .enumerate()
+        .map(|(index, node)| TypeTreeNode {
+            index: index as i32,
+            ..node
+        })
+        .collect();
+    assert_eq!(nodes, expected);
+
+    // The blob reads back as a format 19 TypeTree, flags and all.
+    let type_tree = <io_unity::serialized_file::version19::TypeTree as binrw::BinRead>::read_le(
+        &mut std::io::Cursor::new(&blob),
+    )
+    .unwrap();
+    let fields = type_tree.get_type_fields();
+    assert_eq!(fields.len(), expected.len());
+    for (field, node) in fields.iter().zip(&expected) {
+        assert_eq!(field.get_type(), &node.type_name);
+        assert_eq!(field.get_name(), &node.name);
+        assert_eq!(field.get_type_flags(), node.is_array as u8);
+        assert_eq!(
+            (field.get_level(), field.get_byte_size(), field.get_index()),
+            (node.level, node.byte_size, node.index)
+        );
+    }
+
+    let object: TypeTreeObjectRef = serialized_file
+        .get_object_with_type_tree(1, &nodes)
+        .unwrap()
+        .into();
+    assert_eq!(
+        String::try_cast_from(&object, "/Base/m_Script").unwrap(),
+        "script of 1"
+    );
+}
+
+#[test]
+fn read_format_22_with_64_bit_offsets() {
+    for big_endian in [false, true] {
+        let file = SerializedFileFixture {
+            format_version: 22,
+            big_endian,
+            enable_type_tree: true,
+            types: vec![text_asset_type()],
+            objects: (1..=2)
+                .map(|path_id| FixtureObject {
+                    path_id,
+                    type_index: 0,
+                    data: text_asset_data(big_endian, &format!("text{path_id}"), "script"),
+                })
+                .collect(),
+        }
+        .build();
+        let (viewer, serialized_file_id) = load_serialized_file(file);
+        let serialized_file = &viewer.serialized_file_map[&serialized_file_id];
+
+        assert_eq!(
+            serialized_file.get_serialized_file_version(),
+            &SerializedFileFormatVersion::LargeFilesSupport
+        );
+        let object: TypeTreeObjectRef = serialized_file
+            .get_tt_object_by_path_id(2)
+            .unwrap()
+            .unwrap()
+            .into();
+        assert_eq!(
+            String::try_cast_from(&object, "/Base/m_Name").unwrap(),
+            "text2"
+        );
+    }
+}
+
+#[test]
+fn lazily_read_files_parse_type_trees_on_demand() {
+    use io_unity::serialized_file::SerializedFile;
+
+    for (format_version, big_endian) in [(17, false), (17, true), (22, false), (22, true)] {
+        let file = SerializedFileFixture {
+            format_version,
+            big_endian,
+            enable_type_tree: true,
+            types: vec![text_asset_type()],
+            objects: (1..=2)
+                .map(|path_id| FixtureObject {
+                    path_id,
+                    type_index: 0,
+                    data: text_asset_data(big_endian, &format!("text{path_id}"), "script"),
+                })
+                .collect(),
+        }
+        .build();
+        let eager =
+            SerializedFile::read(Box::new(std::io::Cursor::new(file.clone())), 0, None).unwrap();
+        let lazy =
+            SerializedFile::read_lazy(Box::new(std::io::Cursor::new(file)), 0, None).unwrap();
+
+        assert!(eager.is_type_tree_decoded(0));
+        assert_eq!(lazy.objects_in_file_order(), eager.objects_in_file_order());
+        assert_eq!(lazy.types(), eager.types());
+        assert!(!lazy.is_type_tree_decoded(0));
+        for path_id in [2, 1] {
+            let object: TypeTreeObjectRef = lazy
+                .get_tt_object_by_path_id(path_id)
+                .unwrap()
+                .unwrap()
+                .into();
+            assert!(lazy.is_type_tree_decoded(0));
+            assert_eq!(
+                String::try_cast_from(&object, "/Base/m_Name").unwrap(),
+                format!("text{path_id}")
+            );
+        }
+    }
+}
+
+#[test]
+fn mono_behaviours_are_told_apart_by_script_type_index() {
+    // Format 13 stores the index per object, later formats per type.
+    for format_version in [13, 17, 22] {
+        let behaviour = || FixtureType {
+            class_id: 114,
+            ..text_asset_type()
+        };
+        let file = SerializedFileFixture {
+            format_version,
+            big_endian: false,
+            enable_type_tree: true,
+            types: vec![text_asset_type(), behaviour(), behaviour()],
+            objects: (0..3)
+                .map(|type_index| FixtureObject {
+                    path_id: type_index as i64 + 1,
+                    type_index,
+                    data: text_asset_data(false, "object", "script"),
+                })
+                .collect(),
+        }
+        .build();
+        let (viewer, serialized_file_id) = load_serialized_file(file);
+        let serialized_file = &viewer.serialized_file_map[&serialized_file_id];
+
+        let indices: Vec<_> = (1..=4)
+            .map(|path_id| serialized_file.script_type_index(path_id))
+            .collect();
+        assert_eq!(indices, [Some(-1), Some(0), Some(1), None]);
+        assert_eq!(serialized_file.type_tree_hash(2), Some([2; 16]));
+        assert_eq!(serialized_file.type_tree_hash(3), None);
+        assert_eq!(
+            serialized_file.object_info(3).unwrap().script_type_index,
+            Some(1)
+        );
+        assert_eq!(
+            serialized_file
+                .filter_objects(|info| info.class_id == 114 && info.script_type_index == Some(1)),
+            [3]
+        );
+    }
+}
+
+#[test]
+fn format_13_path_ids_follow_the_big_id_flag() {
+    // The second set of ids needs 64 bits, so the file enables big ids.
+    for path_ids in [[1, 2], [1, 1 << 40]] {
+        for big_endian in [false, true] {
+            let file = SerializedFileFixture {
+                format_version: 13,
+                big_endian,
+                enable_type_tree: true,
+                types: vec![text_asset_type()],
+                objects: path_ids
+                    .iter()
+                    .map(|&path_id| FixtureObject {
+                        path_id,
+                        type_index: 0,
+                        data: text_asset_data(big_endian, &format!("text{path_id}"), "script"),
+                    })
+                    .collect(),
+            }
+            .build();
+            let (viewer, serialized_file_id) = load_serialized_file(file);
+            let serialized_file = &viewer.serialized_file_map[&serialized_file_id];
+
+            let ids: Vec<i64> = serialized_file.get_object_map().keys().copied().collect();
+            assert_eq!(ids, path_ids);
+            for path_id in path_ids {
+                let object: TypeTreeObjectRef = serialized_file
+                    .get_tt_object_by_path_id(path_id)
+                    .unwrap()
+                    .unwrap()
+                    .into();
+                assert_eq!(
+                    String::try_cast_from(&object, "/Base/m_Name").unwrap(),
+                    format!("text{path_id}")
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn newer_format_versions_are_named() {
+    let mut file = text_asset_file(false, true, 1);
+    file[8..12].copy_from_slice(&23u32.to_be_bytes());
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+    let err = viewer
+        .add_serialized_file(Box::new(std::io::Cursor::new(file)), None)
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("unsupported serialized file format version 23"));
+}
+
+#[test]
+fn truncated_serialized_files_fail_with_their_sizes() {
+    for format_version in [17, 22] {
+        let file = SerializedFileFixture {
+            format_version,
+            big_endian: false,
+            enable_type_tree: true,
+            types: vec![text_asset_type()],
+            objects: (1..=2)
+                .map(|path_id| FixtureObject {
+                    path_id,
+                    type_index: 0,
+                    data: text_asset_data(false, &format!("text{path_id}"), "script"),
+                })
+                .collect(),
+        }
+        .build();
+        // Cut into the metadata, then into the last object.
+        for available in [60, file.len() - 4] {
+            let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+            let err = viewer
+                .add_serialized_file(
+                    Box::new(std::io::Cursor::new(file[..available].to_vec())),
+                    None,
+                )
+                .unwrap_err();
+            match err {
+                Error::TruncatedFile {
+                    expected,
+                    available: found,
+                } => {
+                    assert_eq!(found, available as u64);
+                    assert!(expected > found, "{expected} <= {found}");
+                }
+                err => panic!("{format_version}: {err}"),
+            }
+        }
+    }
+
+    let cab = text_asset_file(false, true, 2);
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        temp_dir.path().join("partial.bundle"),
+        bundle(&[(CAB_NAME, &cab[..cab.len() - 4])], Compression::None, 64),
+    )
+    .unwrap();
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+    let report = viewer.read_dir_report(temp_dir.path()).unwrap();
+    assert_eq!(report.bundles_loaded, 0);
+    assert!(report.skipped[0].1.starts_with("truncated file"));
+}
+
+#[test]
+fn iteration_continues_past_corrupt_objects() {
+    let mut corrupt = text_asset_data(false, "text2", "script");
+    // A string length far past the end of the object.
+    corrupt[..4].copy_from_slice(&1000i32.to_le_bytes());
+    let file = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![text_asset_type()],
+        objects: [
+            text_asset_data(false, "text1", "script"),
+            corrupt,
+            text_asset_data(false, "text3", "script"),
+        ]
+        .into_iter()
+        .zip(1..)
+        .map(|(data, path_id)| FixtureObject {
+            path_id,
+            type_index: 0,
+            data,
+        })
+        .collect(),
+    }
+    .build();
+    let (viewer, _) = load_serialized_file(file);
+
+    let results: Vec<_> = viewer.iter_tt_objects(Some(49)).collect();
+    assert_eq!(results.len(), 3);
+    assert!(results[0].1.is_ok() && results[2].1.is_ok());
+    match &results[1].1 {
+        Err(Error::ObjectReadError { object_meta, .. }) => {
+            assert_eq!((object_meta.path_id, object_meta.class), (2, 49))
+        }
+        other => panic!("unexpected {other:?}"),
+    }
+    assert_eq!(viewer.iter_tt_objects(Some(28)).count(), 0);
+}
+
+#[test]
+fn format_22_ref_types_decode_managed_references() {
+    for big_endian in [false, true] {
+        let file = SerializedFileFixture {
+            format_version: 22,
+            big_endian,
+            enable_type_tree: true,
+            types: vec![managed_reference_behaviour_type(), text_asset_type()],
+            objects: vec![
+                FixtureObject {
+                    path_id: 1,
+                    type_index: 0,
+                    data: managed_reference_behaviour_data(big_endian, 42),
+                },
+                FixtureObject {
+                    path_id: 2,
+                    type_index: 1,
+                    data: text_asset_data(big_endian, "text2", "script"),
+                },
+            ],
+        }
+        .build_with_ref_types(&[wolf_ref_type()]);
+        let (viewer, serialized_file_id) = load_serialized_file(file);
+        let serialized_file = &viewer.serialized_file_map[&serialized_file_id];
+
+        let ref_types = serialized_file.ref_types();
+        assert_eq!(ref_types.len(), 1);
+        assert_eq!(
+            (
+                ref_types[0].class_name.as_str(),
+                ref_types[0].name_space.as_str(),
+                ref_types[0].assembly_name.as_str()
+            ),
+            ("Wolf", "Pack", "Game")
+        );
+        assert!(ref_types[0].has_type_tree);
+
+        let behaviour: TypeTreeObjectRef = serialized_file
+            .get_tt_object_by_path_id(1)
+            .unwrap()
+            .unwrap()
+            .into();
+        let wolf = TypeTreeObjectRef::try_cast_from(&behaviour, "/Base/m_Ref")
+            .unwrap()
+            .resolve_managed_reference()
+            .unwrap()
+            .unwrap();
+        assert_eq!(i32::try_cast_from(&wolf, "/Base/m_Teeth").unwrap(), 42);
+
+        let text: TypeTreeObjectRef = serialized_file
+            .get_tt_object_by_path_id(2)
+            .unwrap()
+            .unwrap()
+            .into();
+        assert_eq!(
+            String::try_cast_from(&text, "/Base/m_Name").unwrap(),
+            "text2"
+        );
+    }
+}
+
+#[test]
+fn serialized_files_pair_ids_with_names_in_name_order() {
+    const FIRST: &str = "CAB-11111111111111111111111111111111";
+    const SECOND: &str = "CAB-22222222222222222222222222222222";
+    let viewer = load_bundle(bundle(
+        &[
+            (SECOND, &text_asset_file(false, true, 2)),
+            (FIRST, &text_asset_file(false, true, 1)),
+        ],
+        Compression::None,
+        64,
+    ));
+
+    let files: Vec<(&str, usize)> = viewer
+        .serialized_files()
+        .map(|(name, serialized_file_id, serialized_file)| {
+            assert!(std::ptr::eq(
+                &viewer.serialized_file_map[&serialized_file_id],
+                serialized_file
+            ));
+            (name.as_str(), serialized_file.get_object_map().len())
+        })
+        .collect();
+    assert_eq!(files, [(FIRST, 1), (SECOND, 2)]);
+}
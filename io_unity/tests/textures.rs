@@ -0,0 +1,572 @@
+#![cfg(feature = "external-class-handle-texture2d")]
+
+mod fixtures;
+
+use fixtures::*;
+use io_unity::error::Error;
+use io_unity::type_tree::TypeTreeObjectRef;
+
+#[test]
+fn texture_data_comes_from_the_stream_when_it_has_a_path() {
+    use io_unity::classes::texture2d::{Texture2D, Texture2DObject};
+
+    let stream_path = format!("archive:/{CAB_NAME}/{CAB_NAME}.resS");
+    let resource = [0u8, 0, 0, 0, 10, 20, 30, 40, 50];
+    let textures = [
+        // Everything inline.
+        (&[1u8, 2, 3, 4][..], 0, 0, "", vec![1, 2, 3, 4]),
+        // Everything streamed, with an empty inline buffer.
+        (&[][..], 4, 4, &stream_path[..], vec![10, 20, 30, 40]),
+        // Mip 0 inline and mip 1 streamed.
+        (
+            &[1, 2, 3, 4][..],
+            8,
+            1,
+            &stream_path[..],
+            vec![1, 2, 3, 4, 50],
+        ),
+        // Streamed from the file's own data section, here the "text" of
+        // the first texture's name.
+        (&[][..], 4, 4, "", b"text".to_vec()),
+    ];
+
+    let cab = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![texture2d_type()],
+        objects: textures
+            .iter()
+            .enumerate()
+            .map(
+                |(index, (image_data, stream_offset, stream_size, stream_path, _))| {
+                    FixtureObject {
+                        path_id: index as i64 + 1,
+                        type_index: 0,
+                        data: texture2d_data(
+                            false,
+                            &TextureFixture {
+                                width: 2,
+                                height: 2,
+                                texture_format: 1, // Alpha8
+                                mip_count: if index == 2 { 2 } else { 1 },
+                                image_data,
+                                stream_offset: *stream_offset,
+                                stream_size: *stream_size,
+                                stream_path,
+                            },
+                        ),
+                    }
+                },
+            )
+            .collect(),
+    }
+    .build();
+    let viewer = load_bundle(bundle(
+        &[(CAB_NAME, &cab), (&format!("{CAB_NAME}.resS"), &resource)],
+        Compression::Lz4,
+        64,
+    ));
+
+    let serialized_file = viewer.get_serialized_file_by_path(CAB_NAME).unwrap();
+    assert_eq!(serialized_file.object_info(1).unwrap().byte_start, 0);
+    // Sizes past the end of the file fail before anything is allocated.
+    assert!(matches!(
+        serialized_file.read_data(4, usize::MAX),
+        Err(Error::TruncatedFile { .. })
+    ));
+    assert!(matches!(
+        viewer.read_stream_data(
+            serialized_file.get_serialized_file_id(),
+            &stream_path,
+            0,
+            usize::MAX
+        ),
+        Err(Error::TruncatedFile { .. })
+    ));
+    for (index, (.., expected)) in textures.iter().enumerate() {
+        let texture: TypeTreeObjectRef = viewer
+            .get_object(CAB_NAME, index as i64 + 1)
+            .unwrap()
+            .unwrap()
+            .into();
+        let texture = Texture2D::new(&texture);
+        assert_eq!(&texture.get_image_data(&viewer).unwrap(), expected);
+    }
+}
+
+#[test]
+fn streams_resolve_from_extra_search_paths() {
+    use io_unity::classes::texture2d::{Texture2D, Texture2DObject};
+
+    let stream_name = "fixture_search_path.resS";
+    let bundle = single_object_bundle(
+        texture2d_type(),
+        texture2d_data(
+            false,
+            &TextureFixture {
+                width: 2,
+                height: 2,
+                texture_format: 1, // Alpha8
+                mip_count: 1,
+                image_data: &[],
+                stream_offset: 2,
+                stream_size: 4,
+                stream_path: stream_name,
+            },
+        ),
+    );
+    let mut viewer = load_bundle(bundle);
+    let texture: TypeTreeObjectRef = viewer.get_object(CAB_NAME, 1).unwrap().unwrap().into();
+    assert!(Texture2D::new(&texture).get_image_data(&viewer).is_err());
+
+    let data_dir = tempfile::tempdir().unwrap();
+    std::fs::write(data_dir.path().join(stream_name), [0, 0, 5, 6, 7, 8]).unwrap();
+    viewer.set_stream_search_paths(vec![data_dir.path().to_path_buf()]);
+    assert_eq!(
+        Texture2D::new(&texture).get_image_data(&viewer).unwrap(),
+        [5, 6, 7, 8]
+    );
+}
+
+#[test]
+fn undecodable_textures_say_why() {
+    use io_unity::classes::texture2d::{Texture2D, Texture2DObject, UndecodableReason};
+
+    let textures = [
+        (1, &[1u8, 2, 3, 4][..], "", Ok(())),
+        (1, &[][..], "", Err(UndecodableReason::NoData)),
+        (
+            1,
+            &[][..],
+            "missing.resS",
+            Err(UndecodableReason::MissingStream),
+        ),
+        // ARGB32
+        (
+            5,
+            &[0; 16][..],
+            "",
+            Err(UndecodableReason::UnsupportedFormat),
+        ),
+    ];
+    let cab = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![texture2d_type()],
+        objects: textures
+            .iter()
+            .enumerate()
+            .map(
+                |(index, (texture_format, image_data, stream_path, _))| FixtureObject {
+                    path_id: index as i64 + 1,
+                    type_index: 0,
+                    data: texture2d_data(
+                        false,
+                        &TextureFixture {
+                            width: 2,
+                            height: 2,
+                            texture_format: *texture_format,
+                            mip_count: 1,
+                            image_data,
+                            stream_offset: 0,
+                            stream_size: if stream_path.is_empty() { 0 } else { 4 },
+                            stream_path,
+                        },
+                    ),
+                },
+            )
+            .collect(),
+    }
+    .build();
+    let viewer = load_bundle(bundle(&[(CAB_NAME, &cab)], Compression::None, 64));
+
+    for (index, (.., expected)) in textures.iter().enumerate() {
+        let texture: TypeTreeObjectRef = viewer
+            .get_object(CAB_NAME, index as i64 + 1)
+            .unwrap()
+            .unwrap()
+            .into();
+        let texture = Texture2D::new(&texture);
+        assert_eq!(&texture.check_decodable(&viewer), expected, "{index}");
+        assert_eq!(texture.is_decodable(&viewer), expected.is_ok());
+    }
+}
+
+#[test]
+fn material_textures_skip_empty_slots() {
+    use io_unity::classes::material::{Material, MaterialObject};
+    use io_unity::classes::texture2d::{Texture2D, Texture2DObject};
+
+    let cab = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![material_type(), texture2d_type(), text_asset_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: material_data(false, &[("_MainTex", 2), ("_BumpMap", 0), ("_Other", 3)]),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 1,
+                data: texture2d_data(
+                    false,
+                    &TextureFixture {
+                        width: 2,
+                        height: 2,
+                        texture_format: 1, // Alpha8
+                        mip_count: 1,
+                        image_data: &[1, 2, 3, 4],
+                        stream_offset: 0,
+                        stream_size: 0,
+                        stream_path: "",
+                    },
+                ),
+            },
+            FixtureObject {
+                path_id: 3,
+                type_index: 2,
+                data: text_asset_data(false, "text", "script"),
+            },
+        ],
+    }
+    .build();
+    let viewer = load_bundle(bundle(&[(CAB_NAME, &cab)], Compression::None, 256));
+    let material: TypeTreeObjectRef = viewer.get_object(CAB_NAME, 1).unwrap().unwrap().into();
+    let material = Material::new(&material);
+
+    let names: Vec<_> = material
+        .get_tex_envs()
+        .unwrap()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    assert_eq!(names, ["_MainTex", "_BumpMap", "_Other"]);
+
+    let textures = material.get_referenced_textures(&viewer).unwrap();
+    assert_eq!(textures.len(), 1);
+    assert_eq!(textures[0].0, "_MainTex");
+    let texture = Texture2D::new(&textures[0].1);
+    assert_eq!(texture.get_image_data(&viewer).unwrap(), [1, 2, 3, 4]);
+}
+
+#[test]
+fn streams_prefer_the_owning_bundle() {
+    use io_unity::classes::texture2d::{Texture2D, Texture2DObject};
+
+    const FIRST: &str = "CAB-11111111111111111111111111111111";
+    const SECOND: &str = "CAB-22222222222222222222222222222222";
+    const OTHER: &str = "CAB-33333333333333333333333333333333";
+
+    let texture_cab = |stream_path: &str| {
+        single_object_file(
+            texture2d_type(),
+            texture2d_data(
+                false,
+                &TextureFixture {
+                    width: 2,
+                    height: 2,
+                    texture_format: 1, // Alpha8
+                    mip_count: 1,
+                    image_data: &[],
+                    stream_offset: 0,
+                    stream_size: 4,
+                    stream_path,
+                },
+            ),
+        )
+    };
+
+    // Two CABs of one bundle share a stream named only by file name, and a
+    // second bundle has a stream of the same name.
+    let mut viewer = io_unity::unity_asset_view::UnityAssetViewer::new();
+    let first = texture_cab("shared.resS");
+    let second = texture_cab(&format!("archive:/{SECOND}/shared.resS"));
+    viewer
+        .add_bundle_file(
+            Box::new(std::io::Cursor::new(bundle(
+                &[
+                    (FIRST, &first),
+                    (SECOND, &second),
+                    ("shared.resS", &[1, 2, 3, 4]),
+                ],
+                Compression::Lz4,
+                64,
+            ))),
+            None,
+        )
+        .unwrap();
+    let other = texture_cab("shared.resS");
+    viewer
+        .add_bundle_file(
+            Box::new(std::io::Cursor::new(bundle(
+                &[(OTHER, &other), ("shared.resS", &[5, 6, 7, 8])],
+                Compression::None,
+                64,
+            ))),
+            None,
+        )
+        .unwrap();
+
+    for (cab_name, expected) in [
+        (FIRST, [1, 2, 3, 4]),
+        (SECOND, [1, 2, 3, 4]),
+        (OTHER, [5, 6, 7, 8]),
+    ] {
+        let texture: TypeTreeObjectRef = viewer.get_object(cab_name, 1).unwrap().unwrap().into();
+        assert_eq!(
+            Texture2D::new(&texture).get_image_data(&viewer).unwrap(),
+            expected,
+            "{cab_name}"
+        );
+    }
+}
+
+#[cfg(feature = "external-class-handle")]
+#[test]
+fn containers_resolve_to_decoded_textures() {
+    let pixels: Vec<u8> = (0..16).collect();
+    let cab = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![
+            asset_bundle_type(),
+            texture2d_type(),
+            sprite_type(),
+            text_asset_type(),
+        ],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: asset_bundle_data(
+                    false,
+                    &[
+                        ("ui/icon.png", 2),
+                        ("ui/icon_right.asset", 3),
+                        ("ui/readme.txt", 4),
+                    ],
+                ),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 1,
+                data: texture2d_data(
+                    false,
+                    &TextureFixture {
+                        width: 2,
+                        height: 2,
+                        texture_format: 4, // RGBA32
+                        mip_count: 1,
+                        image_data: &pixels,
+                        stream_offset: 0,
+                        stream_size: 0,
+                        stream_path: "",
+                    },
+                ),
+            },
+            FixtureObject {
+                path_id: 3,
+                type_index: 2,
+                data: sprite_data([1.0, 0.0, 1.0, 2.0], 2, 0),
+            },
+            FixtureObject {
+                path_id: 4,
+                type_index: 3,
+                data: text_asset_data(false, "readme", "not an image"),
+            },
+        ],
+    }
+    .build();
+    let viewer = load_bundle(bundle(&[(CAB_NAME, &cab)], Compression::None, 1 << 16));
+
+    let texture = viewer.texture_by_container("ui/icon.png").unwrap().unwrap();
+    assert_eq!(texture.dimensions(), (2, 2));
+    assert_eq!(texture.as_raw(), &pixels);
+
+    let sprite = viewer
+        .texture_by_container("ui/icon_right.asset")
+        .unwrap()
+        .unwrap();
+    assert_eq!(sprite.dimensions(), (1, 2));
+    assert_eq!(sprite.as_raw(), &[4, 5, 6, 7, 12, 13, 14, 15]);
+
+    assert!(viewer
+        .texture_by_container("ui/readme.txt")
+        .unwrap()
+        .is_none());
+    assert!(viewer.texture_by_container("ui/missing").unwrap().is_none());
+}
+
+#[cfg(feature = "external-class-handle")]
+#[test]
+fn sprites_take_alpha_from_their_alpha_texture() {
+    let rgb: Vec<u8> = (1..=12).collect();
+    let alpha = [10, 0, 0, 20, 0, 0, 30, 0, 0, 40, 0, 0];
+    let rgb24 = |image_data| TextureFixture {
+        width: 2,
+        height: 2,
+        texture_format: 3, // RGB24
+        mip_count: 1,
+        image_data,
+        stream_offset: 0,
+        stream_size: 0,
+        stream_path: "",
+    };
+    let cab = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![asset_bundle_type(), texture2d_type(), sprite_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: asset_bundle_data(false, &[("ui/etc1.asset", 4), ("ui/opaque.asset", 5)]),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 1,
+                data: texture2d_data(false, &rgb24(&rgb)),
+            },
+            FixtureObject {
+                path_id: 3,
+                type_index: 1,
+                data: texture2d_data(false, &rgb24(&alpha)),
+            },
+            FixtureObject {
+                path_id: 4,
+                type_index: 2,
+                data: sprite_data([0.0, 0.0, 2.0, 2.0], 2, 3),
+            },
+            FixtureObject {
+                path_id: 5,
+                type_index: 2,
+                data: sprite_data([0.0, 0.0, 2.0, 2.0], 2, 0),
+            },
+        ],
+    }
+    .build();
+    let viewer = load_bundle(bundle(&[(CAB_NAME, &cab)], Compression::None, 1 << 16));
+
+    let sprite = viewer
+        .texture_by_container("ui/etc1.asset")
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        sprite.as_raw(),
+        &[1, 2, 3, 10, 4, 5, 6, 20, 7, 8, 9, 30, 10, 11, 12, 40]
+    );
+    let sprite = viewer
+        .texture_by_container("ui/opaque.asset")
+        .unwrap()
+        .unwrap();
+    assert!(sprite.pixels().all(|pixel| pixel[3] == 255));
+}
+
+#[cfg(feature = "external-class-handle")]
+#[test]
+fn sprite_atlas_pages_keep_their_sprite_rects() {
+    use io_unity::classes::sprite_atlas::{SpriteAtlas, SpriteAtlasObject, SpriteRect};
+
+    let page = |width, height, image_data: &[u8]| {
+        texture2d_data(
+            false,
+            &TextureFixture {
+                width,
+                height,
+                texture_format: 4, // RGBA32
+                mip_count: 1,
+                image_data,
+                stream_offset: 0,
+                stream_size: 0,
+                stream_path: "",
+            },
+        )
+    };
+    let pixels: Vec<u8> = (0..16).collect();
+    let entries = [
+        AtlasEntry {
+            key: 1,
+            texture_path_id: 1,
+            rect: [0.0, 0.0, 1.0, 2.0],
+            settings_raw: 1,
+        },
+        AtlasEntry {
+            key: 2,
+            texture_path_id: 2,
+            rect: [0.0, 0.0, 1.0, 1.0],
+            settings_raw: 1 | 4 << 2, // Rotate90
+        },
+        // A sprite that is not loaded.
+        AtlasEntry {
+            key: 3,
+            texture_path_id: 1,
+            rect: [1.0, 0.0, 1.0, 2.0],
+            settings_raw: 1,
+        },
+    ];
+    let file = SerializedFileFixture {
+        format_version: 17,
+        big_endian: false,
+        enable_type_tree: true,
+        types: vec![texture2d_type(), packed_sprite_type(), sprite_atlas_type()],
+        objects: vec![
+            FixtureObject {
+                path_id: 1,
+                type_index: 0,
+                data: page(2, 2, &pixels),
+            },
+            FixtureObject {
+                path_id: 2,
+                type_index: 0,
+                data: page(1, 1, &[9; 4]),
+            },
+            FixtureObject {
+                path_id: 3,
+                type_index: 1,
+                data: packed_sprite_data("left", 1),
+            },
+            FixtureObject {
+                path_id: 4,
+                type_index: 1,
+                data: packed_sprite_data("right", 2),
+            },
+            FixtureObject {
+                path_id: 5,
+                type_index: 2,
+                data: sprite_atlas_data(&[(3, "left"), (4, "right")], &entries),
+            },
+        ],
+    }
+    .build();
+    let (viewer, serialized_file_id) = load_serialized_file(file);
+    let atlas: TypeTreeObjectRef = viewer.serialized_file_map[&serialized_file_id]
+        .get_tt_object_by_path_id(5)
+        .unwrap()
+        .unwrap()
+        .into();
+
+    let pages = SpriteAtlas::new(&atlas).pages(&viewer).unwrap();
+    let rect = |name: &str, rect: [f32; 4], rotated| SpriteRect {
+        name: name.to_owned(),
+        rect: glam::Vec4::from_array(rect),
+        rotated,
+    };
+    assert_eq!(pages.len(), 2);
+    assert_eq!(pages[0].0.as_raw(), &pixels);
+    assert_eq!(
+        pages[0].1,
+        [
+            rect("left", [0.0, 0.0, 1.0, 2.0], false),
+            rect("", [1.0, 0.0, 1.0, 2.0], false)
+        ]
+    );
+    assert_eq!(pages[1].0.as_raw(), &[9; 4]);
+    assert_eq!(pages[1].1, [rect("right", [0.0, 0.0, 1.0, 1.0], true)]);
+}
@@ -0,0 +1,293 @@
+mod fixtures;
+
+use fixtures::*;
+use io_unity::type_tree::{convert::TryCastFrom, TypeTreeNode, TypeTreeObjectRef};
+
+#[test]
+fn nested_collections_read_item_by_item() {
+    let file = single_object_file(nested_collections_type(), nested_collections_data());
+    let (viewer, serialized_file_id) = load_serialized_file(file);
+    let object: TypeTreeObjectRef = viewer.serialized_file_map[&serialized_file_id]
+        .get_tt_object_by_path_id(1)
+        .unwrap()
+        .unwrap()
+        .into();
+
+    let grid: Vec<Vec<u32>> =
+        <Vec<TypeTreeObjectRef>>::try_cast_from(&object, "/Base/m_Grid/Array")
+            .unwrap()
+            .iter()
+            .map(|row| <Vec<u32>>::try_cast_from(row, "/Base/Array").unwrap())
+            .collect();
+    assert_eq!(grid, [vec![1, 2], vec![], vec![3]]);
+    let curves: Vec<(i32, Vec<f32>)> =
+        <Vec<TypeTreeObjectRef>>::try_cast_from(&object, "/Base/m_Curves/Array")
+            .unwrap()
+            .iter()
+            .map(|pair| {
+                (
+                    i32::try_cast_from(pair, "/Base/first").unwrap(),
+                    <Vec<f32>>::try_cast_from(pair, "/Base/second/Array").unwrap(),
+                )
+            })
+            .collect();
+    assert_eq!(curves, [(1, vec![0.5]), (2, vec![1.0, 2.0])]);
+    let items: Vec<(Vec<u8>, i32)> =
+        <Vec<TypeTreeObjectRef>>::try_cast_from(&object, "/Base/m_Items/Array")
+            .unwrap()
+            .iter()
+            .map(|item| {
+                (
+                    <Vec<u8>>::try_cast_from(item, "/Base/m_Bytes/Array").unwrap(),
+                    i32::try_cast_from(item, "/Base/m_Id").unwrap(),
+                )
+            })
+            .collect();
+    assert_eq!(items, [(vec![7], 10), (vec![8, 9, 10, 11, 12], 20)]);
+    assert_eq!(i32::try_cast_from(&object, "/Base/m_Tail").unwrap(), 99);
+
+    // A node skipping a level is an error rather than a panic.
+    let skipping = [
+        TypeTreeNode::new(0, "MonoBehaviour", "Base", -1),
+        TypeTreeNode::new(1, "int", "m_Value", 4),
+        TypeTreeNode::new(3, "int", "m_Lost", 4),
+    ];
+    let err = viewer.serialized_file_map[&serialized_file_id]
+        .get_object_with_type_tree(1, &skipping)
+        .unwrap_err();
+    assert!(err.to_string().contains("m_Lost at level 3"), "{err}");
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derived_structs_read_their_fields_by_path() {
+    use io_unity::error::Error;
+    use io_unity::type_tree::convert::FromTypeTree;
+
+    #[derive(FromTypeTree, Debug, PartialEq)]
+    struct Item {
+        #[tt(path = "/Base/m_Bytes/Array")]
+        bytes: Vec<u8>,
+        #[tt(path = "/Base/m_Id")]
+        id: i32,
+    }
+
+    #[derive(FromTypeTree)]
+    #[allow(non_snake_case)]
+    struct Nested {
+        #[tt(path = "/Base/m_Name")]
+        name: String,
+        #[tt(path = "/Base/m_Items/Array", nested)]
+        items: Vec<Item>,
+        m_Tail: i32,
+    }
+
+    let object = read_single_object(nested_collections_type(), nested_collections_data());
+
+    let nested = Nested::from_type_tree(&object).unwrap();
+    assert_eq!(nested.name, "nested");
+    assert_eq!(
+        nested.items,
+        [
+            Item {
+                bytes: vec![7],
+                id: 10
+            },
+            Item {
+                bytes: vec![8, 9, 10, 11, 12],
+                id: 20
+            }
+        ]
+    );
+    assert_eq!(nested.m_Tail, 99);
+
+    #[derive(FromTypeTree, Debug)]
+    struct Missing {
+        #[tt(path = "/Base/m_Missing")]
+        _missing: i32,
+    }
+    assert!(matches!(
+        Missing::from_type_tree(&object),
+        Err(Error::FieldNotFound(_))
+    ));
+}
+
+#[test]
+fn enum_fields_have_labels() {
+    let nodes = vec![
+        TypeTreeNode::new(0, "GameObject", "Base", -1),
+        TypeTreeNode::new(1, "GLTextureSettings", "m_TextureSettings", 8),
+        TypeTreeNode::new(2, "int", "m_FilterMode", 4),
+        TypeTreeNode::new(2, "int", "m_WrapU", 4),
+        TypeTreeNode::new(1, "unsigned int", "m_StaticEditorFlags", 4),
+        TypeTreeNode::new(1, "int", "m_Layer", 4),
+    ];
+    let mut data = fixtures::Writer::new(false);
+    data.i32(1).i32(7).u32(4 | 16).i32(3);
+    let file = single_object_file(FixtureType { class_id: 1, nodes }, data.data);
+    let (viewer, serialized_file_id) = load_serialized_file(file);
+    let object = viewer.serialized_file_map[&serialized_file_id]
+        .get_tt_object_by_path_id(1)
+        .unwrap()
+        .unwrap();
+
+    let label = |path| object.get_enum_label_by_path(path).unwrap();
+    assert_eq!(
+        label("/Base/m_TextureSettings/m_FilterMode").as_deref(),
+        Some("Bilinear")
+    );
+    // Out of range for TextureWrapMode.
+    assert_eq!(label("/Base/m_TextureSettings/m_WrapU"), None);
+    assert_eq!(
+        label("/Base/m_StaticEditorFlags").as_deref(),
+        Some("BatchingStatic|OccludeeStatic")
+    );
+    assert_eq!(label("/Base/m_Layer"), None);
+
+    io_unity::type_tree::enum_label::register_enum_labels("GameObject", "m_Layer", &[(3, "Water")]);
+    assert_eq!(label("/Base/m_Layer").as_deref(), Some("Water"));
+    assert!(object
+        .display_tree()
+        .contains("m_FilterMode : int = 1 (Bilinear)"));
+}
+
+#[test]
+fn visitors_see_every_field_and_can_stop() {
+    use io_unity::type_tree::visitor::{LeafValue, TypeTreeVisitor, Visit};
+
+    #[derive(Default)]
+    struct Leaves {
+        leaves: Vec<(String, LeafValue)>,
+        stop_after: Option<usize>,
+    }
+
+    impl TypeTreeVisitor for Leaves {
+        fn visit_value(&mut self, path: &str, _type_name: &str, value: LeafValue) -> Visit {
+            self.leaves.push((path.to_owned(), value));
+            if Some(self.leaves.len()) == self.stop_after {
+                return Visit::Stop;
+            }
+            Visit::Continue
+        }
+    }
+
+    let viewer = load_bundle(text_asset_bundle(Compression::None, false, true, 1));
+    let object = viewer.get_object(CAB_NAME, 1).unwrap().unwrap();
+
+    let mut leaves = Leaves::default();
+    assert!(object.visit(&mut leaves));
+    assert_eq!(
+        leaves.leaves,
+        [
+            (
+                "/Base/m_Name".to_owned(),
+                LeafValue::String("text1".to_owned())
+            ),
+            (
+                "/Base/m_Script".to_owned(),
+                LeafValue::String("script of 1".to_owned())
+            ),
+        ]
+    );
+
+    let mut leaves = Leaves {
+        stop_after: Some(1),
+        ..Default::default()
+    };
+    assert!(!object.visit(&mut leaves));
+    assert_eq!(leaves.leaves.len(), 1);
+}
+
+#[test]
+fn aligned_array_items_are_padded_one_by_one() {
+    // An array of 3 byte structs flagged as aligned, each followed by one
+    // byte of padding, and a field after the array.
+    let item_nodes = |lead: bool| {
+        let mut nodes = vec![TypeTreeNode::new(0, "GameObject", "Base", -1)];
+        if lead {
+            nodes.push(TypeTreeNode::new(1, "UInt8", "m_Lead", 1));
+        }
+        nodes.extend([
+            TypeTreeNode::new(1, "vector", "m_Items", -1),
+            TypeTreeNode::new(2, "Array", "Array", -1),
+            TypeTreeNode::new(3, "int", "size", 4),
+            TypeTreeNode::new(3, "Item", "data", 3).aligned(),
+            TypeTreeNode::new(4, "UInt8", "r", 1),
+            TypeTreeNode::new(4, "UInt8", "g", 1),
+            TypeTreeNode::new(4, "UInt8", "b", 1),
+            TypeTreeNode::new(1, "unsigned int", "m_Tail", 4),
+        ]);
+        nodes
+    };
+
+    for lead in [false, true] {
+        let mut data = Writer::new(false);
+        if lead {
+            data.bytes(&[9]);
+        }
+        data.i32(3);
+        for item in 0..3u8 {
+            data.bytes(&[item * 3, item * 3 + 1, item * 3 + 2]).align(4);
+        }
+        data.u32(0xdeadbeef);
+
+        let object = read_single_object(
+            FixtureType {
+                class_id: 1,
+                nodes: item_nodes(lead),
+            },
+            data.data,
+        );
+
+        let items =
+            <Vec<TypeTreeObjectRef>>::try_cast_from(&object, "/Base/m_Items/Array").unwrap();
+        let rgb: Vec<[u8; 3]> = items
+            .iter()
+            .map(|item| {
+                [
+                    u8::try_cast_from(item, "/Base/r").unwrap(),
+                    u8::try_cast_from(item, "/Base/g").unwrap(),
+                    u8::try_cast_from(item, "/Base/b").unwrap(),
+                ]
+            })
+            .collect();
+        assert_eq!(rgb, [[0, 1, 2], [3, 4, 5], [6, 7, 8]], "lead {lead}");
+        assert_eq!(
+            u32::try_cast_from(&object, "/Base/m_Tail").unwrap(),
+            0xdeadbeef,
+            "lead {lead}"
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn objects_stream_out_as_json() {
+    let (viewer, serialized_file_id) = load_serialized_file(single_object_file(
+        material_type(),
+        material_data(false, &[("_MainTex", 2), ("_Bump\"Map", 3)]),
+    ));
+    let material = viewer.serialized_file_map[&serialized_file_id]
+        .get_tt_object_by_path_id(1)
+        .unwrap()
+        .unwrap();
+
+    let mut json = Vec::new();
+    material.write_json(&mut json).unwrap();
+    let texture = |name: &str, path_id: i64| {
+        serde_json::json!({
+            "first": name,
+            "second": {"m_Texture": {"m_FileID": 0, "m_PathID": path_id}},
+        })
+    };
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&json).unwrap(),
+        serde_json::json!({
+            "m_Name": "material",
+            "m_Shader": {"m_FileID": 0, "m_PathID": 0},
+            "m_SavedProperties": {
+                "m_TexEnvs": [texture("_MainTex", 2), texture("_Bump\"Map", 3)],
+            },
+        })
+    );
+}
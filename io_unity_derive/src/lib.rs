@@ -0,0 +1,117 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Field, Fields, GenericArgument, LitStr,
+    PathArguments, Type,
+};
+
+/// Implement `io_unity::type_tree::convert::FromTypeTree` for a struct with
+/// named fields, reading each field from a path of the object.
+///
+/// A field is read from `/Base/<field name>` unless it has a
+/// `#[tt(path = "/Base/m_Name")]` attribute. Fields marked `#[tt(nested)]`
+/// are themselves `FromTypeTree` structs, read from the object at the path;
+/// a nested `Vec<T>` reads every item of the array at the path.
+#[proc_macro_derive(FromTypeTree, attributes(tt))]
+pub fn derive_from_type_tree(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "FromTypeTree can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "FromTypeTree needs a struct with named fields",
+        ));
+    };
+
+    let fields = fields
+        .named
+        .iter()
+        .map(read_field)
+        .collect::<syn::Result<Vec<_>>>()?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::io_unity::type_tree::convert::FromTypeTree for #name #ty_generics #where_clause {
+            fn from_type_tree(
+                object: &::io_unity::type_tree::TypeTreeObjectRef,
+            ) -> ::io_unity::error::ReadResult<Self> {
+                Ok(Self { #(#fields,)* })
+            }
+        }
+    })
+}
+
+fn read_field(field: &Field) -> syn::Result<TokenStream2> {
+    let ident = field.ident.as_ref().expect("named field");
+    let mut path = format!("/Base/{ident}");
+    let mut nested = false;
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("tt")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("path") {
+                path = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else if meta.path.is_ident("nested") {
+                nested = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `path = \"...\"` or `nested`"))
+            }
+        })?;
+    }
+
+    let ty = &field.ty;
+    let cast = |ty: TokenStream2| {
+        quote! {
+            <#ty as ::io_unity::type_tree::convert::TryCastFrom<
+                &::io_unity::type_tree::TypeTreeObjectRef,
+                &str,
+            >>::try_cast_from(object, #path)?
+        }
+    };
+    let value = if !nested {
+        cast(quote!(#ty))
+    } else if let Some(item) = vec_item(ty) {
+        let items = cast(quote!(::std::vec::Vec<::io_unity::type_tree::TypeTreeObjectRef>));
+        quote! {
+            #items
+                .iter()
+                .map(<#item as ::io_unity::type_tree::convert::FromTypeTree>::from_type_tree)
+                .collect::<::io_unity::error::ReadResult<#ty>>()?
+        }
+    } else {
+        let object = cast(quote!(::io_unity::type_tree::TypeTreeObjectRef));
+        quote! {
+            <#ty as ::io_unity::type_tree::convert::FromTypeTree>::from_type_tree(&#object)?
+        }
+    };
+    Ok(quote!(#ident: #value))
+}
+
+/// The item type of a field written as `Vec<T>`.
+fn vec_item(ty: &Type) -> Option<&Type> {
+    let Type::Path(ty) = ty else { return None };
+    let segment = ty.path.segments.last()?;
+    if segment.ident != "Vec" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(item) if args.args.len() == 1 => Some(item),
+        _ => None,
+    }
+}
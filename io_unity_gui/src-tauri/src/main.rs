@@ -77,6 +77,10 @@ fn list_fs_cab(state: tauri::State<IOUnityContext>, fs_path: &str) -> Result<Vec
         if let Some(cab) = cabs.get(fs_path) {
             let mut objects = vec![];
             for (pathid, obj) in cab.get_object_map() {
+                // Removed objects of patch-delta files have no data to read.
+                if obj.is_removed() {
+                    continue;
+                }
                 let tt_o = cab.get_tt_object_by_path_id(*pathid).unwrap();
                 let name =
                     String::try_cast_from(&tt_o.unwrap(), "/Base/m_Name").unwrap_or("".to_owned());
@@ -98,6 +102,9 @@ fn list_fs_cab(state: tauri::State<IOUnityContext>, fs_path: &str) -> Result<Vec
                     .unwrap();
                 let mut objects = vec![];
                 for (pathid, obj) in cab.get_object_map() {
+                    if obj.is_removed() {
+                        continue;
+                    }
                     let tt_o = cab.get_tt_object_by_path_id(*pathid).unwrap();
                     let name = String::try_cast_from(&tt_o.unwrap(), "/Base/m_Name")
                         .unwrap_or("".to_owned());
@@ -218,7 +218,7 @@ impl TypeTreeObjectRef {
     }
 
     fn display_tree(&self) {
-        self.0.display_tree();
+        println!("{}", self.0.display_tree());
     }
 
     fn get_data_buff(&self) -> Option<Vec<u8>> {